@@ -59,8 +59,10 @@ struct Fake;
 impl<'a> marker::IsInputType<DefaultScalarValue> for &'a Fake {}
 
 impl<'a> FromInputValue for &'a Fake {
-    fn from_input_value(_v: &InputValue) -> Option<&'a Fake> {
-        None
+    type Error = String;
+
+    fn from_input_value(_v: &InputValue) -> Result<&'a Fake, String> {
+        Err("this is fake".to_owned())
     }
 }
 
@@ -84,6 +86,7 @@ impl<'a> GraphQLType<DefaultScalarValue> for &'a Fake {
                 name: "fake".to_string(),
                 description: None,
                 deprecation_status: juniper::meta::DeprecationStatus::Current,
+                visibility: None,
             }],
         );
         meta.into_meta()
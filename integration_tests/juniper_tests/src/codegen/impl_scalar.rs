@@ -28,10 +28,11 @@ where
         Value::scalar(self.0)
     }
 
-    fn from_input_value(v: &juniper::InputValue) -> Option<DefaultName> {
+    fn from_input_value(v: &juniper::InputValue) -> Result<DefaultName, String> {
         v.as_scalar_value()
             .and_then(|s| s.as_int())
             .map(|i| DefaultName(i))
+            .ok_or_else(|| format!("Expected `Int`, found: {}", v))
     }
 
     fn from_str<'a>(value: juniper::ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -45,8 +46,10 @@ impl GraphQLScalar for OtherOrder {
         Value::scalar(self.0)
     }
 
-    fn from_input_value(v: &juniper::InputValue) -> Option<OtherOrder> {
-        v.as_scalar_value::<i32>().map(|i| OtherOrder(*i))
+    fn from_input_value(v: &juniper::InputValue) -> Result<OtherOrder, String> {
+        v.as_scalar_value::<i32>()
+            .map(|i| OtherOrder(*i))
+            .ok_or_else(|| format!("Expected `Int`, found: {}", v))
     }
 
     fn from_str<'a>(value: juniper::ScalarToken<'a>) -> ParseScalarResult<'a, DefaultScalarValue> {
@@ -60,8 +63,10 @@ impl GraphQLScalar for Named {
         Value::scalar(self.0)
     }
 
-    fn from_input_value(v: &juniper::InputValue) -> Option<Named> {
-        v.as_scalar_value::<i32>().map(|i| Named(*i))
+    fn from_input_value(v: &juniper::InputValue) -> Result<Named, String> {
+        v.as_scalar_value::<i32>()
+            .map(|i| Named(*i))
+            .ok_or_else(|| format!("Expected `Int`, found: {}", v))
     }
 
     fn from_str<'a>(value: juniper::ScalarToken<'a>) -> ParseScalarResult<'a, DefaultScalarValue> {
@@ -75,8 +80,10 @@ impl GraphQLScalar for ScalarDescription {
         Value::scalar(self.0)
     }
 
-    fn from_input_value(v: &juniper::InputValue) -> Option<ScalarDescription> {
-        v.as_scalar_value::<i32>().map(|i| ScalarDescription(*i))
+    fn from_input_value(v: &juniper::InputValue) -> Result<ScalarDescription, String> {
+        v.as_scalar_value::<i32>()
+            .map(|i| ScalarDescription(*i))
+            .ok_or_else(|| format!("Expected `Int`, found: {}", v))
     }
 
     fn from_str<'a>(value: juniper::ScalarToken<'a>) -> ParseScalarResult<'a, DefaultScalarValue> {
@@ -139,8 +146,10 @@ fn path_in_resolve_return_type() {
             Value::scalar(self.0)
         }
 
-        fn from_input_value(v: &juniper::InputValue) -> Option<ResolvePath> {
-            v.as_scalar_value::<i32>().map(|i| ResolvePath(*i))
+        fn from_input_value(v: &juniper::InputValue) -> Result<ResolvePath, String> {
+            v.as_scalar_value::<i32>()
+                .map(|i| ResolvePath(*i))
+                .ok_or_else(|| format!("Expected `Int`, found: {}", v))
         }
 
         fn from_str<'a>(
@@ -1,147 +1,34 @@
-use std::fmt;
-
 use juniper::{
     execute,
     parser::{ParseError, ScalarToken, Spanning, Token},
-    serde::de,
     EmptyMutation, FieldResult, InputValue, Object, ParseScalarResult, RootNode, ScalarValue,
     Value, Variables,
 };
 
 #[derive(Debug, Clone, PartialEq, juniper::GraphQLScalarValue)]
 pub(crate) enum MyScalarValue {
+    #[graphql(int)]
     Int(i32),
+    #[graphql(long)]
     Long(i64),
+    #[graphql(float)]
     Float(f64),
+    #[graphql(string)]
     String(String),
+    #[graphql(boolean)]
     Boolean(bool),
 }
 
-impl ScalarValue for MyScalarValue {
-    type Visitor = MyScalarValueVisitor;
-
-    fn as_int(&self) -> Option<i32> {
-        match *self {
-            MyScalarValue::Int(ref i) => Some(*i),
-            _ => None,
-        }
-    }
-
-    fn as_string(&self) -> Option<String> {
-        match *self {
-            MyScalarValue::String(ref s) => Some(s.clone()),
-            _ => None,
-        }
-    }
-
-    fn as_str(&self) -> Option<&str> {
-        match *self {
-            MyScalarValue::String(ref s) => Some(s.as_str()),
-            _ => None,
-        }
-    }
-
-    fn as_float(&self) -> Option<f64> {
-        match *self {
-            MyScalarValue::Int(ref i) => Some(*i as f64),
-            MyScalarValue::Float(ref f) => Some(*f),
-            _ => None,
-        }
-    }
-
-    fn as_boolean(&self) -> Option<bool> {
-        match *self {
-            MyScalarValue::Boolean(ref b) => Some(*b),
-            _ => None,
-        }
-    }
-}
-
-#[derive(Default, Debug)]
-pub(crate) struct MyScalarValueVisitor;
-
-impl<'de> de::Visitor<'de> for MyScalarValueVisitor {
-    type Value = MyScalarValue;
-
-    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("a valid input value")
-    }
-
-    fn visit_bool<E>(self, value: bool) -> Result<MyScalarValue, E> {
-        Ok(MyScalarValue::Boolean(value))
-    }
-
-    fn visit_i32<E>(self, value: i32) -> Result<MyScalarValue, E>
-    where
-        E: de::Error,
-    {
-        Ok(MyScalarValue::Int(value))
-    }
-
-    fn visit_i64<E>(self, value: i64) -> Result<MyScalarValue, E>
-    where
-        E: de::Error,
-    {
-        if value <= i32::max_value() as i64 {
-            self.visit_i32(value as i32)
-        } else {
-            Ok(MyScalarValue::Long(value))
-        }
-    }
-
-    fn visit_u32<E>(self, value: u32) -> Result<MyScalarValue, E>
-    where
-        E: de::Error,
-    {
-        if value <= i32::max_value() as u32 {
-            self.visit_i32(value as i32)
-        } else {
-            self.visit_u64(value as u64)
-        }
-    }
-
-    fn visit_u64<E>(self, value: u64) -> Result<MyScalarValue, E>
-    where
-        E: de::Error,
-    {
-        if value <= i64::max_value() as u64 {
-            self.visit_i64(value as i64)
-        } else {
-            // Browser's JSON.stringify serialize all numbers having no
-            // fractional part as integers (no decimal point), so we
-            // must parse large integers as floating point otherwise
-            // we would error on transferring large floating point
-            // numbers.
-            Ok(MyScalarValue::Float(value as f64))
-        }
-    }
-
-    fn visit_f64<E>(self, value: f64) -> Result<MyScalarValue, E> {
-        Ok(MyScalarValue::Float(value))
-    }
-
-    fn visit_str<E>(self, value: &str) -> Result<MyScalarValue, E>
-    where
-        E: de::Error,
-    {
-        self.visit_string(value.into())
-    }
-
-    fn visit_string<E>(self, value: String) -> Result<MyScalarValue, E> {
-        Ok(MyScalarValue::String(value))
-    }
-}
-
 #[juniper::graphql_scalar(name = "Long")]
 impl GraphQLScalar for i64 {
     fn resolve(&self) -> Value {
         Value::scalar(*self)
     }
 
-    fn from_input_value(v: &InputValue) -> Option<i64> {
+    fn from_input_value(v: &InputValue) -> Result<i64, String> {
         match *v {
-            InputValue::Scalar(MyScalarValue::Long(i)) => Some(i),
-            _ => None,
+            InputValue::Scalar(MyScalarValue::Long(i)) => Ok(i),
+            _ => Err(format!("Expected `MyScalarValue::Long`, found: {}", v)),
         }
     }
 
@@ -273,3 +160,34 @@ fn deserialize_variable() {
         )])
     );
 }
+
+#[test]
+fn deserialize_large_unsigned_as_float() {
+    let input_value: InputValue<MyScalarValue> =
+        serde_json::from_str(&u64::max_value().to_string()).unwrap();
+    assert_eq!(
+        input_value,
+        InputValue::Scalar(MyScalarValue::Float(u64::max_value() as f64))
+    );
+}
+
+#[test]
+fn derived_conversions() {
+    let long = MyScalarValue::from_i64(i64::min_value());
+    assert_eq!(long, MyScalarValue::Long(i64::min_value()));
+    assert_eq!(long.as_i64(), Some(i64::min_value()));
+    assert_eq!(long.as_u64(), None);
+    assert_eq!(long.as_int(), None);
+    assert_eq!(long.as_float(), Some(i64::min_value() as f64));
+
+    let unsigned = MyScalarValue::from_u64(u64::max_value());
+    assert_eq!(
+        unsigned.as_string().as_deref(),
+        Some("18446744073709551615")
+    );
+
+    assert_eq!(MyScalarValue::Int(-1).as_i64(), Some(-1));
+    assert_eq!(MyScalarValue::Int(-1).as_u64(), None);
+    assert_eq!(MyScalarValue::Boolean(true).as_boolean(), Some(true));
+    assert_eq!(MyScalarValue::String("s".into()).as_str(), Some("s"));
+}
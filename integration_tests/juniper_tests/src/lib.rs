@@ -8,3 +8,5 @@ mod custom_scalar;
 mod issue_371;
 #[cfg(test)]
 mod issue_398;
+#[cfg(test)]
+mod long_scalar;
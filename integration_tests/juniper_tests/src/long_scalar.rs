@@ -0,0 +1,99 @@
+use juniper::{
+    execute, EmptyMutation, EmptySubscription, LongScalarValue, RootNode, ScalarValue as _, Value,
+    Variables,
+};
+
+/// Scalar value without a `long` variant, resolving the built-in `Long` and
+/// `UnsignedLong` scalars beyond `Int` as strings
+#[derive(Debug, Clone, PartialEq, juniper::GraphQLScalarValue)]
+pub(crate) enum StringLongScalarValue {
+    #[graphql(int)]
+    Int(i32),
+    #[graphql(float)]
+    Float(f64),
+    #[graphql(string)]
+    String(String),
+    #[graphql(boolean)]
+    Boolean(bool),
+}
+
+impl LongScalarValue for StringLongScalarValue {}
+
+struct Query;
+
+#[juniper::graphql_object(Scalar = StringLongScalarValue)]
+impl Query {
+    fn long(value: i64) -> i64 {
+        value
+    }
+
+    fn unsigned_long(value: u64) -> u64 {
+        value
+    }
+}
+
+#[tokio::test]
+async fn resolves_longs_beyond_int_as_strings() {
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+    let query = r#"{
+        small: long(value: -42)
+        large: long(value: -9223372036854775808)
+        unsigned: unsignedLong(value: "18446744073709551615")
+    }"#;
+
+    let (result, errs) = execute(query, None, &schema, &Variables::new(), &())
+        .await
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+
+    let obj = result.as_object_value().expect("Result is not an object");
+    assert_eq!(obj.get_field_value("small"), Some(&Value::scalar(-42)));
+    assert_eq!(
+        obj.get_field_value("large"),
+        Some(&Value::scalar("-9223372036854775808".to_owned())),
+    );
+    assert_eq!(
+        obj.get_field_value("unsigned"),
+        Some(&Value::scalar("18446744073709551615".to_owned())),
+    );
+}
+
+/// Scalar value keeping longs as such, but serializing them as strings
+#[derive(Debug, Clone, PartialEq, juniper::GraphQLScalarValue)]
+pub(crate) enum SerializedStringLongScalarValue {
+    #[graphql(int)]
+    Int(i32),
+    #[graphql(long, as_string)]
+    Long(i64),
+    #[graphql(unsigned_long, as_string)]
+    UnsignedLong(u64),
+    #[graphql(float)]
+    Float(f64),
+    #[graphql(string)]
+    String(String),
+    #[graphql(boolean)]
+    Boolean(bool),
+}
+
+#[test]
+fn serializes_longs_marked_as_string_as_strings() {
+    let value = Value::<SerializedStringLongScalarValue>::list(vec![
+        Value::scalar(-42),
+        Value::scalar(-9_223_372_036_854_775_808i64),
+        Value::scalar(18_446_744_073_709_551_615u64),
+    ]);
+
+    assert_eq!(
+        serde_json::to_string(&value).unwrap(),
+        r#"[-42,"-9223372036854775808","18446744073709551615"]"#,
+    );
+    assert_eq!(
+        SerializedStringLongScalarValue::from_i64(1 << 40),
+        SerializedStringLongScalarValue::Long(1 << 40),
+    );
+}
@@ -3,7 +3,7 @@ use std::{borrow::Cow, fmt, hash::Hash, slice, vec};
 use indexmap::IndexMap;
 
 use crate::{
-    executor::Variables,
+    executor::{IntoFieldError, Variables},
     parser::Spanning,
     value::{DefaultScalarValue, ScalarValue},
 };
@@ -146,13 +146,26 @@ pub type Document<'a, S> = Vec<Definition<'a, S>>;
 
 /// Parse an unstructured input value into a Rust data type.
 ///
-/// The conversion _can_ fail, and must in that case return None. Implemented
-/// automatically by the convenience proc macro `graphql_scalar` or by deriving GraphQLEnum.
+/// The conversion _can_ fail, and must in that case return an error describing
+/// why the value was rejected. Its message is reported by query validation,
+/// e.g. `Invalid value for argument "at", expected type "DateTime": expected
+/// RFC3339 timestamp, got "2020-13-45"`. Implemented automatically by the
+/// convenience proc macro `graphql_scalar` or by deriving GraphQLEnum.
 ///
 /// Must be implemented manually when manually exposing new enums or scalars.
 pub trait FromInputValue<S = DefaultScalarValue>: Sized {
+    /// Type of the error returned when the conversion fails.
+    type Error: IntoFieldError<S>;
+
     /// Performs the conversion.
-    fn from_input_value(v: &InputValue<S>) -> Option<Self>;
+    fn from_input_value(v: &InputValue<S>) -> Result<Self, Self::Error>;
+
+    /// Performs the conversion from an absent value (e.g. to distinguish between
+    /// implicit and explicit null). The default implementation just uses
+    /// `from_input_value` as if an explicit null were provided.
+    fn from_implicit_null() -> Result<Self, Self::Error> {
+        Self::from_input_value(&InputValue::<S>::Null)
+    }
 }
 
 /// Losslessly clones a Rust data type into an InputValue.
@@ -293,25 +306,29 @@ where
     }
 
     /// Resolve all variables to their values.
-    pub fn into_const(self, vars: &Variables<S>) -> Self {
+    ///
+    /// Returns `None` if this value is a variable which was not provided.
+    /// Object fields referring to such variables are omitted, so they remain
+    /// distinguishable from fields explicitly set to `null`.
+    pub fn into_const(self, vars: &Variables<S>) -> Option<Self> {
         match self {
-            InputValue::Variable(v) => vars.get(&v).map_or_else(InputValue::null, Clone::clone),
-            InputValue::List(l) => InputValue::List(
+            InputValue::Variable(v) => vars.get(&v).cloned(),
+            InputValue::List(l) => Some(InputValue::List(
                 l.into_iter()
-                    .map(|s| s.map(|v| v.into_const(vars)))
+                    .map(|s| s.map(|v| v.into_const(vars).unwrap_or_else(InputValue::null)))
                     .collect(),
-            ),
-            InputValue::Object(o) => InputValue::Object(
+            )),
+            InputValue::Object(o) => Some(InputValue::Object(
                 o.into_iter()
-                    .map(|(sk, sv)| (sk, sv.map(|v| v.into_const(vars))))
+                    .filter_map(|(sk, sv)| sv.and_then(|v| v.into_const(vars)).map(|sv| (sk, sv)))
                     .collect(),
-            ),
-            v => v,
+            )),
+            v => Some(v),
         }
     }
 
     /// Shorthand form of invoking `FromInputValue::from()`.
-    pub fn convert<T>(&self) -> Option<T>
+    pub fn convert<T>(&self) -> Result<T, T::Error>
     where
         T: FromInputValue<S>,
     {
@@ -0,0 +1,124 @@
+//! Cache policies of responses, computed from the hints of the types and
+//! fields they resolve
+
+use std::fmt;
+
+/// Which caches may store a response
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CacheScope {
+    /// Any cache, e.g. a CDN shared between clients.
+    Public,
+
+    /// Only the cache of the client, as the response holds data specific to
+    /// it.
+    Private,
+}
+
+impl Default for CacheScope {
+    fn default() -> Self {
+        Self::Public
+    }
+}
+
+impl fmt::Display for CacheScope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Public => "public",
+            Self::Private => "private",
+        })
+    }
+}
+
+/// Cache policy of a type or field, as given to
+/// `#[graphql(cache_control(max_age = 60, scope = "PUBLIC"))]`
+///
+/// A field without a `max_age` takes the one of its type if it returns an
+/// object, interface or union. Root fields and fields returning such types
+/// default to a `max_age` of `0`, while other fields don't restrict the
+/// policy of the response unless they have a hint.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheHint {
+    /// Number of seconds the result may be cached for.
+    pub max_age: Option<u32>,
+
+    /// Which caches may store the result.
+    pub scope: Option<CacheScope>,
+}
+
+/// Cache policy of a response, the most restrictive of the hints of the
+/// fields it resolved
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CachePolicy {
+    /// Number of seconds the response may be cached for, if any field
+    /// restricted it.
+    pub max_age: Option<u32>,
+
+    /// Which caches may store the response.
+    pub scope: CacheScope,
+}
+
+impl CachePolicy {
+    /// Restricts this policy to the given hint
+    pub fn restrict(&mut self, hint: CacheHint) {
+        if let Some(max_age) = hint.max_age {
+            self.max_age = Some(self.max_age.map_or(max_age, |age| age.min(max_age)));
+        }
+        if let Some(scope) = hint.scope {
+            self.scope = self.scope.max(scope);
+        }
+    }
+
+    /// Value of the `Cache-Control` header to send along with the response,
+    /// e.g. `max-age=60, public`
+    ///
+    /// Returns `None` if the response can't be cached.
+    pub fn header_value(&self) -> Option<String> {
+        match self.max_age {
+            Some(max_age) if max_age > 0 => Some(format!("max-age={}, {}", max_age, self.scope)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CacheHint, CachePolicy, CacheScope};
+
+    #[test]
+    fn restricts_to_the_most_restrictive_hints() {
+        let mut policy = CachePolicy::default();
+        assert_eq!(policy.header_value(), None);
+
+        policy.restrict(CacheHint {
+            max_age: Some(60),
+            scope: None,
+        });
+        assert_eq!(policy.header_value().as_deref(), Some("max-age=60, public"));
+
+        policy.restrict(CacheHint {
+            max_age: None,
+            scope: Some(CacheScope::Private),
+        });
+        policy.restrict(CacheHint {
+            max_age: Some(120),
+            scope: Some(CacheScope::Public),
+        });
+        assert_eq!(
+            policy,
+            CachePolicy {
+                max_age: Some(60),
+                scope: CacheScope::Private,
+            },
+        );
+        assert_eq!(
+            policy.header_value().as_deref(),
+            Some("max-age=60, private")
+        );
+
+        policy.restrict(CacheHint {
+            max_age: Some(0),
+            scope: None,
+        });
+        assert_eq!(policy.header_value(), None);
+    }
+}
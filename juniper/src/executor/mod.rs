@@ -3,7 +3,7 @@ use std::{
     cmp::Ordering,
     collections::HashMap,
     fmt::{Debug, Display},
-    sync::{Arc, RwLock},
+    sync::{Arc, Mutex, RwLock},
 };
 
 use fnv::FnvHashMap;
@@ -34,6 +34,7 @@ use crate::{
 };
 
 pub use self::{
+    cache_control::{CacheHint, CachePolicy, CacheScope},
     look_ahead::{
         Applies, ChildSelection, ConcreteLookAheadSelection, LookAheadArgument, LookAheadMethods,
         LookAheadSelection, LookAheadValue,
@@ -41,6 +42,7 @@ pub use self::{
     owned_executor::OwnedExecutor,
 };
 
+mod cache_control;
 mod look_ahead;
 mod owned_executor;
 
@@ -77,6 +79,7 @@ where
     schema: &'a SchemaType<'a, S>,
     context: &'a CtxT,
     errors: &'r RwLock<Vec<ExecutionError<S>>>,
+    cache_policy: Option<&'r Mutex<CachePolicy>>,
     field_path: Arc<FieldPath<'a>>,
 }
 
@@ -246,6 +249,24 @@ impl<S> IntoFieldError<S> for FieldError<S> {
     }
 }
 
+impl<S> IntoFieldError<S> for String
+where
+    S: ScalarValue,
+{
+    fn into_field_error(self) -> FieldError<S> {
+        FieldError::new(self, Value::null())
+    }
+}
+
+impl<'a, S> IntoFieldError<S> for &'a str
+where
+    S: ScalarValue,
+{
+    fn into_field_error(self) -> FieldError<S> {
+        FieldError::new(self, Value::null())
+    }
+}
+
 #[doc(hidden)]
 pub trait IntoResolvable<'a, S, T, C>
 where
@@ -510,6 +531,7 @@ where
             schema: self.schema,
             context: ctx,
             errors: self.errors,
+            cache_policy: self.cache_policy,
             field_path: self.field_path.clone(),
         }
     }
@@ -538,6 +560,7 @@ where
             schema: self.schema,
             context: self.context,
             errors: self.errors,
+            cache_policy: self.cache_policy,
             field_path: Arc::new(FieldPath::Field(
                 field_alias,
                 location,
@@ -564,6 +587,7 @@ where
             schema: self.schema,
             context: self.context,
             errors: self.errors,
+            cache_policy: self.cache_policy,
             field_path: self.field_path.clone(),
         }
     }
@@ -625,6 +649,62 @@ where
         });
     }
 
+    /// Restrict the cache policy of the response, e.g. depending on the
+    /// resolved data
+    ///
+    /// This has no effect on subscriptions.
+    pub fn set_cache_hint(&self, hint: CacheHint) {
+        if let Some(cache_policy) = self.cache_policy {
+            cache_policy.lock().unwrap().restrict(hint);
+        }
+    }
+
+    /// Restrict the cache policy of the response to the hints of a field of
+    /// the given type, about to be resolved
+    ///
+    /// Fields of objects without a hint take the one of the same field of
+    /// the interfaces the object implements.
+    pub(crate) fn set_field_cache_hint(&self, parent_type: &MetaType<S>, field: &Field<S>) {
+        let cache_policy = match self.cache_policy {
+            Some(cache_policy) => cache_policy,
+            None => return,
+        };
+
+        let interface_hint = || match parent_type {
+            MetaType::Object(ObjectMeta {
+                ref interface_names,
+                ..
+            }) => interface_names
+                .iter()
+                .filter_map(|name| self.schema.concrete_type_by_name(name))
+                .filter_map(|iface| iface.field_by_name(&field.name)?.cache_control)
+                .next(),
+            _ => None,
+        };
+        let mut hint = field
+            .cache_control
+            .or_else(interface_hint)
+            .unwrap_or_default();
+        let field_type = self
+            .schema
+            .concrete_type_by_name(field.field_type.innermost_name())
+            .filter(|t| t.is_composite());
+        if let Some(type_hint) = field_type.and_then(MetaType::cache_control) {
+            hint.max_age = hint.max_age.or(type_hint.max_age);
+            hint.scope = hint.scope.max(type_hint.scope);
+        }
+        // Unless told otherwise, root fields and fields returning objects
+        // are not cacheable, while other fields inherit from their parent.
+        let is_root = matches!(*self.field_path, FieldPath::Root(_));
+        if hint.max_age.is_none() && (is_root || field_type.is_some()) {
+            hint.max_age = Some(0);
+        }
+
+        if hint != CacheHint::default() {
+            cache_policy.lock().unwrap().restrict(hint);
+        }
+    }
+
     /// Returns new [`ExecutionError`] at current location
     pub fn new_error(&self, error: FieldError<S>) -> ExecutionError<S> {
         let mut path = Vec::new();
@@ -767,7 +847,7 @@ pub fn execute_validated_query<'a, 'b, QueryT, MutationT, SubscriptionT, S>(
     root_node: &RootNode<QueryT, MutationT, SubscriptionT, S>,
     variables: &Variables<S>,
     context: &QueryT::Context,
-) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+) -> Result<(Value<S>, Vec<ExecutionError<S>>, CachePolicy), GraphQLError<'a>>
 where
     S: ScalarValue,
     QueryT: GraphQLType<S>,
@@ -798,6 +878,7 @@ where
     });
 
     let errors = RwLock::new(Vec::new());
+    let cache_policy = Mutex::new(CachePolicy::default());
     let value;
 
     {
@@ -835,6 +916,7 @@ where
             schema: &root_node.schema,
             context,
             errors: &errors,
+            cache_policy: Some(&cache_policy),
             field_path: Arc::new(FieldPath::Root(operation.start)),
         };
 
@@ -850,7 +932,7 @@ where
     let mut errors = errors.into_inner().unwrap();
     errors.sort();
 
-    Ok((value, errors))
+    Ok((value, errors, cache_policy.into_inner().unwrap()))
 }
 
 /// Create new `Executor` and start asynchronous query execution.
@@ -861,7 +943,7 @@ pub async fn execute_validated_query_async<'a, 'b, QueryT, MutationT, Subscripti
     root_node: &RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
     variables: &Variables<S>,
     context: &QueryT::Context,
-) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+) -> Result<(Value<S>, Vec<ExecutionError<S>>, CachePolicy), GraphQLError<'a>>
 where
     QueryT: GraphQLTypeAsync<S>,
     QueryT::TypeInfo: Sync,
@@ -896,6 +978,7 @@ where
     });
 
     let errors = RwLock::new(Vec::new());
+    let cache_policy = Mutex::new(CachePolicy::default());
     let value;
 
     {
@@ -933,6 +1016,7 @@ where
             schema: &root_node.schema,
             context,
             errors: &errors,
+            cache_policy: Some(&cache_policy),
             field_path: Arc::new(FieldPath::Root(operation.start)),
         };
 
@@ -954,7 +1038,7 @@ where
     let mut errors = errors.into_inner().unwrap();
     errors.sort();
 
-    Ok((value, errors))
+    Ok((value, errors, cache_policy.into_inner().unwrap()))
 }
 
 pub fn get_operation<'b, 'd, 'e, S>(
@@ -1078,6 +1162,7 @@ where
             schema: &root_node.schema,
             context,
             errors: &errors,
+            cache_policy: None,
             field_path: Arc::new(FieldPath::Root(operation.start)),
         };
 
@@ -1115,8 +1200,11 @@ where
         T: GraphQLType<S> + ?Sized,
     {
         if let Some(name) = T::name(info) {
-            let validated_name = name.parse::<Name>().unwrap();
             if !self.types.contains_key(name) {
+                // Invalid names are reported when the schema is validated.
+                let validated_name = name
+                    .parse::<Name>()
+                    .unwrap_or_else(|_| Name::new_unchecked(name));
                 self.insert_placeholder(
                     validated_name.clone(),
                     Type::NonNullNamed(Cow::Owned(name.to_string())),
@@ -1141,6 +1229,8 @@ where
             arguments: None,
             field_type: self.get_type::<T>(info),
             deprecation_status: DeprecationStatus::Current,
+            visibility: None,
+            cache_control: None,
         }
     }
 
@@ -1159,6 +1249,8 @@ where
             arguments: None,
             field_type: self.get_type::<I>(info),
             deprecation_status: DeprecationStatus::Current,
+            visibility: None,
+            cache_control: None,
         }
     }
 
@@ -126,6 +126,7 @@ where
             schema: self.schema,
             context: self.context,
             errors: &self.errors,
+            cache_policy: None,
             field_path: Arc::clone(&self.field_path),
         }
     }
@@ -0,0 +1,168 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, HashSet, VecDeque},
+    sync::Arc,
+};
+
+use indexmap::IndexSet;
+
+use crate::{
+    ast::{FromInputValue, InputValue, ToInputValue},
+    executor::Variables,
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    value::{DefaultScalarValue, Value},
+};
+
+struct Query;
+
+#[crate::graphql_object]
+impl Query {
+    fn deque() -> VecDeque<i32> {
+        vec![1, 2, 3].into_iter().collect()
+    }
+
+    fn btree_set() -> BTreeSet<i32> {
+        vec![3, 1, 2].into_iter().collect()
+    }
+
+    fn index_set() -> IndexSet<String> {
+        vec!["b".to_owned(), "a".to_owned()].into_iter().collect()
+    }
+
+    fn array() -> [i32; 2] {
+        [4, 5]
+    }
+
+    fn shared() -> Arc<String> {
+        Arc::new("shared".to_owned())
+    }
+
+    fn borrowed() -> Cow<'static, str> {
+        Cow::Borrowed("borrowed")
+    }
+
+    fn sum(values: HashSet<i32>) -> i32 {
+        values.into_iter().sum()
+    }
+
+    fn pair(values: [i32; 2]) -> i32 {
+        values[0] * values[1]
+    }
+
+    fn greet(name: Arc<String>, greeting: Cow<'static, str>) -> String {
+        format!("{}, {}", greeting, name)
+    }
+}
+
+async fn run_query(query: &str) -> Value<DefaultScalarValue> {
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    let (result, errs) = crate::execute(query, None, &schema, &Variables::new(), &())
+        .await
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    result
+}
+
+fn ints(values: &[i32]) -> Value<DefaultScalarValue> {
+    Value::list(values.iter().map(|&v| Value::scalar(v)).collect())
+}
+
+#[tokio::test]
+async fn resolves_containers_as_lists() {
+    let result = run_query("{ deque btreeSet indexSet array shared borrowed }").await;
+
+    assert_eq!(
+        result,
+        Value::object(
+            vec![
+                ("deque", ints(&[1, 2, 3])),
+                ("btreeSet", ints(&[1, 2, 3])),
+                (
+                    "indexSet",
+                    Value::list(vec![Value::scalar("b"), Value::scalar("a")]),
+                ),
+                ("array", ints(&[4, 5])),
+                ("shared", Value::scalar("shared")),
+                ("borrowed", Value::scalar("borrowed")),
+            ]
+            .into_iter()
+            .collect()
+        ),
+    );
+}
+
+#[tokio::test]
+async fn accepts_containers_as_arguments() {
+    let result = run_query(
+        r#"{ sum(values: [1, 2, 2, 3]) pair(values: [6, 7]) greet(name: "Ann", greeting: "Hi") }"#,
+    )
+    .await;
+
+    assert_eq!(
+        result,
+        Value::object(
+            vec![
+                ("sum", Value::scalar(6)),
+                ("pair", Value::scalar(42)),
+                ("greet", Value::scalar("Hi, Ann")),
+            ]
+            .into_iter()
+            .collect()
+        ),
+    );
+}
+
+#[test]
+fn array_rejects_wrong_length() {
+    let input: InputValue<DefaultScalarValue> =
+        InputValue::list(vec![InputValue::scalar(1), InputValue::scalar(2)]);
+
+    assert!(<[i32; 2]>::from_input_value(&input).is_ok());
+    assert!(<[i32; 3]>::from_input_value(&input).is_err());
+}
+
+#[test]
+fn single_value_is_coerced_to_list() {
+    let input: InputValue<DefaultScalarValue> = InputValue::scalar(1);
+
+    assert_eq!(
+        VecDeque::<i32>::from_input_value(&input),
+        Ok(vec![1].into_iter().collect()),
+    );
+    assert_eq!(<[i32; 1]>::from_input_value(&input).ok(), Some([1]));
+}
+
+#[test]
+fn converts_back_to_input_values() {
+    let set: BTreeSet<i32> = vec![2, 1].into_iter().collect();
+    let cow: Cow<'_, str> = Cow::Borrowed("x");
+
+    assert_eq!(
+        ToInputValue::<DefaultScalarValue>::to_input_value(&set),
+        InputValue::list(vec![InputValue::scalar(1), InputValue::scalar(2)]),
+    );
+    assert_eq!(
+        ToInputValue::<DefaultScalarValue>::to_input_value(&cow),
+        InputValue::scalar("x"),
+    );
+}
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn smallvec_roundtrips() {
+    use smallvec::SmallVec;
+
+    let input: InputValue<DefaultScalarValue> =
+        InputValue::list(vec![InputValue::scalar(1), InputValue::scalar(2)]);
+    let parsed = SmallVec::<[i32; 4]>::from_input_value(&input).unwrap();
+
+    assert_eq!(parsed.as_slice(), &[1, 2]);
+    assert_eq!(parsed.to_input_value(), input);
+}
@@ -244,7 +244,7 @@ fn default_name_input_value() {
         .collect(),
     );
 
-    let dv: Option<DefaultName> = FromInputValue::from_input_value(&iv);
+    let dv: Option<DefaultName> = FromInputValue::from_input_value(&iv).ok();
 
     assert!(dv.is_some());
 
@@ -29,8 +29,10 @@ impl GraphQLScalar for Scalar {
         Value::scalar(self.0)
     }
 
-    fn from_input_value(v: &InputValue) -> Option<Scalar> {
-        v.as_scalar_value().map(|i: &i32| Scalar(*i))
+    fn from_input_value(v: &InputValue) -> Result<Scalar, String> {
+        v.as_scalar_value()
+            .map(|i: &i32| Scalar(*i))
+            .ok_or_else(|| format!(r#"Expected "Int", found {}"#, v))
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, DefaultScalarValue> {
@@ -1,7 +1,9 @@
+mod containers;
 mod directives;
 mod enums;
 mod executor;
 mod introspection;
+mod nullable;
 mod variables;
 
 // FIXME: re-enable
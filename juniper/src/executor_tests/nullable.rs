@@ -0,0 +1,212 @@
+use crate::{
+    ast::InputValue,
+    executor::Variables,
+    schema::model::RootNode,
+    types::{
+        nullable::Nullable,
+        scalars::{EmptyMutation, EmptySubscription},
+    },
+    value::{DefaultScalarValue, Object, Value},
+    GraphQLInputObject,
+};
+
+struct Query;
+
+#[derive(GraphQLInputObject, Debug)]
+struct UserPatch {
+    name: Option<String>,
+    nickname: Nullable<String>,
+}
+
+#[crate::graphql_object]
+impl Query {
+    fn nickname(nickname: Nullable<String>) -> String {
+        format!("{:?}", nickname)
+    }
+
+    fn patch(patch: UserPatch) -> String {
+        format!("{:?}", patch)
+    }
+}
+
+async fn run_variable_query<F>(query: &str, vars: Variables<DefaultScalarValue>, f: F)
+where
+    F: Fn(&Object<DefaultScalarValue>) -> (),
+{
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    let (result, errs) = crate::execute(query, None, &schema, &vars, &())
+        .await
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+
+    println!("Result: {:?}", result);
+
+    let obj = result.as_object_value().expect("Result is not an object");
+
+    f(obj);
+}
+
+async fn run_query<F>(query: &str, f: F)
+where
+    F: Fn(&Object<DefaultScalarValue>) -> (),
+{
+    run_variable_query(query, Variables::new(), f).await;
+}
+
+#[tokio::test]
+async fn argument_omitted() {
+    run_query("{ nickname }", |result| {
+        assert_eq!(
+            result.get_field_value("nickname"),
+            Some(&Value::scalar("ImplicitNull")),
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn argument_explicitly_null() {
+    run_query("{ nickname(nickname: null) }", |result| {
+        assert_eq!(
+            result.get_field_value("nickname"),
+            Some(&Value::scalar("ExplicitNull")),
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn argument_provided() {
+    run_query(r#"{ nickname(nickname: "Bob") }"#, |result| {
+        assert_eq!(
+            result.get_field_value("nickname"),
+            Some(&Value::scalar(r#"Some("Bob")"#)),
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn argument_variable_not_provided() {
+    run_query(
+        "query q($nick: String) { nickname(nickname: $nick) }",
+        |result| {
+            assert_eq!(
+                result.get_field_value("nickname"),
+                Some(&Value::scalar("ImplicitNull")),
+            );
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn argument_variable_null() {
+    run_variable_query(
+        "query q($nick: String) { nickname(nickname: $nick) }",
+        vec![("nick".to_owned(), InputValue::null())]
+            .into_iter()
+            .collect(),
+        |result| {
+            assert_eq!(
+                result.get_field_value("nickname"),
+                Some(&Value::scalar("ExplicitNull")),
+            );
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn input_field_omitted() {
+    run_query(r#"{ patch(patch: {name: "Bob"}) }"#, |result| {
+        assert_eq!(
+            result.get_field_value("patch"),
+            Some(&Value::scalar(
+                r#"UserPatch { name: Some("Bob"), nickname: ImplicitNull }"#
+            )),
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn input_field_explicitly_null() {
+    run_query(r#"{ patch(patch: {nickname: null}) }"#, |result| {
+        assert_eq!(
+            result.get_field_value("patch"),
+            Some(&Value::scalar(
+                r#"UserPatch { name: None, nickname: ExplicitNull }"#
+            )),
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn input_field_provided() {
+    run_query(r#"{ patch(patch: {nickname: "Bobby"}) }"#, |result| {
+        assert_eq!(
+            result.get_field_value("patch"),
+            Some(&Value::scalar(
+                r#"UserPatch { name: None, nickname: Some("Bobby") }"#
+            )),
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn input_field_variable_not_provided() {
+    run_query(
+        "query q($nick: String) { patch(patch: {nickname: $nick}) }",
+        |result| {
+            assert_eq!(
+                result.get_field_value("patch"),
+                Some(&Value::scalar(
+                    r#"UserPatch { name: None, nickname: ImplicitNull }"#
+                )),
+            );
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn input_object_variable() {
+    run_variable_query(
+        "query q($patch: UserPatch!) { patch(patch: $patch) }",
+        vec![(
+            "patch".to_owned(),
+            InputValue::object(vec![("nickname", InputValue::null())].into_iter().collect()),
+        )]
+        .into_iter()
+        .collect(),
+        |result| {
+            assert_eq!(
+                result.get_field_value("patch"),
+                Some(&Value::scalar(
+                    r#"UserPatch { name: None, nickname: ExplicitNull }"#
+                )),
+            );
+        },
+    )
+    .await;
+}
+
+#[test]
+fn nullable_helpers() {
+    assert_eq!(Nullable::<i32>::default(), Nullable::ImplicitNull);
+    assert_eq!(Nullable::Some(1).explicit(), Some(Some(1)));
+    assert_eq!(Nullable::<i32>::ExplicitNull.explicit(), Some(None));
+    assert_eq!(Nullable::<i32>::ImplicitNull.explicit(), None);
+    assert_eq!(Nullable::<i32>::ExplicitNull.some(), None);
+    assert!(Nullable::<i32>::ExplicitNull.is_null());
+    assert!(!Nullable::<i32>::ExplicitNull.is_implicit_null());
+}
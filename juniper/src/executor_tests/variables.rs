@@ -1,5 +1,10 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    path::PathBuf,
+};
+
 use crate::{
-    ast::InputValue,
+    ast::{FromInputValue, InputValue},
     executor::Variables,
     parser::SourcePosition,
     schema::model::RootNode,
@@ -21,14 +26,14 @@ impl GraphQLScalar for TestComplexScalar {
         Value::scalar(String::from("SerializedValue"))
     }
 
-    fn from_input_value(v: &InputValue) -> Option<TestComplexScalar> {
+    fn from_input_value(v: &InputValue) -> Result<TestComplexScalar, String> {
         if let Some(s) = v.as_scalar_value::<String>() {
             if *s == "SerializedValue" {
-                return Some(TestComplexScalar);
+                return Ok(TestComplexScalar);
             }
         }
 
-        None
+        Err(format!(r#"Expected "SerializedValue", found {}"#, v))
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, DefaultScalarValue> {
@@ -124,6 +129,50 @@ impl TestType {
     fn float_input(value: f64) -> String {
         format!("value: {}", value)
     }
+
+    fn long_input(value: i64) -> i64 {
+        value
+    }
+
+    fn unsigned_long_input(value: u64) -> u64 {
+        value
+    }
+
+    fn byte_input(value: i8) -> i8 {
+        value
+    }
+
+    fn unsigned_byte_input(value: u8) -> u8 {
+        value
+    }
+
+    fn short_input(value: i16) -> i16 {
+        value
+    }
+
+    fn unsigned_short_input(value: u16) -> u16 {
+        value
+    }
+
+    fn unsigned_int_input(value: u32) -> u32 {
+        value
+    }
+
+    fn char_input(value: char) -> char {
+        value
+    }
+
+    fn ip_input(value: IpAddr) -> IpAddr {
+        value
+    }
+
+    fn socket_input(value: SocketAddr) -> SocketAddr {
+        value
+    }
+
+    fn path_input(value: PathBuf) -> PathBuf {
+        value
+    }
 }
 
 async fn run_variable_query<F>(query: &str, vars: Variables<DefaultScalarValue>, f: F)
@@ -350,6 +399,86 @@ async fn variable_error_on_incorrect_type() {
     );
 }
 
+#[tokio::test]
+async fn inline_error_on_rejected_scalar() {
+    let schema = RootNode::new(
+        TestType,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    let query = r#"{ fieldWithObjectInput(input: {c: "baz", d: "NotSerialized"}) }"#;
+    let vars = vec![].into_iter().collect();
+
+    let error = crate::execute(query, None, &schema, &vars, &())
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error,
+        ValidationError(vec![RuleError::new(
+            r#"Invalid value for argument "input", expected type "TestInputObject": In field "d": Expected "SerializedValue", found "NotSerialized""#,
+            &[SourcePosition::new(30, 0, 30)],
+        ),])
+    );
+}
+
+#[tokio::test]
+async fn variable_error_on_rejected_scalar() {
+    let schema = RootNode::new(
+        TestType,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    let query = r#"query q($input: TestInputObject) { fieldWithObjectInput(input: $input) }"#;
+    let vars = vec![(
+        "input".to_owned(),
+        InputValue::object(
+            vec![
+                ("c", InputValue::scalar("baz")),
+                ("d", InputValue::scalar("NotSerialized")),
+            ]
+            .into_iter()
+            .collect(),
+        ),
+    )]
+    .into_iter()
+    .collect();
+
+    let error = crate::execute(query, None, &schema, &vars, &())
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error,
+        ValidationError(vec![RuleError::new(
+            r#"Variable "$input" got invalid value. In field "d": Expected "SerializedValue", found "NotSerialized"."#,
+            &[SourcePosition::new(8, 0, 8)],
+        ),])
+    );
+}
+
+#[test]
+fn input_object_reports_rejected_field() {
+    let value: InputValue<DefaultScalarValue> = InputValue::object(
+        vec![
+            ("c", InputValue::scalar("baz")),
+            ("d", InputValue::scalar("NotSerialized")),
+        ]
+        .into_iter()
+        .collect(),
+    );
+
+    let error = <TestInputObject as FromInputValue<DefaultScalarValue>>::from_input_value(&value)
+        .unwrap_err();
+
+    assert_eq!(
+        error.message(),
+        r#"In field "d": Expected "SerializedValue", found "NotSerialized""#,
+    );
+}
+
 #[tokio::test]
 async fn variable_error_on_omit_non_null() {
     let schema = RootNode::new(
@@ -1224,7 +1353,7 @@ mod integers {
         assert_eq!(
             error,
             ValidationError(vec![RuleError::new(
-                r#"Variable "$var" got invalid value. Expected "Int"."#,
+                r#"Variable "$var" got invalid value. Expected "Int", found 10."#,
                 &[SourcePosition::new(8, 0, 8)],
             )])
         );
@@ -1250,7 +1379,7 @@ mod integers {
         assert_eq!(
             error,
             ValidationError(vec![RuleError::new(
-                r#"Variable "$var" got invalid value. Expected "Int"."#,
+                r#"Variable "$var" got invalid value. Expected "Int", found "10"."#,
                 &[SourcePosition::new(8, 0, 8)],
             )])
         );
@@ -1314,9 +1443,268 @@ mod floats {
         assert_eq!(
             error,
             ValidationError(vec![RuleError::new(
-                r#"Variable "$var" got invalid value. Expected "Float"."#,
+                r#"Variable "$var" got invalid value. Expected "Float", found "10"."#,
                 &[SourcePosition::new(8, 0, 8)],
             )])
         );
     }
 }
+
+mod longs {
+    use super::*;
+
+    fn long<T: Into<DefaultScalarValue>>(v: T) -> Value {
+        Value::scalar(v)
+    }
+
+    #[tokio::test]
+    async fn values_beyond_int_should_work() {
+        run_query(
+            r#"{ a: longInput(value: -9223372036854775808) b: unsignedLongInput(value: 18446744073709551615) }"#,
+            |result| {
+                assert_eq!(
+                    result.get_field_value("a"),
+                    Some(&long(i64::min_value()))
+                );
+                assert_eq!(
+                    result.get_field_value("b"),
+                    Some(&long(u64::max_value()))
+                );
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn coercion_from_strings_should_work() {
+        run_query(r#"{ longInput(value: "-3000000000") }"#, |result| {
+            assert_eq!(
+                result.get_field_value("longInput"),
+                Some(&long(-3_000_000_000i64))
+            );
+        })
+        .await;
+
+        run_variable_query(
+            r#"query q($var: UnsignedLong!) { unsignedLongInput(value: $var) }"#,
+            vec![("var".to_owned(), InputValue::scalar("3000000000"))]
+                .into_iter()
+                .collect(),
+            |result| {
+                assert_eq!(
+                    result.get_field_value("unsignedLongInput"),
+                    Some(&long(3_000_000_000u64))
+                );
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn coercion_from_integers_should_work() {
+        run_variable_query(
+            r#"query q($var: Long!) { longInput(value: $var) }"#,
+            vec![("var".to_owned(), InputValue::scalar(-1))]
+                .into_iter()
+                .collect(),
+            |result| {
+                assert_eq!(result.get_field_value("longInput"), Some(&long(-1i64)));
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn does_not_coerce_negative_into_unsigned() {
+        let schema = RootNode::new(
+            TestType,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let query = r#"query q($var: UnsignedLong!) { unsignedLongInput(value: $var) }"#;
+        let vars = vec![("var".to_owned(), InputValue::scalar(-1))]
+            .into_iter()
+            .collect();
+
+        let error = crate::execute(query, None, &schema, &vars, &())
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            ValidationError(vec![RuleError::new(
+                r#"Variable "$var" got invalid value. Expected "UnsignedLong", found -1."#,
+                &[SourcePosition::new(8, 0, 8)],
+            )])
+        );
+    }
+
+    #[tokio::test]
+    async fn does_not_coerce_from_float() {
+        let schema = RootNode::new(
+            TestType,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let query = r#"query q($var: Long!) { longInput(value: $var) }"#;
+        let vars = vec![("var".to_owned(), InputValue::scalar(10.0))]
+            .into_iter()
+            .collect();
+
+        let error = crate::execute(query, None, &schema, &vars, &())
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            ValidationError(vec![RuleError::new(
+                r#"Variable "$var" got invalid value. Expected "Long", found 10."#,
+                &[SourcePosition::new(8, 0, 8)],
+            )])
+        );
+    }
+}
+
+mod small_ints {
+    use super::*;
+
+    #[tokio::test]
+    async fn values_in_range_should_work() {
+        run_query(
+            r#"{
+                a: byteInput(value: -128)
+                b: unsignedByteInput(value: 255)
+                c: shortInput(value: -32768)
+                d: unsignedShortInput(value: 65535)
+                e: unsignedIntInput(value: 4294967295)
+            }"#,
+            |result| {
+                assert_eq!(result.get_field_value("a"), Some(&Value::scalar(-128)));
+                assert_eq!(result.get_field_value("b"), Some(&Value::scalar(255)));
+                assert_eq!(result.get_field_value("c"), Some(&Value::scalar(-32768)));
+                assert_eq!(result.get_field_value("d"), Some(&Value::scalar(65535)));
+                assert_eq!(
+                    result.get_field_value("e"),
+                    Some(&Value::scalar(4_294_967_295i64))
+                );
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn out_of_range_literal_is_a_validation_error() {
+        let schema = RootNode::new(
+            TestType,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let query = r#"{ unsignedByteInput(value: 256) }"#;
+        let vars = vec![].into_iter().collect();
+
+        let error = crate::execute(query, None, &schema, &vars, &())
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            ValidationError(vec![RuleError::new(
+                r#"Invalid value for argument "value", expected type "UnsignedByte!": Expected "UnsignedByte" between 0 and 255, found 256"#,
+                &[SourcePosition::new(27, 0, 27)],
+            )])
+        );
+    }
+
+    #[tokio::test]
+    async fn out_of_range_variable_is_a_validation_error() {
+        let schema = RootNode::new(
+            TestType,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let query = r#"query q($var: Short!) { shortInput(value: $var) }"#;
+        let vars = vec![("var".to_owned(), InputValue::scalar(40000))]
+            .into_iter()
+            .collect();
+
+        let error = crate::execute(query, None, &schema, &vars, &())
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            ValidationError(vec![RuleError::new(
+                r#"Variable "$var" got invalid value. Expected "Short" between -32768 and 32767, found 40000."#,
+                &[SourcePosition::new(8, 0, 8)],
+            )])
+        );
+    }
+}
+
+mod std_strings {
+    use super::*;
+
+    #[tokio::test]
+    async fn string_scalars_should_roundtrip() {
+        run_query(
+            r#"{
+                a: charInput(value: "λ")
+                b: ipInput(value: "::1")
+                c: socketInput(value: "127.0.0.1:8080")
+                d: pathInput(value: "/tmp/file.txt")
+            }"#,
+            |result| {
+                assert_eq!(result.get_field_value("a"), Some(&Value::scalar("λ")));
+                assert_eq!(result.get_field_value("b"), Some(&Value::scalar("::1")));
+                assert_eq!(
+                    result.get_field_value("c"),
+                    Some(&Value::scalar("127.0.0.1:8080"))
+                );
+                assert_eq!(
+                    result.get_field_value("d"),
+                    Some(&Value::scalar("/tmp/file.txt"))
+                );
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn invalid_strings_are_validation_errors() {
+        let schema = RootNode::new(
+            TestType,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let query = r#"query q($ip: IpAddr!, $char: Char!) { ipInput(value: $ip) charInput(value: $char) }"#;
+        let vars = vec![
+            ("ip".to_owned(), InputValue::scalar("256.0.0.1")),
+            ("char".to_owned(), InputValue::scalar("ab")),
+        ]
+        .into_iter()
+        .collect();
+
+        let error = crate::execute(query, None, &schema, &vars, &())
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            ValidationError(vec![
+                RuleError::new(
+                    r#"Variable "$ip" got invalid value. Expected IP address, found "256.0.0.1": invalid IP address syntax."#,
+                    &[SourcePosition::new(8, 0, 8)],
+                ),
+                RuleError::new(
+                    r#"Variable "$char" got invalid value. Expected a single character, found "ab"."#,
+                    &[SourcePosition::new(22, 0, 22)],
+                ),
+            ])
+        );
+    }
+}
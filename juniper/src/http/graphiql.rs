@@ -1,5 +1,7 @@
 //! Utility module to generate a GraphiQL interface
 
+use std::fmt::Write as _;
+
 /// Generate the HTML source to show a GraphiQL interface
 ///
 /// The subscriptions endpoint URL can optionally be provided. For example:
@@ -8,17 +10,160 @@
 /// # use juniper::http::graphiql::graphiql_source;
 /// let graphiql = graphiql_source("/graphql", Some("ws://localhost:8080/subscriptions"));
 /// ```
+///
+/// Use [`GraphiQLSettings`] to configure the interface further.
 pub fn graphiql_source(
     graphql_endpoint_url: &str,
     subscriptions_endpoint_url: Option<&str>,
 ) -> String {
-    let subscriptions_endpoint = if let Some(sub_url) = subscriptions_endpoint_url {
-        sub_url
-    } else {
-        ""
-    };
+    let mut settings = GraphiQLSettings::new(graphql_endpoint_url);
+    if let Some(url) = subscriptions_endpoint_url {
+        settings = settings.subscriptions_endpoint(url);
+    }
+    settings.source()
+}
 
-    let stylesheet_source = r#"
+/// The `credentials` mode of the requests sent by GraphiQL or GraphQL
+/// Playground, i.e. whether cookies are sent along with them
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RequestCredentials {
+    /// Never send cookies
+    Omit,
+    /// Send cookies to the origin of the page only
+    SameOrigin,
+    /// Always send cookies, even to other origins
+    Include,
+}
+
+impl RequestCredentials {
+    /// The value of the mode in the Fetch API
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RequestCredentials::Omit => "omit",
+            RequestCredentials::SameOrigin => "same-origin",
+            RequestCredentials::Include => "include",
+        }
+    }
+}
+
+/// Settings of a GraphiQL interface
+///
+/// Assets are loaded from public CDNs by default, at pinned versions which can
+/// be changed. They can also be self-hosted under a base URL given with
+/// [`assets_url`](GraphiQLSettings::assets_url).
+///
+/// ```
+/// # use juniper::http::graphiql::{GraphiQLSettings, RequestCredentials};
+/// let graphiql = GraphiQLSettings::new("/graphql")
+///     .subscriptions_endpoint("/subscriptions")
+///     .header("Authorization", "Bearer token")
+///     .default_query("{ apiVersion }")
+///     .editor_theme("dracula")
+///     .credentials(RequestCredentials::SameOrigin)
+///     .assets_url("/static/graphiql")
+///     .source();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphiQLSettings {
+    graphql_endpoint_url: String,
+    subscriptions_endpoint_url: Option<String>,
+    headers: Vec<(String, String)>,
+    default_query: Option<String>,
+    editor_theme: Option<String>,
+    credentials: RequestCredentials,
+    graphiql_version: String,
+    react_version: String,
+    assets_url: Option<String>,
+}
+
+impl GraphiQLSettings {
+    /// Settings of a GraphiQL interface sending queries to the given URL
+    pub fn new(graphql_endpoint_url: &str) -> Self {
+        GraphiQLSettings {
+            graphql_endpoint_url: graphql_endpoint_url.to_owned(),
+            subscriptions_endpoint_url: None,
+            headers: vec![],
+            default_query: None,
+            editor_theme: None,
+            credentials: RequestCredentials::Include,
+            graphiql_version: "0.17.5".to_owned(),
+            react_version: "16.10.2".to_owned(),
+            assets_url: None,
+        }
+    }
+
+    /// Send subscriptions over WebSocket to the given URL
+    ///
+    /// Relative URLs are resolved against the host of the page.
+    pub fn subscriptions_endpoint(mut self, url: &str) -> Self {
+        self.subscriptions_endpoint_url = Some(url.to_owned());
+        self
+    }
+
+    /// Send the given header with every query, e.g. an authentication token
+    ///
+    /// Headers are sent as connection parameters of subscriptions.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// The query shown to users who haven't edited one yet
+    pub fn default_query(mut self, query: &str) -> Self {
+        self.default_query = Some(query.to_owned());
+        self
+    }
+
+    /// Use the CodeMirror theme of the given name in the editors, e.g.
+    /// `"dracula"`
+    pub fn editor_theme(mut self, theme: &str) -> Self {
+        self.editor_theme = Some(theme.to_owned());
+        self
+    }
+
+    /// Send queries with the given `credentials` mode, `Include` by default
+    pub fn credentials(mut self, credentials: RequestCredentials) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    /// Load the given version of GraphiQL from the CDN, `0.17.5` by default
+    pub fn graphiql_version(mut self, version: &str) -> Self {
+        self.graphiql_version = version.to_owned();
+        self
+    }
+
+    /// Load the given version of React from the CDN, `16.10.2` by default
+    pub fn react_version(mut self, version: &str) -> Self {
+        self.react_version = version.to_owned();
+        self
+    }
+
+    /// Load all assets from the given base URL instead of public CDNs
+    ///
+    /// The following files are expected under it:
+    /// - `graphiql.min.css` and `graphiql.min.js`, from the `graphiql` package
+    /// - `react.production.min.js` and `react-dom.production.min.js`
+    /// - `fetch.js`, the `whatwg-fetch` polyfill
+    /// - `subscriptions-transport-ws.js` and `graphiql-subscriptions-fetcher.js`,
+    ///   the `browser/client.js` of these packages
+    /// - `theme/<name>.css`, the CodeMirror editor theme if one is set
+    pub fn assets_url(mut self, url: &str) -> Self {
+        self.assets_url = Some(url.trim_end_matches('/').to_owned());
+        self
+    }
+
+    /// The URL of the given asset, either self-hosted or on a CDN
+    fn asset(&self, file: &str, cdn_url: String) -> String {
+        match &self.assets_url {
+            Some(base) => format!("{}/{}", base, file),
+            None => cdn_url,
+        }
+    }
+
+    /// Generate the HTML source of the interface
+    pub fn source(&self) -> String {
+        let stylesheet_source = r#"
     <style>
         html, body, #app {
             height: 100%;
@@ -28,11 +173,11 @@ pub fn graphiql_source(
         }
     </style>
     "#;
-    let fetcher_source = r#"
+        let fetcher_source = r#"
     <script>
         if (usingSubscriptions) {
             var subscriptionEndpoint = normalizeSubscriptionEndpoint(GRAPHQL_URL, GRAPHQL_SUBSCRIPTIONS_URL);
-            var subscriptionsClient = new window.SubscriptionsTransportWs.SubscriptionClient(subscriptionEndpoint, { reconnect: true });
+            var subscriptionsClient = new window.SubscriptionsTransportWs.SubscriptionClient(subscriptionEndpoint, { reconnect: true, connectionParams: GRAPHQL_HEADERS });
         }
 
         function normalizeSubscriptionEndpoint(endpoint, subscriptionEndpoint) {
@@ -51,13 +196,17 @@ pub fn graphiql_source(
         }
 
         function graphQLFetcher(params) {
+            var headers = {
+                'Accept': 'application/json',
+                'Content-Type': 'application/json',
+            };
+            for (var name in GRAPHQL_HEADERS) {
+                headers[name] = GRAPHQL_HEADERS[name];
+            }
             return fetch(GRAPHQL_URL, {
                 method: 'post',
-                headers: {
-                    'Accept': 'application/json',
-                    'Content-Type': 'application/json',
-                },
-                credentials: 'include',
+                headers: headers,
+                credentials: GRAPHQL_CREDENTIALS,
                 body: JSON.stringify(params)
             }).then(function (response) {
                 return response.text();
@@ -75,39 +224,226 @@ pub fn graphiql_source(
         ReactDOM.render(
             React.createElement(GraphiQL, {
                 fetcher,
+                defaultQuery: GRAPHIQL_DEFAULT_QUERY,
+                editorTheme: GRAPHIQL_EDITOR_THEME,
             }),
             document.querySelector('#app'));
     </script>
     "#;
 
-    format!(
-        r#"
+        let graphiql = |file: &str| {
+            self.asset(
+                file,
+                format!(
+                    "//cdn.jsdelivr.net/npm/graphiql@{}/{}",
+                    self.graphiql_version, file,
+                ),
+            )
+        };
+        let react = |file: &str| {
+            self.asset(
+                file,
+                format!(
+                    "//cdnjs.cloudflare.com/ajax/libs/{}/{}/umd/{}",
+                    file.trim_end_matches(".production.min.js"),
+                    self.react_version,
+                    file,
+                ),
+            )
+        };
+        let theme_stylesheet = match &self.editor_theme {
+            Some(theme) => {
+                let file = format!("theme/{}.css", theme);
+                let url = format!("//cdn.jsdelivr.net/npm/codemirror@5/{}", file);
+                format!(
+                    r#"<link rel="stylesheet" type="text/css" href="{}">"#,
+                    html_attr(&self.asset(&file, url)),
+                )
+            }
+            None => String::new(),
+        };
+
+        format!(
+            r#"
 <!DOCTYPE html>
 <html>
 <head>
     <title>GraphQL</title>
     {stylesheet_source}
-    <link rel="stylesheet" type="text/css" href="//cdn.jsdelivr.net/npm/graphiql@0.17.5/graphiql.min.css">
+    <link rel="stylesheet" type="text/css" href="{graphiql_css}">
+    {theme_stylesheet}
 </head>
 <body>
     <div id="app"></div>
-    <script src="//cdnjs.cloudflare.com/ajax/libs/fetch/2.0.3/fetch.js"></script>
-    <script src="//unpkg.com/subscriptions-transport-ws@0.8.3/browser/client.js"></script>
-    <script src="//unpkg.com/graphiql-subscriptions-fetcher@0.0.2/browser/client.js"></script>
-    <script src="//cdnjs.cloudflare.com/ajax/libs/react/16.10.2/umd/react.production.min.js"></script>
-    <script src="//cdnjs.cloudflare.com/ajax/libs/react-dom/16.10.2/umd/react-dom.production.min.js"></script>
-    <script src="//cdn.jsdelivr.net/npm/graphiql@0.17.5/graphiql.min.js"></script>
-    <script>var GRAPHQL_URL = '{graphql_url}';</script>
+    <script src="{fetch_js}"></script>
+    <script src="{subscriptions_transport_js}"></script>
+    <script src="{subscriptions_fetcher_js}"></script>
+    <script src="{react_js}"></script>
+    <script src="{react_dom_js}"></script>
+    <script src="{graphiql_js}"></script>
+    <script>var GRAPHQL_URL = {graphql_url};</script>
     <script>var usingSubscriptions = {using_subscriptions};</script>
-    <script>var GRAPHQL_SUBSCRIPTIONS_URL = '{graphql_subscriptions_url}';</script>
+    <script>var GRAPHQL_SUBSCRIPTIONS_URL = {graphql_subscriptions_url};</script>
+    <script>var GRAPHQL_HEADERS = {headers};</script>
+    <script>var GRAPHQL_CREDENTIALS = {credentials};</script>
+    <script>var GRAPHIQL_DEFAULT_QUERY = {default_query};</script>
+    <script>var GRAPHIQL_EDITOR_THEME = {editor_theme};</script>
     {fetcher_source}
 </body>
 </html>
 "#,
-        graphql_url = graphql_endpoint_url,
-        stylesheet_source = stylesheet_source,
-        fetcher_source = fetcher_source,
-        graphql_subscriptions_url = subscriptions_endpoint,
-        using_subscriptions = subscriptions_endpoint_url.is_some(),
-    )
+            stylesheet_source = stylesheet_source,
+            graphiql_css = html_attr(&graphiql("graphiql.min.css")),
+            theme_stylesheet = theme_stylesheet,
+            fetch_js = html_attr(&self.asset(
+                "fetch.js",
+                "//cdnjs.cloudflare.com/ajax/libs/fetch/2.0.3/fetch.js".to_owned(),
+            )),
+            subscriptions_transport_js = html_attr(&self.asset(
+                "subscriptions-transport-ws.js",
+                "//unpkg.com/subscriptions-transport-ws@0.8.3/browser/client.js".to_owned(),
+            )),
+            subscriptions_fetcher_js = html_attr(&self.asset(
+                "graphiql-subscriptions-fetcher.js",
+                "//unpkg.com/graphiql-subscriptions-fetcher@0.0.2/browser/client.js".to_owned(),
+            )),
+            react_js = html_attr(&react("react.production.min.js")),
+            react_dom_js = html_attr(&react("react-dom.production.min.js")),
+            graphiql_js = html_attr(&graphiql("graphiql.min.js")),
+            graphql_url = js_string(&self.graphql_endpoint_url),
+            using_subscriptions = self.subscriptions_endpoint_url.is_some(),
+            graphql_subscriptions_url = js_string(
+                self.subscriptions_endpoint_url
+                    .as_deref()
+                    .unwrap_or_default()
+            ),
+            headers = js_object(&self.headers),
+            credentials = js_string(self.credentials.as_str()),
+            default_query = js_option(self.default_query.as_deref()),
+            editor_theme = js_option(self.editor_theme.as_deref()),
+            fetcher_source = fetcher_source,
+        )
+    }
+}
+
+/// The given text as a single-quoted JavaScript string, safe to embed in a
+/// `<script>` element
+pub(super) fn js_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('\'');
+    for c in text.chars() {
+        match c {
+            '\'' => quoted.push_str("\\'"),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '<' => quoted.push_str("\\x3C"),
+            c if c.is_control() || c == '\u{2028}' || c == '\u{2029}' => {
+                write!(quoted, "\\u{:04x}", c as u32).unwrap()
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// The given text as a JavaScript string, or `undefined`
+pub(super) fn js_option(text: Option<&str>) -> String {
+    text.map_or_else(|| "undefined".to_owned(), js_string)
+}
+
+/// The given entries as a JavaScript object of strings
+pub(super) fn js_object(entries: &[(String, String)]) -> String {
+    let entries: Vec<_> = entries
+        .iter()
+        .map(|(name, value)| format!("{}: {}", js_string(name), js_string(value)))
+        .collect();
+    format!("{{{}}}", entries.join(", "))
+}
+
+/// The given text escaped for a double-quoted HTML attribute
+pub(super) fn html_attr(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{graphiql_source, js_string, GraphiQLSettings, RequestCredentials};
+
+    #[test]
+    fn escapes_js_strings() {
+        assert_eq!(js_string("/graphql"), "'/graphql'");
+        assert_eq!(js_string("it's\n</script>\\"), r"'it\'s\n\x3C/script>\\'");
+    }
+
+    #[test]
+    fn keeps_defaults() {
+        let source = graphiql_source("/graphql", Some("/subscriptions"));
+
+        assert!(source.contains("<script>var GRAPHQL_URL = '/graphql';</script>"));
+        assert!(source.contains("<script>var usingSubscriptions = true;</script>"));
+        assert!(source.contains("<script>var GRAPHQL_HEADERS = {};</script>"));
+        assert!(source.contains("<script>var GRAPHQL_CREDENTIALS = 'include';</script>"));
+        assert!(source.contains("<script>var GRAPHIQL_DEFAULT_QUERY = undefined;</script>"));
+        assert!(
+            source.contains(r#"href="//cdn.jsdelivr.net/npm/graphiql@0.17.5/graphiql.min.css""#)
+        );
+        assert!(source.contains(
+            r#"src="//cdnjs.cloudflare.com/ajax/libs/react-dom/16.10.2/umd/react-dom.production.min.js""#
+        ));
+        assert!(!source.contains("codemirror"));
+    }
+
+    #[test]
+    fn uses_settings() {
+        let source = GraphiQLSettings::new("/graphql")
+            .header("Authorization", "Bearer 't'")
+            .header("X-Tenant", "1")
+            .default_query("{ a }")
+            .editor_theme("dracula")
+            .credentials(RequestCredentials::Omit)
+            .graphiql_version("1.0.0")
+            .react_version("17.0.0")
+            .source();
+
+        assert!(source.contains("<script>var usingSubscriptions = false;</script>"));
+        assert!(source.contains(
+            r"<script>var GRAPHQL_HEADERS = {'Authorization': 'Bearer \'t\'', 'X-Tenant': '1'};</script>"
+        ));
+        assert!(source.contains("<script>var GRAPHQL_CREDENTIALS = 'omit';</script>"));
+        assert!(source.contains("<script>var GRAPHIQL_DEFAULT_QUERY = '{ a }';</script>"));
+        assert!(source.contains("<script>var GRAPHIQL_EDITOR_THEME = 'dracula';</script>"));
+        assert!(source.contains(r#"href="//cdn.jsdelivr.net/npm/codemirror@5/theme/dracula.css""#));
+        assert!(source.contains(r#"src="//cdn.jsdelivr.net/npm/graphiql@1.0.0/graphiql.min.js""#));
+        assert!(source.contains(
+            r#"src="//cdnjs.cloudflare.com/ajax/libs/react/17.0.0/umd/react.production.min.js""#
+        ));
+    }
+
+    #[test]
+    fn self_hosts_assets() {
+        let source = GraphiQLSettings::new("/graphql")
+            .editor_theme("dracula")
+            .assets_url("/static/graphiql/")
+            .source();
+
+        for file in &[
+            "graphiql.min.css",
+            "graphiql.min.js",
+            "react.production.min.js",
+            "react-dom.production.min.js",
+            "fetch.js",
+            "subscriptions-transport-ws.js",
+            "graphiql-subscriptions-fetcher.js",
+            "theme/dracula.css",
+        ] {
+            assert!(source.contains(&format!("\"/static/graphiql/{}\"", file)));
+        }
+        assert!(!source.contains("//cdn"));
+        assert!(!source.contains("//unpkg"));
+    }
 }
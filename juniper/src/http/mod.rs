@@ -1,8 +1,19 @@
 //! Utilities for building HTTP endpoints in a library-agnostic manner
 
 pub mod graphiql;
+#[cfg(feature = "serde_json")]
+mod multipart;
+#[cfg(feature = "serde_json")]
+pub mod parse;
+pub mod persisted_queries;
 pub mod playground;
+pub mod response_cache;
+pub mod safelist;
+pub mod upload;
 
+use std::{fmt, sync::Arc};
+
+use futures::{future, stream, StreamExt as _};
 use serde::{
     de,
     ser::{self, SerializeMap},
@@ -10,30 +21,79 @@ use serde::{
 };
 
 use crate::{
-    ast::InputValue,
-    executor::{ExecutionError, ValuesStream},
+    ast::{InputValue, OperationType},
+    executor::{CacheHint, CachePolicy, ExecutionError, ValuesStream},
+    parser::{Lexer, Token},
+    validation::RuleError,
     value::{DefaultScalarValue, ScalarValue},
     FieldError, GraphQLError, GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, RootNode,
     Value, Variables,
 };
 
+use self::{
+    persisted_queries::{PersistedQuery, PersistedQueryStore},
+    response_cache::{CachedResponse, ResponseCache},
+    safelist::Safelist,
+    upload::Uploads,
+};
+
 /// The expected structure of the decoded JSON document for either POST or GET requests.
 ///
 /// For POST, you can use Serde to deserialize the incoming JSON data directly
 /// into this struct - it derives Deserialize for exactly this reason.
 ///
-/// For GET, you will need to parse the query string and extract "query",
-/// "operationName", and "variables" manually.
+/// For GET, use [`parse::parse_get_request`][3] to extract "query",
+/// "operationName", and "variables" from the query string.
+///
+/// The query text may be omitted in favor of a [persisted query][1] hash, or
+/// the `id` of an operation of a [safelist][2].
+///
+/// [1]: persisted_queries
+/// [2]: safelist
+/// [3]: parse::parse_get_request
 #[derive(Deserialize, Clone, Serialize, PartialEq, Debug)]
 pub struct GraphQLRequest<S = DefaultScalarValue>
 where
     S: ScalarValue,
 {
-    query: String,
+    query: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
     #[serde(rename = "operationName")]
     operation_name: Option<String>,
     #[serde(bound(deserialize = "InputValue<S>: Deserialize<'de> + Serialize"))]
     variables: Option<InputValue<S>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    extensions: Option<GraphQLRequestExtensions>,
+    /// Whether the request was sent with GET, so mutations must be refused.
+    #[serde(skip)]
+    forbid_mutations: bool,
+    /// Files sent along with a multipart request, referred to by its variables.
+    #[serde(skip)]
+    uploads: Option<Arc<Uploads>>,
+}
+
+/// The extensions of a request understood by Juniper
+#[derive(Deserialize, Clone, Serialize, PartialEq, Debug)]
+struct GraphQLRequestExtensions {
+    #[serde(rename = "persistedQuery")]
+    persisted_query: Option<PersistedQuery>,
+}
+
+/// The query text of a request
+enum Document<'a> {
+    /// Borrowed from the request or the safelist of the schema.
+    Borrowed(&'a str),
+    Persisted(Arc<str>),
+}
+
+impl<'a> Document<'a> {
+    fn as_str(&self) -> &str {
+        match self {
+            Document::Borrowed(query) => query,
+            Document::Persisted(query) => query,
+        }
+    }
 }
 
 impl<S> GraphQLRequest<S>
@@ -45,6 +105,59 @@ where
         self.operation_name.as_deref()
     }
 
+    /// Returns the persisted query extension of this request, if any.
+    pub fn persisted_query(&self) -> Option<&PersistedQuery> {
+        self.extensions.as_ref()?.persisted_query.as_ref()
+    }
+
+    /// Returns the id of the safelisted operation to execute, if any.
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Resolves the query text to execute, checking it against the safelist
+    /// of the given config, or else looking up and registering persisted
+    /// queries in its store.
+    fn document<'a, CtxT>(
+        &'a self,
+        config: Option<&'a HttpConfig<CtxT, S>>,
+    ) -> Result<Document<'a>, GraphQLResponse<'static, S>> {
+        let query = self.query.as_deref();
+        let store = config.and_then(HttpConfig::persisted_query_store);
+        if let Some(safelist) = config.and_then(HttpConfig::safelist) {
+            match safelist.resolve(self.id(), query, self.persisted_query()) {
+                Ok(Some(query)) => return self.check_operation_type(Document::Borrowed(query)),
+                Ok(None) => {}
+                Err(e) => return Err(GraphQLResponse::error(e)),
+            }
+        }
+        let document = match persisted_queries::resolve(query, self.persisted_query(), store) {
+            Ok(Some(persisted)) => Document::Persisted(persisted),
+            Ok(None) => query.map(Document::Borrowed).ok_or_else(|| {
+                GraphQLResponse::from_result(Err(GraphQLError::NoOperationProvided))
+            })?,
+            Err(e) => return Err(GraphQLResponse::error(e)),
+        };
+        self.check_operation_type(document)
+    }
+
+    /// Refuses mutations in requests sent with GET.
+    ///
+    /// Query texts sent along are already checked while parsing the request,
+    /// but persisted and safelisted ones are only known once resolved.
+    fn check_operation_type<'a>(
+        &self,
+        document: Document<'a>,
+    ) -> Result<Document<'a>, GraphQLResponse<'static, S>> {
+        if self.forbid_mutations
+            && operation_type(document.as_str(), self.operation_name())
+                == Some(OperationType::Mutation)
+        {
+            return Err(GraphQLResponse::rejected(mutation_over_get_error(), 405));
+        }
+        Ok(document)
+    }
+
     fn variables(&self) -> Variables<S> {
         self.variables
             .as_ref()
@@ -65,12 +178,22 @@ where
         variables: Option<InputValue<S>>,
     ) -> Self {
         GraphQLRequest {
-            query,
+            query: Some(query),
+            id: None,
             operation_name,
             variables,
+            extensions: None,
+            forbid_mutations: false,
+            uploads: None,
         }
     }
 
+    /// Refuse to execute mutations, as for requests sent with GET
+    pub fn forbid_mutations(mut self) -> Self {
+        self.forbid_mutations = true;
+        self
+    }
+
     /// Execute a GraphQL request synchronously using the specified schema and context
     ///
     /// This is a simple wrapper around the `execute_sync` function exposed at the
@@ -86,13 +209,62 @@ where
         MutationT: GraphQLType<S, Context = QueryT::Context>,
         SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
     {
-        GraphQLResponse(crate::execute_sync(
-            &self.query,
-            self.operation_name(),
-            root_node,
-            &self.variables(),
-            context,
-        ))
+        self.execute_sync_inner(root_node, context, None)
+    }
+
+    /// Execute a GraphQL request synchronously using the specified schema and
+    /// context, along with the persisted queries, safelist and response cache
+    /// of the given [`HttpConfig`]
+    pub fn execute_sync_with_config<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+        context: &QueryT::Context,
+        config: &'a HttpConfig<QueryT::Context, S>,
+    ) -> GraphQLResponse<'a, S>
+    where
+        S: ScalarValue,
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        self.execute_sync_inner(root_node, context, Some(config))
+    }
+
+    fn execute_sync_inner<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+        context: &QueryT::Context,
+        config: Option<&'a HttpConfig<QueryT::Context, S>>,
+    ) -> GraphQLResponse<'a, S>
+    where
+        S: ScalarValue,
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        let op = self.operation_name();
+        let vars = &self.variables();
+        let document = match self.document(config) {
+            Ok(document) => document,
+            Err(resp) => return resp,
+        };
+        let cache = self.response_cache(config, context, document.as_str(), vars);
+        if let Some(resp) = cache.as_ref().and_then(|(cache, key)| cache.get(key)) {
+            return GraphQLResponse::cached(resp);
+        }
+
+        let resp = upload::scope(self.uploads.clone(), || match document {
+            Document::Borrowed(query) => GraphQLResponse::executed(
+                crate::execute_sync_with_cache_policy(query, op, root_node, vars, context),
+            ),
+            Document::Persisted(query) => GraphQLResponse::detached(
+                crate::execute_sync_with_cache_policy(&query, op, root_node, vars, context),
+            ),
+        });
+        if let Some((cache, key)) = cache {
+            resp.store(cache, key);
+        }
+        resp
     }
 
     /// Execute a GraphQL request using the specified schema and context
@@ -104,6 +276,47 @@ where
         root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
         context: &'a QueryT::Context,
     ) -> GraphQLResponse<'a, S>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+        SubscriptionT::TypeInfo: Sync,
+        S: ScalarValue + Send + Sync,
+    {
+        self.execute_inner(root_node, context, None).await
+    }
+
+    /// Execute a GraphQL request using the specified schema and context,
+    /// along with the persisted queries, safelist and response cache of the
+    /// given [`HttpConfig`]
+    pub async fn execute_with_config<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+        config: &'a HttpConfig<QueryT::Context, S>,
+    ) -> GraphQLResponse<'a, S>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+        SubscriptionT::TypeInfo: Sync,
+        S: ScalarValue + Send + Sync,
+    {
+        self.execute_inner(root_node, context, Some(config)).await
+    }
+
+    async fn execute_inner<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+        config: Option<&'a HttpConfig<QueryT::Context, S>>,
+    ) -> GraphQLResponse<'a, S>
     where
         QueryT: GraphQLTypeAsync<S>,
         QueryT::TypeInfo: Sync,
@@ -116,8 +329,51 @@ where
     {
         let op = self.operation_name();
         let vars = &self.variables();
-        let res = crate::execute(&self.query, op, root_node, vars, context).await;
-        GraphQLResponse(res)
+        let document = match self.document(config) {
+            Ok(document) => document,
+            Err(resp) => return resp,
+        };
+        let cache = self.response_cache(config, context, document.as_str(), vars);
+        if let Some(resp) = cache.as_ref().and_then(|(cache, key)| cache.get(key)) {
+            return GraphQLResponse::cached(resp);
+        }
+
+        let execution = async {
+            match document {
+                Document::Borrowed(query) => GraphQLResponse::executed(
+                    crate::execute_with_cache_policy(query, op, root_node, vars, context).await,
+                ),
+                Document::Persisted(query) => GraphQLResponse::detached(
+                    crate::execute_with_cache_policy(&query, op, root_node, vars, context).await,
+                ),
+            }
+        };
+        let resp = upload::scoped(self.uploads.clone(), execution).await;
+        if let Some((cache, key)) = cache {
+            resp.store(cache, key);
+        }
+        resp
+    }
+
+    /// The response cache of the config along with the key of the response
+    /// to this request, if it may be cached
+    ///
+    /// Only queries are cached, and never along with file uploads.
+    fn response_cache<'r, CtxT>(
+        &self,
+        config: Option<&'r HttpConfig<CtxT, S>>,
+        context: &CtxT,
+        query: &str,
+        variables: &Variables<S>,
+    ) -> Option<(&'r dyn ResponseCache<S>, String)> {
+        let (cache, scope) = config?.response_cache_for(context)?;
+        if self.uploads.is_some()
+            || operation_type(query, self.operation_name()) != Some(OperationType::Query)
+        {
+            return None;
+        }
+        let key = response_cache::cache_key(query, self.operation_name(), variables, &scope);
+        Some((cache, key))
     }
 }
 
@@ -125,6 +381,9 @@ where
 /// specified schema and context.
 /// This is a wrapper around the `resolve_into_stream` function exposed at the top
 /// level of this crate.
+///
+/// Persisted queries are not supported, so the request must contain the query
+/// text.
 pub async fn resolve_into_stream<'req, 'rn, 'ctx, 'a, QueryT, MutationT, SubscriptionT, S>(
     req: &'req GraphQLRequest<S>,
     root_node: &'rn RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
@@ -143,20 +402,241 @@ where
     SubscriptionT::TypeInfo: Sync,
     S: ScalarValue + Send + Sync,
 {
+    resolve_into_stream_inner(req, root_node, context, None).await
+}
+
+/// Resolve a GraphQL subscription like [`resolve_into_stream`], checking it
+/// against the safelist of the given [`HttpConfig`], if any
+///
+/// The request must then contain the query text or the id of a safelisted
+/// operation.
+pub async fn resolve_into_stream_with_config<
+    'req,
+    'rn,
+    'ctx,
+    'cfg,
+    'a,
+    QueryT,
+    MutationT,
+    SubscriptionT,
+    S,
+>(
+    req: &'req GraphQLRequest<S>,
+    root_node: &'rn RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    context: &'ctx QueryT::Context,
+    config: &'cfg HttpConfig<QueryT::Context, S>,
+) -> Result<(Value<ValuesStream<'a, S>>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+where
+    'req: 'a,
+    'rn: 'a,
+    'ctx: 'a,
+    'cfg: 'a,
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = QueryT::Context>,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    resolve_into_stream_inner(req, root_node, context, Some(config)).await
+}
+
+async fn resolve_into_stream_inner<'a, QueryT, MutationT, SubscriptionT, S>(
+    req: &'a GraphQLRequest<S>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    context: &'a QueryT::Context,
+    config: Option<&'a HttpConfig<QueryT::Context, S>>,
+) -> Result<(Value<ValuesStream<'a, S>>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = QueryT::Context>,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    let mut query = req.query.as_deref();
+    if let Some(safelist) = config.and_then(HttpConfig::safelist) {
+        query = safelist
+            .resolve::<S>(req.id(), query, req.persisted_query())
+            .map_err(|e| GraphQLError::ValidationError(vec![RuleError::new(e.message(), &[])]))?;
+    }
+    let query = query.ok_or(GraphQLError::NoOperationProvided)?;
     let op = req.operation_name();
     let vars = req.variables();
 
-    crate::resolve_into_stream(&req.query, op, root_node, &vars, context).await
+    let resolution = crate::resolve_into_stream(query, op, root_node, &vars, context);
+    upload::scoped(req.uploads.clone(), resolution).await
+}
+
+/// Finds the type of the operation to execute in the given query text,
+/// without parsing or validating it
+///
+/// Returns `None` if the operation can't be found, leaving the error to be
+/// reported while executing the query.
+fn operation_type(query: &str, operation_name: Option<&str>) -> Option<OperationType> {
+    let mut tokens = Lexer::new(query)
+        .map(|token| token.map(|t| t.item))
+        .take_while(|token| !matches!(token, Ok(Token::EndOfFile)))
+        .peekable();
+    let mut operations = vec![];
+    let (mut depth, mut definition_start) = (0usize, true);
+
+    while let Some(token) = tokens.next() {
+        let token = token.ok()?;
+        if depth == 0 && definition_start {
+            definition_start = false;
+            let op_type = match token {
+                Token::CurlyOpen => Some(OperationType::Query),
+                Token::Name("query") => Some(OperationType::Query),
+                Token::Name("mutation") => Some(OperationType::Mutation),
+                Token::Name("subscription") => Some(OperationType::Subscription),
+                _ => None,
+            };
+            if let Some(op_type) = op_type {
+                let name = match tokens.peek() {
+                    Some(Ok(Token::Name(name))) if token != Token::CurlyOpen => Some(*name),
+                    _ => None,
+                };
+                operations.push((op_type, name));
+            }
+        }
+        match token {
+            Token::CurlyOpen | Token::ParenOpen | Token::BracketOpen => depth += 1,
+            Token::CurlyClose | Token::ParenClose | Token::BracketClose => {
+                depth = depth.checked_sub(1)?;
+                definition_start = depth == 0 && token == Token::CurlyClose;
+            }
+            _ => {}
+        }
+    }
+
+    if depth != 0 {
+        return None;
+    }
+    match operation_name {
+        Some(operation_name) => operations
+            .into_iter()
+            .find(|(_, name)| *name == Some(operation_name))
+            .map(|(op_type, _)| op_type),
+        None if operations.len() == 1 => operations.pop().map(|(op_type, _)| op_type),
+        None => None,
+    }
+}
+
+/// The error returned for mutations in requests sent with GET
+pub fn mutation_over_get_error<S: ScalarValue>() -> FieldError<S> {
+    FieldError::new(
+        "Mutations can only be sent with POST",
+        graphql_value!({ "code": "MUTATION_OVER_GET" }),
+    )
+}
+
+/// Media type of a response to a GraphQL request, as negotiated with the
+/// client through the `Accept` header
+///
+/// See the [GraphQL over HTTP][1] specification for the semantics of each.
+///
+/// [1]: https://graphql.github.io/graphql-over-http/draft/
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResponseContentType {
+    /// `application/graphql-response+json`, where request, parse and
+    /// validation failures are reported with a 4xx status code.
+    GraphQLResponseJson,
+
+    /// `application/json`, the legacy media type, used when the client
+    /// doesn't ask for anything more specific.
+    Json,
+}
+
+impl ResponseContentType {
+    /// Picks the media type to respond with from the value of the request's
+    /// `Accept` header
+    ///
+    /// A missing or empty header falls back to `application/json`. Returns
+    /// `None` if the client accepts neither media type, in which case a
+    /// `406 Not Acceptable` should be returned.
+    pub fn negotiate(accept: Option<&str>) -> Option<Self> {
+        let accept = match accept {
+            Some(accept) if !accept.trim().is_empty() => accept,
+            _ => return Some(Self::Json),
+        };
+
+        let gql = media_type_quality(accept, "application/graphql-response+json");
+        let json = media_type_quality(accept, "application/json");
+
+        // Wildcards don't express a preference for the new media type, so
+        // clients not asking for it explicitly keep the legacy behavior.
+        match (gql, json) {
+            ((q, exact), (json_q, _)) if q > 0 && (q > json_q || (q == json_q && exact)) => {
+                Some(Self::GraphQLResponseJson)
+            }
+            (_, (q, _)) if q > 0 => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    /// Value of the `Content-Type` header for this media type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::GraphQLResponseJson => "application/graphql-response+json",
+            Self::Json => "application/json",
+        }
+    }
+}
+
+/// Quality (in thousandths) the `accept` header value assigns to
+/// `media_type`, taken from the most specific matching media range, and
+/// whether that range names `media_type` exactly.
+fn media_type_quality(accept: &str, media_type: &str) -> (u16, bool) {
+    let (ty, _) = media_type.split_at(media_type.find('/').unwrap_or(0));
+
+    let mut best: Option<(u8, u16)> = None;
+    for range in accept.split(',') {
+        let mut parts = range.split(';');
+        let range = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+        let specificity = if range == media_type {
+            2
+        } else if range.strip_suffix("/*") == Some(ty) {
+            1
+        } else if range == "*/*" {
+            0
+        } else {
+            continue;
+        };
+        let quality = parts
+            .find_map(|param| {
+                let (name, value) = param.split_at(param.find('=')?);
+                if name.trim().eq_ignore_ascii_case("q") {
+                    value[1..].trim().parse::<f32>().ok()
+                } else {
+                    None
+                }
+            })
+            .map_or(1000, |q| (q.max(0.0).min(1.0) * 1000.0).round() as u16);
+        if best.map_or(true, |(s, _)| specificity > s) {
+            best = Some((specificity, quality));
+        }
+    }
+
+    best.map_or((0, false), |(s, q)| (q, s == 2))
 }
 
 /// Simple wrapper around the result from executing a GraphQL query
 ///
 /// This struct implements Serialize, so you can simply serialize this
-/// to JSON and send it over the wire. Use the `is_ok` method to determine
-/// whether to send a 200 or 400 HTTP status code.
+/// to JSON and send it over the wire. Use the `status_code` method to
+/// determine which HTTP status code to send.
 #[derive(Debug)]
 pub struct GraphQLResponse<'a, S = DefaultScalarValue>(
     Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>,
+    Option<CachePolicy>,
+    /// Status code of a request rejected before being executed
+    Option<u16>,
 );
 
 impl<'a, S> GraphQLResponse<'a, S>
@@ -165,12 +645,74 @@ where
 {
     /// Constructs new `GraphQLResponse` using the given result
     pub fn from_result(r: Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>) -> Self {
-        Self(r)
+        Self(r, None, None)
+    }
+
+    /// Constructs a response from the result of executing a query, along with
+    /// the cache policy computed while executing it
+    fn executed(
+        r: Result<(Value<S>, Vec<ExecutionError<S>>, CachePolicy), GraphQLError<'a>>,
+    ) -> Self {
+        match r {
+            Ok((value, errors, cache_policy)) => {
+                Self(Ok((value, errors)), Some(cache_policy), None)
+            }
+            Err(e) => Self(Err(e), None, None),
+        }
+    }
+
+    /// Constructs a response from the result of executing a query text not
+    /// owned by the request, i.e. a persisted query
+    fn detached(
+        r: Result<(Value<S>, Vec<ExecutionError<S>>, CachePolicy), GraphQLError<'_>>,
+    ) -> Self {
+        Self::executed(r.map_err(|e| match e {
+            // Parse errors borrow the query text, so they are reported as
+            // validation errors of the same shape instead.
+            GraphQLError::ParseError(e) => {
+                GraphQLError::ValidationError(vec![RuleError::new(&e.item.to_string(), &[e.start])])
+            }
+            GraphQLError::ValidationError(errs) => GraphQLError::ValidationError(errs),
+            GraphQLError::NoOperationProvided => GraphQLError::NoOperationProvided,
+            GraphQLError::MultipleOperationsProvided => GraphQLError::MultipleOperationsProvided,
+            GraphQLError::UnknownOperationName => GraphQLError::UnknownOperationName,
+            GraphQLError::IsSubscription => GraphQLError::IsSubscription,
+            GraphQLError::NotSubscription => GraphQLError::NotSubscription,
+        }))
+    }
+
+    /// Constructs a response stored in a [`ResponseCache`]
+    fn cached(resp: CachedResponse<S>) -> Self {
+        Self(Ok((resp.data, vec![])), Some(resp.cache_policy), None)
+    }
+
+    /// Stores this response in the given cache, unless it has errors
+    fn store(&self, cache: &dyn ResponseCache<S>, key: String) {
+        if let (Ok((data, errors)), Some(cache_policy)) = (&self.0, self.1) {
+            if errors.is_empty() {
+                let data = data.clone();
+                cache.insert(key, CachedResponse { data, cache_policy });
+            }
+        }
     }
 
     /// Constructs an error response outside of the normal execution flow
+    ///
+    /// The request is considered rejected before being executed, i.e. it's
+    /// answered with `400 Bad Request` in `application/graphql-response+json`.
     pub fn error(error: FieldError<S>) -> Self {
-        GraphQLResponse(Ok((Value::null(), vec![ExecutionError::at_origin(error)])))
+        Self::rejected(error, 400)
+    }
+
+    /// Constructs an error response for a request rejected before being
+    /// executed, answered with the given status code in
+    /// `application/graphql-response+json`
+    fn rejected(error: FieldError<S>, status_code: u16) -> Self {
+        GraphQLResponse(
+            Ok((Value::null(), vec![ExecutionError::at_origin(error)])),
+            None,
+            Some(status_code),
+        )
     }
 
     /// Was the request successful or not?
@@ -180,6 +722,37 @@ where
     pub fn is_ok(&self) -> bool {
         self.0.is_ok()
     }
+
+    /// HTTP status code to respond with in the given media type
+    ///
+    /// Requests failing to parse or validate are answered with
+    /// `400 Bad Request`, and executed ones with `200 OK`, even if execution
+    /// produced errors.
+    ///
+    /// Requests rejected before being executed, e.g. referring to an unknown
+    /// persisted query, are answered with `200 OK` in `application/json`, and
+    /// with a 4xx status code in `application/graphql-response+json`.
+    pub fn status_code(&self, content_type: ResponseContentType) -> u16 {
+        match (&self.0, self.2, content_type) {
+            (Err(_), _, _) => 400,
+            (Ok(_), Some(status_code), ResponseContentType::GraphQLResponseJson) => status_code,
+            (Ok(_), _, _) => 200,
+        }
+    }
+
+    /// Cache policy of the response, computed from the [cache hints][1] of
+    /// the fields it resolved
+    ///
+    /// Responses with errors, and responses that weren't built by executing a
+    /// [`GraphQLRequest`], have none and shouldn't be cached.
+    ///
+    /// [1]: crate::CacheHint
+    pub fn cache_policy(&self) -> Option<CachePolicy> {
+        match &self.0 {
+            Ok((_, errors)) if errors.is_empty() => self.1,
+            _ => None,
+        }
+    }
 }
 
 impl<'a, T> Serialize for GraphQLResponse<'a, T>
@@ -257,11 +830,45 @@ where
     /// Execute a GraphQL batch request synchronously using the specified schema and context
     ///
     /// This is a simple wrapper around the `execute_sync` function exposed in GraphQLRequest.
+    /// The operations of a batch are executed one after another.
     pub fn execute_sync<'a, QueryT, MutationT, SubscriptionT>(
         &'a self,
         root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
         context: &QueryT::Context,
     ) -> GraphQLBatchResponse<'a, S>
+    where
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        self.execute_sync_inner(root_node, context, None)
+    }
+
+    /// Execute a GraphQL batch request synchronously using the specified
+    /// schema and context, along with the given [`HttpConfig`]
+    ///
+    /// Batches larger than allowed by its [`BatchLimits`] are rejected before
+    /// any of their operations is executed.
+    pub fn execute_sync_with_config<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+        context: &QueryT::Context,
+        config: &'a HttpConfig<QueryT::Context, S>,
+    ) -> GraphQLBatchResponse<'a, S>
+    where
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        self.execute_sync_inner(root_node, context, Some(config))
+    }
+
+    fn execute_sync_inner<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+        context: &QueryT::Context,
+        config: Option<&'a HttpConfig<QueryT::Context, S>>,
+    ) -> GraphQLBatchResponse<'a, S>
     where
         QueryT: GraphQLType<S>,
         MutationT: GraphQLType<S, Context = QueryT::Context>,
@@ -269,25 +876,76 @@ where
     {
         match *self {
             Self::Single(ref req) => {
-                GraphQLBatchResponse::Single(req.execute_sync(root_node, context))
+                GraphQLBatchResponse::Single(req.execute_sync_inner(root_node, context, config))
+            }
+            Self::Batch(ref reqs) => {
+                let limits = config.map(HttpConfig::batch_limits).copied();
+                if let Err(resp) = limits.unwrap_or_default().check_size(reqs.len()) {
+                    return GraphQLBatchResponse::Single(resp);
+                }
+                GraphQLBatchResponse::Batch(
+                    reqs.iter()
+                        .map(|req| req.execute_sync_inner(root_node, context, config))
+                        .collect(),
+                )
             }
-            Self::Batch(ref reqs) => GraphQLBatchResponse::Batch(
-                reqs.iter()
-                    .map(|req| req.execute_sync(root_node, context))
-                    .collect(),
-            ),
         }
     }
 
     /// Executes a GraphQL request using the specified schema and context
     ///
     /// This is a simple wrapper around the `execute` function exposed in
-    /// GraphQLRequest
+    /// GraphQLRequest. The operations of a batch are all executed
+    /// concurrently.
     pub async fn execute<'a, QueryT, MutationT, SubscriptionT>(
         &'a self,
         root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
         context: &'a QueryT::Context,
     ) -> GraphQLBatchResponse<'a, S>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLSubscriptionType<S, Context = QueryT::Context>,
+        SubscriptionT::TypeInfo: Sync,
+        S: Send + Sync,
+    {
+        self.execute_inner(root_node, context, None).await
+    }
+
+    /// Executes a GraphQL request using the specified schema and context,
+    /// along with the given [`HttpConfig`]
+    ///
+    /// Batches larger than allowed by its [`BatchLimits`] are rejected before
+    /// any of their operations is executed, and the others are executed as
+    /// concurrently as allowed.
+    pub async fn execute_with_config<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+        config: &'a HttpConfig<QueryT::Context, S>,
+    ) -> GraphQLBatchResponse<'a, S>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLSubscriptionType<S, Context = QueryT::Context>,
+        SubscriptionT::TypeInfo: Sync,
+        S: Send + Sync,
+    {
+        self.execute_inner(root_node, context, Some(config)).await
+    }
+
+    async fn execute_inner<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+        config: Option<&'a HttpConfig<QueryT::Context, S>>,
+    ) -> GraphQLBatchResponse<'a, S>
     where
         QueryT: GraphQLTypeAsync<S>,
         QueryT::TypeInfo: Sync,
@@ -300,14 +958,22 @@ where
     {
         match self {
             Self::Single(req) => {
-                let resp = req.execute(root_node, context).await;
+                let resp = req.execute_inner(root_node, context, config).await;
                 GraphQLBatchResponse::Single(resp)
             }
             Self::Batch(reqs) => {
-                let resps = futures::future::join_all(
-                    reqs.iter().map(|req| req.execute(root_node, context)),
-                )
-                .await;
+                let limits = config.map(HttpConfig::batch_limits).copied();
+                let limits = limits.unwrap_or_default();
+                if let Err(resp) = limits.check_size(reqs.len()) {
+                    return GraphQLBatchResponse::Single(resp);
+                }
+                let resps = reqs
+                    .iter()
+                    .map(|req| req.execute_inner(root_node, context, config));
+                let resps = match limits.max_concurrency {
+                    Some(max) => stream::iter(resps).buffered(max).collect().await,
+                    None => future::join_all(resps).await,
+                };
                 GraphQLBatchResponse::Batch(resps)
             }
         }
@@ -322,11 +988,275 @@ where
     }
 }
 
+/// Settings of the GraphQL HTTP endpoints, passed to the `*_with_config`
+/// functions of this module and of the integrations
+///
+/// By default, persisted queries, safelisting and response caching are
+/// disabled, and requests and batches of any size are accepted.
+///
+/// ```
+/// # use juniper::http::{
+/// #     persisted_queries::InMemoryPersistedQueryStore, BatchLimits, HttpConfig, RequestLimits,
+/// # };
+/// let config = HttpConfig::<()>::new()
+///     .enable_persisted_queries(InMemoryPersistedQueryStore::default())
+///     .limit_batches(BatchLimits::new().max_size(10))
+///     .limit_requests(RequestLimits::new().max_body_size(10 * 1024 * 1024));
+/// ```
+pub struct HttpConfig<CtxT, S = DefaultScalarValue> {
+    persisted_queries: Option<Box<dyn PersistedQueryStore>>,
+    safelist: Option<Safelist>,
+    batch_limits: BatchLimits,
+    request_limits: RequestLimits,
+    response_cache: Option<ResponseCaching<CtxT, S>>,
+}
+
+/// Response cache of an [`HttpConfig`], along with the function giving the
+/// cache scope of a request
+struct ResponseCaching<CtxT, S> {
+    cache: Box<dyn ResponseCache<S>>,
+    scope: Box<dyn Fn(&CtxT) -> Option<String> + Send + Sync>,
+}
+
+impl<CtxT, S> Default for HttpConfig<CtxT, S> {
+    fn default() -> Self {
+        Self {
+            persisted_queries: None,
+            safelist: None,
+            batch_limits: BatchLimits::default(),
+            request_limits: RequestLimits::default(),
+            response_cache: None,
+        }
+    }
+}
+
+impl<CtxT, S> fmt::Debug for HttpConfig<CtxT, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HttpConfig")
+            .field("persisted_queries", &self.persisted_queries)
+            .field("safelist", &self.safelist)
+            .field("batch_limits", &self.batch_limits)
+            .field("request_limits", &self.request_limits)
+            .field(
+                "response_cache",
+                &self.response_cache.as_ref().map(|c| &c.cache),
+            )
+            .finish()
+    }
+}
+
+impl<CtxT, S> HttpConfig<CtxT, S> {
+    /// The default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable [Automatic Persisted Queries][1], keeping the queries in the
+    /// given store
+    ///
+    /// [1]: crate::http::persisted_queries
+    pub fn enable_persisted_queries<T>(mut self, store: T) -> Self
+    where
+        T: PersistedQueryStore + 'static,
+    {
+        self.persisted_queries = Some(Box::new(store));
+        self
+    }
+
+    /// The store of persisted queries, if they are enabled
+    pub fn persisted_query_store(&self) -> Option<&dyn PersistedQueryStore> {
+        self.persisted_queries.as_deref()
+    }
+
+    /// Only execute the operations of the given [safelist][1]
+    ///
+    /// [1]: crate::http::safelist
+    pub fn enable_safelist(mut self, safelist: Safelist) -> Self {
+        self.safelist = Some(safelist);
+        self
+    }
+
+    /// The safelist of operations, if enabled
+    pub fn safelist(&self) -> Option<&Safelist> {
+        self.safelist.as_ref()
+    }
+
+    /// Limit the size of the batch requests, and how many of their operations
+    /// are executed concurrently
+    pub fn limit_batches(mut self, limits: BatchLimits) -> Self {
+        self.batch_limits = limits;
+        self
+    }
+
+    /// The limits applied to batch requests
+    pub fn batch_limits(&self) -> &BatchLimits {
+        &self.batch_limits
+    }
+
+    /// Limit the size of the bodies and files of the requests parsed by the
+    /// integrations through `juniper::http::parse`
+    pub fn limit_requests(mut self, limits: RequestLimits) -> Self {
+        self.request_limits = limits;
+        self
+    }
+
+    /// The limits applied while parsing requests
+    pub fn request_limits(&self) -> &RequestLimits {
+        &self.request_limits
+    }
+
+    /// Cache the responses to queries in the given [cache][1]
+    ///
+    /// Responses are shared between the requests whose contexts give the same
+    /// cache scope, e.g. the id of the user or `"public"`. Requests whose
+    /// context gives no scope bypass the cache.
+    ///
+    /// [1]: crate::http::response_cache
+    pub fn enable_response_cache<T, F>(mut self, cache: T, scope: F) -> Self
+    where
+        T: ResponseCache<S> + 'static,
+        F: Fn(&CtxT) -> Option<String> + Send + Sync + 'static,
+    {
+        self.response_cache = Some(ResponseCaching {
+            cache: Box::new(cache),
+            scope: Box::new(scope),
+        });
+        self
+    }
+
+    /// The response cache, if enabled
+    pub fn response_cache(&self) -> Option<&dyn ResponseCache<S>> {
+        self.response_cache.as_ref().map(|c| &*c.cache)
+    }
+
+    /// The response cache along with the cache scope of a request with the
+    /// given context, if both are set
+    fn response_cache_for(&self, context: &CtxT) -> Option<(&dyn ResponseCache<S>, String)> {
+        let caching = self.response_cache.as_ref()?;
+        Some((&*caching.cache, (caching.scope)(context)?))
+    }
+}
+
+/// Limits applied to [`GraphQLBatchRequest`]s, set with
+/// [`HttpConfig::limit_batches`]
+///
+/// By default, batches of any size are accepted, and their operations are all
+/// executed concurrently.
+///
+/// ```
+/// # use juniper::http::{BatchLimits, HttpConfig};
+/// let config = HttpConfig::<()>::new()
+///     .limit_batches(BatchLimits::new().max_size(10).max_concurrency(2));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BatchLimits {
+    max_size: Option<usize>,
+    max_concurrency: Option<usize>,
+}
+
+impl BatchLimits {
+    /// No limits, the default
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject batches of more than `max_size` operations
+    ///
+    /// Such batches are answered with a single error and a `400 Bad Request`
+    /// status code, without executing any of their operations.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Execute at most `max_concurrency` operations of a batch at once
+    ///
+    /// A limit of `0` is treated as `1`. This only affects asynchronous
+    /// execution, synchronous execution being sequential anyway.
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = Some(max_concurrency.max(1));
+        self
+    }
+
+    /// Execute the operations of a batch one after another, in order
+    pub fn sequential(self) -> Self {
+        self.max_concurrency(1)
+    }
+
+    /// The response rejecting a batch of `size` operations, if too large
+    fn check_size<'a, S: ScalarValue>(&self, size: usize) -> Result<(), GraphQLResponse<'a, S>> {
+        let max_size = match self.max_size {
+            Some(max_size) if size > max_size => max_size,
+            _ => return Ok(()),
+        };
+        let message = format!(
+            "Batch of {} operations exceeds the maximum of {}",
+            size, max_size,
+        );
+        Err(GraphQLResponse(
+            Err(GraphQLError::ValidationError(vec![RuleError::new(
+                &message,
+                &[],
+            )])),
+            None,
+            None,
+        ))
+    }
+}
+
+/// Limits applied while parsing requests with
+/// [`parse_request_with_limits`](parse::parse_request_with_limits), set with
+/// [`HttpConfig::limit_requests`]
+///
+/// By default, bodies and files of any size are accepted.
+///
+/// ```
+/// # use juniper::http::{HttpConfig, RequestLimits};
+/// let config = HttpConfig::<()>::new()
+///     .limit_requests(
+///         RequestLimits::new()
+///             .max_body_size(10 * 1024 * 1024)
+///             .max_file_size(5 * 1024 * 1024),
+///     );
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RequestLimits {
+    max_body_size: Option<usize>,
+    max_file_size: Option<usize>,
+}
+
+impl RequestLimits {
+    /// No limits, the default
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject `POST` requests whose body is larger than `max_body_size` bytes
+    ///
+    /// Such requests are answered with a `413 Payload Too Large` status code.
+    /// Integrations stop reading their body as soon as it exceeds the limit,
+    /// where the web framework allows it.
+    pub fn max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = Some(max_body_size);
+        self
+    }
+
+    /// Reject multipart requests sending a file larger than `max_file_size`
+    /// bytes
+    ///
+    /// Such requests are answered with a `413 Payload Too Large` status code,
+    /// before executing any of their operations.
+    pub fn max_file_size(mut self, max_file_size: usize) -> Self {
+        self.max_file_size = Some(max_file_size);
+        self
+    }
+}
+
 /// Simple wrapper around the result (GraphQLResponse) from executing a GraphQLBatchRequest
 ///
 /// This struct implements Serialize, so you can simply serialize this
-/// to JSON and send it over the wire. use the `is_ok` to determine
-/// wheter to send a 200 or 400 HTTP status code.
+/// to JSON and send it over the wire. Use the `status_code` method to
+/// determine which HTTP status code to send.
 #[derive(Serialize)]
 #[serde(untagged)]
 pub enum GraphQLBatchResponse<'a, S = DefaultScalarValue>
@@ -351,6 +1281,48 @@ where
             Self::Batch(resps) => resps.iter().all(GraphQLResponse::is_ok),
         }
     }
+
+    /// HTTP status code to respond with in the given media type, see
+    /// [`GraphQLResponse::status_code`]
+    ///
+    /// A batch is answered with `400 Bad Request` if any of its operations
+    /// wouldn't be answered with `200 OK` on its own.
+    pub fn status_code(&self, content_type: ResponseContentType) -> u16 {
+        match self {
+            Self::Single(resp) => resp.status_code(content_type),
+            Self::Batch(resps) => {
+                if resps
+                    .iter()
+                    .all(|resp| resp.status_code(content_type) == 200)
+                {
+                    200
+                } else {
+                    400
+                }
+            }
+        }
+    }
+
+    /// Cache policy of the response, see [`GraphQLResponse::cache_policy`]
+    ///
+    /// A batch has the most restrictive policy of its operations, and none if
+    /// any of them has none.
+    pub fn cache_policy(&self) -> Option<CachePolicy> {
+        match self {
+            Self::Single(resp) => resp.cache_policy(),
+            Self::Batch(resps) => {
+                let mut cache_policy = CachePolicy::default();
+                for resp in resps {
+                    let policy = resp.cache_policy()?;
+                    cache_policy.restrict(CacheHint {
+                        max_age: policy.max_age,
+                        scope: Some(policy.scope),
+                    });
+                }
+                Some(cache_policy)
+            }
+        }
+    }
 }
 
 #[cfg(feature = "expose-test-schema")]
@@ -381,6 +1353,14 @@ pub mod tests {
         /// Sends POST HTTP request to this integration with the provided raw GraphQL query as
         /// `body`, and returns response returned by this integration.
         fn post_graphql(&self, url: &str, body: &str) -> TestResponse;
+
+        /// Sends GET HTTP request to this integration with the provided `url` parameters string
+        /// and `Accept` header, and returns response returned by this integration.
+        fn get_with_accept(&self, url: &str, accept: &str) -> TestResponse;
+
+        /// Sends POST HTTP request to this integration with the provided JSON-encoded `body` and
+        /// `Accept` header, and returns response returned by this integration.
+        fn post_json_with_accept(&self, url: &str, body: &str, accept: &str) -> TestResponse;
     }
 
     #[allow(missing_docs)]
@@ -419,6 +1399,21 @@ pub mod tests {
 
         println!("  - test_invalid_graphql_post");
         test_invalid_graphql_post(integration);
+
+        println!("  - test_persisted_query_post");
+        test_persisted_query_post(integration);
+
+        println!("  - test_accept_legacy_json");
+        test_accept_legacy_json(integration);
+
+        println!("  - test_accept_graphql_response_json");
+        test_accept_graphql_response_json(integration);
+
+        println!("  - test_graphql_response_json_errors");
+        test_graphql_response_json_errors(integration);
+
+        println!("  - test_not_acceptable");
+        test_not_acceptable(integration);
     }
 
     fn unwrap_json_response(response: &TestResponse) -> Json {
@@ -585,6 +1580,215 @@ pub mod tests {
         assert_eq!(resp.status_code, 400);
     }
 
+    fn test_persisted_query_post<T: HttpIntegration>(integration: &T) {
+        // The test schemas don't enable persisted queries, so a hash alone
+        // isn't enough.
+        let resp = integration.post_json(
+            "/",
+            r#"{"extensions": {"persistedQuery": {"version": 1, "sha256Hash": "0"}}}"#,
+        );
+
+        assert_eq!(resp.status_code, 200);
+        assert_eq!(
+            unwrap_json_response(&resp)["errors"][0]["message"],
+            "PersistedQueryNotSupported",
+        );
+    }
+
+    fn test_accept_legacy_json<T: HttpIntegration>(integration: &T) {
+        let expected = serde_json::from_str::<Json>(r#"{"data": {"hero": {"name": "R2-D2"}}}"#)
+            .expect("Invalid JSON constant in test");
+
+        for accept in &[
+            "application/json",
+            "*/*",
+            "application/graphql-response+json;q=0.5, application/json",
+        ] {
+            // {hero{name}}
+            let resp = integration.get_with_accept("/?query=%7Bhero%7Bname%7D%7D", accept);
+
+            assert_eq!(resp.status_code, 200);
+            assert_eq!(resp.content_type, "application/json");
+            assert_eq!(unwrap_json_response(&resp), expected);
+        }
+
+        // Legacy responses keep rejecting invalid documents with 400.
+        let resp = integration.post_json_with_accept(
+            "/",
+            r#"{"query": "{hero{blah}}"}"#,
+            "application/json",
+        );
+        assert_eq!(resp.status_code, 400);
+        assert_eq!(resp.content_type, "application/json");
+    }
+
+    fn test_accept_graphql_response_json<T: HttpIntegration>(integration: &T) {
+        let expected = serde_json::from_str::<Json>(r#"{"data": {"hero": {"name": "R2-D2"}}}"#)
+            .expect("Invalid JSON constant in test");
+
+        for accept in &[
+            "application/graphql-response+json",
+            "application/graphql-response+json, application/json",
+            "application/json;q=0.9, application/graphql-response+json",
+        ] {
+            // {hero{name}}
+            let resp = integration.get_with_accept("/?query=%7Bhero%7Bname%7D%7D", accept);
+
+            assert_eq!(resp.status_code, 200);
+            assert_eq!(resp.content_type, "application/graphql-response+json");
+            assert_eq!(unwrap_json_response(&resp), expected);
+
+            let resp =
+                integration.post_json_with_accept("/", r#"{"query": "{hero{name}}"}"#, accept);
+
+            assert_eq!(resp.status_code, 200);
+            assert_eq!(resp.content_type, "application/graphql-response+json");
+            assert_eq!(unwrap_json_response(&resp), expected);
+        }
+    }
+
+    fn test_graphql_response_json_errors<T: HttpIntegration>(integration: &T) {
+        let accept = "application/graphql-response+json";
+
+        // Documents failing to parse or validate are never executed.
+        for body in &[
+            r#"{"query": "{hero{name}"}"#,
+            r#"{"query": "{hero{blah}}"}"#,
+        ] {
+            let resp = integration.post_json_with_accept("/", body, accept);
+
+            assert_eq!(resp.status_code, 400);
+            assert_eq!(resp.content_type, "application/graphql-response+json");
+
+            let json = unwrap_json_response(&resp);
+            assert!(json.get("data").is_none());
+            assert!(json["errors"].is_array());
+        }
+
+        // Persisted queries aren't enabled on the test schema.
+        let body = r#"{"extensions": {"persistedQuery": {"version": 1, "sha256Hash": "abc"}}}"#;
+        let resp = integration.post_json_with_accept("/", body, accept);
+        assert_eq!(resp.status_code, 400);
+        assert_eq!(resp.content_type, "application/graphql-response+json");
+        assert_eq!(
+            unwrap_json_response(&resp)["errors"][0]["extensions"]["code"],
+            "PERSISTED_QUERY_NOT_SUPPORTED",
+        );
+
+        // Malformed requests are rejected before reaching GraphQL at all.
+        let resp = integration.post_json_with_accept("/", "blah", accept);
+        assert_eq!(resp.status_code, 400);
+    }
+
+    fn test_not_acceptable<T: HttpIntegration>(integration: &T) {
+        // {hero{name}}
+        let resp = integration.get_with_accept("/?query=%7Bhero%7Bname%7D%7D", "text/html");
+        assert_eq!(resp.status_code, 406);
+
+        let resp = integration.post_json_with_accept(
+            "/",
+            r#"{"query": "{hero{name}}"}"#,
+            "application/xml",
+        );
+        assert_eq!(resp.status_code, 406);
+    }
+
+    /// Normalized way to send multipart requests to the HTTP framework integration we are testing.
+    pub trait MultipartIntegration {
+        /// Sends POST HTTP request to this integration with the provided `multipart/form-data`
+        /// `body`, whose parts are delimited by `boundary`, and returns response returned by this
+        /// integration.
+        fn post_multipart(&self, url: &str, boundary: &str, body: &str) -> TestResponse;
+    }
+
+    #[allow(missing_docs)]
+    pub fn run_multipart_test_suite<T: MultipartIntegration>(integration: &T) {
+        println!("Running multipart test suite for integration");
+
+        println!("  - test_multipart_post");
+        test_multipart_post(integration);
+
+        println!("  - test_multipart_upload");
+        test_multipart_upload(integration);
+
+        println!("  - test_invalid_multipart_post");
+        test_invalid_multipart_post(integration);
+    }
+
+    const BOUNDARY: &str = "juniper-test-boundary";
+
+    /// A `multipart/form-data` body with the given fields, and the `0` file
+    /// if `file` is given
+    fn multipart_body(operations: &str, map: &str, file: Option<&str>) -> String {
+        let mut body = format!(
+            "--{b}\r\nContent-Disposition: form-data; name=\"operations\"\r\n\r\n{}\r\n\
+             --{b}\r\nContent-Disposition: form-data; name=\"map\"\r\n\r\n{}\r\n",
+            operations,
+            map,
+            b = BOUNDARY,
+        );
+        if let Some(file) = file {
+            body.push_str(&format!(
+                "--{b}\r\nContent-Disposition: form-data; name=\"0\"; filename=\"a.txt\"\r\n\
+                 Content-Type: text/plain\r\n\r\n{}\r\n",
+                file,
+                b = BOUNDARY,
+            ));
+        }
+        body.push_str(&format!("--{}--\r\n", BOUNDARY));
+        body
+    }
+
+    fn test_multipart_post<T: MultipartIntegration>(integration: &T) {
+        let body = multipart_body(r#"{"query": "{hero{name}}"}"#, "{}", None);
+        let resp = integration.post_multipart("/", BOUNDARY, &body);
+
+        assert_eq!(resp.status_code, 200);
+        assert_eq!(resp.content_type, "application/json");
+        assert_eq!(
+            unwrap_json_response(&resp),
+            serde_json::from_str::<Json>(r#"{"data": {"hero": {"name": "R2-D2"}}}"#)
+                .expect("Invalid JSON constant in test"),
+        );
+    }
+
+    fn test_multipart_upload<T: MultipartIntegration>(integration: &T) {
+        // The test schema has no `Upload` arguments, so the file ends up as
+        // the opaque token standing for it.
+        let body = multipart_body(
+            r#"{"query": "query ($id: String!) {human(id: $id){name}}", "variables": {"id": null}}"#,
+            r#"{"0": ["variables.id"]}"#,
+            Some("content"),
+        );
+        let resp = integration.post_multipart("/", BOUNDARY, &body);
+
+        assert_eq!(resp.status_code, 200);
+        assert_eq!(
+            unwrap_json_response(&resp),
+            serde_json::from_str::<Json>(r#"{"data": {"human": null}}"#)
+                .expect("Invalid JSON constant in test"),
+        );
+    }
+
+    fn test_invalid_multipart_post<T: MultipartIntegration>(integration: &T) {
+        let operations = r#"{"query": "query ($id: String!) {human(id: $id){name}}", "variables": {"id": null}}"#;
+
+        // The mapped file is missing.
+        let body = multipart_body(operations, r#"{"0": ["variables.id"]}"#, None);
+        let resp = integration.post_multipart("/", BOUNDARY, &body);
+        assert_eq!(resp.status_code, 400);
+
+        // Files only replace `null` variables.
+        let body = multipart_body(operations, r#"{"0": ["query"]}"#, Some("content"));
+        let resp = integration.post_multipart("/", BOUNDARY, &body);
+        assert_eq!(resp.status_code, 400);
+
+        // The body isn't delimited by the announced boundary.
+        let body = multipart_body(operations, "{}", None);
+        let resp = integration.post_multipart("/", "other-boundary", &body);
+        assert_eq!(resp.status_code, 400);
+    }
+
     /// Normalized way to make requests to the WebSocket framework integration we are testing.
     pub trait WsIntegration {
         /// Runs a test with the given messages
@@ -0,0 +1,221 @@
+//! Minimal `multipart/form-data` parsing ([RFC 7578][1]), enough for the
+//! [GraphQL multipart request spec][2]
+//!
+//! [1]: https://tools.ietf.org/html/rfc7578
+//! [2]: https://github.com/jaydenseric/graphql-multipart-request-spec
+
+use std::str;
+
+/// A field of a `multipart/form-data` body
+#[derive(Debug, PartialEq)]
+pub(super) struct Part<'a> {
+    pub(super) name: String,
+    pub(super) filename: Option<String>,
+    pub(super) content_type: Option<String>,
+    pub(super) content: &'a [u8],
+}
+
+/// The `boundary` parameter of a `multipart/form-data` content type
+pub(super) fn boundary(content_type: &str) -> Option<String> {
+    parameters(content_type)?
+        .into_iter()
+        .find(|(name, _)| name == "boundary")
+        .map(|(_, boundary)| boundary)
+        .filter(|boundary| !boundary.is_empty())
+}
+
+/// Splits a `multipart/form-data` body into its fields
+///
+/// Returns `None` if the body is malformed.
+pub(super) fn parse<'a>(boundary: &str, body: &'a [u8]) -> Option<Vec<Part<'a>>> {
+    let delimiter = format!("\r\n--{}", boundary).into_bytes();
+
+    // The first delimiter may be at the very start of the body, without the
+    // preceding line break.
+    let mut rest = if body.starts_with(&delimiter[2..]) {
+        &body[delimiter.len() - 2..]
+    } else {
+        &body[find(body, &delimiter)? + delimiter.len()..]
+    };
+
+    let mut parts = Vec::new();
+    loop {
+        if rest.starts_with(b"--") {
+            return Some(parts);
+        }
+
+        // Skip the transport padding ending the delimiter line.
+        let line_end = find(rest, b"\r\n")?;
+        if !rest[..line_end].iter().all(|b| *b == b' ' || *b == b'\t') {
+            return None;
+        }
+        rest = &rest[line_end + 2..];
+
+        let headers_end = find(rest, b"\r\n\r\n")?;
+        let headers = str::from_utf8(&rest[..headers_end]).ok()?;
+        rest = &rest[headers_end + 4..];
+
+        let content_end = find(rest, &delimiter)?;
+        parts.push(part(headers, &rest[..content_end])?);
+        rest = &rest[content_end + delimiter.len()..];
+    }
+}
+
+fn part<'a>(headers: &str, content: &'a [u8]) -> Option<Part<'a>> {
+    let (mut disposition, mut content_type) = (None, None);
+    for header in headers.split("\r\n") {
+        let colon = header.find(':')?;
+        let value = header[colon + 1..].trim();
+        match header[..colon].trim().to_ascii_lowercase().as_str() {
+            "content-disposition" => disposition = Some(value),
+            "content-type" => content_type = Some(value.to_owned()),
+            _ => {}
+        }
+    }
+
+    let disposition = disposition?;
+    if !disposition
+        .split(';')
+        .next()?
+        .trim()
+        .eq_ignore_ascii_case("form-data")
+    {
+        return None;
+    }
+
+    let (mut name, mut filename) = (None, None);
+    for (param, value) in parameters(disposition)? {
+        match param.as_str() {
+            "name" => name = Some(value),
+            "filename" => filename = Some(value),
+            _ => {}
+        }
+    }
+
+    Some(Part {
+        name: name?,
+        filename,
+        content_type,
+        content,
+    })
+}
+
+/// Parameters following the `;` separated value of a header, with lowercase
+/// names and unquoted values
+fn parameters(header: &str) -> Option<Vec<(String, String)>> {
+    let mut params = Vec::new();
+    let mut rest = &header[header.find(';').map_or(header.len(), |i| i + 1)..];
+
+    while !rest.trim().is_empty() {
+        let eq = rest.find('=')?;
+        let name = rest[..eq].trim().to_ascii_lowercase();
+        rest = rest[eq + 1..].trim_start();
+
+        let value = if let Some(quoted) = rest.strip_prefix('"') {
+            let mut value = String::new();
+            let mut chars = quoted.char_indices();
+            loop {
+                match chars.next()? {
+                    (_, '\\') => value.push(chars.next()?.1),
+                    (i, '"') => {
+                        rest = &quoted[i + 1..];
+                        break;
+                    }
+                    (_, c) => value.push(c),
+                }
+            }
+            value
+        } else {
+            let end = rest.find(';').unwrap_or(rest.len());
+            let value = rest[..end].trim().to_owned();
+            rest = &rest[end..];
+            value
+        };
+        params.push((name, value));
+
+        rest = rest.trim_start();
+        if !rest.is_empty() {
+            rest = rest.strip_prefix(';')?;
+        }
+    }
+
+    Some(params)
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{boundary, parse, Part};
+
+    #[test]
+    fn boundary_parameter() {
+        assert_eq!(
+            boundary("multipart/form-data; boundary=abc").as_deref(),
+            Some("abc"),
+        );
+        assert_eq!(
+            boundary(r#"multipart/form-data; charset=utf-8; Boundary="a b;c""#).as_deref(),
+            Some("a b;c"),
+        );
+        assert_eq!(boundary("multipart/form-data"), None);
+        assert_eq!(boundary("multipart/form-data; boundary="), None);
+    }
+
+    #[test]
+    fn parses_fields() {
+        let body = b"preamble\r\n\
+            --xyz\r\n\
+            Content-Disposition: form-data; name=\"operations\"\r\n\
+            \r\n\
+            {}\r\n\
+            --xyz  \r\n\
+            content-disposition: form-data; name=\"0\"; filename=\"a \\\"b\\\".txt\"\r\n\
+            Content-Type: text/plain\r\n\
+            \r\n\
+            line\r\n--xy\r\n\
+            --xyz--\r\n\
+            epilogue";
+
+        assert_eq!(
+            parse("xyz", body),
+            Some(vec![
+                Part {
+                    name: "operations".to_owned(),
+                    filename: None,
+                    content_type: None,
+                    content: b"{}",
+                },
+                Part {
+                    name: "0".to_owned(),
+                    filename: Some("a \"b\".txt".to_owned()),
+                    content_type: Some("text/plain".to_owned()),
+                    content: b"line\r\n--xy",
+                },
+            ]),
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_bodies() {
+        for body in &[
+            &b""[..],
+            b"--xyz\r\n\r\n{}\r\n--xyz--",
+            b"--xyz\r\nContent-Disposition: form-data\r\n\r\n{}\r\n--xyz--",
+            b"--xyz\r\nContent-Disposition: attachment; name=\"a\"\r\n\r\n{}\r\n--xyz--",
+            b"--xyz\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\n{}",
+            b"--xyzjunk\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\n{}\r\n--xyz--",
+        ] {
+            assert_eq!(
+                parse("xyz", body),
+                None,
+                "{:?}",
+                String::from_utf8_lossy(body)
+            );
+        }
+    }
+}
@@ -0,0 +1,747 @@
+//! Parsing of GraphQL requests sent over HTTP, independently of the web
+//! framework
+//!
+//! Integrations hand the method, content type, query string and body of an
+//! HTTP request to [`parse_request`], which supports:
+//!
+//! - `GET` requests, with the `query`, `operationName`, `variables`,
+//!   `extensions` and `id` parameters in the query string. Mutations are
+//!   refused, as `GET` requests must not have side effects.
+//! - `POST` requests with an `application/json` body, holding either a single
+//!   request or a batch of them.
+//! - `POST` requests with an `application/graphql` body, holding the query text.
+//! - `POST` requests with a `multipart/form-data` body, following the
+//!   [GraphQL multipart request spec][1]. Files are available to resolvers as
+//!   [`Upload`] variables.
+//!
+//! Failures are reported as a [`ParseRequestError`], carrying the HTTP status
+//! code to answer with. The size of bodies and files can be limited with
+//! [`parse_request_with_limits`], using the [`RequestLimits`] of an
+//! [`HttpConfig`](super::HttpConfig).
+//!
+//! ```
+//! # use juniper::http::parse::parse_request;
+//! # use juniper::http::GraphQLBatchRequest;
+//! let request: GraphQLBatchRequest = parse_request(
+//!     "GET",
+//!     None,
+//!     Some("query=%7Bhero%7Bname%7D%7D"),
+//!     &[],
+//! ).unwrap();
+//!
+//! let error = parse_request::<juniper::DefaultScalarValue>(
+//!     "GET",
+//!     None,
+//!     Some("query=mutation%20%7BaddHero%7D"),
+//!     &[],
+//! ).unwrap_err();
+//! assert_eq!(error.status_code(), 405);
+//! ```
+//!
+//! [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+//! [`Upload`]: super::upload::Upload
+
+use std::{collections::BTreeMap, error::Error, fmt, str, sync::Arc};
+
+use serde_json::Value as Json;
+
+use crate::{ast::OperationType, ScalarValue};
+
+use super::{
+    multipart, operation_type, upload::Uploads, GraphQLBatchRequest, GraphQLRequest, RequestLimits,
+};
+
+/// Parse a GraphQL request from the parts of an HTTP request
+///
+/// The body is only read for `POST` requests, and the query string only for
+/// `GET` ones.
+pub fn parse_request<S>(
+    method: &str,
+    content_type: Option<&str>,
+    query_string: Option<&str>,
+    body: &[u8],
+) -> Result<GraphQLBatchRequest<S>, ParseRequestError>
+where
+    S: ScalarValue,
+{
+    parse_request_with_limits(
+        method,
+        content_type,
+        query_string,
+        body,
+        &RequestLimits::default(),
+    )
+}
+
+/// Parse a GraphQL request from the parts of an HTTP request, rejecting the
+/// bodies and files exceeding the given limits
+pub fn parse_request_with_limits<S>(
+    method: &str,
+    content_type: Option<&str>,
+    query_string: Option<&str>,
+    body: &[u8],
+    limits: &RequestLimits,
+) -> Result<GraphQLBatchRequest<S>, ParseRequestError>
+where
+    S: ScalarValue,
+{
+    match method {
+        "GET" => {
+            parse_get_request(query_string.unwrap_or_default()).map(GraphQLBatchRequest::Single)
+        }
+        "POST" => parse_post_request_with_limits(content_type, body, limits),
+        _ => Err(ParseRequestError::MethodNotAllowed(method.to_owned())),
+    }
+}
+
+/// Parse a GraphQL request from the query string of a `GET` request
+///
+/// The query string is expected without its leading `?`.
+pub fn parse_get_request<S>(query_string: &str) -> Result<GraphQLRequest<S>, ParseRequestError>
+where
+    S: ScalarValue,
+{
+    let (mut query, mut operation_name, mut variables, mut extensions, mut id) =
+        (None, None, None, None, None);
+
+    for (key, value) in form_urlencoded::parse(query_string.as_bytes()) {
+        let param = match key.as_ref() {
+            "query" => &mut query,
+            "operationName" => &mut operation_name,
+            "variables" => &mut variables,
+            "extensions" => &mut extensions,
+            "id" => &mut id,
+            _ => continue,
+        };
+        if param.replace(value.into_owned()).is_some() {
+            return Err(ParseRequestError::DuplicateParameter(key.into_owned()));
+        }
+    }
+
+    let variables = variables
+        .filter(|v: &String| !v.is_empty())
+        .map(|v| serde_json::from_str(&v))
+        .transpose()
+        .map_err(ParseRequestError::Variables)?;
+    let extensions = extensions
+        .filter(|e: &String| !e.is_empty())
+        .map(|e| serde_json::from_str(&e))
+        .transpose()
+        .map_err(ParseRequestError::Extensions)?;
+
+    let request = GraphQLRequest {
+        query,
+        id,
+        operation_name,
+        variables,
+        extensions,
+        forbid_mutations: true,
+        uploads: None,
+    };
+    match request.query.as_deref() {
+        Some(query) => {
+            if operation_type(query, request.operation_name()) == Some(OperationType::Mutation) {
+                return Err(ParseRequestError::MutationOverGet);
+            }
+        }
+        None if request.id.is_none() && request.persisted_query().is_none() => {
+            return Err(ParseRequestError::MissingQuery);
+        }
+        None => {}
+    }
+    Ok(request)
+}
+
+/// Parse a GraphQL request from the body of a `POST` request with the given
+/// content type
+///
+/// Parameters of the content type, such as `charset`, are ignored.
+pub fn parse_post_request<S>(
+    content_type: Option<&str>,
+    body: &[u8],
+) -> Result<GraphQLBatchRequest<S>, ParseRequestError>
+where
+    S: ScalarValue,
+{
+    parse_post_request_with_limits(content_type, body, &RequestLimits::default())
+}
+
+/// Parse a GraphQL request from the body of a `POST` request with the given
+/// content type, rejecting the bodies and files exceeding the given limits
+pub fn parse_post_request_with_limits<S>(
+    content_type: Option<&str>,
+    body: &[u8],
+    limits: &RequestLimits,
+) -> Result<GraphQLBatchRequest<S>, ParseRequestError>
+where
+    S: ScalarValue,
+{
+    limits.check_body_size(body.len())?;
+
+    let mime_type = content_type
+        .and_then(|ct| ct.split(';').next())
+        .map(|mime| mime.trim().to_ascii_lowercase());
+
+    match mime_type.as_deref() {
+        Some("application/json") => serde_json::from_slice(body).map_err(ParseRequestError::Body),
+        Some("application/graphql") => {
+            let query = str::from_utf8(body).map_err(ParseRequestError::BodyUtf8)?;
+            Ok(GraphQLBatchRequest::Single(GraphQLRequest::new(
+                query.to_owned(),
+                None,
+                None,
+            )))
+        }
+        Some("multipart/form-data") => {
+            parse_multipart_request(content_type.unwrap_or_default(), body, limits)
+        }
+        _ => Err(ParseRequestError::UnsupportedMediaType(
+            content_type.map(ToOwned::to_owned),
+        )),
+    }
+}
+
+/// Parse a GraphQL request from a `multipart/form-data` body, along with its
+/// files
+fn parse_multipart_request<S>(
+    content_type: &str,
+    body: &[u8],
+    limits: &RequestLimits,
+) -> Result<GraphQLBatchRequest<S>, ParseRequestError>
+where
+    S: ScalarValue,
+{
+    let parts = multipart::boundary(content_type)
+        .and_then(|boundary| multipart::parse(&boundary, body))
+        .ok_or(ParseRequestError::Multipart)?;
+    let field = |name| {
+        parts
+            .iter()
+            .find(|part| part.name == name)
+            .ok_or(ParseRequestError::MissingMultipartField(name))
+    };
+
+    let mut operations: Json =
+        serde_json::from_slice(field("operations")?.content).map_err(ParseRequestError::Body)?;
+    let map: BTreeMap<String, Vec<String>> =
+        serde_json::from_slice(field("map")?.content).map_err(ParseRequestError::UploadMap)?;
+
+    let mut uploads = Uploads::default();
+    for (name, paths) in map {
+        let file = parts
+            .iter()
+            .find(|part| part.name == name)
+            .ok_or_else(|| ParseRequestError::MissingUpload(name.clone()))?;
+        if let Some(max_file_size) = limits.max_file_size {
+            if file.content.len() > max_file_size {
+                return Err(ParseRequestError::FileTooLarge(name, max_file_size));
+            }
+        }
+        for path in paths {
+            *upload_target(&mut operations, &path)
+                .ok_or(ParseRequestError::InvalidUploadPath(path))? = Json::String(name.clone());
+        }
+        uploads.insert(
+            name,
+            file.filename.clone(),
+            file.content_type.clone(),
+            file.content.to_vec(),
+        );
+    }
+
+    let mut request = serde_json::from_value(operations).map_err(ParseRequestError::Body)?;
+    if uploads.is_empty() {
+        return Ok(request);
+    }
+    let uploads = Arc::new(uploads);
+    match &mut request {
+        GraphQLBatchRequest::Single(req) => req.uploads = Some(uploads),
+        GraphQLBatchRequest::Batch(reqs) => {
+            for req in reqs {
+                req.uploads = Some(uploads.clone());
+            }
+        }
+    }
+    Ok(request)
+}
+
+impl RequestLimits {
+    /// Check the size of a body against the limit, e.g. while it is read
+    ///
+    /// Integrations can call it with the size read so far, to stop reading
+    /// bodies as soon as they get too large.
+    pub fn check_body_size(&self, size: usize) -> Result<(), ParseRequestError> {
+        match self.max_body_size {
+            Some(max_body_size) if size > max_body_size => {
+                Err(ParseRequestError::BodyTooLarge(max_body_size))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The value at a path of the `map` field of a multipart request, such as
+/// `variables.files.0`, prefixed with the index of the operation for batches
+///
+/// Files may only be sent in place of `null` variables.
+fn upload_target<'a>(operations: &'a mut Json, path: &str) -> Option<&'a mut Json> {
+    let mut segments = path.split('.');
+    let operation = match operations {
+        Json::Array(batch) => batch.get_mut(segments.next()?.parse::<usize>().ok()?)?,
+        single => single,
+    };
+    if segments.next() != Some("variables") {
+        return None;
+    }
+
+    let mut target = operation.get_mut("variables")?;
+    for segment in segments {
+        target = match target {
+            Json::Object(fields) => fields.get_mut(segment)?,
+            Json::Array(items) => items.get_mut(segment.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(target).filter(|target| target.is_null())
+}
+
+/// Error while parsing a GraphQL request from an HTTP request
+#[derive(Debug)]
+pub enum ParseRequestError {
+    /// The request was sent with a method other than `GET` or `POST`.
+    MethodNotAllowed(String),
+
+    /// The body of a `POST` request has an unsupported content type.
+    UnsupportedMediaType(Option<String>),
+
+    /// A `GET` request has neither a query text nor refers to a persisted or
+    /// safelisted operation.
+    MissingQuery,
+
+    /// A parameter of a `GET` request is specified multiple times.
+    DuplicateParameter(String),
+
+    /// The `variables` parameter of a `GET` request is not valid JSON.
+    Variables(serde_json::Error),
+
+    /// The `extensions` parameter of a `GET` request is not valid JSON.
+    Extensions(serde_json::Error),
+
+    /// A `GET` request tries to execute a mutation.
+    MutationOverGet,
+
+    /// The JSON body of a `POST` request is invalid.
+    Body(serde_json::Error),
+
+    /// The `application/graphql` body of a `POST` request is not valid UTF-8.
+    BodyUtf8(str::Utf8Error),
+
+    /// The `multipart/form-data` body of a `POST` request is malformed.
+    Multipart,
+
+    /// The `operations` or `map` field of a multipart request is missing.
+    MissingMultipartField(&'static str),
+
+    /// The `map` field of a multipart request is invalid.
+    UploadMap(serde_json::Error),
+
+    /// A path of the `map` field of a multipart request doesn't point to a
+    /// `null` variable.
+    InvalidUploadPath(String),
+
+    /// A file of the `map` field of a multipart request is missing.
+    MissingUpload(String),
+
+    /// The body of a `POST` request is larger than the maximum size, in bytes.
+    BodyTooLarge(usize),
+
+    /// A file of a multipart request is larger than the maximum size, in
+    /// bytes.
+    FileTooLarge(String, usize),
+}
+
+impl ParseRequestError {
+    /// The HTTP status code to answer the request with
+    pub fn status_code(&self) -> u16 {
+        match self {
+            ParseRequestError::MethodNotAllowed(_) | ParseRequestError::MutationOverGet => 405,
+            ParseRequestError::BodyTooLarge(_) | ParseRequestError::FileTooLarge(..) => 413,
+            ParseRequestError::UnsupportedMediaType(_) => 415,
+            _ => 400,
+        }
+    }
+}
+
+impl fmt::Display for ParseRequestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseRequestError::MethodNotAllowed(method) => write!(
+                f,
+                "GraphQL requests can only be sent with GET or POST, not {}",
+                method,
+            ),
+            ParseRequestError::UnsupportedMediaType(content_type) => write!(
+                f,
+                "GraphQL requests should have content type `application/json`, \
+                 `application/graphql` or `multipart/form-data`, not {}",
+                content_type.as_deref().unwrap_or("none"),
+            ),
+            ParseRequestError::MissingQuery => write!(f, "'query' parameter is missing"),
+            ParseRequestError::DuplicateParameter(param) => {
+                write!(f, "'{}' parameter is specified multiple times", param)
+            }
+            ParseRequestError::Variables(e) => write!(f, "Invalid 'variables' parameter: {}", e),
+            ParseRequestError::Extensions(e) => {
+                write!(f, "Invalid 'extensions' parameter: {}", e)
+            }
+            ParseRequestError::MutationOverGet => {
+                write!(f, "Mutations can only be sent with POST")
+            }
+            ParseRequestError::Body(e) => write!(f, "Invalid request body: {}", e),
+            ParseRequestError::BodyUtf8(e) => {
+                write!(f, "Request body is not a valid UTF-8 string: {}", e)
+            }
+            ParseRequestError::Multipart => write!(f, "Invalid multipart/form-data body"),
+            ParseRequestError::MissingMultipartField(field) => {
+                write!(f, "'{}' field of the multipart request is missing", field)
+            }
+            ParseRequestError::UploadMap(e) => write!(f, "Invalid 'map' field: {}", e),
+            ParseRequestError::InvalidUploadPath(path) => write!(
+                f,
+                "Files can only be sent in place of null variables, not at '{}'",
+                path,
+            ),
+            ParseRequestError::MissingUpload(name) => {
+                write!(f, "File '{}' of the 'map' field is missing", name)
+            }
+            ParseRequestError::BodyTooLarge(max_size) => write!(
+                f,
+                "Request body exceeds the maximum size of {} bytes",
+                max_size,
+            ),
+            ParseRequestError::FileTooLarge(name, max_size) => write!(
+                f,
+                "File '{}' exceeds the maximum size of {} bytes",
+                name, max_size,
+            ),
+        }
+    }
+}
+
+impl Error for ParseRequestError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseRequestError::Variables(e)
+            | ParseRequestError::Extensions(e)
+            | ParseRequestError::Body(e)
+            | ParseRequestError::UploadMap(e) => Some(e),
+            ParseRequestError::BodyUtf8(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{
+        http::{
+            persisted_queries::sha256_hex,
+            safelist::Safelist,
+            upload::{self, Upload},
+            GraphQLBatchRequest, GraphQLRequest, HttpConfig,
+        },
+        schema::model::RootNode,
+        tests::fixtures::starwars::schema::{Database, Query},
+        types::scalars::{EmptyMutation, EmptySubscription},
+        DefaultScalarValue, FromInputValue as _, InputValue,
+    };
+
+    use super::{
+        parse_get_request, parse_post_request_with_limits, parse_request, ParseRequestError,
+        RequestLimits,
+    };
+
+    fn parse(
+        method: &str,
+        content_type: Option<&str>,
+        query_string: &str,
+        body: &str,
+    ) -> Result<GraphQLBatchRequest, ParseRequestError> {
+        parse_request(method, content_type, Some(query_string), body.as_bytes())
+    }
+
+    #[test]
+    fn parses_get_requests() {
+        let request = parse_get_request::<DefaultScalarValue>(
+            "query=query%20Hero(%24e%3A%20Episode)%20%7Bhero(episode%3A%20%24e)%7Bname%7D%7D\
+             &operationName=Hero&variables=%7B%22e%22%3A%22EMPIRE%22%7D&unknown=1",
+        )
+        .unwrap();
+        let expected = GraphQLRequest::new(
+            "query Hero($e: Episode) {hero(episode: $e){name}}".to_owned(),
+            Some("Hero".to_owned()),
+            Some(serde_json::from_value(json!({"e": "EMPIRE"})).unwrap()),
+        )
+        .forbid_mutations();
+
+        assert_eq!(request, expected);
+    }
+
+    #[test]
+    fn parses_get_requests_without_query_text() {
+        let hash = sha256_hex("{hero{name}}");
+
+        let request = parse_get_request::<DefaultScalarValue>(&format!(
+            "extensions=%7B%22persistedQuery%22%3A%7B%22version%22%3A1%2C%22sha256Hash%22%3A%22{}%22%7D%7D",
+            hash,
+        ))
+        .unwrap();
+        assert_eq!(request.persisted_query().unwrap().sha256_hash, hash);
+
+        let request = parse_get_request::<DefaultScalarValue>("id=HeroName").unwrap();
+        assert_eq!(request.id(), Some("HeroName"));
+    }
+
+    #[test]
+    fn parses_post_requests() {
+        for (content_type, body) in vec![
+            ("application/json", r#"{"query": "{hero{name}}"}"#),
+            (
+                "application/json; charset=utf-8",
+                r#"{"query": "{hero{name}}"}"#,
+            ),
+            ("application/graphql", "{hero{name}}"),
+        ] {
+            let request = parse("POST", Some(content_type), "", body).unwrap();
+            let expected = serde_json::from_value(json!({ "query": "{hero{name}}" })).unwrap();
+
+            assert_eq!(request, expected, "{}", content_type);
+        }
+
+        let request = parse(
+            "POST",
+            Some("application/json"),
+            "",
+            r#"[{"query": "{hero{name}}"}, {"query": "{hero{id}}"}]"#,
+        )
+        .unwrap();
+        assert_eq!(request.operation_names(), vec![None, None]);
+    }
+
+    #[test]
+    fn rejects_invalid_requests() {
+        for (method, content_type, query_string, body, status_code) in vec![
+            ("PUT", None, "query=%7Bhero%7Bname%7D%7D", "", 405),
+            ("GET", None, "", "", 400),
+            ("GET", None, "operationName=Hero", "", 400),
+            ("GET", None, "query=%7Ba%7D&query=%7Bb%7D", "", 400),
+            ("GET", None, "query=%7Ba%7D&variables=blah", "", 400),
+            ("GET", None, "query=%7Ba%7D&extensions=blah", "", 400),
+            ("POST", None, "", r#"{"query": "{hero{name}}"}"#, 415),
+            ("POST", Some("text/plain"), "", "{hero{name}}", 415),
+            ("POST", Some("application/json"), "", "blah", 400),
+            ("POST", Some("application/json"), "", "[]", 400),
+        ] {
+            let error = parse(method, content_type, query_string, body).unwrap_err();
+
+            assert_eq!(error.status_code(), status_code, "{}", error);
+        }
+    }
+
+    #[test]
+    fn rejects_mutations_over_get() {
+        for query_string in vec![
+            "query=mutation%20%7Ba%7D",
+            "query=mutation%20M%20%7Ba%7D%20query%20Q%20%7Bb%7D&operationName=M",
+            "query=fragment%20F%20on%20Mutation%20%7Ba%7D%20mutation%20%7B...F%7D",
+        ] {
+            let error = parse("GET", None, query_string, "").unwrap_err();
+
+            assert_eq!(error.status_code(), 405, "{}", query_string);
+            assert_eq!(error.to_string(), "Mutations can only be sent with POST");
+        }
+
+        for query_string in vec![
+            "query=mutation%20M%20%7Ba%7D%20query%20Q%20%7Bb%7D&operationName=Q",
+            "query=query%20Q(%24a%3A%20In%20%3D%20%7Bmutation%3A%201%7D)%20%7Ba%7D",
+            "query=%7Bmutation%7D",
+            "query=mutation%20%7B",
+        ] {
+            assert!(
+                parse("GET", None, query_string, "").is_ok(),
+                "{}",
+                query_string
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_safelisted_mutations_over_get() {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+        let config = HttpConfig::new()
+            .enable_safelist(Safelist::new(vec![("AddHero", "mutation AddHero { a }")]));
+        let request = parse_get_request::<DefaultScalarValue>("id=AddHero").unwrap();
+
+        let response = request.execute_sync_with_config(&schema, &Database::new(), &config);
+        let response = serde_json::to_value(&response).unwrap();
+
+        assert_eq!(
+            response["errors"][0]["message"],
+            "Mutations can only be sent with POST",
+        );
+        assert_eq!(
+            response["errors"][0]["extensions"]["code"],
+            "MUTATION_OVER_GET"
+        );
+    }
+
+    fn multipart(fields: &[(&str, &str)]) -> String {
+        let mut body = String::new();
+        for (name, content) in fields {
+            body.push_str(&format!(
+                "--boundary\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                name, content,
+            ));
+        }
+        body.push_str("--boundary--\r\n");
+        body
+    }
+
+    fn parse_multipart(fields: &[(&str, &str)]) -> Result<GraphQLBatchRequest, ParseRequestError> {
+        parse(
+            "POST",
+            Some("multipart/form-data; boundary=boundary"),
+            "",
+            &multipart(fields),
+        )
+    }
+
+    #[test]
+    fn parses_multipart_requests() {
+        let request = parse_multipart(&[
+            ("operations", r#"{"query": "{hero{name}}"}"#),
+            ("map", "{}"),
+        ])
+        .unwrap();
+        let expected = serde_json::from_value(json!({ "query": "{hero{name}}" })).unwrap();
+
+        assert_eq!(request, GraphQLBatchRequest::Single(expected));
+    }
+
+    #[test]
+    fn looks_files_up_in_their_request_only() {
+        let request = parse_multipart(&[
+            (
+                "operations",
+                r#"{"query": "mutation ($f: Upload) {a(f: $f)}", "variables": {"f": null}}"#,
+            ),
+            ("map", r#"{"0": ["variables.f"]}"#),
+            ("0", "content"),
+        ])
+        .unwrap();
+        let req = match &request {
+            GraphQLBatchRequest::Single(req) => req,
+            GraphQLBatchRequest::Batch(_) => panic!("expected a single request"),
+        };
+        let name = req.variables().get("f").unwrap().clone();
+        assert_eq!(name, InputValue::scalar("0"));
+
+        let upload = upload::scope(req.uploads.clone(), || Upload::from_input_value(&name));
+        let upload = upload.unwrap();
+        assert_eq!(upload.as_bytes(), b"content");
+
+        let other = upload::scope(None, || Upload::from_input_value(&name));
+        assert!(other.is_err());
+        assert!(Upload::from_input_value(&name).is_err());
+
+        drop(request);
+        assert_eq!(upload.as_bytes(), b"content");
+    }
+
+    #[test]
+    fn rejects_invalid_multipart_requests() {
+        let operations =
+            r#"{"query": "mutation ($f: Upload) {a(f: $f)}", "variables": {"f": null}}"#;
+
+        for fields in vec![
+            vec![("map", "{}")],
+            vec![("operations", operations)],
+            vec![("operations", "blah"), ("map", "{}")],
+            vec![("operations", operations), ("map", "blah")],
+            vec![
+                ("operations", operations),
+                ("map", r#"{"0": ["variables.f"]}"#),
+            ],
+            vec![
+                ("operations", operations),
+                ("map", r#"{"0": ["variables.g"]}"#),
+                ("0", "content"),
+            ],
+            vec![
+                ("operations", operations),
+                ("map", r#"{"0": ["query"]}"#),
+                ("0", "content"),
+            ],
+            vec![
+                ("operations", operations),
+                ("map", r#"{"0": ["0.variables.f"]}"#),
+                ("0", "content"),
+            ],
+        ] {
+            let error = parse_multipart(&fields).unwrap_err();
+
+            assert_eq!(error.status_code(), 400, "{}", error);
+        }
+
+        let error = parse(
+            "POST",
+            Some("multipart/form-data"),
+            "",
+            &multipart(&[("operations", operations), ("map", "{}")]),
+        )
+        .unwrap_err();
+        assert_eq!(error.status_code(), 400, "{}", error);
+    }
+
+    #[test]
+    fn rejects_requests_exceeding_limits() {
+        let operations =
+            r#"{"query": "mutation ($f: Upload) {a(f: $f)}", "variables": {"f": null}}"#;
+        let body = multipart(&[
+            ("operations", operations),
+            ("map", r#"{"0": ["variables.f"]}"#),
+            ("0", "content"),
+        ]);
+        let parse = |limits: RequestLimits| {
+            parse_post_request_with_limits::<DefaultScalarValue>(
+                Some("multipart/form-data; boundary=boundary"),
+                body.as_bytes(),
+                &limits,
+            )
+        };
+
+        assert!(parse(RequestLimits::new()).is_ok());
+        assert!(parse(
+            RequestLimits::new()
+                .max_body_size(body.len())
+                .max_file_size(7)
+        )
+        .is_ok());
+
+        let error = parse(RequestLimits::new().max_body_size(body.len() - 1)).unwrap_err();
+        assert_eq!(error.status_code(), 413, "{}", error);
+
+        let error = parse(RequestLimits::new().max_file_size(6)).unwrap_err();
+        assert_eq!(error.status_code(), 413, "{}", error);
+        assert_eq!(
+            error.to_string(),
+            "File '0' exceeds the maximum size of 6 bytes",
+        );
+    }
+}
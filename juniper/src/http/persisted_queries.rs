@@ -0,0 +1,259 @@
+//! Support for [Automatic Persisted Queries][1] (APQ)
+//!
+//! Instead of sending the whole query text, clients send its SHA-256 hash in
+//! the `extensions.persistedQuery` field of the request. If the server doesn't
+//! know the hash yet, it answers with a `PersistedQueryNotFound` error, and the
+//! client retries with both the query text and its hash, registering the query
+//! for the following requests.
+//!
+//! Persisted queries are enabled with
+//! [`HttpConfig::enable_persisted_queries`](super::HttpConfig::enable_persisted_queries),
+//! after which every request executed along with that config, e.g. through
+//! [`GraphQLRequest::execute_with_config`](super::GraphQLRequest::execute_with_config),
+//! supports them.
+//!
+//! [1]: https://www.apollographql.com/docs/apollo-server/performance/apq/
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use linked_hash_map::LinkedHashMap;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{FieldError, ScalarValue, Value};
+
+/// The only version of the persisted query protocol
+pub const PERSISTED_QUERY_VERSION: i32 = 1;
+
+/// The `extensions.persistedQuery` field of a request
+#[derive(Deserialize, Clone, Serialize, PartialEq, Debug)]
+pub struct PersistedQuery {
+    /// Version of the protocol, always `1`.
+    pub version: i32,
+
+    /// Lowercase hex-encoded SHA-256 hash of the query text.
+    #[serde(rename = "sha256Hash")]
+    pub sha256_hash: String,
+}
+
+impl PersistedQuery {
+    /// Construct the persisted query extension for the given query text
+    pub fn new(query: &str) -> Self {
+        PersistedQuery {
+            version: PERSISTED_QUERY_VERSION,
+            sha256_hash: sha256_hex(query),
+        }
+    }
+}
+
+/// Storage for persisted queries, keyed by their SHA-256 hash
+///
+/// Hashes are verified before queries are inserted, so implementations may
+/// trust them.
+pub trait PersistedQueryStore: Send + Sync {
+    /// Look up the query registered under the given hash
+    fn get(&self, sha256_hash: &str) -> Option<Arc<str>>;
+
+    /// Register a query under its hash
+    fn insert(&self, sha256_hash: String, query: Arc<str>);
+}
+
+impl fmt::Debug for dyn PersistedQueryStore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PersistedQueryStore")
+    }
+}
+
+/// In-memory `PersistedQueryStore`, evicting the least recently used queries
+/// once full
+#[derive(Debug)]
+pub struct InMemoryPersistedQueryStore {
+    capacity: usize,
+    queries: Mutex<LinkedHashMap<String, Arc<str>>>,
+}
+
+impl InMemoryPersistedQueryStore {
+    /// Construct a store keeping at most `capacity` queries
+    pub fn new(capacity: usize) -> Self {
+        InMemoryPersistedQueryStore {
+            capacity,
+            queries: Mutex::new(LinkedHashMap::new()),
+        }
+    }
+
+    /// The number of queries currently stored
+    pub fn len(&self) -> usize {
+        self.queries.lock().unwrap().len()
+    }
+
+    /// Whether no query is stored
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for InMemoryPersistedQueryStore {
+    /// Construct a store keeping at most 1000 queries
+    fn default() -> Self {
+        InMemoryPersistedQueryStore::new(1000)
+    }
+}
+
+impl PersistedQueryStore for InMemoryPersistedQueryStore {
+    fn get(&self, sha256_hash: &str) -> Option<Arc<str>> {
+        // The most recently used queries are kept at the back.
+        self.queries
+            .lock()
+            .unwrap()
+            .get_refresh(sha256_hash)
+            .cloned()
+    }
+
+    fn insert(&self, sha256_hash: String, query: Arc<str>) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut queries = self.queries.lock().unwrap();
+        if !queries.contains_key(&sha256_hash) && queries.len() >= self.capacity {
+            queries.pop_front();
+        }
+        queries.insert(sha256_hash, query);
+    }
+}
+
+/// Lowercase hex-encoded SHA-256 hash of the given query text
+pub fn sha256_hex(query: &str) -> String {
+    Sha256::digest(query.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// The error returned when a persisted query isn't known yet, asking the
+/// client to send the query text along with its hash
+pub fn not_found_error<S: ScalarValue>() -> FieldError<S> {
+    FieldError::new(
+        "PersistedQueryNotFound",
+        graphql_value!({ "code": "PERSISTED_QUERY_NOT_FOUND" }),
+    )
+}
+
+/// The error returned when a persisted query is sent to an endpoint not
+/// supporting them
+pub fn not_supported_error<S: ScalarValue>() -> FieldError<S> {
+    FieldError::new(
+        "PersistedQueryNotSupported",
+        graphql_value!({ "code": "PERSISTED_QUERY_NOT_SUPPORTED" }),
+    )
+}
+
+/// Resolve the query text of a request, looking up and registering persisted
+/// queries in the given store
+///
+/// Returns `Ok(None)` if the query text of the request should be used as is.
+pub(crate) fn resolve<S: ScalarValue>(
+    query: Option<&str>,
+    persisted_query: Option<&PersistedQuery>,
+    store: Option<&dyn PersistedQueryStore>,
+) -> Result<Option<Arc<str>>, FieldError<S>> {
+    let persisted_query = match persisted_query {
+        Some(persisted_query) => persisted_query,
+        None => return Ok(None),
+    };
+    let store = match (store, query) {
+        (Some(store), _) => store,
+        // Without a store, the query text is executed as in a regular request.
+        (None, Some(_)) => return Ok(None),
+        (None, None) => return Err(not_supported_error()),
+    };
+    if persisted_query.version != PERSISTED_QUERY_VERSION {
+        return Err(FieldError::new(
+            format!(
+                "Unsupported persisted query version {}",
+                persisted_query.version,
+            ),
+            Value::null(),
+        ));
+    }
+
+    match query {
+        Some(query) => {
+            if !persisted_query
+                .sha256_hash
+                .eq_ignore_ascii_case(&sha256_hex(query))
+            {
+                return Err(FieldError::new(
+                    "provided sha does not match query",
+                    Value::null(),
+                ));
+            }
+            store.insert(
+                persisted_query.sha256_hash.to_ascii_lowercase(),
+                query.into(),
+            );
+            Ok(None)
+        }
+        None => store
+            .get(&persisted_query.sha256_hash.to_ascii_lowercase())
+            .map(Some)
+            .ok_or_else(not_found_error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::DefaultScalarValue;
+
+    use super::{
+        resolve, sha256_hex, InMemoryPersistedQueryStore, PersistedQuery, PersistedQueryStore,
+    };
+
+    #[test]
+    fn hashes_as_lowercase_hex() {
+        assert_eq!(
+            sha256_hex("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        );
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let store = InMemoryPersistedQueryStore::new(2);
+        store.insert("a".into(), "{ a }".into());
+        store.insert("b".into(), "{ b }".into());
+        assert_eq!(store.get("a"), Some(Arc::from("{ a }")));
+
+        store.insert("c".into(), "{ c }".into());
+
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.get("b"), None);
+        assert!(store.get("a").is_some());
+        assert!(store.get("c").is_some());
+    }
+
+    #[test]
+    fn registers_verified_queries_only() {
+        let store = InMemoryPersistedQueryStore::default();
+        let query = "{ a }";
+
+        let mut wrong = PersistedQuery::new(query);
+        wrong.sha256_hash = sha256_hex("{ b }");
+        assert!(resolve::<DefaultScalarValue>(Some(query), Some(&wrong), Some(&store)).is_err());
+        assert!(store.is_empty());
+
+        let persisted = PersistedQuery::new(query);
+        assert_eq!(
+            resolve::<DefaultScalarValue>(Some(query), Some(&persisted), Some(&store)),
+            Ok(None),
+        );
+        assert_eq!(
+            resolve::<DefaultScalarValue>(None, Some(&persisted), Some(&store)),
+            Ok(Some(Arc::from(query))),
+        );
+    }
+}
@@ -1,18 +1,243 @@
 //! Utility module to generate a GraphQL Playground interface
 
+use super::graphiql::{html_attr, js_object, js_string, RequestCredentials};
+
 /// Generate the HTML source to show a GraphQL Playground interface
-// source: https://github.com/prisma/graphql-playground/blob/master/packages/graphql-playground-html/withAnimation.html
+///
+/// Use [`PlaygroundSettings`] to configure the interface further.
 pub fn playground_source(
     graphql_endpoint_url: &str,
     subscriptions_endpoint_url: Option<&str>,
 ) -> String {
-    let subscriptions_endpoint = if let Some(sub_url) = subscriptions_endpoint_url {
-        sub_url
-    } else {
-        graphql_endpoint_url
-    };
+    let mut settings = PlaygroundSettings::new(graphql_endpoint_url);
+    if let Some(url) = subscriptions_endpoint_url {
+        settings = settings.subscriptions_endpoint(url);
+    }
+    settings.source()
+}
 
-    r##"
+/// Theme of the GraphQL Playground interface
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaygroundTheme {
+    /// The default dark theme
+    Dark,
+    /// A light theme
+    Light,
+}
+
+impl PlaygroundTheme {
+    fn as_str(self) -> &'static str {
+        match self {
+            PlaygroundTheme::Dark => "dark",
+            PlaygroundTheme::Light => "light",
+        }
+    }
+}
+
+/// A tab opened by default in GraphQL Playground
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlaygroundTab {
+    query: String,
+    name: Option<String>,
+    variables: Option<String>,
+    headers: Vec<(String, String)>,
+}
+
+impl PlaygroundTab {
+    /// A tab showing the given query
+    pub fn new(query: &str) -> Self {
+        PlaygroundTab {
+            query: query.to_owned(),
+            name: None,
+            variables: None,
+            headers: vec![],
+        }
+    }
+
+    /// Name the tab
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_owned());
+        self
+    }
+
+    /// Fill the variables of the query with the given JSON
+    pub fn variables(mut self, variables: &str) -> Self {
+        self.variables = Some(variables.to_owned());
+        self
+    }
+
+    /// Send the given header with the queries of this tab
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_owned(), value.to_owned()));
+        self
+    }
+}
+
+/// Settings of a GraphQL Playground interface
+///
+/// Assets are loaded from a public CDN by default, at a pinned version which
+/// can be changed. They can also be self-hosted under a base URL given with
+/// [`assets_url`](PlaygroundSettings::assets_url).
+///
+/// ```
+/// # use juniper::http::{
+/// #     graphiql::RequestCredentials,
+/// #     playground::{PlaygroundSettings, PlaygroundTab, PlaygroundTheme},
+/// # };
+/// let playground = PlaygroundSettings::new("/graphql")
+///     .subscriptions_endpoint("/subscriptions")
+///     .header("Authorization", "Bearer token")
+///     .tab(PlaygroundTab::new("{ apiVersion }").name("Version"))
+///     .editor_theme(PlaygroundTheme::Light)
+///     .credentials(RequestCredentials::SameOrigin)
+///     .assets_url("/static/playground")
+///     .source();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlaygroundSettings {
+    graphql_endpoint_url: String,
+    subscriptions_endpoint_url: Option<String>,
+    headers: Vec<(String, String)>,
+    tabs: Vec<PlaygroundTab>,
+    editor_theme: Option<PlaygroundTheme>,
+    credentials: Option<RequestCredentials>,
+    version: String,
+    assets_url: Option<String>,
+}
+
+impl PlaygroundSettings {
+    /// Settings of a GraphQL Playground interface sending queries to the given
+    /// URL
+    pub fn new(graphql_endpoint_url: &str) -> Self {
+        PlaygroundSettings {
+            graphql_endpoint_url: graphql_endpoint_url.to_owned(),
+            subscriptions_endpoint_url: None,
+            headers: vec![],
+            tabs: vec![],
+            editor_theme: None,
+            credentials: None,
+            version: "1.7.20".to_owned(),
+            assets_url: None,
+        }
+    }
+
+    /// Send subscriptions to the given URL instead of the GraphQL endpoint
+    pub fn subscriptions_endpoint(mut self, url: &str) -> Self {
+        self.subscriptions_endpoint_url = Some(url.to_owned());
+        self
+    }
+
+    /// Send the given header with every query, e.g. an authentication token
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Open the given tab by default
+    pub fn tab(mut self, tab: PlaygroundTab) -> Self {
+        self.tabs.push(tab);
+        self
+    }
+
+    /// Use the given theme, `Dark` by default
+    pub fn editor_theme(mut self, theme: PlaygroundTheme) -> Self {
+        self.editor_theme = Some(theme);
+        self
+    }
+
+    /// Send queries with the given `credentials` mode, `Omit` by default
+    pub fn credentials(mut self, credentials: RequestCredentials) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
+
+    /// Load the given version of `graphql-playground-react` from the CDN,
+    /// `1.7.20` by default
+    pub fn version(mut self, version: &str) -> Self {
+        self.version = version.to_owned();
+        self
+    }
+
+    /// Load all assets from the given base URL instead of a public CDN
+    ///
+    /// The `index.css`, `favicon.png` and `middleware.js` files of the `build`
+    /// directory of the `graphql-playground-react` package are expected under
+    /// it, without their subdirectories.
+    pub fn assets_url(mut self, url: &str) -> Self {
+        self.assets_url = Some(url.trim_end_matches('/').to_owned());
+        self
+    }
+
+    /// The URL of the given asset, either self-hosted or on the CDN
+    fn asset(&self, path: &str) -> String {
+        let file = path.rsplit('/').next().unwrap_or(path);
+        match &self.assets_url {
+            Some(base) => format!("{}/{}", base, file),
+            None => format!(
+                "//cdn.jsdelivr.net/npm/graphql-playground-react@{}/build/{}",
+                self.version, path,
+            ),
+        }
+    }
+
+    /// The options given to `GraphQLPlayground.init`
+    fn options(&self) -> String {
+        let mut options = format!(
+            "{{ endpoint: {}, subscriptionEndpoint: {}",
+            js_string(&self.graphql_endpoint_url),
+            js_string(
+                self.subscriptions_endpoint_url
+                    .as_deref()
+                    .unwrap_or(&self.graphql_endpoint_url),
+            ),
+        );
+        if !self.headers.is_empty() {
+            options += &format!(", headers: {}", js_object(&self.headers));
+        }
+        let mut settings = vec![];
+        if let Some(theme) = self.editor_theme {
+            settings.push(("editor.theme".to_owned(), theme.as_str().to_owned()));
+        }
+        if let Some(credentials) = self.credentials {
+            settings.push((
+                "request.credentials".to_owned(),
+                credentials.as_str().to_owned(),
+            ));
+        }
+        if !settings.is_empty() {
+            options += &format!(", settings: {}", js_object(&settings));
+        }
+        if !self.tabs.is_empty() {
+            let tabs: Vec<_> = self
+                .tabs
+                .iter()
+                .map(|tab| {
+                    let mut fields = format!(
+                        "{{ endpoint: {}, query: {}",
+                        js_string(&self.graphql_endpoint_url),
+                        js_string(&tab.query),
+                    );
+                    if let Some(name) = &tab.name {
+                        fields += &format!(", name: {}", js_string(name));
+                    }
+                    if let Some(variables) = &tab.variables {
+                        fields += &format!(", variables: {}", js_string(variables));
+                    }
+                    if !tab.headers.is_empty() {
+                        fields += &format!(", headers: {}", js_object(&tab.headers));
+                    }
+                    fields + " }"
+                })
+                .collect();
+            options += &format!(", tabs: [{}]", tabs.join(", "));
+        }
+        options + " }"
+    }
+
+    /// Generate the HTML source of the interface
+    // source: https://github.com/prisma/graphql-playground/blob/master/packages/graphql-playground-html/withAnimation.html
+    pub fn source(&self) -> String {
+        r##"
 <!DOCTYPE html>
 
 <html>
@@ -21,9 +246,9 @@ pub fn playground_source(
   <meta charset=utf-8 />
   <meta name="viewport" content="user-scalable=no, initial-scale=1.0, minimum-scale=1.0, maximum-scale=1.0, minimal-ui">
   <title>GraphQL Playground</title>
-  <link rel="stylesheet" href="//cdn.jsdelivr.net/npm/graphql-playground-react/build/static/css/index.css" />
-  <link rel="shortcut icon" href="//cdn.jsdelivr.net/npm/graphql-playground-react/build/favicon.png" />
-  <script src="//cdn.jsdelivr.net/npm/graphql-playground-react@1.7.20/build/static/js/middleware.js"></script>
+  <link rel="stylesheet" href="JUNIPER_STYLESHEET_URL" />
+  <link rel="shortcut icon" href="JUNIPER_FAVICON_URL" />
+  <script src="JUNIPER_MIDDLEWARE_URL"></script>
 
 </head>
 
@@ -546,11 +771,86 @@ pub fn playground_source(
       const root = document.getElementById('root');
       root.classList.add('playgroundIn');
 
-      GraphQLPlayground.init(root, { endpoint: 'JUNIPER_GRAPHQL_URL', subscriptionEndpoint: 'JUNIPER_SUBSCRIPTIONS_URL' })
+      GraphQLPlayground.init(root, JUNIPER_PLAYGROUND_OPTIONS)
     })
   </script>
 </body>
 </html>
-  "##.replace("JUNIPER_GRAPHQL_URL", graphql_endpoint_url)
-     .replace("JUNIPER_SUBSCRIPTIONS_URL", subscriptions_endpoint)
+  "##
+        .replace(
+            "JUNIPER_STYLESHEET_URL",
+            &html_attr(&self.asset("static/css/index.css")),
+        )
+        .replace("JUNIPER_FAVICON_URL", &html_attr(&self.asset("favicon.png")))
+        .replace(
+            "JUNIPER_MIDDLEWARE_URL",
+            &html_attr(&self.asset("static/js/middleware.js")),
+        )
+        .replace("JUNIPER_PLAYGROUND_OPTIONS", &self.options())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{playground_source, PlaygroundSettings, PlaygroundTab, PlaygroundTheme};
+    use crate::http::graphiql::RequestCredentials;
+
+    #[test]
+    fn keeps_defaults() {
+        let source = playground_source("/graphql", None);
+
+        assert!(source.contains(
+            "GraphQLPlayground.init(root, { endpoint: '/graphql', subscriptionEndpoint: '/graphql' })"
+        ));
+        assert!(source.contains(
+            r#"src="//cdn.jsdelivr.net/npm/graphql-playground-react@1.7.20/build/static/js/middleware.js""#
+        ));
+    }
+
+    #[test]
+    fn uses_settings() {
+        let source = PlaygroundSettings::new("/graphql")
+            .subscriptions_endpoint("/subscriptions")
+            .header("Authorization", "Bearer token")
+            .tab(
+                PlaygroundTab::new("query ($id: ID!) { user(id: $id) { name } }")
+                    .name("User")
+                    .variables(r#"{"id": "1"}"#)
+                    .header("X-Tenant", "1"),
+            )
+            .editor_theme(PlaygroundTheme::Light)
+            .credentials(RequestCredentials::Include)
+            .version("1.7.26")
+            .source();
+
+        assert!(source.contains(
+            "GraphQLPlayground.init(root, { \
+             endpoint: '/graphql', \
+             subscriptionEndpoint: '/subscriptions', \
+             headers: {'Authorization': 'Bearer token'}, \
+             settings: {'editor.theme': 'light', 'request.credentials': 'include'}, \
+             tabs: [{ \
+               endpoint: '/graphql', \
+               query: 'query ($id: ID!) { user(id: $id) { name } }', \
+               name: 'User', \
+               variables: '{\"id\": \"1\"}', \
+               headers: {'X-Tenant': '1'} \
+             }] })"
+        ));
+        assert!(source.contains(
+            r#"href="//cdn.jsdelivr.net/npm/graphql-playground-react@1.7.26/build/static/css/index.css""#
+        ));
+    }
+
+    #[test]
+    fn self_hosts_assets() {
+        let source = PlaygroundSettings::new("/graphql")
+            .assets_url("/static/playground")
+            .source();
+
+        assert!(source.contains(r#"href="/static/playground/index.css""#));
+        assert!(source.contains(r#"href="/static/playground/favicon.png""#));
+        assert!(source.contains(r#"src="/static/playground/middleware.js""#));
+        assert!(!source.contains("//cdn"));
+    }
 }
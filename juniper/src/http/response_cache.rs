@@ -0,0 +1,373 @@
+//! Server-side caching of whole responses
+//!
+//! Once enabled with
+//! [`HttpConfig::enable_response_cache`](super::HttpConfig::enable_response_cache),
+//! the responses to queries executed along with that config, e.g. through
+//! [`GraphQLRequest::execute_with_config`](super::GraphQLRequest::execute_with_config),
+//! are looked up in a [`ResponseCache`] before being executed, and stored in it
+//! afterwards if they have no errors.
+//!
+//! Responses are keyed by the query text, normalized so that whitespace,
+//! commas, comments and the `query` keyword of anonymous queries don't matter,
+//! along with the operation name, the variables and a cache scope provided by
+//! the context of the request, e.g. the id of the user or `"public"`.
+//! Mutations, subscriptions and requests with file uploads are never cached.
+//!
+//! ```
+//! # use std::time::Duration;
+//! # use juniper::http::{response_cache::InMemoryResponseCache, HttpConfig};
+//! struct User {
+//!     id: Option<i32>,
+//! }
+//!
+//! let config = HttpConfig::<User>::new().enable_response_cache(
+//!     InMemoryResponseCache::new(1000, Duration::from_secs(30)),
+//!     |user: &User| Some(user.id.map_or("public".to_owned(), |id| id.to_string())),
+//! );
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write as _},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use linked_hash_map::LinkedHashMap;
+
+use crate::{
+    ast::InputValue,
+    executor::CachePolicy,
+    http::persisted_queries::sha256_hex,
+    parser::{Lexer, Token},
+    value::{DefaultScalarValue, ScalarValue},
+    Value, Variables,
+};
+
+/// A response stored in a [`ResponseCache`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CachedResponse<S = DefaultScalarValue> {
+    /// The `data` of the response.
+    pub data: Value<S>,
+
+    /// Cache policy computed while executing the query, from the
+    /// [cache hints](crate::CacheHint) of the fields it resolved.
+    pub cache_policy: CachePolicy,
+}
+
+/// Storage for responses, keyed by the lowercase hex-encoded SHA-256 hash of
+/// their normalized operation, variables and cache scope
+pub trait ResponseCache<S = DefaultScalarValue>: Send + Sync {
+    /// Look up the response stored under the given key
+    fn get(&self, key: &str) -> Option<CachedResponse<S>>;
+
+    /// Store a response without errors under the given key
+    ///
+    /// Implementations may decline to store it, e.g. depending on its cache
+    /// policy.
+    fn insert(&self, key: String, response: CachedResponse<S>);
+}
+
+impl<S> fmt::Debug for dyn ResponseCache<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ResponseCache")
+    }
+}
+
+/// In-memory `ResponseCache`, keeping responses for a fixed time to live and
+/// evicting the least recently used ones once full
+#[derive(Debug)]
+pub struct InMemoryResponseCache<S = DefaultScalarValue> {
+    capacity: usize,
+    ttl: Duration,
+    only_cacheable: bool,
+    responses: Mutex<LinkedHashMap<String, (Instant, CachedResponse<S>)>>,
+}
+
+impl<S> InMemoryResponseCache<S> {
+    /// Construct a cache keeping at most `capacity` responses, each for `ttl`
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        InMemoryResponseCache {
+            capacity,
+            ttl,
+            only_cacheable: false,
+            responses: Mutex::new(LinkedHashMap::new()),
+        }
+    }
+
+    /// Only store the responses whose cache policy allows caching, i.e. whose
+    /// fields all have a `max_age`, and keep them no longer than it
+    pub fn only_cacheable(mut self) -> Self {
+        self.only_cacheable = true;
+        self
+    }
+
+    /// The number of responses currently stored, including expired ones not
+    /// evicted yet
+    pub fn len(&self) -> usize {
+        self.responses.lock().unwrap().len()
+    }
+
+    /// Whether no response is stored
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all the stored responses
+    pub fn clear(&self) {
+        self.responses.lock().unwrap().clear();
+    }
+}
+
+impl<S> ResponseCache<S> for InMemoryResponseCache<S>
+where
+    S: ScalarValue + Send,
+{
+    fn get(&self, key: &str) -> Option<CachedResponse<S>> {
+        let mut responses = self.responses.lock().unwrap();
+        // The most recently used responses are kept at the back.
+        let (expires_at, response) = responses.get_refresh(key)?;
+        if *expires_at <= Instant::now() {
+            responses.remove(key);
+            return None;
+        }
+        Some(response.clone())
+    }
+
+    fn insert(&self, key: String, response: CachedResponse<S>) {
+        let mut ttl = self.ttl;
+        if self.only_cacheable {
+            match response.cache_policy.max_age {
+                Some(max_age) if max_age > 0 => {
+                    ttl = ttl.min(Duration::from_secs(max_age.into()));
+                }
+                _ => return,
+            }
+        }
+        if self.capacity == 0 || ttl == Duration::from_secs(0) {
+            return;
+        }
+
+        let mut responses = self.responses.lock().unwrap();
+        if !responses.contains_key(&key) && responses.len() >= self.capacity {
+            responses.pop_front();
+        }
+        responses.insert(key, (Instant::now() + ttl, response));
+    }
+}
+
+/// The key of the response to the given query, as sent by a context with the
+/// given cache scope
+pub fn cache_key<S: ScalarValue>(
+    query: &str,
+    operation_name: Option<&str>,
+    variables: &Variables<S>,
+    scope: &str,
+) -> String {
+    let mut source = normalize_query(query);
+    source.push('\n');
+    source.push_str(operation_name.unwrap_or_default());
+    source.push('\n');
+    let variables: BTreeMap<_, _> = variables.iter().collect();
+    for (name, value) in variables {
+        write!(source, "${}:", name).unwrap();
+        write_canonical(value, &mut source);
+        source.push(' ');
+    }
+    source.push('\n');
+    source.push_str(scope);
+    sha256_hex(&source)
+}
+
+/// The tokens of the given query text, separated by single spaces, without
+/// the `query` keyword of anonymous queries so that they're the same as their
+/// shorthand `{ … }`
+///
+/// Query texts that fail to tokenize are kept as is, their execution failing
+/// anyway.
+fn normalize_query(query: &str) -> String {
+    let mut normalized = String::with_capacity(query.len());
+    let mut depth = 0usize;
+    let mut tokens = Lexer::new(query).peekable();
+    while let Some(token) = tokens.next() {
+        let token = match token {
+            Ok(token) => token.item,
+            Err(_) => return query.to_owned(),
+        };
+        match token {
+            Token::EndOfFile => break,
+            Token::CurlyOpen => depth += 1,
+            Token::CurlyClose => depth = depth.saturating_sub(1),
+            Token::Name("query") if depth == 0 => {
+                if let Some(Ok(next)) = tokens.peek() {
+                    if next.item == Token::CurlyOpen {
+                        continue;
+                    }
+                }
+            }
+            _ => {}
+        }
+        write!(normalized, "{} ", token).unwrap();
+    }
+    normalized
+}
+
+/// Writes the given value with quoted strings and sorted object fields, so
+/// that equal values are always written the same
+fn write_canonical<S: ScalarValue>(value: &InputValue<S>, out: &mut String) {
+    match value {
+        InputValue::Null => out.push_str("null"),
+        InputValue::Scalar(s) => match s.as_str() {
+            Some(s) => write!(out, "{:?}", s).unwrap(),
+            None => write!(out, "{}", s).unwrap(),
+        },
+        InputValue::Enum(v) => out.push_str(v),
+        InputValue::Variable(v) => write!(out, "${}", v).unwrap(),
+        InputValue::List(items) => {
+            out.push('[');
+            for item in items {
+                write_canonical(&item.item, out);
+                out.push(',');
+            }
+            out.push(']');
+        }
+        InputValue::Object(fields) => {
+            let mut fields: Vec<_> = fields.iter().collect();
+            fields.sort_by(|(a, _), (b, _)| a.item.cmp(&b.item));
+            out.push('{');
+            for (name, value) in fields {
+                write!(out, "{:?}:", name.item).unwrap();
+                write_canonical(&value.item, out);
+                out.push(',');
+            }
+            out.push('}');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use indexmap::IndexMap;
+
+    use super::{cache_key, CachedResponse, InMemoryResponseCache, ResponseCache};
+    use crate::{executor::CachePolicy, DefaultScalarValue, InputValue, Variables};
+
+    const QUERY: &str = "query Q($a: Int, $b: Input) { f(a: $a, b: $b) }";
+
+    fn object(fields: &[(&str, InputValue)]) -> InputValue {
+        InputValue::object(fields.iter().cloned().collect::<IndexMap<_, _>>())
+    }
+
+    fn variables(a: InputValue, b: InputValue) -> Variables {
+        vec![("a".to_owned(), a), ("b".to_owned(), b)]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn normalizes_keys() {
+        let list = InputValue::list(vec![InputValue::scalar(1), InputValue::scalar(2)]);
+        let vars = variables(
+            InputValue::scalar(1),
+            object(&[("c", InputValue::scalar("x")), ("d", list.clone())]),
+        );
+        let key = cache_key(QUERY, Some("Q"), &vars, "public");
+
+        assert_eq!(
+            cache_key(
+                "# Comment\nquery Q($a: Int $b: Input)\n{\n  f(a: $a, b: $b),\n}\n",
+                Some("Q"),
+                &variables(
+                    InputValue::scalar(1),
+                    object(&[("d", list.clone()), ("c", InputValue::scalar("x"))]),
+                ),
+                "public",
+            ),
+            key,
+        );
+
+        assert_eq!(
+            cache_key("query {\n  f(a: 1),\n}", None, &vars, "public"),
+            cache_key("{ f(a: 1) }", None, &vars, "public"),
+        );
+        assert_ne!(
+            cache_key("{ query { f } }", None, &vars, "public"),
+            cache_key("{ f }", None, &vars, "public"),
+        );
+
+        assert_ne!(cache_key(QUERY, None, &vars, "public"), key);
+        assert_ne!(cache_key(QUERY, Some("Q"), &vars, "user:1"), key);
+        assert_ne!(
+            cache_key(
+                QUERY,
+                Some("Q"),
+                &variables(
+                    InputValue::scalar("1"),
+                    object(&[("c", InputValue::scalar("x")), ("d", list)]),
+                ),
+                "public",
+            ),
+            key,
+        );
+        assert_ne!(
+            cache_key(
+                "query Q($a: Int, $b: Input) { g(a: $a, b: $b) }",
+                Some("Q"),
+                &vars,
+                "public"
+            ),
+            key,
+        );
+    }
+
+    fn response(max_age: Option<u32>) -> CachedResponse<DefaultScalarValue> {
+        CachedResponse {
+            data: graphql_value!({"a": 1}),
+            cache_policy: CachePolicy {
+                max_age,
+                ..CachePolicy::default()
+            },
+        }
+    }
+
+    #[test]
+    fn evicts_least_recently_used_responses() {
+        let cache = InMemoryResponseCache::new(2, Duration::from_secs(60));
+        cache.insert("a".to_owned(), response(None));
+        cache.insert("b".to_owned(), response(None));
+        assert!(cache.get("a").is_some());
+
+        cache.insert("c".to_owned(), response(None));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("c").is_some());
+    }
+
+    #[test]
+    fn expires_responses() {
+        let cache = InMemoryResponseCache::new(2, Duration::from_millis(10));
+        cache.insert("a".to_owned(), response(None));
+        assert!(cache.get("a").is_some());
+
+        std::thread::sleep(Duration::from_millis(20));
+
+        assert!(cache.get("a").is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn only_stores_cacheable_responses() {
+        let cache = InMemoryResponseCache::new(3, Duration::from_secs(60)).only_cacheable();
+        cache.insert("uncached".to_owned(), response(None));
+        cache.insert("expired".to_owned(), response(Some(0)));
+        cache.insert("cached".to_owned(), response(Some(30)));
+
+        assert!(cache.get("uncached").is_none());
+        assert!(cache.get("expired").is_none());
+        assert_eq!(cache.get("cached"), Some(response(Some(30))));
+    }
+}
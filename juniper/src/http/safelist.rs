@@ -0,0 +1,193 @@
+//! Operation safelisting, only executing pre-registered operations
+//!
+//! A safelist is built from a manifest of approved operations, usually produced
+//! when building the clients, mapping operation ids to query texts:
+//!
+//! ```json
+//! {
+//!     "HeroName": "query HeroName { hero { name } }",
+//!     "HeroFriends": "query HeroFriends { hero { friends { name } } }"
+//! }
+//! ```
+//!
+//! Once enabled with
+//! [`HttpConfig::enable_safelist`](super::HttpConfig::enable_safelist),
+//! requests executed along with that config, e.g. through
+//! [`GraphQLRequest::execute_with_config`](super::GraphQLRequest::execute_with_config),
+//! must either refer
+//! to an operation by its `id`, by the SHA-256 hash of its text in the
+//! [`extensions.persistedQuery`](super::persisted_queries) field, or send a
+//! query text present in the manifest. Any other document is rejected before
+//! being parsed, and each request of a batch is checked on its own.
+//!
+//! ```
+//! # use juniper::http::safelist::Safelist;
+//! let safelist: Safelist = serde_json::from_str(
+//!     r#"{"HeroName": "query HeroName { hero { name } }"}"#,
+//! ).unwrap();
+//!
+//! assert_eq!(safelist.get("HeroName"), Some("query HeroName { hero { name } }"));
+//! ```
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::{FieldError, ScalarValue};
+
+use super::persisted_queries::{sha256_hex, PersistedQuery};
+
+/// The operations allowed to be executed on a schema
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(from = "HashMap<String, String>")]
+pub struct Safelist {
+    ids: HashMap<String, String>,
+    queries: HashMap<String, String>,
+}
+
+impl Safelist {
+    /// Construct a safelist from pairs of operation ids and query texts
+    pub fn new<I, K, V>(operations: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let mut safelist = Safelist::default();
+        for (id, query) in operations {
+            let (id, query) = (id.into(), query.into());
+            let hash = sha256_hex(&query);
+            safelist.ids.insert(id, hash.clone());
+            safelist.queries.insert(hash, query);
+        }
+        safelist
+    }
+
+    /// The query text of the operation with the given id
+    pub fn get(&self, id: &str) -> Option<&str> {
+        self.get_by_hash(self.ids.get(id)?)
+    }
+
+    /// The query text of the operation with the given SHA-256 hash
+    pub fn get_by_hash(&self, sha256_hash: &str) -> Option<&str> {
+        self.queries
+            .get(&sha256_hash.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+
+    /// Whether the given query text is allowed
+    pub fn contains(&self, query: &str) -> bool {
+        self.get_by_hash(&sha256_hex(query)).is_some()
+    }
+
+    /// The number of operations in the safelist
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Whether the safelist contains no operation
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Resolve the query text of a request, which must refer to an operation
+    /// of the safelist
+    ///
+    /// Returns `Ok(None)` for requests not referring to any operation.
+    pub(crate) fn resolve<S: ScalarValue>(
+        &self,
+        id: Option<&str>,
+        query: Option<&str>,
+        persisted_query: Option<&PersistedQuery>,
+    ) -> Result<Option<&str>, FieldError<S>> {
+        let found = match (id, query, persisted_query) {
+            (Some(id), _, _) => self.get(id),
+            (None, Some(query), _) => self.get_by_hash(&sha256_hex(query)),
+            (None, None, Some(persisted)) => self.get_by_hash(&persisted.sha256_hash),
+            (None, None, None) => return Ok(None),
+        };
+        match found {
+            // A query text sent along must be the one of the safelist.
+            Some(text) if query.map_or(true, |q| q == text) => Ok(Some(text)),
+            _ => Err(not_safelisted_error()),
+        }
+    }
+}
+
+impl From<HashMap<String, String>> for Safelist {
+    fn from(operations: HashMap<String, String>) -> Self {
+        Safelist::new(operations)
+    }
+}
+
+/// The error returned for requests not referring to an operation of the
+/// safelist
+pub fn not_safelisted_error<S: ScalarValue>() -> FieldError<S> {
+    FieldError::new(
+        "Operation is not in the safelist",
+        graphql_value!({ "code": "OPERATION_NOT_SAFELISTED" }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{http::persisted_queries::PersistedQuery, DefaultScalarValue};
+
+    use super::Safelist;
+
+    const QUERY: &str = "query HeroName { hero { name } }";
+
+    fn safelist() -> Safelist {
+        Safelist::new(vec![("HeroName", QUERY)])
+    }
+
+    #[test]
+    fn looks_up_operations() {
+        let safelist = safelist();
+
+        assert_eq!(safelist.len(), 1);
+        assert_eq!(safelist.get("HeroName"), Some(QUERY));
+        assert_eq!(safelist.get("Unknown"), None);
+        assert_eq!(
+            safelist.get_by_hash(&PersistedQuery::new(QUERY).sha256_hash.to_uppercase()),
+            Some(QUERY),
+        );
+        assert!(safelist.contains(QUERY));
+        assert!(!safelist.contains("{ hero { name } }"));
+    }
+
+    #[test]
+    fn resolves_requests() {
+        let safelist = safelist();
+        let persisted = PersistedQuery::new(QUERY);
+
+        for (id, query, persisted_query) in vec![
+            (Some("HeroName"), None, None),
+            (Some("HeroName"), Some(QUERY), None),
+            (None, Some(QUERY), None),
+            (None, None, Some(&persisted)),
+        ] {
+            assert_eq!(
+                safelist.resolve::<DefaultScalarValue>(id, query, persisted_query),
+                Ok(Some(QUERY)),
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_operations() {
+        let safelist = safelist();
+        let persisted = PersistedQuery::new("{ hero { id } }");
+
+        for (id, query, persisted_query) in vec![
+            (Some("Unknown"), None, None),
+            (Some("HeroName"), Some("{ hero { id } }"), None),
+            (None, Some("{ hero { id } }"), Some(&persisted)),
+            (None, None, Some(&persisted)),
+        ] {
+            assert!(safelist
+                .resolve::<DefaultScalarValue>(id, query, persisted_query)
+                .is_err());
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! File uploads, as described by the [GraphQL multipart request spec][1]
+//!
+//! Files sent in a `multipart/form-data` request are exposed to resolvers
+//! through [`Upload`] arguments:
+//!
+//! ```
+//! # use juniper::{graphql_object, http::upload::Upload};
+//! struct Mutation;
+//!
+//! #[graphql_object]
+//! impl Mutation {
+//!     fn upload_avatar(avatar: Upload) -> i32 {
+//!         avatar.len() as i32
+//!     }
+//! }
+//! ```
+//!
+//! The request variables only refer to the files by the name of their part,
+//! which is only looked up among the files of the request being executed.
+//! Upload values obtained from it stay usable afterwards.
+//!
+//! Files are held in memory, as the whole body of a request is read before it
+//! is parsed: [`RequestLimits::max_file_size`] and
+//! [`RequestLimits::max_body_size`] should be set to bound their size.
+//!
+//! [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+//! [`RequestLimits::max_file_size`]: crate::http::RequestLimits::max_file_size
+//! [`RequestLimits::max_body_size`]: crate::http::RequestLimits::max_body_size
+
+#![allow(clippy::needless_lifetimes)]
+use std::{cell::RefCell, collections::BTreeMap, fmt, future::Future, io, sync::Arc};
+
+use futures::{
+    future,
+    stream::{self, Stream},
+};
+
+use crate::{
+    parser::{ParseError, Token},
+    value::ParseScalarResult,
+    Value,
+};
+
+/// Size of the chunks yielded by [`Upload::stream`]
+const CHUNK_SIZE: usize = 64 * 1024;
+
+thread_local! {
+    /// Files of the request being executed on this thread
+    static CURRENT: RefCell<Option<Arc<Uploads>>> = RefCell::new(None);
+}
+
+#[derive(Debug, PartialEq)]
+struct File {
+    filename: Option<String>,
+    content_type: Option<String>,
+    content: Vec<u8>,
+}
+
+/// A file uploaded along with a multipart request
+///
+/// Cloning an upload is cheap, as its content is shared.
+#[derive(Clone)]
+pub struct Upload(Arc<File>);
+
+impl Upload {
+    /// Name of the file on the client, if sent
+    pub fn filename(&self) -> Option<&str> {
+        self.0.filename.as_deref()
+    }
+
+    /// Content type of the file, if sent
+    pub fn content_type(&self) -> Option<&str> {
+        self.0.content_type.as_deref()
+    }
+
+    /// Size of the file in bytes
+    pub fn len(&self) -> usize {
+        self.0.content.len()
+    }
+
+    /// Whether the file is empty
+    pub fn is_empty(&self) -> bool {
+        self.0.content.is_empty()
+    }
+
+    /// Content of the file
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0.content
+    }
+
+    /// Reader over the content of the file
+    pub fn reader(&self) -> impl io::Read + '_ {
+        io::Cursor::new(self.as_bytes())
+    }
+
+    /// Stream of the content of the file, in chunks of 64 KiB
+    ///
+    /// The content is already in memory, this is only meant for APIs taking
+    /// streams.
+    pub fn stream(&self) -> impl Stream<Item = Vec<u8>> + Send + 'static {
+        let file = self.0.clone();
+        stream::iter(
+            (0..file.content.len())
+                .step_by(CHUNK_SIZE)
+                .map(move |start| {
+                    let end = file.content.len().min(start + CHUNK_SIZE);
+                    file.content[start..end].to_vec()
+                }),
+        )
+    }
+}
+
+impl fmt::Debug for Upload {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Upload")
+            .field("filename", &self.0.filename)
+            .field("content_type", &self.0.content_type)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[crate::graphql_scalar(
+    name = "Upload",
+    description = "A file sent along with a multipart request. Only usable as a variable."
+)]
+impl<S> GraphQLScalar for Upload
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        // Uploads are input only, there is nothing to send back.
+        Value::null()
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Upload, String> {
+        v.as_string_value()
+            .and_then(|name| {
+                CURRENT.with(|current| current.borrow().as_ref()?.files.get(name).cloned())
+            })
+            .map(Upload)
+            .ok_or_else(|| format!("Expected a file of the multipart request, found {}", v))
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+    }
+}
+
+/// Files of a multipart request, by the name of their part
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Uploads {
+    files: BTreeMap<String, Arc<File>>,
+}
+
+#[cfg(feature = "serde_json")]
+impl Uploads {
+    /// Adds the file sent in the part of the given name
+    pub(crate) fn insert(
+        &mut self,
+        name: String,
+        filename: Option<String>,
+        content_type: Option<String>,
+        content: Vec<u8>,
+    ) {
+        let file = File {
+            filename,
+            content_type,
+            content,
+        };
+        self.files.insert(name, Arc::new(file));
+    }
+
+    /// Whether no file was sent
+    pub(crate) fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// Runs `f` with the files of the given request, so that its `Upload`
+/// arguments are looked up among them only
+pub(crate) fn scope<R>(uploads: Option<Arc<Uploads>>, f: impl FnOnce() -> R) -> R {
+    /// Restores the files of the outer request, even if `f` panics
+    struct Restore(Option<Arc<Uploads>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            CURRENT.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+
+    let _restore = Restore(CURRENT.with(|current| current.replace(uploads)));
+    f()
+}
+
+/// Polls `fut` with the files of the given request, as in [`scope`]
+pub(crate) async fn scoped<F: Future>(uploads: Option<Arc<Uploads>>, fut: F) -> F::Output {
+    futures::pin_mut!(fut);
+    future::poll_fn(|cx| scope(uploads.clone(), || fut.as_mut().poll(cx))).await
+}
@@ -0,0 +1,289 @@
+/*!
+
+# Supported types
+
+| Rust Type      | GraphQL Scalar | JSON Serialization                            |
+|----------------|----------------|-----------------------------------------------|
+| `Base64`       | `Base64`       | standard base64 string (RFC 4648 §4), padded  |
+| `Base64Url`    | `Base64Url`    | url-safe base64 string (RFC 4648 §5), padded  |
+| `bytes::Bytes` | `Base64`       | same as `Base64`. Requires the `bytes` feature |
+
+Decoding is strict: invalid characters, missing or superfluous padding and
+non-zero trailing bits are rejected.
+
+*/
+#![allow(clippy::needless_lifetimes)]
+use std::ops::{Deref, DerefMut};
+
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE},
+    Engine as _,
+};
+#[cfg(feature = "bytes")]
+use bytes::Bytes;
+
+use crate::{
+    parser::{ParseError, ScalarToken, Token},
+    value::ParseScalarResult,
+    Value,
+};
+
+/// Binary data, represented in GraphQL as a standard base64 string.
+///
+/// `Vec<u8>` is already exposed as a list of integers, so binary data has to be
+/// wrapped to be sent as a string.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Base64(pub Vec<u8>);
+
+/// Binary data, represented in GraphQL as a url-safe base64 string.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Base64Url(pub Vec<u8>);
+
+impl From<Vec<u8>> for Base64 {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Base64> for Vec<u8> {
+    fn from(bytes: Base64) -> Self {
+        bytes.0
+    }
+}
+
+impl Deref for Base64 {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Vec<u8> {
+        &self.0
+    }
+}
+
+impl DerefMut for Base64 {
+    fn deref_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.0
+    }
+}
+
+impl From<Vec<u8>> for Base64Url {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Base64Url> for Vec<u8> {
+    fn from(bytes: Base64Url) -> Self {
+        bytes.0
+    }
+}
+
+impl Deref for Base64Url {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Vec<u8> {
+        &self.0
+    }
+}
+
+impl DerefMut for Base64Url {
+    fn deref_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.0
+    }
+}
+
+#[crate::graphql_scalar(
+    name = "Base64",
+    description = "Binary data, encoded as a standard base64 string with padding (RFC 4648 §4)"
+)]
+impl<S> GraphQLScalar for Base64
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(STANDARD.encode(&self.0))
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Base64, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
+            .and_then(|s| {
+                STANDARD
+                    .decode(s)
+                    .map(Base64)
+                    .map_err(|e| format!(r#"Expected base64 string, found "{}": {}"#, s, e))
+            })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
+#[crate::graphql_scalar(
+    name = "Base64Url",
+    description = "Binary data, encoded as a url-safe base64 string with padding (RFC 4648 §5)"
+)]
+impl<S> GraphQLScalar for Base64Url
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(URL_SAFE.encode(&self.0))
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Base64Url, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
+            .and_then(|s| {
+                URL_SAFE.decode(s).map(Base64Url).map_err(|e| {
+                    format!(r#"Expected url-safe base64 string, found "{}": {}"#, s, e)
+                })
+            })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+#[crate::graphql_scalar(
+    name = "Base64",
+    description = "Binary data, encoded as a standard base64 string with padding (RFC 4648 §4)"
+)]
+impl<S> GraphQLScalar for Bytes
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(STANDARD.encode(self))
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Bytes, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
+            .and_then(|s| {
+                STANDARD
+                    .decode(s)
+                    .map(Bytes::from)
+                    .map_err(|e| format!(r#"Expected base64 string, found "{}": {}"#, s, e))
+            })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{value::DefaultScalarValue, FromInputValue, InputValue, ToInputValue};
+
+    use super::{Base64, Base64Url};
+
+    #[test]
+    fn base64_roundtrip() {
+        let bytes = Base64(vec![0xfb, 0xff, 0x00, 0x41]);
+        let input: InputValue<DefaultScalarValue> = bytes.to_input_value();
+
+        assert_eq!(input, InputValue::scalar("+/8AQQ=="));
+        assert_eq!(Base64::from_input_value(&input), Ok(bytes));
+    }
+
+    #[test]
+    fn base64_url_roundtrip() {
+        let bytes = Base64Url(vec![0xfb, 0xff, 0x00, 0x41]);
+        let input: InputValue<DefaultScalarValue> = bytes.to_input_value();
+
+        assert_eq!(input, InputValue::scalar("-_8AQQ=="));
+        assert_eq!(Base64Url::from_input_value(&input), Ok(bytes));
+    }
+
+    #[test]
+    fn decoding_is_strict() {
+        for raw in &["+/8AQQ", "+/8AQQ===", "-_8AQQ==", "+/8AQR==", "not base64"] {
+            let input: InputValue<DefaultScalarValue> = InputValue::scalar(*raw);
+
+            assert!(Base64::from_input_value(&input).is_err(), "{}", raw);
+        }
+
+        let input: InputValue<DefaultScalarValue> = InputValue::scalar("+/8AQQ==");
+        assert!(Base64Url::from_input_value(&input).is_err());
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn bytes_roundtrip() {
+        let bytes = bytes::Bytes::from_static(b"juniper");
+        let input: InputValue<DefaultScalarValue> = bytes.to_input_value();
+
+        assert_eq!(input, InputValue::scalar("anVuaXBlcg=="));
+        assert_eq!(bytes::Bytes::from_input_value(&input), Ok(bytes));
+    }
+}
+
+#[cfg(test)]
+mod integration_test {
+    use crate::{
+        executor::Variables,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        value::Value,
+    };
+
+    use super::Base64;
+
+    #[tokio::test]
+    async fn test_serialization() {
+        struct Root;
+
+        #[crate::graphql_object]
+        impl Root {
+            fn thumbnail() -> Base64 {
+                Base64(b"\x89PNG".to_vec())
+            }
+
+            fn length(data: Base64) -> i32 {
+                data.len() as i32
+            }
+        }
+
+        let doc = r#"{ thumbnail, length(data: "AAEC") }"#;
+
+        let schema = RootNode::new(
+            Root,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let (result, errs) = crate::execute(doc, None, &schema, &Variables::new(), &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        assert_eq!(
+            result,
+            Value::object(
+                vec![
+                    ("thumbnail", Value::scalar("iVBORw==")),
+                    ("length", Value::scalar(3)),
+                ]
+                .into_iter()
+                .collect()
+            )
+        );
+    }
+}
@@ -0,0 +1,166 @@
+//! [`BigDecimal`] scalar implementation, represented as a string.
+//!
+//! Numeric literals and variables are accepted as well, but only strings and
+//! literals preserve the full precision of the value.
+//!
+//! [`BigDecimal`]: bigdecimal::BigDecimal
+
+use std::str::FromStr as _;
+
+use bigdecimal::BigDecimal;
+
+use crate::{
+    graphql_scalar,
+    parser::ScalarToken,
+    value::{ParseScalarResult, ScalarValue},
+    Value,
+};
+
+#[graphql_scalar(
+    name = "BigDecimal",
+    description = "An arbitrary precision decimal number, represented as a string"
+)]
+impl<S> GraphQLScalar for BigDecimal
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(self.to_string())
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<BigDecimal, String> {
+        if let Some(i) = v.as_scalar().and_then(ScalarValue::as_i64) {
+            Ok(BigDecimal::from(i))
+        } else if let Some(f) = v.as_float_value() {
+            BigDecimal::from_str(&f.to_string())
+                .map_err(|e| format!(r#"Expected "BigDecimal", found {}: {}"#, f, e))
+        } else {
+            v.as_string_value()
+                .ok_or_else(|| format!(r#"Expected "BigDecimal", found {}"#, v))
+                .and_then(|s| {
+                    BigDecimal::from_str(s)
+                        .map_err(|e| format!(r#"Expected decimal number, found "{}": {}"#, s, e))
+                })
+        }
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        // Numeric literals are kept as strings, so no precision is lost.
+        match value {
+            ScalarToken::String(s) | ScalarToken::Int(s) | ScalarToken::Float(s) => {
+                Ok(S::from(s.to_owned()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr as _;
+
+    use bigdecimal::BigDecimal;
+
+    use crate::{DefaultScalarValue, FromInputValue, InputValue};
+
+    #[test]
+    fn from_input_value() {
+        for (input, expected) in vec![
+            (InputValue::scalar("123.456"), "123.456"),
+            (
+                InputValue::scalar("-98765432109876543210.0123456789012345678901"),
+                "-98765432109876543210.0123456789012345678901",
+            ),
+            (
+                InputValue::scalar("1e-30"),
+                "0.000000000000000000000000000001",
+            ),
+            (InputValue::scalar(42), "42"),
+            (
+                InputValue::scalar(9_007_199_254_740_993i64),
+                "9007199254740993",
+            ),
+            (InputValue::scalar(0.1), "0.1"),
+        ] {
+            let input: InputValue<DefaultScalarValue> = input;
+            let parsed = BigDecimal::from_input_value(&input);
+
+            assert_eq!(
+                parsed,
+                Ok(BigDecimal::from_str(expected).unwrap()),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn from_invalid_input_value() {
+        for input in vec![
+            InputValue::scalar("12,5"),
+            InputValue::scalar(true),
+            InputValue::null(),
+        ] {
+            let input: InputValue<DefaultScalarValue> = input;
+
+            assert!(BigDecimal::from_input_value(&input).is_err(), "{}", input);
+        }
+    }
+}
+
+#[cfg(test)]
+mod integration_test {
+    use bigdecimal::BigDecimal;
+
+    use crate::{
+        executor::Variables,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        value::Value,
+    };
+
+    #[tokio::test]
+    async fn keeps_precision() {
+        struct Root;
+
+        #[crate::graphql_object]
+        impl Root {
+            fn echo(value: BigDecimal) -> BigDecimal {
+                value
+            }
+        }
+
+        let doc = r#"
+        {
+            literal: echo(value: 123456789012345678901234567890.000000000000000000001),
+            string: echo(value: "-0.1"),
+        }
+        "#;
+
+        let schema = RootNode::new(
+            Root,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let (result, errs) = crate::execute(doc, None, &schema, &Variables::new(), &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        assert_eq!(
+            result,
+            Value::object(
+                vec![
+                    (
+                        "literal",
+                        Value::scalar("123456789012345678901234567890.000000000000000000001"),
+                    ),
+                    ("string", Value::scalar("-0.1")),
+                ]
+                .into_iter()
+                .collect()
+            )
+        );
+    }
+}
@@ -16,9 +16,13 @@ where
         Value::scalar(self.to_hex())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<ObjectId> {
+    fn from_input_value(v: &InputValue) -> Result<ObjectId, String> {
         v.as_string_value()
-            .and_then(|s| ObjectId::with_string(s).ok())
+            .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
+            .and_then(|s| {
+                ObjectId::with_string(s)
+                    .map_err(|e| format!(r#"Expected ObjectId, found "{}": {}"#, s, e))
+            })
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -39,9 +43,13 @@ where
         Value::scalar((*self).to_rfc3339())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<UtcDateTime> {
+    fn from_input_value(v: &InputValue) -> Result<UtcDateTime, String> {
         v.as_string_value()
-            .and_then(|s| (s.parse::<DateTime<Utc>>().ok()))
+            .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
+            .and_then(|s| {
+                s.parse::<DateTime<Utc>>()
+                    .map_err(|e| format!(r#"Expected RFC 3339 date-time, found "{}": {}"#, s, e))
+            })
             .map(UtcDateTime)
     }
 
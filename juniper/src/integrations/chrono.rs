@@ -13,13 +13,17 @@
 |                         |                        | resolution.                               |
 | `NaiveTime`             | H:M:S                  | Optional. Use the `scalar-naivetime`      |
 |                         |                        | feature.                                  |
+| `Duration`              | ISO 8601 duration      | Formatted in hours, minutes and seconds,  |
+|                         |                        | e.g. `-PT26H3M4.5S`. Years and months     |
+|                         |                        | are not accepted as input.                |
 
 */
 #![allow(clippy::needless_lifetimes)]
-use chrono::prelude::*;
+use chrono::{prelude::*, Duration};
 
 use crate::{
     parser::{ParseError, ScalarToken, Token},
+    types::duration::{format_iso8601, parse_iso8601},
     value::{ParseScalarResult, ParseScalarValue},
     Value,
 };
@@ -36,9 +40,13 @@ where
         Value::scalar(self.to_rfc3339())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<DateTime<FixedOffset>> {
+    fn from_input_value(v: &InputValue) -> Result<DateTime<FixedOffset>, String> {
         v.as_string_value()
-            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
+            .and_then(|s| {
+                DateTime::parse_from_rfc3339(s)
+                    .map_err(|e| format!(r#"Expected RFC 3339 date-time, found "{}": {}"#, s, e))
+            })
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -59,9 +67,13 @@ where
         Value::scalar(self.to_rfc3339())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<DateTime<Utc>> {
+    fn from_input_value(v: &InputValue) -> Result<DateTime<Utc>, String> {
         v.as_string_value()
-            .and_then(|s| (s.parse::<DateTime<Utc>>().ok()))
+            .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
+            .and_then(|s| {
+                s.parse::<DateTime<Utc>>()
+                    .map_err(|e| format!(r#"Expected RFC 3339 date-time, found "{}": {}"#, s, e))
+            })
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -87,9 +99,13 @@ where
         Value::scalar(self.format("%Y-%m-%d").to_string())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<NaiveDate> {
+    fn from_input_value(v: &InputValue) -> Result<NaiveDate, String> {
         v.as_string_value()
-            .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+            .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
+            .and_then(|s| {
+                NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                    .map_err(|e| format!(r#"Expected YYYY-MM-DD date, found "{}": {}"#, s, e))
+            })
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -111,9 +127,13 @@ where
         Value::scalar(self.format("%H:%M:%S").to_string())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<NaiveTime> {
+    fn from_input_value(v: &InputValue) -> Result<NaiveTime, String> {
         v.as_string_value()
-            .and_then(|s| NaiveTime::parse_from_str(s, "%H:%M:%S").ok())
+            .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
+            .and_then(|s| {
+                NaiveTime::parse_from_str(s, "%H:%M:%S")
+                    .map_err(|e| format!(r#"Expected HH:MM:SS time, found "{}": {}"#, s, e))
+            })
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -136,9 +156,13 @@ where
         Value::scalar(self.timestamp() as f64)
     }
 
-    fn from_input_value(v: &InputValue) -> Option<NaiveDateTime> {
+    fn from_input_value(v: &InputValue) -> Result<NaiveDateTime, String> {
         v.as_float_value()
-            .and_then(|f| NaiveDateTime::from_timestamp_opt(f as i64, 0))
+            .ok_or_else(|| format!(r#"Expected "Float", found {}"#, v))
+            .and_then(|f| {
+                NaiveDateTime::from_timestamp_opt(f as i64, 0)
+                    .ok_or_else(|| format!("Expected UNIX timestamp, found out-of-range {}", f))
+            })
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -146,10 +170,57 @@ where
     }
 }
 
+#[crate::graphql_scalar(
+    name = "Duration",
+    description = "An ISO 8601 duration, e.g. `PT1H30M`"
+)]
+impl<S> GraphQLScalar for Duration
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        let secs = self.num_seconds();
+        let nanos = (*self - Duration::seconds(secs))
+            .num_nanoseconds()
+            .unwrap_or(0);
+        Value::scalar(format_iso8601(
+            secs < 0 || nanos < 0,
+            secs.unsigned_abs(),
+            nanos.unsigned_abs() as u32,
+        ))
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Duration, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
+            .and_then(|s| {
+                parse_iso8601(s)
+                    .and_then(|(negative, secs, nanos)| {
+                        // `Duration` is limited to `i64::MAX` milliseconds.
+                        if secs >= (i64::MAX / 1000) as u64 {
+                            return Err("out of range".into());
+                        }
+                        let d = Duration::seconds(secs as i64)
+                            + Duration::nanoseconds(i64::from(nanos));
+                        Ok(if negative { -d } else { d })
+                    })
+                    .map_err(|e| format!(r#"Expected ISO 8601 duration, found "{}": {}"#, s, e))
+            })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{value::DefaultScalarValue, InputValue};
-    use chrono::prelude::*;
+    use chrono::{prelude::*, Duration};
 
     fn datetime_fixedoffset_test(raw: &'static str) {
         let input: crate::InputValue<DefaultScalarValue> = InputValue::scalar(raw.to_string());
@@ -245,11 +316,28 @@ mod test {
         assert_eq!(parsed, expected);
         assert_eq!(raw, expected.timestamp() as f64);
     }
+
+    #[test]
+    fn duration_from_input_value() {
+        for (raw, expected) in vec![
+            ("PT0S", Duration::zero()),
+            ("P1DT2H", Duration::hours(26)),
+            ("-PT1M0.5S", -Duration::milliseconds(60_500)),
+        ] {
+            let input: InputValue<DefaultScalarValue> = InputValue::scalar(raw);
+            let parsed: Duration = crate::FromInputValue::from_input_value(&input).unwrap();
+
+            assert_eq!(parsed, expected);
+        }
+
+        let input: InputValue<DefaultScalarValue> = InputValue::scalar("P1M");
+        assert!(<Duration as crate::FromInputValue>::from_input_value(&input).is_err());
+    }
 }
 
 #[cfg(test)]
 mod integration_test {
-    use chrono::{prelude::*, Utc};
+    use chrono::{prelude::*, Duration, Utc};
 
     use crate::{
         executor::Variables,
@@ -280,6 +368,9 @@ mod integration_test {
             fn exampleDateTimeUtc() -> DateTime<Utc> {
                 Utc.timestamp(61, 0)
             }
+            fn exampleDuration() -> Duration {
+                -(Duration::hours(26) + Duration::milliseconds(4_500))
+            }
         }
 
         #[crate::graphql_object]
@@ -297,6 +388,9 @@ mod integration_test {
             fn exampleDateTimeUtc() -> DateTime<Utc> {
                 Utc.timestamp(61, 0)
             }
+            fn exampleDuration() -> Duration {
+                -(Duration::hours(26) + Duration::milliseconds(4_500))
+            }
         }
 
         #[cfg(feature = "scalar-naivetime")]
@@ -307,6 +401,7 @@ mod integration_test {
             exampleNaiveTime,
             exampleDateTimeFixedOffset,
             exampleDateTimeUtc,
+            exampleDuration,
         }
         "#;
 
@@ -317,6 +412,7 @@ mod integration_test {
             exampleNaiveDateTime,
             exampleDateTimeFixedOffset,
             exampleDateTimeUtc,
+            exampleDuration,
         }
         "#;
 
@@ -348,6 +444,7 @@ mod integration_test {
                         "exampleDateTimeUtc",
                         Value::scalar("1970-01-01T00:01:01+00:00"),
                     ),
+                    ("exampleDuration", Value::scalar("-PT26H4.5S")),
                 ]
                 .into_iter()
                 .collect()
@@ -21,8 +21,13 @@ where
         Value::scalar(self.name().to_owned())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<Tz> {
-        v.as_string_value().and_then(|s| s.parse::<Tz>().ok())
+    fn from_input_value(v: &InputValue) -> Result<Tz, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
+            .and_then(|s| {
+                s.parse::<Tz>()
+                    .map_err(|e| format!(r#"Expected IANA time zone, found "{}": {}"#, s, e))
+            })
     }
 
     fn from_str<'a>(val: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -43,7 +48,7 @@ mod test {
 
         fn tz_input_test(raw: &'static str, expected: Option<Tz>) {
             let input = <InputValue<DefaultScalarValue>>::scalar(raw.to_string());
-            let parsed: Option<Tz> = FromInputValue::from_input_value(&input);
+            let parsed: Option<Tz> = FromInputValue::from_input_value(&input).ok();
 
             assert_eq!(parsed, expected);
         }
@@ -11,6 +11,14 @@ pub mod chrono;
 /// GraphQL support for [chrono-tz](https://github.com/chronotope/chrono-tz) types.
 pub mod chrono_tz;
 
+#[cfg(feature = "serde_json")]
+/// GraphQL support for [serde_json](https://github.com/serde-rs/json) types.
+pub mod serde_json;
+
+#[cfg(feature = "time")]
+/// GraphQL support for [time](https://github.com/time-rs/time) types.
+pub mod time;
+
 #[cfg(feature = "url")]
 /// GraphQL support for [url](https://github.com/servo/rust-url) types.
 pub mod url;
@@ -19,6 +27,22 @@ pub mod url;
 /// GraphQL support for [uuid](https://doc.rust-lang.org/uuid/uuid/struct.Uuid.html) types.
 pub mod uuid;
 
+#[cfg(feature = "base64")]
+/// GraphQL support for binary data encoded with [base64](https://github.com/marshallpierce/rust-base64).
+pub mod base64;
+
 #[cfg(feature = "bson")]
 /// GraphQL support for [bson](https://github.com/mongodb/bson-rust) types.
 pub mod bson;
+
+#[cfg(feature = "rust_decimal")]
+/// GraphQL support for [rust_decimal](https://github.com/paupino/rust-decimal) types.
+pub mod rust_decimal;
+
+#[cfg(feature = "bigdecimal")]
+/// GraphQL support for [bigdecimal](https://github.com/akubera/bigdecimal-rs) types.
+pub mod bigdecimal;
+
+#[cfg(feature = "num-bigint")]
+/// GraphQL support for [num-bigint](https://github.com/rust-num/num-bigint) types.
+pub mod num_bigint;
@@ -0,0 +1,215 @@
+//! [`BigInt`] and [`BigUint`] scalar implementations, represented as strings.
+//!
+//! Integer literals and variables are accepted as well, but only strings and
+//! literals preserve the full precision of values beyond 64 bit.
+//!
+//! [`BigInt`]: num_bigint::BigInt
+//! [`BigUint`]: num_bigint::BigUint
+
+use std::str::FromStr as _;
+
+use num_bigint::{BigInt, BigUint};
+
+use crate::{
+    graphql_scalar,
+    parser::{ParseError, ScalarToken, Token},
+    value::{ParseScalarResult, ScalarValue},
+    Value,
+};
+
+#[graphql_scalar(
+    name = "BigInt",
+    description = "An arbitrary precision signed integer, represented as a string"
+)]
+impl<S> GraphQLScalar for BigInt
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(self.to_string())
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<BigInt, String> {
+        let scalar = v.as_scalar();
+        if let Some(i) = scalar.and_then(ScalarValue::as_i64) {
+            Ok(BigInt::from(i))
+        } else if let Some(u) = scalar.and_then(ScalarValue::as_u64) {
+            Ok(BigInt::from(u))
+        } else {
+            v.as_string_value()
+                .ok_or_else(|| format!(r#"Expected "BigInt", found {}"#, v))
+                .and_then(|s| {
+                    BigInt::from_str(s)
+                        .map_err(|e| format!(r#"Expected integer, found "{}": {}"#, s, e))
+                })
+        }
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        // Integer literals are kept as strings, so no precision is lost.
+        match value {
+            ScalarToken::String(s) | ScalarToken::Int(s) => Ok(S::from(s.to_owned())),
+            ScalarToken::Float(_) => Err(ParseError::UnexpectedToken(Token::Scalar(value))),
+        }
+    }
+}
+
+#[graphql_scalar(
+    name = "UnsignedBigInt",
+    description = "An arbitrary precision unsigned integer, represented as a string"
+)]
+impl<S> GraphQLScalar for BigUint
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(self.to_string())
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<BigUint, String> {
+        if let Some(u) = v.as_scalar().and_then(ScalarValue::as_u64) {
+            Ok(BigUint::from(u))
+        } else {
+            v.as_string_value()
+                .ok_or_else(|| format!(r#"Expected "UnsignedBigInt", found {}"#, v))
+                .and_then(|s| {
+                    BigUint::from_str(s)
+                        .map_err(|e| format!(r#"Expected unsigned integer, found "{}": {}"#, s, e))
+                })
+        }
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        // Integer literals are kept as strings, so no precision is lost.
+        match value {
+            ScalarToken::String(s) | ScalarToken::Int(s) => Ok(S::from(s.to_owned())),
+            ScalarToken::Float(_) => Err(ParseError::UnexpectedToken(Token::Scalar(value))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr as _;
+
+    use num_bigint::{BigInt, BigUint};
+
+    use crate::{DefaultScalarValue, FromInputValue, InputValue};
+
+    #[test]
+    fn big_int_from_input_value() {
+        for (input, expected) in vec![
+            (
+                InputValue::scalar("-340282366920938463463374607431768211456"),
+                "-340282366920938463463374607431768211456",
+            ),
+            (InputValue::scalar(-42), "-42"),
+            (InputValue::scalar(i64::min_value()), "-9223372036854775808"),
+            (InputValue::scalar(u64::max_value()), "18446744073709551615"),
+        ] {
+            let input: InputValue<DefaultScalarValue> = input;
+            let parsed = BigInt::from_input_value(&input);
+
+            assert_eq!(parsed, Ok(BigInt::from_str(expected).unwrap()), "{}", input);
+        }
+    }
+
+    #[test]
+    fn big_uint_from_input_value() {
+        for (input, expected) in vec![
+            (
+                InputValue::scalar("340282366920938463463374607431768211456"),
+                "340282366920938463463374607431768211456",
+            ),
+            (InputValue::scalar(42), "42"),
+            (InputValue::scalar(u64::max_value()), "18446744073709551615"),
+        ] {
+            let input: InputValue<DefaultScalarValue> = input;
+            let parsed = BigUint::from_input_value(&input);
+
+            assert_eq!(
+                parsed,
+                Ok(BigUint::from_str(expected).unwrap()),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn from_invalid_input_value() {
+        for input in vec![
+            InputValue::scalar("1.5"),
+            InputValue::scalar(1.5),
+            InputValue::scalar(true),
+            InputValue::null(),
+        ] {
+            let input: InputValue<DefaultScalarValue> = input;
+
+            assert!(BigInt::from_input_value(&input).is_err(), "{}", input);
+            assert!(BigUint::from_input_value(&input).is_err(), "{}", input);
+        }
+
+        let negative: InputValue<DefaultScalarValue> = InputValue::scalar(-1);
+        assert!(BigUint::from_input_value(&negative).is_err());
+    }
+}
+
+#[cfg(test)]
+mod integration_test {
+    use num_bigint::{BigInt, BigUint};
+
+    use crate::{
+        executor::Variables,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        value::Value,
+    };
+
+    #[tokio::test]
+    async fn keeps_precision() {
+        struct Root;
+
+        #[crate::graphql_object]
+        impl Root {
+            fn signed(value: BigInt) -> BigInt {
+                value
+            }
+
+            fn unsigned(value: BigUint) -> BigUint {
+                value
+            }
+        }
+
+        let doc = r#"
+        {
+            literal: signed(value: -123456789012345678901234567890),
+            string: unsigned(value: "123456789012345678901234567890"),
+        }
+        "#;
+
+        let schema = RootNode::new(
+            Root,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let (result, errs) = crate::execute(doc, None, &schema, &Variables::new(), &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        assert_eq!(
+            result,
+            Value::object(
+                vec![
+                    ("literal", Value::scalar("-123456789012345678901234567890")),
+                    ("string", Value::scalar("123456789012345678901234567890")),
+                ]
+                .into_iter()
+                .collect()
+            )
+        );
+    }
+}
@@ -285,6 +285,36 @@ where
     }
 }
 
+impl<'de, S> de::Deserialize<'de> for Value<S>
+where
+    S: ScalarValue,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Value<S>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        // JSON documents only ever produce nulls, scalars, lists and objects,
+        // which map one to one onto output values.
+        fn into_value<S: ScalarValue>(input: InputValue<S>) -> Value<S> {
+            match input {
+                InputValue::Null | InputValue::Variable(_) => Value::Null,
+                InputValue::Scalar(s) => Value::Scalar(s),
+                InputValue::Enum(e) => Value::Scalar(S::from(e)),
+                InputValue::List(l) => {
+                    Value::List(l.into_iter().map(|v| into_value(v.item)).collect())
+                }
+                InputValue::Object(o) => Value::Object(
+                    o.into_iter()
+                        .map(|(k, v)| (k.item, into_value(v.item)))
+                        .collect(),
+                ),
+            }
+        }
+
+        InputValue::<S>::deserialize(deserializer).map(into_value)
+    }
+}
+
 impl<T> ser::Serialize for InputValue<T>
 where
     T: ScalarValue,
@@ -407,6 +437,69 @@ where
     }
 }
 
+/// Converts a constant [`InputValue`] into a JSON value.
+///
+/// Enum values become strings. Variables have to be substituted beforehand.
+#[cfg(feature = "serde_json")]
+pub(crate) fn input_value_to_json<S>(v: &InputValue<S>) -> Result<serde_json::Value, String>
+where
+    S: ScalarValue,
+{
+    Ok(match *v {
+        InputValue::Null => serde_json::Value::Null,
+        InputValue::Variable(ref name) => return Err(format!("Unexpected variable ${}", name)),
+        // Integers are converted as numbers even if the scalar value serializes
+        // them as strings, e.g. with the `long-as-string` feature.
+        InputValue::Scalar(ref s) => match (s.as_i64(), s.as_u64()) {
+            (Some(i), _) => i.into(),
+            (None, Some(u)) => u.into(),
+            (None, None) => serde_json::to_value(s).map_err(|e| e.to_string())?,
+        },
+        InputValue::Enum(ref e) => serde_json::Value::String(e.clone()),
+        InputValue::List(ref l) => serde_json::Value::Array(
+            l.iter()
+                .map(|v| input_value_to_json(&v.item))
+                .collect::<Result<_, _>>()?,
+        ),
+        InputValue::Object(ref o) => serde_json::Value::Object(
+            o.iter()
+                .map(|(k, v)| Ok((k.item.clone(), input_value_to_json(&v.item)?)))
+                .collect::<Result<_, String>>()?,
+        ),
+    })
+}
+
+/// Converts a JSON value into an output [`Value`], keeping objects and arrays
+/// structured.
+#[cfg(feature = "serde_json")]
+pub(crate) fn json_to_value<S>(json: &serde_json::Value) -> Value<S>
+where
+    S: ScalarValue,
+{
+    use std::convert::TryFrom as _;
+
+    match *json {
+        serde_json::Value::Null => Value::null(),
+        serde_json::Value::Bool(b) => Value::scalar(b),
+        serde_json::Value::Number(ref n) => {
+            if let Some(i) = n.as_i64() {
+                Value::Scalar(i32::try_from(i).map_or_else(|_| S::from_i64(i), S::from))
+            } else if let Some(u) = n.as_u64() {
+                Value::Scalar(S::from_u64(u))
+            } else {
+                Value::scalar(n.as_f64().unwrap_or_default())
+            }
+        }
+        serde_json::Value::String(ref s) => Value::scalar(s.clone()),
+        serde_json::Value::Array(ref a) => Value::list(a.iter().map(json_to_value).collect()),
+        serde_json::Value::Object(ref o) => Value::object(
+            o.iter()
+                .map(|(k, v)| (k.as_str(), json_to_value(v)))
+                .collect(),
+        ),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{ExecutionError, GraphQLError};
@@ -431,10 +524,58 @@ mod tests {
             from_str::<InputValue<DefaultScalarValue>>("2.0").unwrap(),
             InputValue::scalar(2.0)
         );
-        // large value without a decimal part is also float
+        // large value without a decimal part is still usable as float
+        assert_eq!(
+            from_str::<InputValue<DefaultScalarValue>>("123567890123")
+                .unwrap()
+                .as_float_value(),
+            Some(123_567_890_123.0)
+        );
+    }
+
+    #[test]
+    fn long() {
         assert_eq!(
             from_str::<InputValue<DefaultScalarValue>>("123567890123").unwrap(),
-            InputValue::scalar(123_567_890_123.0)
+            InputValue::scalar(123_567_890_123i64)
+        );
+        assert_eq!(
+            from_str::<InputValue<DefaultScalarValue>>("-123567890123").unwrap(),
+            InputValue::scalar(-123_567_890_123i64)
+        );
+        assert_eq!(
+            from_str::<InputValue<DefaultScalarValue>>("18446744073709551615").unwrap(),
+            InputValue::scalar(u64::max_value())
+        );
+    }
+
+    #[cfg(not(feature = "long-as-string"))]
+    #[test]
+    fn long_as_number() {
+        assert_eq!(
+            to_string(&Value::<DefaultScalarValue>::scalar(i64::min_value())).unwrap(),
+            "-9223372036854775808"
+        );
+        assert_eq!(
+            to_string(&Value::<DefaultScalarValue>::scalar(u64::max_value())).unwrap(),
+            "18446744073709551615"
+        );
+    }
+
+    #[cfg(feature = "long-as-string")]
+    #[test]
+    fn long_as_string() {
+        assert_eq!(
+            to_string(&Value::<DefaultScalarValue>::scalar(i64::min_value())).unwrap(),
+            r#""-9223372036854775808""#
+        );
+        assert_eq!(
+            to_string(&Value::<DefaultScalarValue>::scalar(u64::max_value())).unwrap(),
+            r#""18446744073709551615""#
+        );
+        assert_eq!(
+            to_string(&Value::<DefaultScalarValue>::scalar(1)).unwrap(),
+            "1"
         );
     }
 
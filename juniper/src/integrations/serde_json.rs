@@ -0,0 +1,295 @@
+/*!
+
+# Supported types
+
+| Rust Type                 | GraphQL Scalar | Notes                                         |
+|---------------------------|----------------|-----------------------------------------------|
+| `serde_json::Value`       | `JSON`         | Resolved as a nested object or list, not as   |
+|                           |                | a string.                                     |
+| `serde_json::Map`         | `JSONObject`   | Only accepts objects.                         |
+
+Both scalars accept object and list literals as well as variables holding
+arbitrary JSON.
+
+*/
+#![allow(clippy::needless_lifetimes)]
+use serde_json::{Map, Value as Json};
+
+use crate::{
+    integrations::serde::{input_value_to_json, json_to_value},
+    parser::ScalarToken,
+    value::{ParseScalarResult, ParseScalarValue},
+    Value,
+};
+
+#[crate::graphql_scalar(name = "JSON", description = "Arbitrary JSON value")]
+impl<S> GraphQLScalar for Json
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        json_to_value(self)
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Json, String> {
+        input_value_to_json(v)
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        match value {
+            ScalarToken::String(_) => <String as ParseScalarValue<S>>::from_str(value),
+            ScalarToken::Int(v) => v
+                .parse()
+                .map(S::from_i64)
+                .or_else(|_| v.parse().map(S::from_u64))
+                .or_else(|_| <f64 as ParseScalarValue<S>>::from_str(value)),
+            ScalarToken::Float(_) => <f64 as ParseScalarValue<S>>::from_str(value),
+        }
+    }
+}
+
+#[crate::graphql_scalar(name = "JSONObject", description = "Arbitrary JSON object")]
+impl<S> GraphQLScalar for Map<String, Json>
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::object(
+            self.iter()
+                .map(|(k, v)| (k.as_str(), json_to_value(v)))
+                .collect(),
+        )
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Map<String, Json>, String> {
+        match input_value_to_json(v)? {
+            Json::Object(map) => Ok(map),
+            _ => Err(format!(r#"Expected "JSONObject", found {}"#, v)),
+        }
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        <Json as ParseScalarValue<S>>::from_str(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{json, Map, Value as Json};
+
+    use crate::{
+        integrations::serde::{input_value_to_json, json_to_value},
+        value::{DefaultScalarValue, Value},
+        FromInputValue, InputValue,
+    };
+
+    #[test]
+    fn input_value_to_json_converts_nested_values() {
+        let input: InputValue<DefaultScalarValue> = InputValue::object(
+            vec![
+                ("int", InputValue::scalar(1)),
+                ("long", InputValue::scalar(5_000_000_000i64)),
+                ("float", InputValue::scalar(1.5)),
+                ("enum", InputValue::enum_value("RED")),
+                (
+                    "list",
+                    InputValue::list(vec![InputValue::scalar(true), InputValue::null()]),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        assert_eq!(
+            input_value_to_json(&input),
+            Ok(json!({
+                "int": 1,
+                "long": 5_000_000_000i64,
+                "float": 1.5,
+                "enum": "RED",
+                "list": [true, null],
+            })),
+        );
+    }
+
+    #[test]
+    fn input_value_to_json_rejects_variables() {
+        let input: InputValue<DefaultScalarValue> =
+            InputValue::list(vec![InputValue::variable("x")]);
+
+        assert!(input_value_to_json(&input).is_err());
+    }
+
+    #[test]
+    fn json_to_value_keeps_structure() {
+        let value: Value<DefaultScalarValue> = json_to_value(&json!({"a": [1, "b", null]}));
+
+        assert_eq!(
+            value,
+            Value::object(
+                vec![(
+                    "a",
+                    Value::list(vec![Value::scalar(1), Value::scalar("b"), Value::null()]),
+                )]
+                .into_iter()
+                .collect()
+            ),
+        );
+    }
+
+    #[test]
+    fn map_rejects_non_objects() {
+        let input: InputValue<DefaultScalarValue> = InputValue::list(vec![]);
+
+        assert!(<Map<String, Json>>::from_input_value(&input).is_err());
+    }
+}
+
+#[cfg(test)]
+mod integration_test {
+    use serde_json::{json, Map, Value as Json};
+
+    use crate::{
+        executor::Variables,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        value::{DefaultScalarValue, Value},
+        InputValue,
+    };
+
+    struct Root;
+
+    #[crate::graphql_object]
+    impl Root {
+        fn metadata() -> Json {
+            json!({"count": 2, "nested": {"ok": true}, "tags": ["a", "b"]})
+        }
+
+        fn echo(value: Json) -> Json {
+            value
+        }
+
+        fn keys(object: Map<String, Json>) -> Vec<String> {
+            object.keys().cloned().collect()
+        }
+    }
+
+    async fn run(doc: &str, vars: Variables<DefaultScalarValue>) -> Value<DefaultScalarValue> {
+        let schema = RootNode::new(
+            Root,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+        result
+    }
+
+    #[tokio::test]
+    async fn resolves_as_structured_value() {
+        let result = run("{ metadata }", Variables::new()).await;
+
+        assert_eq!(
+            result,
+            Value::object(
+                vec![(
+                    "metadata",
+                    Value::object(
+                        vec![
+                            ("count", Value::scalar(2)),
+                            (
+                                "nested",
+                                Value::object(
+                                    vec![("ok", Value::scalar(true))].into_iter().collect()
+                                ),
+                            ),
+                            (
+                                "tags",
+                                Value::list(vec![Value::scalar("a"), Value::scalar("b")]),
+                            ),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    ),
+                )]
+                .into_iter()
+                .collect()
+            ),
+        );
+    }
+
+    #[tokio::test]
+    async fn accepts_literals() {
+        let result = run(
+            r#"{ echo(value: {a: [1, 2.5, "x", null]}), scalar: echo(value: 3) }"#,
+            Variables::new(),
+        )
+        .await;
+
+        assert_eq!(
+            result,
+            Value::object(
+                vec![
+                    (
+                        "echo",
+                        Value::object(
+                            vec![(
+                                "a",
+                                Value::list(vec![
+                                    Value::scalar(1),
+                                    Value::scalar(2.5),
+                                    Value::scalar("x"),
+                                    Value::null(),
+                                ]),
+                            )]
+                            .into_iter()
+                            .collect(),
+                        ),
+                    ),
+                    ("scalar", Value::scalar(3)),
+                ]
+                .into_iter()
+                .collect()
+            ),
+        );
+    }
+
+    #[tokio::test]
+    async fn accepts_variables() {
+        let result = run(
+            "query q($v: JSON!, $o: JSONObject!, $x: String) { echo(value: $v), keys(object: $o), \
+             nested: echo(value: [$x]) }",
+            vec![
+                (
+                    "v".to_owned(),
+                    InputValue::list(vec![InputValue::scalar("a")]),
+                ),
+                (
+                    "o".to_owned(),
+                    InputValue::object(vec![("key", InputValue::null())].into_iter().collect()),
+                ),
+                ("x".to_owned(), InputValue::scalar("y")),
+            ]
+            .into_iter()
+            .collect(),
+        )
+        .await;
+
+        assert_eq!(
+            result,
+            Value::object(
+                vec![
+                    ("echo", Value::list(vec![Value::scalar("a")])),
+                    ("keys", Value::list(vec![Value::scalar("key")])),
+                    ("nested", Value::list(vec![Value::scalar("y")])),
+                ]
+                .into_iter()
+                .collect()
+            ),
+        );
+    }
+}
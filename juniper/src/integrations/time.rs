@@ -0,0 +1,343 @@
+/*!
+
+# Supported types
+
+The scalars use the same names and formats as the [`chrono`](super::chrono)
+integration, so schemas built with either crate are interchangeable.
+
+| Rust Type            | GraphQL Scalar        | JSON Serialization     | Notes                       |
+|----------------------|-----------------------|------------------------|-----------------------------|
+| `OffsetDateTime`     | `DateTimeFixedOffset` | RFC3339 string         |                             |
+| `Date`               | `NaiveDate`           | YYYY-MM-DD             |                             |
+| `Time`               | `NaiveTime`           | H:M:S                  |                             |
+| `PrimitiveDateTime`  | `NaiveDateTime`       | float (unix timestamp) | Interpreted as UTC. Values  |
+|                      |                       |                        | are truncated to seconds.   |
+| `UtcOffset`          | `UtcOffset`           | +HH:MM                 | `Z` is accepted as input.   |
+
+*/
+#![allow(clippy::needless_lifetimes)]
+use time::{
+    format_description::{well_known::Rfc3339, FormatItem},
+    macros::format_description,
+    Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset,
+};
+
+use crate::{
+    parser::{ParseError, ScalarToken, Token},
+    value::{ParseScalarResult, ParseScalarValue},
+    Value,
+};
+
+const DATE_FORMAT: &[FormatItem<'_>] = format_description!("[year]-[month]-[day]");
+const TIME_FORMAT: &[FormatItem<'_>] = format_description!("[hour]:[minute]:[second]");
+const UTC_OFFSET_FORMAT: &[FormatItem<'_>] =
+    format_description!("[offset_hour sign:mandatory]:[offset_minute]");
+
+#[crate::graphql_scalar(name = "DateTimeFixedOffset", description = "DateTime")]
+impl<S> GraphQLScalar for OffsetDateTime
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(
+            self.format(&Rfc3339)
+                .unwrap_or_else(|e| panic!("Failed to format `OffsetDateTime`: {}", e)),
+        )
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<OffsetDateTime, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
+            .and_then(|s| {
+                OffsetDateTime::parse(s, &Rfc3339)
+                    .map_err(|e| format!(r#"Expected RFC 3339 date-time, found "{}": {}"#, s, e))
+            })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
+#[crate::graphql_scalar(name = "NaiveDate", description = "NaiveDate")]
+impl<S> GraphQLScalar for Date
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(
+            self.format(DATE_FORMAT)
+                .unwrap_or_else(|e| panic!("Failed to format `Date`: {}", e)),
+        )
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Date, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
+            .and_then(|s| {
+                Date::parse(s, DATE_FORMAT)
+                    .map_err(|e| format!(r#"Expected YYYY-MM-DD date, found "{}": {}"#, s, e))
+            })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
+#[crate::graphql_scalar(name = "NaiveTime", description = "NaiveTime")]
+impl<S> GraphQLScalar for Time
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(
+            self.format(TIME_FORMAT)
+                .unwrap_or_else(|e| panic!("Failed to format `Time`: {}", e)),
+        )
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Time, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
+            .and_then(|s| {
+                Time::parse(s, TIME_FORMAT)
+                    .map_err(|e| format!(r#"Expected HH:MM:SS time, found "{}": {}"#, s, e))
+            })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
+// JSON numbers (i.e. IEEE doubles) are not precise enough for nanosecond
+// datetimes. Values are truncated to seconds, like `chrono::NaiveDateTime`.
+#[crate::graphql_scalar(name = "NaiveDateTime", description = "NaiveDateTime")]
+impl<S> GraphQLScalar for PrimitiveDateTime
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(self.assume_utc().unix_timestamp() as f64)
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<PrimitiveDateTime, String> {
+        v.as_float_value()
+            .ok_or_else(|| format!(r#"Expected "Float", found {}"#, v))
+            .and_then(|f| {
+                OffsetDateTime::from_unix_timestamp(f as i64)
+                    .map(|dt| PrimitiveDateTime::new(dt.date(), dt.time()))
+                    .map_err(|_| format!("Expected UNIX timestamp, found out-of-range {}", f))
+            })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        <f64 as ParseScalarValue<S>>::from_str(value)
+    }
+}
+
+#[crate::graphql_scalar(name = "UtcOffset", description = "UTC offset, e.g. `+09:00`")]
+impl<S> GraphQLScalar for UtcOffset
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(
+            self.format(UTC_OFFSET_FORMAT)
+                .unwrap_or_else(|e| panic!("Failed to format `UtcOffset`: {}", e)),
+        )
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<UtcOffset, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
+            .and_then(|s| match s {
+                "Z" => Ok(UtcOffset::UTC),
+                s => UtcOffset::parse(s, UTC_OFFSET_FORMAT)
+                    .map_err(|e| format!(r#"Expected +HH:MM UTC offset, found "{}": {}"#, s, e)),
+            })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use time::{
+        macros::{date, datetime, offset, time},
+        Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset,
+    };
+
+    use crate::{value::DefaultScalarValue, FromInputValue, InputValue};
+
+    fn offset_datetime_test(raw: &'static str, expected: OffsetDateTime) {
+        let input: InputValue<DefaultScalarValue> = InputValue::scalar(raw);
+
+        assert_eq!(OffsetDateTime::from_input_value(&input), Ok(expected));
+    }
+
+    #[test]
+    fn offset_datetime_from_input_value() {
+        offset_datetime_test(
+            "2014-11-28T21:00:09+09:00",
+            datetime!(2014-11-28 21:00:09 +09:00),
+        );
+    }
+
+    #[test]
+    fn offset_datetime_from_input_value_with_z_timezone() {
+        offset_datetime_test("2014-11-28T21:00:09Z", datetime!(2014-11-28 21:00:09 UTC));
+    }
+
+    #[test]
+    fn offset_datetime_from_input_value_with_fractional_seconds() {
+        offset_datetime_test(
+            "2014-11-28T21:00:09.05+09:00",
+            datetime!(2014-11-28 21:00:09.05 +09:00),
+        );
+    }
+
+    #[test]
+    fn date_from_input_value() {
+        let input: InputValue<DefaultScalarValue> = InputValue::scalar("1996-12-19");
+
+        assert_eq!(Date::from_input_value(&input), Ok(date!(1996 - 12 - 19)));
+    }
+
+    #[test]
+    fn time_from_input_value() {
+        let input: InputValue<DefaultScalarValue> = InputValue::scalar("21:12:19");
+
+        assert_eq!(Time::from_input_value(&input), Ok(time!(21:12:19)));
+    }
+
+    #[test]
+    fn primitive_datetime_from_input_value() {
+        let input: InputValue<DefaultScalarValue> = InputValue::scalar(1_000_000_000_f64);
+
+        assert_eq!(
+            PrimitiveDateTime::from_input_value(&input),
+            Ok(datetime!(2001-09-09 01:46:40)),
+        );
+    }
+
+    #[test]
+    fn utc_offset_from_input_value() {
+        for (raw, expected) in vec![
+            ("+09:00", offset!(+9)),
+            ("-03:30", offset!(-3:30)),
+            ("Z", UtcOffset::UTC),
+        ] {
+            let input: InputValue<DefaultScalarValue> = InputValue::scalar(raw);
+
+            assert_eq!(UtcOffset::from_input_value(&input), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        let input: InputValue<DefaultScalarValue> = InputValue::scalar("1996-13-19");
+        assert!(Date::from_input_value(&input).is_err());
+
+        let input: InputValue<DefaultScalarValue> = InputValue::scalar("9:00");
+        assert!(UtcOffset::from_input_value(&input).is_err());
+    }
+}
+
+#[cfg(test)]
+mod integration_test {
+    use time::{
+        macros::{date, datetime, offset, time},
+        Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset,
+    };
+
+    use crate::{
+        executor::Variables,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        value::Value,
+    };
+
+    #[tokio::test]
+    async fn test_serialization() {
+        struct Root;
+
+        #[crate::graphql_object]
+        impl Root {
+            fn exampleDate() -> Date {
+                date!(2015 - 03 - 14)
+            }
+            fn examplePrimitiveDateTime() -> PrimitiveDateTime {
+                datetime!(2016-07-08 09:10:11)
+            }
+            fn exampleTime() -> Time {
+                time!(16:07:08)
+            }
+            fn exampleOffsetDateTime() -> OffsetDateTime {
+                datetime!(1996-12-19 16:39:57 -08:00)
+            }
+            fn exampleUtcOffset() -> UtcOffset {
+                offset!(+5:30)
+            }
+        }
+
+        let doc = r#"
+        {
+            exampleDate,
+            examplePrimitiveDateTime,
+            exampleTime,
+            exampleOffsetDateTime,
+            exampleUtcOffset,
+        }
+        "#;
+
+        let schema = RootNode::new(
+            Root,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let (result, errs) = crate::execute(doc, None, &schema, &Variables::new(), &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        assert_eq!(
+            result,
+            Value::object(
+                vec![
+                    ("exampleDate", Value::scalar("2015-03-14")),
+                    ("examplePrimitiveDateTime", Value::scalar(1_467_969_011.0)),
+                    ("exampleTime", Value::scalar("16:07:08")),
+                    (
+                        "exampleOffsetDateTime",
+                        Value::scalar("1996-12-19T16:39:57-08:00"),
+                    ),
+                    ("exampleUtcOffset", Value::scalar("+05:30")),
+                ]
+                .into_iter()
+                .collect()
+            )
+        );
+    }
+}
@@ -17,8 +17,12 @@ where
         Value::scalar(self.to_string())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<Uuid> {
-        v.as_string_value().and_then(|s| Uuid::parse_str(s).ok())
+    fn from_input_value(v: &InputValue) -> Result<Uuid, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
+            .and_then(|s| {
+                Uuid::parse_str(s).map_err(|e| format!(r#"Expected UUID, found "{}": {}"#, s, e))
+            })
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
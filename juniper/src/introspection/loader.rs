@@ -0,0 +1,734 @@
+//! Reconstruction of a `SchemaType` from the result of an introspection query
+
+use std::{borrow::Cow, collections::HashSet, error::Error, fmt, sync::Arc};
+
+use fnv::FnvHashMap;
+
+use crate::{
+    ast::{InputValue, Type},
+    executor::{FieldError, Registry},
+    parser::{parse_value_literal, Lexer, ParseError, Parser, ScalarToken, Token},
+    schema::{
+        meta::{
+            Argument, DeprecationStatus, EnumMeta, EnumValue, Field, InputObjectMeta,
+            InterfaceMeta, MetaType, ObjectMeta, ScalarMeta, UnionMeta,
+        },
+        model::{DirectiveLocation, DirectiveType, SchemaType, TypeType},
+    },
+    types::{name::Name, scalars::ID},
+    value::{Object, ParseScalarResult, ParseScalarValue, ScalarValue, Value},
+};
+
+/// An error that prevented building a schema from an introspection result
+#[derive(Clone, Debug, PartialEq)]
+pub enum IntrospectionError {
+    /// Neither the value nor its `data` field contain a `__schema` object.
+    MissingSchema,
+    /// A field is missing or has an unexpected shape.
+    InvalidField {
+        /// Path to the offending field, e.g. `__schema.types[3].fields`.
+        path: String,
+        /// What was expected at that path.
+        expected: &'static str,
+    },
+    /// A type or field name isn't a valid GraphQL name.
+    InvalidName(String),
+    /// A type has an unknown `kind`.
+    UnknownTypeKind(String),
+    /// A type is referenced but not part of the introspected types.
+    UnknownType(String),
+    /// A default value can't be parsed as a GraphQL literal.
+    InvalidDefaultValue {
+        /// Path to the argument or input field.
+        path: String,
+        /// The unparsable default value.
+        value: String,
+    },
+}
+
+impl fmt::Display for IntrospectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IntrospectionError::MissingSchema => write!(f, "No `__schema` found"),
+            IntrospectionError::InvalidField { path, expected } => {
+                write!(f, "Expected {} at `{}`", expected, path)
+            }
+            IntrospectionError::InvalidName(name) => write!(f, "Invalid name \"{}\"", name),
+            IntrospectionError::UnknownTypeKind(kind) => write!(f, "Unknown type kind {}", kind),
+            IntrospectionError::UnknownType(name) => write!(f, "Unknown type \"{}\"", name),
+            IntrospectionError::InvalidDefaultValue { path, value } => {
+                write!(f, "Invalid default value `{}` at `{}`", value, path)
+            }
+        }
+    }
+}
+
+impl Error for IntrospectionError {}
+
+type LoadResult<T> = Result<T, IntrospectionError>;
+
+/// A default value that can only be parsed once all types are known.
+struct PendingDefault {
+    type_name: String,
+    field_name: Option<String>,
+    arg_name: String,
+    literal: String,
+    path: String,
+}
+
+pub(crate) fn schema_from_introspection<S>(value: &Value<S>) -> LoadResult<SchemaType<'static, S>>
+where
+    S: ScalarValue + 'static,
+{
+    let schema = value
+        .as_object_value()
+        .and_then(|o| {
+            o.get_field_value("__schema").or_else(|| {
+                o.get_field_value("data")
+                    .and_then(Value::as_object_value)
+                    .and_then(|d| d.get_field_value("__schema"))
+            })
+        })
+        .and_then(Value::as_object_value)
+        .ok_or(IntrospectionError::MissingSchema)?;
+
+    let query_type_name = root_type_name(schema, "queryType")?
+        .ok_or_else(|| invalid_field("__schema.queryType", "an object"))?;
+    let mutation_type_name = root_type_name(schema, "mutationType")?;
+    let subscription_type_name = root_type_name(schema, "subscriptionType")?;
+
+    // Introspection types are registered the same way `SchemaType::new` does,
+    // so introspection queries can be validated against the loaded schema.
+    let mut registry = Registry::new(FnvHashMap::default());
+    registry.get_type::<SchemaType<S>>(&());
+
+    let mut pending = vec![];
+    for (i, t) in list(schema, "types", "__schema")?.iter().enumerate() {
+        let path = format!("__schema.types[{}]", i);
+        let meta = load_type(t, &path, &mut pending)?;
+        let name = meta.name().expect("Loaded types are named").to_owned();
+        if name.starts_with("__") {
+            continue;
+        }
+        let name = name
+            .parse::<Name>()
+            .map_err(|_| IntrospectionError::InvalidName(name))?;
+        registry.types.insert(name, meta);
+    }
+
+    let mut meta_fields = vec![
+        registry.field::<SchemaType<S>>("__schema", &()),
+        registry
+            .field::<Option<TypeType<S>>>("__type", &())
+            .argument(registry.arg::<String>("name", &())),
+    ];
+    match registry.types.get_mut(&query_type_name) {
+        Some(MetaType::Object(ObjectMeta { ref mut fields, .. })) => {
+            fields.append(&mut meta_fields)
+        }
+        _ => return Err(IntrospectionError::UnknownType(query_type_name)),
+    }
+
+    let mut directives = FnvHashMap::default();
+    if let Some(list) = optional_list(schema, "directives", "__schema")? {
+        for (i, d) in list.iter().enumerate() {
+            let path = format!("__schema.directives[{}]", i);
+            let directive = load_directive(d, &path, &mut pending)?;
+            directives.insert(directive.name.clone(), directive);
+        }
+    }
+
+    let mut schema = SchemaType {
+        types: registry.types,
+        query_type_name,
+        mutation_type_name,
+        subscription_type_name,
+        directives,
+    };
+
+    check_references(&schema)?;
+    apply_defaults(&mut schema, pending)?;
+
+    Ok(schema)
+}
+
+fn load_type<S>(
+    value: &Value<S>,
+    path: &str,
+    pending: &mut Vec<PendingDefault>,
+) -> LoadResult<MetaType<'static, S>>
+where
+    S: ScalarValue + 'static,
+{
+    let obj = object(value, path)?;
+    let kind = string(obj, "kind", path)?;
+    let name = string(obj, "name", path)?;
+    let description = optional_string(obj, "description", path)?;
+
+    let meta = match kind.as_str() {
+        "SCALAR" => MetaType::Scalar(ScalarMeta {
+            description,
+            visibility: None,
+            ..scalar_meta(name)
+        }),
+        "OBJECT" => {
+            let fields = load_fields(obj, &name, path, pending)?;
+            let interface_names = optional_list(obj, "interfaces", path)?
+                .unwrap_or(&[])
+                .iter()
+                .enumerate()
+                .map(|(i, t)| {
+                    let path = format!("{}.interfaces[{}]", path, i);
+                    string(object(t, &path)?, "name", &path)
+                })
+                .collect::<LoadResult<_>>()?;
+            MetaType::Object(ObjectMeta {
+                name: Cow::Owned(name),
+                description,
+                visibility: None,
+                cache_control: None,
+                fields,
+                interface_names,
+            })
+        }
+        "INTERFACE" => {
+            let fields = load_fields(obj, &name, path, pending)?;
+            MetaType::Interface(InterfaceMeta {
+                name: Cow::Owned(name),
+                description,
+                visibility: None,
+                cache_control: None,
+                fields,
+            })
+        }
+        "UNION" => {
+            let of_type_names = list(obj, "possibleTypes", path)?
+                .iter()
+                .enumerate()
+                .map(|(i, t)| {
+                    let path = format!("{}.possibleTypes[{}]", path, i);
+                    string(object(t, &path)?, "name", &path)
+                })
+                .collect::<LoadResult<_>>()?;
+            MetaType::Union(UnionMeta {
+                name: Cow::Owned(name),
+                description,
+                visibility: None,
+                cache_control: None,
+                of_type_names,
+            })
+        }
+        "ENUM" => {
+            let values = list(obj, "enumValues", path)?
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    let path = format!("{}.enumValues[{}]", path, i);
+                    let v = object(v, &path)?;
+                    Ok(EnumValue {
+                        name: string(v, "name", &path)?,
+                        description: optional_string(v, "description", &path)?,
+                        deprecation_status: deprecation_status(v, &path)?,
+                        visibility: None,
+                    })
+                })
+                .collect::<LoadResult<Vec<_>>>()?;
+            let names = values.iter().map(|v| v.name.clone()).collect();
+            let try_parse_fn = Arc::new(try_parse_enum(name.clone(), names));
+            MetaType::Enum(EnumMeta {
+                name: Cow::Owned(name),
+                description,
+                visibility: None,
+                values,
+                try_parse_fn,
+            })
+        }
+        "INPUT_OBJECT" => {
+            let input_fields = list(obj, "inputFields", path)?
+                .iter()
+                .enumerate()
+                .map(|(i, f)| {
+                    let path = format!("{}.inputFields[{}]", path, i);
+                    load_argument(f, &path, &name, None, pending)
+                })
+                .collect::<LoadResult<_>>()?;
+            MetaType::InputObject(InputObjectMeta {
+                name: Cow::Owned(name),
+                description,
+                visibility: None,
+                input_fields,
+                try_parse_fn: try_parse_input_object::<S>,
+            })
+        }
+        _ => return Err(IntrospectionError::UnknownTypeKind(kind)),
+    };
+
+    Ok(meta)
+}
+
+fn load_fields<S>(
+    obj: &Object<S>,
+    type_name: &str,
+    path: &str,
+    pending: &mut Vec<PendingDefault>,
+) -> LoadResult<Vec<Field<'static, S>>>
+where
+    S: ScalarValue,
+{
+    let mut fields = list(obj, "fields", path)?
+        .iter()
+        .enumerate()
+        .map(|(i, f)| {
+            let path = format!("{}.fields[{}]", path, i);
+            let f = object(f, &path)?;
+            let name = string(f, "name", &path)?;
+            let arguments = list(f, "args", &path)?
+                .iter()
+                .enumerate()
+                .map(|(i, a)| {
+                    let path = format!("{}.args[{}]", path, i);
+                    load_argument(a, &path, type_name, Some(&name), pending)
+                })
+                .collect::<LoadResult<Vec<_>>>()?;
+            Ok(Field {
+                description: optional_string(f, "description", &path)?,
+                arguments: if arguments.is_empty() {
+                    None
+                } else {
+                    Some(arguments)
+                },
+                field_type: type_ref(required(f, "type", &path)?, &format!("{}.type", path))?,
+                deprecation_status: deprecation_status(f, &path)?,
+                visibility: None,
+                cache_control: None,
+                name,
+            })
+        })
+        .collect::<LoadResult<Vec<_>>>()?;
+
+    // Mirrors `Registry::build_object_type`, which isn't exposed through
+    // introspection.
+    fields.push(Field {
+        name: "__typename".to_owned(),
+        description: None,
+        arguments: None,
+        field_type: Type::NonNullNamed(Cow::Borrowed("String")),
+        deprecation_status: DeprecationStatus::Current,
+        visibility: None,
+        cache_control: None,
+    });
+
+    Ok(fields)
+}
+
+fn load_argument<S>(
+    value: &Value<S>,
+    path: &str,
+    type_name: &str,
+    field_name: Option<&str>,
+    pending: &mut Vec<PendingDefault>,
+) -> LoadResult<Argument<'static, S>>
+where
+    S: ScalarValue,
+{
+    let obj = object(value, path)?;
+    let name = string(obj, "name", path)?;
+
+    if let Some(literal) = optional_string(obj, "defaultValue", path)? {
+        pending.push(PendingDefault {
+            type_name: type_name.to_owned(),
+            field_name: field_name.map(ToOwned::to_owned),
+            arg_name: name.clone(),
+            literal,
+            path: path.to_owned(),
+        });
+    }
+
+    Ok(Argument {
+        description: optional_string(obj, "description", path)?,
+        arg_type: type_ref(required(obj, "type", path)?, &format!("{}.type", path))?,
+        default_value: None,
+        name,
+    })
+}
+
+fn load_directive<S>(
+    value: &Value<S>,
+    path: &str,
+    pending: &mut Vec<PendingDefault>,
+) -> LoadResult<DirectiveType<'static, S>>
+where
+    S: ScalarValue,
+{
+    let obj = object(value, path)?;
+    let name = string(obj, "name", path)?;
+
+    // Only executable locations are modelled, the others can't affect queries.
+    let locations = list(obj, "locations", path)?
+        .iter()
+        .filter_map(|l| match l.as_scalar()?.as_str()? {
+            "QUERY" => Some(DirectiveLocation::Query),
+            "MUTATION" => Some(DirectiveLocation::Mutation),
+            "SUBSCRIPTION" => Some(DirectiveLocation::Subscription),
+            "FIELD" => Some(DirectiveLocation::Field),
+            "FRAGMENT_DEFINITION" => Some(DirectiveLocation::FragmentDefinition),
+            "FRAGMENT_SPREAD" => Some(DirectiveLocation::FragmentSpread),
+            "INLINE_FRAGMENT" => Some(DirectiveLocation::InlineFragment),
+            _ => None,
+        })
+        .collect();
+
+    let directive_path = format!("@{}", name);
+    let arguments = list(obj, "args", path)?
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            let path = format!("{}.args[{}]", path, i);
+            load_argument(a, &path, &directive_path, None, pending)
+        })
+        .collect::<LoadResult<_>>()?;
+
+    Ok(DirectiveType {
+        description: optional_string(obj, "description", path)?,
+        name,
+        locations,
+        arguments,
+    })
+}
+
+fn type_ref<S>(value: &Value<S>, path: &str) -> LoadResult<Type<'static>>
+where
+    S: ScalarValue,
+{
+    let obj = object(value, path)?;
+    match string(obj, "kind", path)?.as_str() {
+        "NON_NULL" => {
+            let path = format!("{}.ofType", path);
+            match type_ref(required(obj, "ofType", &path)?, &path)? {
+                Type::Named(name) => Ok(Type::NonNullNamed(name)),
+                Type::List(inner) => Ok(Type::NonNullList(inner)),
+                _ => Err(invalid_field(&path, "a nullable type")),
+            }
+        }
+        "LIST" => {
+            let path = format!("{}.ofType", path);
+            Ok(Type::List(Box::new(type_ref(
+                required(obj, "ofType", &path)?,
+                &path,
+            )?)))
+        }
+        _ => Ok(Type::Named(Cow::Owned(string(obj, "name", path)?))),
+    }
+}
+
+/// Ensures that every type referenced by a field or argument was introspected.
+fn check_references<S>(schema: &SchemaType<S>) -> LoadResult<()> {
+    let exists = |t: &Type| {
+        let name = t.innermost_name();
+        if schema.types.contains_key(name) {
+            Ok(())
+        } else {
+            Err(IntrospectionError::UnknownType(name.to_owned()))
+        }
+    };
+
+    for meta in schema.types.values() {
+        match meta {
+            MetaType::Object(ObjectMeta { fields, .. })
+            | MetaType::Interface(InterfaceMeta { fields, .. }) => {
+                for f in fields {
+                    exists(&f.field_type)?;
+                    for a in f.arguments.iter().flatten() {
+                        exists(&a.arg_type)?;
+                    }
+                }
+            }
+            MetaType::InputObject(InputObjectMeta { input_fields, .. }) => {
+                for f in input_fields {
+                    exists(&f.arg_type)?;
+                }
+            }
+            MetaType::Union(UnionMeta { of_type_names, .. }) => {
+                for name in of_type_names {
+                    if !schema.types.contains_key(name.as_str()) {
+                        return Err(IntrospectionError::UnknownType(name.clone()));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    for d in schema.directives.values() {
+        for a in &d.arguments {
+            exists(&a.arg_type)?;
+        }
+    }
+    Ok(())
+}
+
+/// Parses the default values of arguments and input fields now that the
+/// types they refer to are known.
+fn apply_defaults<S>(schema: &mut SchemaType<S>, pending: Vec<PendingDefault>) -> LoadResult<()>
+where
+    S: ScalarValue,
+{
+    for default in pending {
+        let value = {
+            let arg = find_argument(schema, &default).expect("Argument was loaded");
+            let meta = schema.concrete_type_by_name(arg.arg_type.innermost_name());
+            parse_default_value(&default.literal, schema, meta).ok_or_else(|| {
+                IntrospectionError::InvalidDefaultValue {
+                    path: default.path.clone(),
+                    value: default.literal.clone(),
+                }
+            })?
+        };
+        find_argument_mut(schema, &default)
+            .expect("Argument was loaded")
+            .default_value = Some(value);
+    }
+    Ok(())
+}
+
+fn parse_default_value<S>(
+    literal: &str,
+    schema: &SchemaType<S>,
+    meta: Option<&MetaType<S>>,
+) -> Option<InputValue<S>>
+where
+    S: ScalarValue,
+{
+    let mut lexer = Lexer::new(literal);
+    let mut parser = Parser::new(&mut lexer).ok()?;
+    let value = parse_value_literal(&mut parser, true, schema, meta).ok()?;
+    match parser.peek().item {
+        Token::EndOfFile => Some(value.item),
+        _ => None,
+    }
+}
+
+fn find_argument<'s, 'a, S>(
+    schema: &'s SchemaType<'a, S>,
+    default: &PendingDefault,
+) -> Option<&'s Argument<'a, S>> {
+    let args = if let Some(name) = default.type_name.strip_prefix('@') {
+        &schema.directives.get(name)?.arguments
+    } else {
+        match (
+            schema.types.get(default.type_name.as_str())?,
+            &default.field_name,
+        ) {
+            (MetaType::InputObject(InputObjectMeta { input_fields, .. }), None) => input_fields,
+            (MetaType::Object(ObjectMeta { fields, .. }), Some(field))
+            | (MetaType::Interface(InterfaceMeta { fields, .. }), Some(field)) => fields
+                .iter()
+                .find(|f| &f.name == field)?
+                .arguments
+                .as_ref()?,
+            _ => return None,
+        }
+    };
+    args.iter().find(|a| a.name == default.arg_name)
+}
+
+fn find_argument_mut<'s, 'a, S>(
+    schema: &'s mut SchemaType<'a, S>,
+    default: &PendingDefault,
+) -> Option<&'s mut Argument<'a, S>> {
+    let args = if let Some(name) = default.type_name.strip_prefix('@') {
+        &mut schema.directives.get_mut(name)?.arguments
+    } else {
+        match (
+            schema.types.get_mut(default.type_name.as_str())?,
+            &default.field_name,
+        ) {
+            (MetaType::InputObject(InputObjectMeta { input_fields, .. }), None) => input_fields,
+            (MetaType::Object(ObjectMeta { fields, .. }), Some(field))
+            | (MetaType::Interface(InterfaceMeta { fields, .. }), Some(field)) => fields
+                .iter_mut()
+                .find(|f| &f.name == field)?
+                .arguments
+                .as_mut()?,
+            _ => return None,
+        }
+    };
+    args.iter_mut().find(|a| a.name == default.arg_name)
+}
+
+/// Builds the metadata of an introspected scalar.
+///
+/// Built-in scalars keep their usual parsing rules, while custom scalars
+/// accept any scalar value since their actual format is unknown.
+fn scalar_meta<S>(name: String) -> ScalarMeta<'static, S>
+where
+    S: ScalarValue + 'static,
+{
+    let name: Cow<'static, str> = Cow::Owned(name);
+    match name.as_ref() {
+        "Int" => ScalarMeta::new::<i32>(name),
+        "Float" => ScalarMeta::new::<f64>(name),
+        "String" => ScalarMeta::new::<String>(name),
+        "Boolean" => ScalarMeta::new::<bool>(name),
+        "ID" => ScalarMeta::new::<ID>(name),
+        _ => ScalarMeta {
+            name,
+            description: None,
+            visibility: None,
+            try_parse_fn: try_parse_custom_scalar::<S>,
+            parse_fn: parse_custom_scalar::<S>,
+        },
+    }
+}
+
+fn try_parse_custom_scalar<S>(value: &InputValue<S>) -> Result<(), FieldError<S>>
+where
+    S: ScalarValue,
+{
+    match value {
+        InputValue::Scalar(_) => Ok(()),
+        _ => Err(format!("Expected a scalar, found {}", value).into()),
+    }
+}
+
+fn parse_custom_scalar<S>(value: ScalarToken<'_>) -> ParseScalarResult<'_, S>
+where
+    S: ScalarValue,
+{
+    match value {
+        ScalarToken::String(_) => <String as ParseScalarValue<S>>::from_str(value),
+        ScalarToken::Int(_) => <i32 as ParseScalarValue<S>>::from_str(value)
+            .or_else(|_| <f64 as ParseScalarValue<S>>::from_str(value)),
+        ScalarToken::Float(_) => <f64 as ParseScalarValue<S>>::from_str(value),
+    }
+    .map_err(|_| ParseError::UnexpectedToken(Token::Scalar(value)))
+}
+
+/// Accepts the enum literals and strings naming one of the given values
+fn try_parse_enum<S>(
+    name: String,
+    values: HashSet<String>,
+) -> impl Fn(&InputValue<S>) -> Result<(), FieldError<S>> + Send + Sync
+where
+    S: ScalarValue,
+{
+    move |value| match value.as_enum_value().or_else(|| value.as_string_value()) {
+        Some(v) if values.contains(v) => Ok(()),
+        _ => Err(format!(r#"Expected "{}", found {}"#, name, value).into()),
+    }
+}
+
+/// Input object fields are checked one by one during validation, so any
+/// object is accepted here.
+fn try_parse_input_object<S>(value: &InputValue<S>) -> Result<(), FieldError<S>>
+where
+    S: ScalarValue,
+{
+    match value {
+        InputValue::Object(_) => Ok(()),
+        _ => Err(format!("Expected an object, found {}", value).into()),
+    }
+}
+
+fn root_type_name<S>(schema: &Object<S>, field: &str) -> LoadResult<Option<String>>
+where
+    S: ScalarValue,
+{
+    match schema.get_field_value(field) {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => {
+            let path = format!("__schema.{}", field);
+            string(object(v, &path)?, "name", &path).map(Some)
+        }
+    }
+}
+
+fn deprecation_status<S>(obj: &Object<S>, path: &str) -> LoadResult<DeprecationStatus>
+where
+    S: ScalarValue,
+{
+    let is_deprecated = match obj.get_field_value("isDeprecated") {
+        None | Some(Value::Null) => false,
+        Some(v) => v
+            .as_scalar()
+            .and_then(ScalarValue::as_boolean)
+            .ok_or_else(|| invalid_field(&format!("{}.isDeprecated", path), "a boolean"))?,
+    };
+    Ok(if is_deprecated {
+        DeprecationStatus::Deprecated(optional_string(obj, "deprecationReason", path)?)
+    } else {
+        DeprecationStatus::Current
+    })
+}
+
+fn required<'v, S>(obj: &'v Object<S>, field: &str, path: &str) -> LoadResult<&'v Value<S>>
+where
+    S: ScalarValue,
+{
+    match obj.get_field_value(field) {
+        None | Some(Value::Null) => Err(invalid_field(&format!("{}.{}", path, field), "a value")),
+        Some(v) => Ok(v),
+    }
+}
+
+fn object<'v, S>(value: &'v Value<S>, path: &str) -> LoadResult<&'v Object<S>>
+where
+    S: ScalarValue,
+{
+    value
+        .as_object_value()
+        .ok_or_else(|| invalid_field(path, "an object"))
+}
+
+fn string<S>(obj: &Object<S>, field: &str, path: &str) -> LoadResult<String>
+where
+    S: ScalarValue,
+{
+    optional_string(obj, field, path)?
+        .ok_or_else(|| invalid_field(&format!("{}.{}", path, field), "a string"))
+}
+
+fn optional_string<S>(obj: &Object<S>, field: &str, path: &str) -> LoadResult<Option<String>>
+where
+    S: ScalarValue,
+{
+    match obj.get_field_value(field) {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => v
+            .as_scalar()
+            .and_then(ScalarValue::as_str)
+            .map(|s| Some(s.to_owned()))
+            .ok_or_else(|| invalid_field(&format!("{}.{}", path, field), "a string")),
+    }
+}
+
+fn list<'v, S>(obj: &'v Object<S>, field: &str, path: &str) -> LoadResult<&'v [Value<S>]>
+where
+    S: ScalarValue,
+{
+    optional_list(obj, field, path)?
+        .ok_or_else(|| invalid_field(&format!("{}.{}", path, field), "a list"))
+}
+
+fn optional_list<'v, S>(
+    obj: &'v Object<S>,
+    field: &str,
+    path: &str,
+) -> LoadResult<Option<&'v [Value<S>]>>
+where
+    S: ScalarValue,
+{
+    match obj.get_field_value(field) {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => v
+            .as_list_value()
+            .map(|l| Some(l.as_slice()))
+            .ok_or_else(|| invalid_field(&format!("{}.{}", path, field), "a list")),
+    }
+}
+
+fn invalid_field(path: &str, expected: &'static str) -> IntrospectionError {
+    IntrospectionError::InvalidField {
+        path: path.to_owned(),
+        expected,
+    }
+}
@@ -146,29 +146,34 @@ use crate::{
     executor::{execute_validated_query, get_operation},
     introspection::{INTROSPECTION_QUERY, INTROSPECTION_QUERY_WITHOUT_DESCRIPTIONS},
     parser::{parse_document_source, ParseError, Spanning},
-    validation::{validate_input_values, visit_all_rules, ValidatorContext},
+    validation::{
+        validate_input_values, visit_all_rules, visit_disable_introspection, ValidatorContext,
+    },
 };
 
 pub use crate::{
     ast::{FromInputValue, InputValue, Selection, ToInputValue, Type},
     executor::{
-        Applies, Context, ExecutionError, ExecutionResult, Executor, FieldError, FieldResult,
-        FromContext, IntoFieldError, IntoResolvable, LookAheadArgument, LookAheadMethods,
-        LookAheadSelection, LookAheadValue, OwnedExecutor, Registry, ValuesStream, Variables,
+        Applies, CacheHint, CachePolicy, CacheScope, Context, ExecutionError, ExecutionResult,
+        Executor, FieldError, FieldResult, FromContext, IntoFieldError, IntoResolvable,
+        LookAheadArgument, LookAheadMethods, LookAheadSelection, LookAheadValue, OwnedExecutor,
+        Registry, ValuesStream, Variables,
     },
-    introspection::IntrospectionFormat,
+    introspection::{IntrospectionError, IntrospectionFormat},
     macros::helper::{
         subscription::{ExtractTypeFromStream, IntoFieldResult},
         AsDynGraphQLValue,
     },
     schema::{
-        meta,
+        diff, meta,
         model::{RootNode, SchemaType},
+        validation::SchemaError,
     },
     types::{
         async_await::{DynGraphQLValueAsync, GraphQLTypeAsync, GraphQLValueAsync},
         base::{Arguments, DynGraphQLValue, GraphQLType, GraphQLValue, TypeKind},
         marker::{self, GraphQLInterface, GraphQLUnion},
+        nullable::Nullable,
         scalars::{EmptyMutation, EmptySubscription, ID},
         subscriptions::{
             ExecutionOutput, GraphQLSubscriptionType, GraphQLSubscriptionValue,
@@ -176,7 +181,10 @@ pub use crate::{
         },
     },
     validation::RuleError,
-    value::{DefaultScalarValue, Object, ParseScalarResult, ParseScalarValue, ScalarValue, Value},
+    value::{
+        DefaultScalarValue, LongScalarValue, Object, ParseScalarResult, ParseScalarValue,
+        ScalarValue, Value,
+    },
 };
 
 /// An error that prevented query execution
@@ -221,6 +229,31 @@ pub fn execute_sync<'a, S, QueryT, MutationT, SubscriptionT>(
     variables: &Variables<S>,
     context: &QueryT::Context,
 ) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+where
+    S: ScalarValue,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+{
+    execute_sync_with_cache_policy(
+        document_source,
+        operation_name,
+        root_node,
+        variables,
+        context,
+    )
+    .map(|(value, errors, _)| (value, errors))
+}
+
+/// Execute a query synchronously in a provided schema, computing the cache
+/// policy of the response along the way
+pub(crate) fn execute_sync_with_cache_policy<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>, CachePolicy), GraphQLError<'a>>
 where
     S: ScalarValue,
     QueryT: GraphQLType<S>,
@@ -228,10 +261,14 @@ where
     SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
 {
     let document = parse_document_source(document_source, &root_node.schema)?;
+    let schema = root_node.visible_schema(context);
 
     {
-        let mut ctx = ValidatorContext::new(&root_node.schema, &document);
+        let mut ctx = ValidatorContext::new(&schema, &document);
         visit_all_rules(&mut ctx, &document);
+        if !root_node.is_introspection_allowed(context) {
+            visit_disable_introspection(&mut ctx, &document);
+        }
 
         let errors = ctx.into_errors();
         if !errors.is_empty() {
@@ -242,7 +279,7 @@ where
     let operation = get_operation(&document, operation_name)?;
 
     {
-        let errors = validate_input_values(variables, operation, &root_node.schema);
+        let errors = validate_input_values(variables, operation, &schema);
 
         if !errors.is_empty() {
             return Err(GraphQLError::ValidationError(errors));
@@ -260,6 +297,36 @@ pub async fn execute<'a, S, QueryT, MutationT, SubscriptionT>(
     variables: &Variables<S>,
     context: &QueryT::Context,
 ) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    execute_with_cache_policy(
+        document_source,
+        operation_name,
+        root_node,
+        variables,
+        context,
+    )
+    .await
+    .map(|(value, errors, _)| (value, errors))
+}
+
+/// Execute a query in a provided schema, computing the cache policy of the
+/// response along the way
+pub(crate) async fn execute_with_cache_policy<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>, CachePolicy), GraphQLError<'a>>
 where
     QueryT: GraphQLTypeAsync<S>,
     QueryT::TypeInfo: Sync,
@@ -271,10 +338,14 @@ where
     S: ScalarValue + Send + Sync,
 {
     let document = parse_document_source(document_source, &root_node.schema)?;
+    let schema = root_node.visible_schema(context);
 
     {
-        let mut ctx = ValidatorContext::new(&root_node.schema, &document);
+        let mut ctx = ValidatorContext::new(&schema, &document);
         visit_all_rules(&mut ctx, &document);
+        if !root_node.is_introspection_allowed(context) {
+            visit_disable_introspection(&mut ctx, &document);
+        }
 
         let errors = ctx.into_errors();
         if !errors.is_empty() {
@@ -285,7 +356,7 @@ where
     let operation = get_operation(&document, operation_name)?;
 
     {
-        let errors = validate_input_values(variables, operation, &root_node.schema);
+        let errors = validate_input_values(variables, operation, &schema);
 
         if !errors.is_empty() {
             return Err(GraphQLError::ValidationError(errors));
@@ -316,10 +387,14 @@ where
 {
     let document: crate::ast::Document<'a, S> =
         parse_document_source(document_source, &root_node.schema)?;
+    let schema = root_node.visible_schema(context);
 
     {
-        let mut ctx = ValidatorContext::new(&root_node.schema, &document);
+        let mut ctx = ValidatorContext::new(&schema, &document);
         visit_all_rules(&mut ctx, &document);
+        if !root_node.is_introspection_allowed(context) {
+            visit_disable_introspection(&mut ctx, &document);
+        }
 
         let errors = ctx.into_errors();
         if !errors.is_empty() {
@@ -330,7 +405,7 @@ where
     let operation = get_operation(&document, operation_name)?;
 
     {
-        let errors = validate_input_values(&variables, operation, &root_node.schema);
+        let errors = validate_input_values(&variables, operation, &schema);
 
         if !errors.is_empty() {
             return Err(GraphQLError::ValidationError(errors));
@@ -342,6 +417,9 @@ where
 }
 
 /// Execute the reference introspection query in the provided schema
+///
+/// Like any other query, this fails validation if introspection isn't allowed
+/// for the given context.
 pub fn introspect<'a, S, QueryT, MutationT, SubscriptionT>(
     root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
     context: &QueryT::Context,
@@ -88,6 +88,13 @@ impl<T> Spanning<T> {
             end: self.end,
         }
     }
+
+    /// Modify the contents of the spanned item in case `f` returns `Some`,
+    /// or return `None` otherwise
+    pub fn and_then<O: fmt::Debug, F: Fn(T) -> Option<O>>(self, f: F) -> Option<Spanning<O>> {
+        let (start, end) = (self.start, self.end);
+        f(self.item).map(|item| Spanning { item, start, end })
+    }
 }
 
 impl<T: fmt::Display> fmt::Display for Spanning<T> {
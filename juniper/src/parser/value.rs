@@ -2,10 +2,7 @@ use crate::ast::InputValue;
 
 use crate::{
     parser::{ParseError, ParseResult, Parser, ScalarToken, SourcePosition, Spanning, Token},
-    schema::{
-        meta::{InputObjectMeta, MetaType},
-        model::SchemaType,
-    },
+    schema::{meta::MetaType, model::SchemaType},
     value::ScalarValue,
 };
 
@@ -26,20 +23,28 @@ where
             },
             _,
         ) => parse_list_literal(parser, is_const, schema, tpe),
+        // Scalars may accept structured values as well, e.g. a `JSON` scalar.
         (
             &Spanning {
                 item: Token::CurlyOpen,
                 ..
             },
             None,
-        ) => parse_object_literal(parser, is_const, schema, None),
-        (
+        )
+        | (
+            &Spanning {
+                item: Token::CurlyOpen,
+                ..
+            },
+            Some(&MetaType::InputObject(_)),
+        )
+        | (
             &Spanning {
                 item: Token::CurlyOpen,
                 ..
             },
-            Some(&MetaType::InputObject(ref o)),
-        ) => parse_object_literal(parser, is_const, schema, Some(o)),
+            Some(&MetaType::Scalar(_)),
+        ) => parse_object_literal(parser, is_const, schema, tpe),
         (
             &Spanning {
                 item: Token::Dollar,
@@ -141,7 +146,7 @@ fn parse_object_literal<'a, 'b, S>(
     parser: &mut Parser<'a>,
     is_const: bool,
     schema: &'b SchemaType<'b, S>,
-    object_tpe: Option<&InputObjectMeta<'b, S>>,
+    object_tpe: Option<&MetaType<'b, S>>,
 ) -> ParseResult<'a, InputValue<S>>
 where
     S: ScalarValue,
@@ -159,16 +164,23 @@ fn parse_object_field<'a, 'b, S>(
     parser: &mut Parser<'a>,
     is_const: bool,
     schema: &'b SchemaType<'b, S>,
-    object_meta: Option<&InputObjectMeta<'b, S>>,
+    object_tpe: Option<&MetaType<'b, S>>,
 ) -> ParseResult<'a, (Spanning<String>, Spanning<InputValue<S>>)>
 where
     S: ScalarValue,
 {
     let key = parser.expect_name()?;
 
-    let tpe = object_meta
-        .and_then(|o| o.input_fields.iter().find(|f| f.name == key.item))
-        .and_then(|f| schema.lookup_type(&f.arg_type));
+    let tpe = match object_tpe {
+        Some(&MetaType::InputObject(ref o)) => o
+            .input_fields
+            .iter()
+            .find(|f| f.name == key.item)
+            .and_then(|f| schema.lookup_type(&f.arg_type)),
+        // Fields of a structured scalar are parsed as the scalar itself.
+        Some(t @ &MetaType::Scalar(_)) => Some(t),
+        _ => None,
+    };
 
     parser.expect(&Token::Colon)?;
 
@@ -0,0 +1,972 @@
+//! Detection of changes between two versions of a schema
+//!
+//! [`diff`](fn.diff.html) compares an old and a new [`SchemaType`](../struct.SchemaType.html)
+//! and classifies every difference by how it affects existing clients, so that
+//! breaking changes can be caught before they're deployed.
+//!
+//! ```rust
+//! # use juniper::{graphql_object, diff, EmptyMutation, EmptySubscription, RootNode};
+//! struct OldQuery;
+//!
+//! #[graphql_object(name = "Query")]
+//! impl OldQuery {
+//!     fn nickname() -> Option<String> { None }
+//! }
+//!
+//! struct NewQuery;
+//!
+//! #[graphql_object(name = "Query")]
+//! impl NewQuery {
+//!     fn name() -> String { "Luke".into() }
+//! }
+//!
+//! let old = RootNode::new(OldQuery, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+//! let new = RootNode::new(NewQuery, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+//!
+//! let changes = diff::diff(&old.schema, &new.schema);
+//! assert!(changes.has_breaking_changes());
+//! assert_eq!(
+//!     changes.breaking_changes().next().unwrap().message,
+//!     "Field `Query.nickname` was removed",
+//! );
+//! ```
+
+use std::{collections::BTreeSet, fmt};
+
+use serde::Serialize;
+
+use crate::{
+    ast::Type,
+    schema::{
+        meta::{Argument, EnumMeta, Field, InputObjectMeta, MetaType, ObjectMeta, UnionMeta},
+        model::{DirectiveType, SchemaType},
+    },
+    value::ScalarValue,
+};
+
+/// How a change affects existing clients of a schema
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Criticality {
+    /// Queries that were valid against the old schema may fail against the new one.
+    Breaking,
+    /// Existing queries stay valid, but clients may observe values they don't expect.
+    Dangerous,
+    /// Existing clients are not affected.
+    Safe,
+}
+
+/// The kind of a single schema change
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[allow(missing_docs)]
+pub enum ChangeKind {
+    TypeAdded,
+    TypeRemoved,
+    TypeKindChanged,
+    TypeDescriptionChanged,
+    RootTypeChanged,
+    FieldAdded,
+    FieldRemoved,
+    FieldTypeChanged,
+    FieldDescriptionChanged,
+    FieldDeprecationChanged,
+    ArgumentAdded,
+    ArgumentRemoved,
+    ArgumentTypeChanged,
+    ArgumentDefaultValueChanged,
+    InputFieldAdded,
+    InputFieldRemoved,
+    InputFieldTypeChanged,
+    InputFieldDefaultValueChanged,
+    EnumValueAdded,
+    EnumValueRemoved,
+    EnumValueDeprecationChanged,
+    UnionMemberAdded,
+    UnionMemberRemoved,
+    InterfaceImplementationAdded,
+    InterfaceImplementationRemoved,
+    DirectiveAdded,
+    DirectiveRemoved,
+    DirectiveLocationAdded,
+    DirectiveLocationRemoved,
+}
+
+/// A single difference between two schemas
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SchemaChange {
+    /// The kind of the change.
+    pub kind: ChangeKind,
+    /// How the change affects existing clients.
+    pub criticality: Criticality,
+    /// Dotted path to the changed schema element, e.g. `User.friends.first`.
+    ///
+    /// Directives are prefixed with `@`.
+    pub path: String,
+    /// Human readable description of the change.
+    pub message: String,
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let criticality = match self.criticality {
+            Criticality::Breaking => "BREAKING",
+            Criticality::Dangerous => "DANGEROUS",
+            Criticality::Safe => "SAFE",
+        };
+        write!(f, "[{}] {}", criticality, self.message)
+    }
+}
+
+/// All the differences between two schemas
+///
+/// This struct implements `Serialize`, so a report can be stored or inspected
+/// by tooling, e.g. to fail a build on breaking changes.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct SchemaDiff {
+    /// The changes, in the order they were detected.
+    pub changes: Vec<SchemaChange>,
+}
+
+impl SchemaDiff {
+    /// Returns `true` if the two schemas are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns `true` if any change may break existing clients.
+    pub fn has_breaking_changes(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|c| c.criticality == Criticality::Breaking)
+    }
+
+    /// Iterates over the changes that may break existing clients.
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &SchemaChange> {
+        self.with_criticality(Criticality::Breaking)
+    }
+
+    /// Iterates over the changes that are dangerous for existing clients.
+    pub fn dangerous_changes(&self) -> impl Iterator<Item = &SchemaChange> {
+        self.with_criticality(Criticality::Dangerous)
+    }
+
+    /// Iterates over the changes that don't affect existing clients.
+    pub fn safe_changes(&self) -> impl Iterator<Item = &SchemaChange> {
+        self.with_criticality(Criticality::Safe)
+    }
+
+    fn with_criticality(&self, criticality: Criticality) -> impl Iterator<Item = &SchemaChange> {
+        self.changes
+            .iter()
+            .filter(move |c| c.criticality == criticality)
+    }
+
+    fn push(&mut self, kind: ChangeKind, criticality: Criticality, path: String, message: String) {
+        self.changes.push(SchemaChange {
+            kind,
+            criticality,
+            path,
+            message,
+        });
+    }
+}
+
+impl fmt::Display for SchemaDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares two schemas and classifies the changes from `old` to `new`
+///
+/// Both schemas may use different scalar value types, which makes it possible
+/// to compare a schema against a snapshot loaded with
+/// [`SchemaType::from_introspection`](../struct.SchemaType.html#method.from_introspection).
+/// Types are matched by name; built-in introspection types are ignored.
+pub fn diff<'a, 'b, S1, S2>(old: &SchemaType<'a, S1>, new: &SchemaType<'b, S2>) -> SchemaDiff
+where
+    S1: ScalarValue,
+    S2: ScalarValue,
+{
+    let mut diff = SchemaDiff::default();
+
+    diff_root_type(
+        &mut diff,
+        "query",
+        Some(old.query_type_name.as_str()),
+        Some(new.query_type_name.as_str()),
+    );
+    diff_root_type(
+        &mut diff,
+        "mutation",
+        old.mutation_type_name.as_deref(),
+        new.mutation_type_name.as_deref(),
+    );
+    diff_root_type(
+        &mut diff,
+        "subscription",
+        old.subscription_type_name.as_deref(),
+        new.subscription_type_name.as_deref(),
+    );
+
+    let names = sorted_names(
+        old.types.values().filter_map(MetaType::name),
+        new.types.values().filter_map(MetaType::name),
+    );
+    for name in names {
+        // Introspection types and the empty root markers aren't user-visible.
+        if name.starts_with("__") || name == "_EmptyMutation" || name == "_EmptySubscription" {
+            continue;
+        }
+        match (
+            old.concrete_type_by_name(name),
+            new.concrete_type_by_name(name),
+        ) {
+            (Some(old_type), Some(new_type)) => diff_type(&mut diff, name, old_type, new_type),
+            (Some(old_type), None) => diff.push(
+                ChangeKind::TypeRemoved,
+                Criticality::Breaking,
+                name.to_owned(),
+                format!("{} `{}` was removed", kind_name(old_type), name),
+            ),
+            (None, Some(new_type)) => diff.push(
+                ChangeKind::TypeAdded,
+                Criticality::Safe,
+                name.to_owned(),
+                format!("{} `{}` was added", kind_name(new_type), name),
+            ),
+            (None, None) => unreachable!(),
+        }
+    }
+
+    let directive_names = sorted_names(
+        old.directive_list().into_iter().map(|d| d.name.as_str()),
+        new.directive_list().into_iter().map(|d| d.name.as_str()),
+    );
+    for name in directive_names {
+        match (old.directive_by_name(name), new.directive_by_name(name)) {
+            (Some(old_directive), Some(new_directive)) => {
+                diff_directive(&mut diff, old_directive, new_directive)
+            }
+            (Some(_), None) => diff.push(
+                ChangeKind::DirectiveRemoved,
+                Criticality::Breaking,
+                format!("@{}", name),
+                format!("Directive `@{}` was removed", name),
+            ),
+            (None, Some(_)) => diff.push(
+                ChangeKind::DirectiveAdded,
+                Criticality::Safe,
+                format!("@{}", name),
+                format!("Directive `@{}` was added", name),
+            ),
+            (None, None) => unreachable!(),
+        }
+    }
+
+    diff
+}
+
+fn diff_root_type(diff: &mut SchemaDiff, operation: &str, old: Option<&str>, new: Option<&str>) {
+    if old == new {
+        return;
+    }
+    let (criticality, message) = match (old, new) {
+        (Some(old), Some(new)) => (
+            Criticality::Breaking,
+            format!(
+                "Root {} type changed from `{}` to `{}`",
+                operation, old, new,
+            ),
+        ),
+        (Some(old), None) => (
+            Criticality::Breaking,
+            format!("Root {} type `{}` was removed", operation, old),
+        ),
+        (None, Some(new)) => (
+            Criticality::Safe,
+            format!("Root {} type `{}` was added", operation, new),
+        ),
+        (None, None) => unreachable!(),
+    };
+    diff.push(
+        ChangeKind::RootTypeChanged,
+        criticality,
+        operation.to_owned(),
+        message,
+    );
+}
+
+fn diff_type<S1, S2>(diff: &mut SchemaDiff, name: &str, old: &MetaType<S1>, new: &MetaType<S2>)
+where
+    S1: ScalarValue,
+    S2: ScalarValue,
+{
+    if old.description() != new.description() {
+        diff.push(
+            ChangeKind::TypeDescriptionChanged,
+            Criticality::Safe,
+            name.to_owned(),
+            format!("Description of `{}` changed", name),
+        );
+    }
+
+    match (old, new) {
+        (MetaType::Object(old), MetaType::Object(new)) => {
+            diff_interfaces(diff, old, new);
+            diff_fields(diff, name, &old.fields, &new.fields);
+        }
+        (MetaType::Interface(old), MetaType::Interface(new)) => {
+            diff_fields(diff, name, &old.fields, &new.fields);
+        }
+        (MetaType::Union(old), MetaType::Union(new)) => diff_union(diff, old, new),
+        (MetaType::Enum(old), MetaType::Enum(new)) => diff_enum(diff, old, new),
+        (MetaType::InputObject(old), MetaType::InputObject(new)) => {
+            diff_input_object(diff, old, new)
+        }
+        (MetaType::Scalar(_), MetaType::Scalar(_)) => {}
+        _ => diff.push(
+            ChangeKind::TypeKindChanged,
+            Criticality::Breaking,
+            name.to_owned(),
+            format!(
+                "`{}` changed from {} to {}",
+                name,
+                kind_name(old),
+                kind_name(new),
+            ),
+        ),
+    }
+}
+
+fn diff_interfaces<S1, S2>(diff: &mut SchemaDiff, old: &ObjectMeta<S1>, new: &ObjectMeta<S2>) {
+    for name in sorted_names(
+        old.interface_names.iter().map(String::as_str),
+        new.interface_names.iter().map(String::as_str),
+    ) {
+        let in_old = old.interface_names.iter().any(|n| n == name);
+        let in_new = new.interface_names.iter().any(|n| n == name);
+        if in_old && !in_new {
+            diff.push(
+                ChangeKind::InterfaceImplementationRemoved,
+                Criticality::Breaking,
+                old.name.to_string(),
+                format!("`{}` no longer implements interface `{}`", old.name, name),
+            );
+        } else if !in_old && in_new {
+            diff.push(
+                ChangeKind::InterfaceImplementationAdded,
+                Criticality::Dangerous,
+                new.name.to_string(),
+                format!("`{}` now implements interface `{}`", new.name, name),
+            );
+        }
+    }
+}
+
+fn diff_fields<S1, S2>(diff: &mut SchemaDiff, type_name: &str, old: &[Field<S1>], new: &[Field<S2>])
+where
+    S1: ScalarValue,
+    S2: ScalarValue,
+{
+    let names = sorted_names(
+        old.iter().map(|f| f.name.as_str()),
+        new.iter().map(|f| f.name.as_str()),
+    );
+    for name in names {
+        if name.starts_with("__") {
+            continue;
+        }
+        let path = format!("{}.{}", type_name, name);
+        let (old, new) = match (
+            old.iter().find(|f| f.name == name),
+            new.iter().find(|f| f.name == name),
+        ) {
+            (Some(old), Some(new)) => (old, new),
+            (Some(_), None) => {
+                diff.push(
+                    ChangeKind::FieldRemoved,
+                    Criticality::Breaking,
+                    path.clone(),
+                    format!("Field `{}` was removed", path),
+                );
+                continue;
+            }
+            (None, Some(_)) => {
+                diff.push(
+                    ChangeKind::FieldAdded,
+                    Criticality::Safe,
+                    path.clone(),
+                    format!("Field `{}` was added", path),
+                );
+                continue;
+            }
+            (None, None) => unreachable!(),
+        };
+
+        if old.field_type != new.field_type {
+            let criticality = if is_safe_output_change(&old.field_type, &new.field_type) {
+                Criticality::Safe
+            } else {
+                Criticality::Breaking
+            };
+            diff.push(
+                ChangeKind::FieldTypeChanged,
+                criticality,
+                path.clone(),
+                format!(
+                    "Field `{}` changed type from `{}` to `{}`",
+                    path, old.field_type, new.field_type,
+                ),
+            );
+        }
+        if old.description != new.description {
+            diff.push(
+                ChangeKind::FieldDescriptionChanged,
+                Criticality::Safe,
+                path.clone(),
+                format!("Description of field `{}` changed", path),
+            );
+        }
+        if old.deprecation_status != new.deprecation_status {
+            let message = if new.deprecation_status.is_deprecated() {
+                format!("Field `{}` was deprecated", path)
+            } else {
+                format!("Field `{}` is no longer deprecated", path)
+            };
+            diff.push(
+                ChangeKind::FieldDeprecationChanged,
+                Criticality::Safe,
+                path.clone(),
+                message,
+            );
+        }
+
+        let empty1 = vec![];
+        let empty2 = vec![];
+        diff_arguments(
+            diff,
+            &path,
+            old.arguments.as_ref().unwrap_or(&empty1),
+            new.arguments.as_ref().unwrap_or(&empty2),
+        );
+    }
+}
+
+fn diff_arguments<S1, S2>(
+    diff: &mut SchemaDiff,
+    parent_path: &str,
+    old: &[Argument<S1>],
+    new: &[Argument<S2>],
+) where
+    S1: ScalarValue,
+    S2: ScalarValue,
+{
+    diff_input_values(
+        diff,
+        parent_path,
+        old,
+        new,
+        [
+            ChangeKind::ArgumentAdded,
+            ChangeKind::ArgumentRemoved,
+            ChangeKind::ArgumentTypeChanged,
+            ChangeKind::ArgumentDefaultValueChanged,
+        ],
+        "Argument",
+    )
+}
+
+fn diff_input_object<S1, S2>(
+    diff: &mut SchemaDiff,
+    old: &InputObjectMeta<S1>,
+    new: &InputObjectMeta<S2>,
+) where
+    S1: ScalarValue,
+    S2: ScalarValue,
+{
+    diff_input_values(
+        diff,
+        &old.name,
+        &old.input_fields,
+        &new.input_fields,
+        [
+            ChangeKind::InputFieldAdded,
+            ChangeKind::InputFieldRemoved,
+            ChangeKind::InputFieldTypeChanged,
+            ChangeKind::InputFieldDefaultValueChanged,
+        ],
+        "Input field",
+    )
+}
+
+/// Compares arguments or input fields, which follow the same rules.
+///
+/// `kinds` holds the added, removed, type changed and default value changed
+/// kinds, in that order.
+fn diff_input_values<S1, S2>(
+    diff: &mut SchemaDiff,
+    parent_path: &str,
+    old: &[Argument<S1>],
+    new: &[Argument<S2>],
+    kinds: [ChangeKind; 4],
+    label: &str,
+) where
+    S1: ScalarValue,
+    S2: ScalarValue,
+{
+    let [added, removed, type_changed, default_changed] = kinds;
+
+    let names = sorted_names(
+        old.iter().map(|a| a.name.as_str()),
+        new.iter().map(|a| a.name.as_str()),
+    );
+    for name in names {
+        let path = format!("{}.{}", parent_path, name);
+        match (
+            old.iter().find(|a| a.name == name),
+            new.iter().find(|a| a.name == name),
+        ) {
+            (Some(old), Some(new)) => {
+                if old.arg_type != new.arg_type {
+                    let criticality = if is_safe_input_change(&old.arg_type, &new.arg_type) {
+                        Criticality::Safe
+                    } else {
+                        Criticality::Breaking
+                    };
+                    diff.push(
+                        type_changed,
+                        criticality,
+                        path.clone(),
+                        format!(
+                            "{} `{}` changed type from `{}` to `{}`",
+                            label, path, old.arg_type, new.arg_type,
+                        ),
+                    );
+                }
+
+                let old_default = old.default_value.as_ref().map(ToString::to_string);
+                let new_default = new.default_value.as_ref().map(ToString::to_string);
+                if old_default != new_default {
+                    diff.push(
+                        default_changed,
+                        Criticality::Dangerous,
+                        path.clone(),
+                        format!(
+                            "Default value of {} `{}` changed from `{}` to `{}`",
+                            label.to_lowercase(),
+                            path,
+                            old_default.as_deref().unwrap_or("none"),
+                            new_default.as_deref().unwrap_or("none"),
+                        ),
+                    );
+                }
+            }
+            (Some(_), None) => diff.push(
+                removed,
+                Criticality::Breaking,
+                path.clone(),
+                format!("{} `{}` was removed", label, path),
+            ),
+            (None, Some(new)) => {
+                let (criticality, requirement) =
+                    if new.arg_type.is_non_null() && new.default_value.is_none() {
+                        (Criticality::Breaking, "Required")
+                    } else {
+                        (Criticality::Safe, "Optional")
+                    };
+                diff.push(
+                    added,
+                    criticality,
+                    path.clone(),
+                    format!(
+                        "{} {} `{}` was added",
+                        requirement,
+                        label.to_lowercase(),
+                        path,
+                    ),
+                );
+            }
+            (None, None) => unreachable!(),
+        }
+    }
+}
+
+fn diff_enum<S1, S2>(diff: &mut SchemaDiff, old: &EnumMeta<S1>, new: &EnumMeta<S2>) {
+    let names = sorted_names(
+        old.values.iter().map(|v| v.name.as_str()),
+        new.values.iter().map(|v| v.name.as_str()),
+    );
+    for name in names {
+        let path = format!("{}.{}", old.name, name);
+        match (
+            old.values.iter().find(|v| v.name == name),
+            new.values.iter().find(|v| v.name == name),
+        ) {
+            (Some(old), Some(new)) => {
+                if old.deprecation_status != new.deprecation_status {
+                    let message = if new.deprecation_status.is_deprecated() {
+                        format!("Enum value `{}` was deprecated", path)
+                    } else {
+                        format!("Enum value `{}` is no longer deprecated", path)
+                    };
+                    diff.push(
+                        ChangeKind::EnumValueDeprecationChanged,
+                        Criticality::Safe,
+                        path,
+                        message,
+                    );
+                }
+            }
+            (Some(_), None) => diff.push(
+                ChangeKind::EnumValueRemoved,
+                Criticality::Breaking,
+                path.clone(),
+                format!("Enum value `{}` was removed", path),
+            ),
+            (None, Some(_)) => diff.push(
+                ChangeKind::EnumValueAdded,
+                Criticality::Dangerous,
+                path.clone(),
+                format!("Enum value `{}` was added", path),
+            ),
+            (None, None) => unreachable!(),
+        }
+    }
+}
+
+fn diff_union(diff: &mut SchemaDiff, old: &UnionMeta, new: &UnionMeta) {
+    for name in sorted_names(
+        old.of_type_names.iter().map(String::as_str),
+        new.of_type_names.iter().map(String::as_str),
+    ) {
+        let in_old = old.of_type_names.iter().any(|n| n == name);
+        let in_new = new.of_type_names.iter().any(|n| n == name);
+        if in_old && !in_new {
+            diff.push(
+                ChangeKind::UnionMemberRemoved,
+                Criticality::Breaking,
+                old.name.to_string(),
+                format!("`{}` was removed from union `{}`", name, old.name),
+            );
+        } else if !in_old && in_new {
+            diff.push(
+                ChangeKind::UnionMemberAdded,
+                Criticality::Dangerous,
+                new.name.to_string(),
+                format!("`{}` was added to union `{}`", name, new.name),
+            );
+        }
+    }
+}
+
+fn diff_directive<S1, S2>(diff: &mut SchemaDiff, old: &DirectiveType<S1>, new: &DirectiveType<S2>)
+where
+    S1: ScalarValue,
+    S2: ScalarValue,
+{
+    let path = format!("@{}", old.name);
+
+    for location in &old.locations {
+        if !new.locations.contains(location) {
+            diff.push(
+                ChangeKind::DirectiveLocationRemoved,
+                Criticality::Breaking,
+                path.clone(),
+                format!("Directive `{}` can no longer be used on {}", path, location,),
+            );
+        }
+    }
+    for location in &new.locations {
+        if !old.locations.contains(location) {
+            diff.push(
+                ChangeKind::DirectiveLocationAdded,
+                Criticality::Safe,
+                path.clone(),
+                format!("Directive `{}` can now be used on {}", path, location),
+            );
+        }
+    }
+
+    diff_arguments(diff, &path, &old.arguments, &new.arguments);
+}
+
+/// Checks whether values of the `new` output type can be read by clients
+/// expecting the `old` one.
+fn is_safe_output_change(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (Type::Named(o), Type::Named(n)) | (Type::Named(o), Type::NonNullNamed(n)) => o == n,
+        (Type::NonNullNamed(o), Type::NonNullNamed(n)) => o == n,
+        (Type::List(o), Type::List(n)) | (Type::List(o), Type::NonNullList(n)) => {
+            is_safe_output_change(o, n)
+        }
+        (Type::NonNullList(o), Type::NonNullList(n)) => is_safe_output_change(o, n),
+        _ => false,
+    }
+}
+
+/// Checks whether values written by clients for the `old` input type are
+/// still accepted by the `new` one.
+fn is_safe_input_change(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (Type::Named(o), Type::Named(n)) | (Type::NonNullNamed(o), Type::Named(n)) => o == n,
+        (Type::NonNullNamed(o), Type::NonNullNamed(n)) => o == n,
+        (Type::List(o), Type::List(n)) | (Type::NonNullList(o), Type::List(n)) => {
+            is_safe_input_change(o, n)
+        }
+        (Type::NonNullList(o), Type::NonNullList(n)) => is_safe_input_change(o, n),
+        _ => false,
+    }
+}
+
+fn kind_name<S>(meta: &MetaType<S>) -> &'static str {
+    match meta {
+        MetaType::Scalar(_) => "Scalar",
+        MetaType::Object(_) => "Object",
+        MetaType::Interface(_) => "Interface",
+        MetaType::Union(_) => "Union",
+        MetaType::Enum(_) => "Enum",
+        MetaType::InputObject(_) => "Input object",
+        MetaType::List(_) => "List",
+        MetaType::Nullable(_) => "Nullable",
+        MetaType::Placeholder(_) => "Placeholder",
+    }
+}
+
+fn sorted_names<'n>(
+    old: impl Iterator<Item = &'n str>,
+    new: impl Iterator<Item = &'n str>,
+) -> BTreeSet<&'n str> {
+    old.chain(new).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        schema::meta::{Argument, EnumMeta, EnumValue, Field, InputObjectMeta, ObjectMeta},
+        EmptyMutation, EmptySubscription, GraphQLEnum, GraphQLInputObject, GraphQLObject, RootNode,
+    };
+
+    use super::{diff, ChangeKind, Criticality};
+
+    #[allow(unused_variables)]
+    mod v1 {
+        use super::*;
+
+        #[derive(GraphQLEnum)]
+        pub enum Episode {
+            NewHope,
+            Empire,
+            Jedi,
+        }
+
+        #[derive(GraphQLInputObject)]
+        pub struct HumanFilter {
+            pub name: Option<String>,
+        }
+
+        #[derive(GraphQLObject)]
+        pub struct Human {
+            pub id: String,
+            pub name: Option<String>,
+            pub home_planet: String,
+            pub appears_in: Vec<Episode>,
+        }
+
+        pub struct Query;
+
+        #[crate::graphql_object(name = "Query")]
+        impl Query {
+            fn human(id: String) -> Option<Human> {
+                None
+            }
+
+            fn humans(filter: Option<HumanFilter>) -> Vec<Human> {
+                vec![]
+            }
+        }
+    }
+
+    #[allow(unused_variables)]
+    mod v2 {
+        use super::*;
+
+        #[derive(GraphQLEnum)]
+        pub enum Episode {
+            NewHope,
+            Empire,
+            Clones,
+        }
+
+        #[derive(GraphQLInputObject)]
+        pub struct HumanFilter {
+            pub name: Option<String>,
+            pub episode: Episode,
+        }
+
+        #[derive(GraphQLObject)]
+        pub struct Human {
+            pub id: String,
+            pub name: String,
+            pub home_planet: Option<String>,
+            pub appears_in: Vec<Episode>,
+            pub mass: Option<f64>,
+        }
+
+        pub struct Query;
+
+        #[crate::graphql_object(name = "Query")]
+        impl Query {
+            fn human(id: Option<String>, first: i32) -> Option<Human> {
+                None
+            }
+
+            fn humans(filter: Option<HumanFilter>, limit: Option<i32>) -> Vec<Human> {
+                vec![]
+            }
+        }
+    }
+
+    fn schemas() -> (
+        RootNode<'static, v1::Query, EmptyMutation, EmptySubscription>,
+        RootNode<'static, v2::Query, EmptyMutation, EmptySubscription>,
+    ) {
+        (
+            RootNode::new(v1::Query, EmptyMutation::new(), EmptySubscription::new()),
+            RootNode::new(v2::Query, EmptyMutation::new(), EmptySubscription::new()),
+        )
+    }
+
+    fn criticality_of(changes: &super::SchemaDiff, path: &str, kind: ChangeKind) -> Criticality {
+        changes
+            .changes
+            .iter()
+            .find(|c| c.path == path && c.kind == kind)
+            .unwrap_or_else(|| panic!("no {:?} change for `{}`", kind, path))
+            .criticality
+    }
+
+    #[test]
+    fn identical_schemas_have_no_changes() {
+        let (old, _) = schemas();
+        let (same, _) = schemas();
+
+        assert!(diff(&old.schema, &same.schema).is_empty());
+    }
+
+    #[test]
+    fn classifies_changes() {
+        let (old, new) = schemas();
+        let changes = diff(&old.schema, &new.schema);
+
+        assert!(changes.has_breaking_changes());
+
+        assert_eq!(
+            criticality_of(&changes, "Human.name", ChangeKind::FieldTypeChanged),
+            Criticality::Safe,
+        );
+        assert_eq!(
+            criticality_of(&changes, "Human.homePlanet", ChangeKind::FieldTypeChanged),
+            Criticality::Breaking,
+        );
+        assert_eq!(
+            criticality_of(&changes, "Human.mass", ChangeKind::FieldAdded),
+            Criticality::Safe,
+        );
+        assert_eq!(
+            criticality_of(&changes, "Query.human.id", ChangeKind::ArgumentTypeChanged),
+            Criticality::Safe,
+        );
+        assert_eq!(
+            criticality_of(&changes, "Query.human.first", ChangeKind::ArgumentAdded),
+            Criticality::Breaking,
+        );
+        assert_eq!(
+            criticality_of(&changes, "Query.humans.limit", ChangeKind::ArgumentAdded),
+            Criticality::Safe,
+        );
+        assert_eq!(
+            criticality_of(&changes, "HumanFilter.episode", ChangeKind::InputFieldAdded),
+            Criticality::Breaking,
+        );
+        assert_eq!(
+            criticality_of(&changes, "Episode.JEDI", ChangeKind::EnumValueRemoved),
+            Criticality::Breaking,
+        );
+        assert_eq!(
+            criticality_of(&changes, "Episode.CLONES", ChangeKind::EnumValueAdded),
+            Criticality::Dangerous,
+        );
+    }
+
+    #[test]
+    fn detects_nullability_tightening_on_inputs() {
+        let old = InputObjectMeta::<crate::DefaultScalarValue>::new::<i32>(
+            "Filter".into(),
+            &[Argument::new("name", crate::Type::Named("String".into()))],
+        );
+        let new = InputObjectMeta::<crate::DefaultScalarValue>::new::<i32>(
+            "Filter".into(),
+            &[Argument::new(
+                "name",
+                crate::Type::NonNullNamed("String".into()),
+            )],
+        );
+
+        let mut changes = super::SchemaDiff::default();
+        super::diff_input_object(&mut changes, &old, &new);
+
+        assert_eq!(changes.changes.len(), 1);
+        assert_eq!(changes.changes[0].kind, ChangeKind::InputFieldTypeChanged);
+        assert_eq!(changes.changes[0].criticality, Criticality::Breaking);
+    }
+
+    #[test]
+    fn detects_removed_types_and_kind_changes() {
+        let mut changes = super::SchemaDiff::default();
+
+        let object = ObjectMeta::<crate::DefaultScalarValue>::new(
+            "Droid".into(),
+            &[Field {
+                name: "id".into(),
+                description: None,
+                arguments: None,
+                field_type: crate::Type::NonNullNamed("String".into()),
+                deprecation_status: crate::meta::DeprecationStatus::Current,
+                visibility: None,
+                cache_control: None,
+            }],
+        )
+        .into_meta();
+        let enumeration = EnumMeta::<crate::DefaultScalarValue>::new::<i32>(
+            "Droid".into(),
+            &[EnumValue::new("R2D2")],
+        )
+        .into_meta();
+        super::diff_type(&mut changes, "Droid", &object, &enumeration);
+
+        assert_eq!(changes.changes.len(), 1);
+        assert_eq!(changes.changes[0].kind, ChangeKind::TypeKindChanged);
+        assert_eq!(
+            changes.changes[0].message,
+            "`Droid` changed from Object to Enum"
+        );
+    }
+
+    #[test]
+    fn serializes_report() {
+        let (old, new) = schemas();
+        let changes = diff(&old.schema, &new.schema);
+
+        let json = serde_json::to_value(&changes).unwrap();
+        let first = &json["changes"][0];
+
+        assert_eq!(first["kind"], "ENUM_VALUE_ADDED");
+        assert_eq!(first["criticality"], "DANGEROUS");
+        assert_eq!(first["path"], "Episode.CLONES");
+    }
+}
@@ -1,12 +1,15 @@
 //! Types used to describe a `GraphQL` schema
 
 use std::{
+    any::Any,
     borrow::{Cow, ToOwned},
     fmt,
+    sync::Arc,
 };
 
 use crate::{
     ast::{FromInputValue, InputValue, Type},
+    executor::{CacheHint, FieldError, IntoFieldError},
     parser::{ParseError, ScalarToken},
     schema::model::SchemaType,
     types::base::TypeKind,
@@ -40,99 +43,133 @@ impl DeprecationStatus {
     }
 }
 
+/// Predicate deciding whether a type, field or enum value is visible to a
+/// request, given its context
+///
+/// See `RootNode::new_with_info_and_visibility`.
+pub type VisibilityFn = fn(&dyn Any) -> bool;
+
 /// Scalar type metadata
+#[derive(Clone)]
 pub struct ScalarMeta<'a, S> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
     #[doc(hidden)]
     pub description: Option<String>,
-    pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> bool,
+    #[doc(hidden)]
+    pub visibility: Option<VisibilityFn>,
+    pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> Result<(), FieldError<S>>,
     pub(crate) parse_fn: for<'b> fn(ScalarToken<'b>) -> Result<S, ParseError<'b>>,
 }
 
 /// List type metadata
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ListMeta<'a> {
     #[doc(hidden)]
     pub of_type: Type<'a>,
 }
 
 /// Nullable type metadata
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct NullableMeta<'a> {
     #[doc(hidden)]
     pub of_type: Type<'a>,
 }
 
 /// Object type metadata
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ObjectMeta<'a, S> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
+    pub visibility: Option<VisibilityFn>,
+    #[doc(hidden)]
+    pub cache_control: Option<CacheHint>,
+    #[doc(hidden)]
     pub fields: Vec<Field<'a, S>>,
     #[doc(hidden)]
     pub interface_names: Vec<String>,
 }
 
 /// Enum type metadata
+#[derive(Clone)]
 pub struct EnumMeta<'a, S> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
+    pub visibility: Option<VisibilityFn>,
+    #[doc(hidden)]
     pub values: Vec<EnumValue>,
-    pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> bool,
+    pub(crate) try_parse_fn: EnumParseFn<'a, S>,
 }
 
+/// Checks whether an `InputValue` is one of the values of an enum
+///
+/// Unlike the other input types, the enums of schemas built from introspection
+/// results only know their values at runtime, hence the closure.
+pub(crate) type EnumParseFn<'a, S> =
+    Arc<dyn Fn(&InputValue<S>) -> Result<(), FieldError<S>> + Send + Sync + 'a>;
+
 /// Interface type metadata
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct InterfaceMeta<'a, S> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
+    pub visibility: Option<VisibilityFn>,
+    #[doc(hidden)]
+    pub cache_control: Option<CacheHint>,
+    #[doc(hidden)]
     pub fields: Vec<Field<'a, S>>,
 }
 
 /// Union type metadata
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct UnionMeta<'a> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
+    pub visibility: Option<VisibilityFn>,
+    #[doc(hidden)]
+    pub cache_control: Option<CacheHint>,
+    #[doc(hidden)]
     pub of_type_names: Vec<String>,
 }
 
 /// Input object metadata
+#[derive(Clone)]
 pub struct InputObjectMeta<'a, S> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
+    pub visibility: Option<VisibilityFn>,
+    #[doc(hidden)]
     pub input_fields: Vec<Argument<'a, S>>,
-    pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> bool,
+    pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> Result<(), FieldError<S>>,
 }
 
 /// A placeholder for not-yet-registered types
 ///
 /// After a type's `meta` method has been called but before it has returned, a placeholder type
 /// is inserted into a registry to indicate existence.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PlaceholderMeta<'a> {
     #[doc(hidden)]
     pub of_type: Type<'a>,
 }
 
 /// Generic type metadata
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum MetaType<'a, S = DefaultScalarValue> {
     #[doc(hidden)]
     Scalar(ScalarMeta<'a, S>),
@@ -167,6 +204,10 @@ pub struct Field<'a, S> {
     pub field_type: Type<'a>,
     #[doc(hidden)]
     pub deprecation_status: DeprecationStatus,
+    #[doc(hidden)]
+    pub visibility: Option<VisibilityFn>,
+    #[doc(hidden)]
+    pub cache_control: Option<CacheHint>,
 }
 
 impl<'a, S> Field<'a, S> {
@@ -212,6 +253,8 @@ pub struct EnumValue {
     pub description: Option<String>,
     /// Whether the field is deprecated or not, with an optional reason.
     pub deprecation_status: DeprecationStatus,
+    /// The predicate deciding whether the value is visible to a request, if any.
+    pub visibility: Option<VisibilityFn>,
 }
 
 impl<'a, S> MetaType<'a, S> {
@@ -257,6 +300,29 @@ impl<'a, S> MetaType<'a, S> {
         }
     }
 
+    /// Access the visibility predicate of the type, if applicable
+    pub fn visibility(&self) -> Option<VisibilityFn> {
+        match *self {
+            MetaType::Scalar(ScalarMeta { visibility, .. })
+            | MetaType::Object(ObjectMeta { visibility, .. })
+            | MetaType::Enum(EnumMeta { visibility, .. })
+            | MetaType::Interface(InterfaceMeta { visibility, .. })
+            | MetaType::Union(UnionMeta { visibility, .. })
+            | MetaType::InputObject(InputObjectMeta { visibility, .. }) => visibility,
+            _ => None,
+        }
+    }
+
+    /// Access the cache hint of the type, if applicable
+    pub fn cache_control(&self) -> Option<CacheHint> {
+        match *self {
+            MetaType::Object(ObjectMeta { cache_control, .. })
+            | MetaType::Interface(InterfaceMeta { cache_control, .. })
+            | MetaType::Union(UnionMeta { cache_control, .. }) => cache_control,
+            _ => None,
+        }
+    }
+
     /// Construct a `TypeKind` for a given type
     ///
     /// # Panics
@@ -326,20 +392,22 @@ impl<'a, S> MetaType<'a, S> {
     /// Access the input value parse function, if applicable
     ///
     /// An input value parse function is a function that takes an `InputValue` instance and returns
-    /// `true` if it can be parsed as the provided type.
+    /// `Ok(())` if it can be parsed as the provided type, or the error explaining why it can't.
     ///
     /// Only scalars, enums, and input objects have parse functions.
-    pub fn input_value_parse_fn(&self) -> Option<for<'b> fn(&'b InputValue<S>) -> bool> {
+    pub fn input_value_parse_fn(
+        &self,
+    ) -> Option<&dyn Fn(&InputValue<S>) -> Result<(), FieldError<S>>> {
         match *self {
             MetaType::Scalar(ScalarMeta {
                 ref try_parse_fn, ..
             })
-            | MetaType::Enum(EnumMeta {
-                ref try_parse_fn, ..
-            })
             | MetaType::InputObject(InputObjectMeta {
                 ref try_parse_fn, ..
-            }) => Some(*try_parse_fn),
+            }) => Some(try_parse_fn),
+            MetaType::Enum(EnumMeta {
+                ref try_parse_fn, ..
+            }) => Some(&**try_parse_fn),
             _ => None,
         }
     }
@@ -431,6 +499,7 @@ where
         ScalarMeta {
             name,
             description: None,
+            visibility: None,
             try_parse_fn: try_parse_fn::<S, T>,
             parse_fn: <T as ParseScalarValue<S>>::from_str,
         }
@@ -444,6 +513,14 @@ where
         self
     }
 
+    /// Set the predicate deciding whether the type is visible to a request
+    ///
+    /// If a predicate was provided prior to calling this method, it will be overwritten.
+    pub fn visibility(mut self, predicate: VisibilityFn) -> ScalarMeta<'a, S> {
+        self.visibility = Some(predicate);
+        self
+    }
+
     /// Wrap the scalar in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Scalar(self)
@@ -483,6 +560,8 @@ where
         ObjectMeta {
             name,
             description: None,
+            visibility: None,
+            cache_control: None,
             fields: fields.to_vec(),
             interface_names: vec![],
         }
@@ -496,6 +575,22 @@ where
         self
     }
 
+    /// Set the predicate deciding whether the type is visible to a request
+    ///
+    /// If a predicate was provided prior to calling this method, it will be overwritten.
+    pub fn visibility(mut self, predicate: VisibilityFn) -> ObjectMeta<'a, S> {
+        self.visibility = Some(predicate);
+        self
+    }
+
+    /// Set the cache hint of the type
+    ///
+    /// If a hint was provided prior to calling this method, it will be overwritten.
+    pub fn cache_control(mut self, hint: CacheHint) -> ObjectMeta<'a, S> {
+        self.cache_control = Some(hint);
+        self
+    }
+
     /// Set the interfaces this type implements
     ///
     /// If a list of interfaces already was provided prior to calling this method, they will be
@@ -526,8 +621,9 @@ where
         EnumMeta {
             name,
             description: None,
+            visibility: None,
             values: values.to_vec(),
-            try_parse_fn: try_parse_fn::<S, T>,
+            try_parse_fn: Arc::new(try_parse_fn::<S, T> as fn(&InputValue<S>) -> _),
         }
     }
 
@@ -539,6 +635,14 @@ where
         self
     }
 
+    /// Set the predicate deciding whether the type is visible to a request
+    ///
+    /// If a predicate was provided prior to calling this method, it will be overwritten.
+    pub fn visibility(mut self, predicate: VisibilityFn) -> EnumMeta<'a, S> {
+        self.visibility = Some(predicate);
+        self
+    }
+
     /// Wrap this enum type in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Enum(self)
@@ -554,6 +658,8 @@ where
         InterfaceMeta {
             name,
             description: None,
+            visibility: None,
+            cache_control: None,
             fields: fields.to_vec(),
         }
     }
@@ -566,6 +672,22 @@ where
         self
     }
 
+    /// Set the predicate deciding whether the type is visible to a request
+    ///
+    /// If a predicate was provided prior to calling this method, it will be overwritten.
+    pub fn visibility(mut self, predicate: VisibilityFn) -> InterfaceMeta<'a, S> {
+        self.visibility = Some(predicate);
+        self
+    }
+
+    /// Set the cache hint of the type
+    ///
+    /// If a hint was provided prior to calling this method, it will be overwritten.
+    pub fn cache_control(mut self, hint: CacheHint) -> InterfaceMeta<'a, S> {
+        self.cache_control = Some(hint);
+        self
+    }
+
     /// Wrap this interface type in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Interface(self)
@@ -578,6 +700,8 @@ impl<'a> UnionMeta<'a> {
         UnionMeta {
             name,
             description: None,
+            visibility: None,
+            cache_control: None,
             of_type_names: of_types
                 .iter()
                 .map(|t| t.innermost_name().to_owned())
@@ -593,6 +717,22 @@ impl<'a> UnionMeta<'a> {
         self
     }
 
+    /// Set the predicate deciding whether the type is visible to a request
+    ///
+    /// If a predicate was provided prior to calling this method, it will be overwritten.
+    pub fn visibility(mut self, predicate: VisibilityFn) -> UnionMeta<'a> {
+        self.visibility = Some(predicate);
+        self
+    }
+
+    /// Set the cache hint of the type
+    ///
+    /// If a hint was provided prior to calling this method, it will be overwritten.
+    pub fn cache_control(mut self, hint: CacheHint) -> UnionMeta<'a> {
+        self.cache_control = Some(hint);
+        self
+    }
+
     /// Wrap this union type in a generic meta type
     pub fn into_meta<S>(self) -> MetaType<'a, S> {
         MetaType::Union(self)
@@ -611,6 +751,7 @@ where
         InputObjectMeta {
             name,
             description: None,
+            visibility: None,
             input_fields: input_fields.to_vec(),
             try_parse_fn: try_parse_fn::<S, T>,
         }
@@ -624,6 +765,14 @@ where
         self
     }
 
+    /// Set the predicate deciding whether the type is visible to a request
+    ///
+    /// If a predicate was provided prior to calling this method, it will be overwritten.
+    pub fn visibility(mut self, predicate: VisibilityFn) -> InputObjectMeta<'a, S> {
+        self.visibility = Some(predicate);
+        self
+    }
+
     /// Wrap this union type in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::InputObject(self)
@@ -662,6 +811,22 @@ impl<'a, S> Field<'a, S> {
         self.deprecation_status = DeprecationStatus::Deprecated(reason.map(ToOwned::to_owned));
         self
     }
+
+    /// Set the predicate deciding whether the field is visible to a request
+    ///
+    /// This overwrites the predicate if any was previously set.
+    pub fn visibility(mut self, predicate: VisibilityFn) -> Self {
+        self.visibility = Some(predicate);
+        self
+    }
+
+    /// Set the cache hint of the field
+    ///
+    /// This overwrites the hint if any was previously set.
+    pub fn cache_control(mut self, hint: CacheHint) -> Self {
+        self.cache_control = Some(hint);
+        self
+    }
 }
 
 impl<'a, S> Argument<'a, S> {
@@ -699,6 +864,7 @@ impl EnumValue {
             name: name.to_owned(),
             description: None,
             deprecation_status: DeprecationStatus::Current,
+            visibility: None,
         }
     }
 
@@ -717,6 +883,14 @@ impl EnumValue {
         self.deprecation_status = DeprecationStatus::Deprecated(reason.map(ToOwned::to_owned));
         self
     }
+
+    /// Set the predicate deciding whether the enum value is visible to a request
+    ///
+    /// This overwrites the predicate if any was previously set.
+    pub fn visibility(mut self, predicate: VisibilityFn) -> Self {
+        self.visibility = Some(predicate);
+        self
+    }
 }
 
 impl<'a, S: fmt::Debug> fmt::Debug for ScalarMeta<'a, S> {
@@ -748,9 +922,11 @@ impl<'a, S: fmt::Debug> fmt::Debug for InputObjectMeta<'a, S> {
     }
 }
 
-fn try_parse_fn<S, T>(v: &InputValue<S>) -> bool
+fn try_parse_fn<S, T>(v: &InputValue<S>) -> Result<(), FieldError<S>>
 where
     T: FromInputValue<S>,
 {
-    <T as FromInputValue<S>>::from_input_value(v).is_some()
+    <T as FromInputValue<S>>::from_input_value(v)
+        .map(drop)
+        .map_err(IntoFieldError::into_field_error)
 }
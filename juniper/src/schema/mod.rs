@@ -1,6 +1,9 @@
 #![allow(clippy::module_inception)]
 
+pub mod diff;
 pub mod meta;
 pub mod model;
 pub mod schema;
 pub mod translate;
+pub mod validation;
+mod visibility;
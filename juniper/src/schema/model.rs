@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{any::Any, borrow::Cow, fmt};
 
 use fnv::FnvHashMap;
 #[cfg(feature = "graphql-parser-integration")]
@@ -7,9 +7,14 @@ use graphql_parser::schema::Document;
 use crate::{
     ast::Type,
     executor::{Context, Registry},
-    schema::meta::{Argument, InterfaceMeta, MetaType, ObjectMeta, PlaceholderMeta, UnionMeta},
+    introspection::{schema_from_introspection, IntrospectionError},
+    schema::{
+        meta::{Argument, InterfaceMeta, MetaType, ObjectMeta, UnionMeta},
+        validation::{validate_schema, validate_structure, SchemaError},
+        visibility::VisibleSchemas,
+    },
     types::{base::GraphQLType, name::Name},
-    value::{DefaultScalarValue, ScalarValue},
+    value::{DefaultScalarValue, ScalarValue, Value},
     GraphQLEnum,
 };
 
@@ -44,16 +49,40 @@ pub struct RootNode<
     pub subscription_info: SubscriptionT::TypeInfo,
     #[doc(hidden)]
     pub schema: SchemaType<'a, S>,
+    introspection: Introspection<QueryT::Context>,
+    context_as_any: Option<fn(&QueryT::Context) -> &dyn Any>,
+    visible_schemas: VisibleSchemas<'a, S>,
+}
+
+/// Whether introspection queries are allowed on a schema
+enum Introspection<CtxT> {
+    Enabled,
+    Disabled,
+    Restricted(Box<dyn Fn(&CtxT) -> bool + Send + Sync>),
+}
+
+impl<CtxT> fmt::Debug for Introspection<CtxT> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Introspection::Enabled => write!(f, "Enabled"),
+            Introspection::Disabled => write!(f, "Disabled"),
+            Introspection::Restricted(_) => write!(f, "Restricted"),
+        }
+    }
+}
+
+fn as_any<T: Any>(value: &T) -> &dyn Any {
+    value
 }
 
 /// Metadata for a schema
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SchemaType<'a, S> {
     pub(crate) types: FnvHashMap<Name, MetaType<'a, S>>,
     pub(crate) query_type_name: String,
     pub(crate) mutation_type_name: Option<String>,
     pub(crate) subscription_type_name: Option<String>,
-    directives: FnvHashMap<String, DirectiveType<'a, S>>,
+    pub(crate) directives: FnvHashMap<String, DirectiveType<'a, S>>,
 }
 
 impl<'a, S> Context for SchemaType<'a, S> {}
@@ -65,7 +94,7 @@ pub enum TypeType<'a, S: 'a> {
     List(Box<TypeType<'a, S>>),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DirectiveType<'a, S> {
     pub name: String,
     pub description: Option<String>,
@@ -99,6 +128,9 @@ where
     ///
     /// If the schema should not support mutations, use the
     /// `new` constructor instead.
+    ///
+    /// Schemas having visibility predicates are built with
+    /// `new_with_visibility` instead.
     pub fn new(
         query_obj: QueryT,
         mutation_obj: MutationT,
@@ -107,6 +139,66 @@ where
         RootNode::new_with_info(query_obj, mutation_obj, subscription_obj, (), (), ())
     }
 
+    /// Construct a new root node from query, mutation, and subscription nodes,
+    /// checking the consistency of the resulting schema
+    ///
+    /// Unlike `new`, this reports all the problems found in the schema, e.g.
+    /// objects not implementing their interfaces correctly, instead of
+    /// panicking on the first fatal one.
+    pub fn try_new(
+        query_obj: QueryT,
+        mutation_obj: MutationT,
+        subscription_obj: SubscriptionT,
+    ) -> Result<Self, Vec<SchemaError>> {
+        RootNode::try_new_with_info(query_obj, mutation_obj, subscription_obj, (), (), ())
+    }
+
+    /// Construct a new root node from query, mutation, and subscription nodes,
+    /// evaluating the visibility predicates of the schema against the context
+    /// of each request
+    ///
+    /// See `new_with_info_and_visibility` for details.
+    pub fn new_with_visibility(
+        query_obj: QueryT,
+        mutation_obj: MutationT,
+        subscription_obj: SubscriptionT,
+    ) -> Self
+    where
+        QueryT::Context: Any,
+    {
+        RootNode::new_with_info_and_visibility(
+            query_obj,
+            mutation_obj,
+            subscription_obj,
+            (),
+            (),
+            (),
+        )
+    }
+
+    /// Construct a new root node from query, mutation, and subscription nodes,
+    /// checking the consistency of the resulting schema and evaluating its
+    /// visibility predicates against the context of each request
+    ///
+    /// See `try_new` and `new_with_info_and_visibility` for details.
+    pub fn try_new_with_visibility(
+        query_obj: QueryT,
+        mutation_obj: MutationT,
+        subscription_obj: SubscriptionT,
+    ) -> Result<Self, Vec<SchemaError>>
+    where
+        QueryT::Context: Any,
+    {
+        RootNode::try_new_with_info_and_visibility(
+            query_obj,
+            mutation_obj,
+            subscription_obj,
+            (),
+            (),
+            (),
+        )
+    }
+
     #[cfg(feature = "schema-language")]
     /// The schema definition as a `String` in the
     /// [GraphQL Schema Language](https://graphql.org/learn/schema/#type-language)
@@ -116,6 +208,18 @@ where
         format!("{}", doc)
     }
 
+    #[cfg(feature = "schema-language")]
+    /// The schema definition as a `String` in the
+    /// [GraphQL Schema Language](https://graphql.org/learn/schema/#type-language)
+    /// format, as seen by a request with the given context.
+    ///
+    /// See `new_with_info_and_visibility` for details.
+    pub fn as_schema_language_for(&self, context: &QueryT::Context) -> String {
+        let schema = self.visible_schema(context);
+        let doc: Document<&str> = GraphQLParserTranslator::translate_schema(&schema);
+        format!("{}", doc)
+    }
+
     #[cfg(feature = "graphql-parser-integration")]
     /// The schema definition as a [`graphql_parser`](https://crates.io/crates/graphql-parser)
     /// [`Document`](https://docs.rs/graphql-parser/latest/graphql_parser/schema/struct.Document.html).
@@ -134,6 +238,11 @@ where
     /// Construct a new root node from query and mutation nodes,
     /// while also providing type info objects for the query and
     /// mutation types.
+    ///
+    /// # Panics
+    ///
+    /// If the schema has visibility predicates, which are only evaluated by
+    /// the `*_with_visibility` constructors.
     pub fn new_with_info(
         query_obj: QueryT,
         mutation_obj: MutationT,
@@ -142,29 +251,274 @@ where
         mutation_info: MutationT::TypeInfo,
         subscription_info: SubscriptionT::TypeInfo,
     ) -> Self {
-        RootNode {
+        let schema = SchemaType::new::<QueryT, MutationT, SubscriptionT>(
+            &query_info,
+            &mutation_info,
+            &subscription_info,
+        );
+        RootNode::from_schema(
+            schema,
+            query_obj,
+            mutation_obj,
+            subscription_obj,
+            query_info,
+            mutation_info,
+            subscription_info,
+            None,
+        )
+        .unwrap_or_else(|errors| panic!("{}", errors[0]))
+    }
+
+    /// Construct a new root node from query and mutation nodes, while also
+    /// providing type info objects for the query and mutation types, checking
+    /// the consistency of the resulting schema
+    ///
+    /// See `try_new` for details.
+    pub fn try_new_with_info(
+        query_obj: QueryT,
+        mutation_obj: MutationT,
+        subscription_obj: SubscriptionT,
+        query_info: QueryT::TypeInfo,
+        mutation_info: MutationT::TypeInfo,
+        subscription_info: SubscriptionT::TypeInfo,
+    ) -> Result<Self, Vec<SchemaError>> {
+        let schema = SchemaType::try_new::<QueryT, MutationT, SubscriptionT>(
+            &query_info,
+            &mutation_info,
+            &subscription_info,
+        )?;
+        RootNode::from_schema(
+            schema,
+            query_obj,
+            mutation_obj,
+            subscription_obj,
+            query_info,
+            mutation_info,
+            subscription_info,
+            None,
+        )
+    }
+
+    /// Construct a new root node from query and mutation nodes, while also
+    /// providing type info objects for the query and mutation types, and
+    /// evaluating the visibility predicates of the schema against the context
+    /// of each request
+    ///
+    /// Types, fields and enum values whose predicate isn't satisfied are hidden
+    /// from the request: they can't be queried, introspected, nor used as
+    /// input values.
+    ///
+    /// The schema is filtered once for each combination of the predicates'
+    /// results, unless it has more than 8 distinct predicates, in which case
+    /// it's filtered for each request instead.
+    ///
+    /// Schemas having visibility predicates can only be built this way, as
+    /// the predicates require a `'static` context.
+    pub fn new_with_info_and_visibility(
+        query_obj: QueryT,
+        mutation_obj: MutationT,
+        subscription_obj: SubscriptionT,
+        query_info: QueryT::TypeInfo,
+        mutation_info: MutationT::TypeInfo,
+        subscription_info: SubscriptionT::TypeInfo,
+    ) -> Self
+    where
+        QueryT::Context: Any,
+    {
+        let schema = SchemaType::new::<QueryT, MutationT, SubscriptionT>(
+            &query_info,
+            &mutation_info,
+            &subscription_info,
+        );
+        RootNode::from_schema(
+            schema,
+            query_obj,
+            mutation_obj,
+            subscription_obj,
+            query_info,
+            mutation_info,
+            subscription_info,
+            Some(as_any::<QueryT::Context>),
+        )
+        .unwrap_or_else(|errors| panic!("{}", errors[0]))
+    }
+
+    /// Construct a new root node from query and mutation nodes, while also
+    /// providing type info objects for the query and mutation types, checking
+    /// the consistency of the resulting schema and evaluating its visibility
+    /// predicates against the context of each request
+    ///
+    /// See `try_new` and `new_with_info_and_visibility` for details.
+    pub fn try_new_with_info_and_visibility(
+        query_obj: QueryT,
+        mutation_obj: MutationT,
+        subscription_obj: SubscriptionT,
+        query_info: QueryT::TypeInfo,
+        mutation_info: MutationT::TypeInfo,
+        subscription_info: SubscriptionT::TypeInfo,
+    ) -> Result<Self, Vec<SchemaError>>
+    where
+        QueryT::Context: Any,
+    {
+        let schema = SchemaType::try_new::<QueryT, MutationT, SubscriptionT>(
+            &query_info,
+            &mutation_info,
+            &subscription_info,
+        )?;
+        RootNode::from_schema(
+            schema,
+            query_obj,
+            mutation_obj,
+            subscription_obj,
+            query_info,
+            mutation_info,
+            subscription_info,
+            Some(as_any::<QueryT::Context>),
+        )
+    }
+
+    /// Refuses schemas having visibility predicates unless the context can be
+    /// given to them, so that they never go unevaluated
+    #[allow(clippy::too_many_arguments)]
+    fn from_schema(
+        schema: SchemaType<'a, S>,
+        query_obj: QueryT,
+        mutation_obj: MutationT,
+        subscription_obj: SubscriptionT,
+        query_info: QueryT::TypeInfo,
+        mutation_info: MutationT::TypeInfo,
+        subscription_info: SubscriptionT::TypeInfo,
+        context_as_any: Option<fn(&QueryT::Context) -> &dyn Any>,
+    ) -> Result<Self, Vec<SchemaError>> {
+        let mut visible_schemas = VisibleSchemas::new(&schema);
+        if context_as_any.is_some() {
+            visible_schemas.compute(&schema);
+        } else if visible_schemas.has_predicates() {
+            return Err(vec![SchemaError::VisibilityNotEnabled]);
+        }
+        Ok(RootNode {
             query_type: query_obj,
             mutation_type: mutation_obj,
             subscription_type: subscription_obj,
-            schema: SchemaType::new::<QueryT, MutationT, SubscriptionT>(
-                &query_info,
-                &mutation_info,
-                &subscription_info,
-            ),
+            visible_schemas,
+            schema,
             query_info,
             mutation_info,
             subscription_info,
+            introspection: Introspection::Enabled,
+            context_as_any,
+        })
+    }
+
+    /// Disable introspection for all requests
+    ///
+    /// Queries selecting the `__schema` or `__type` fields then fail
+    /// validation, while `__typename` keeps working.
+    pub fn disable_introspection(mut self) -> Self {
+        self.introspection = Introspection::Disabled;
+        self
+    }
+
+    /// Enable introspection for all requests
+    ///
+    /// This is the default.
+    pub fn enable_introspection(mut self) -> Self {
+        self.introspection = Introspection::Enabled;
+        self
+    }
+
+    /// Only allow introspection for requests whose context satisfies the
+    /// given predicate, e.g. authenticated users
+    ///
+    /// For the other requests, queries selecting the `__schema` or `__type`
+    /// fields fail validation, while `__typename` keeps working.
+    pub fn restrict_introspection<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&QueryT::Context) -> bool + Send + Sync + 'static,
+    {
+        self.introspection = Introspection::Restricted(Box::new(predicate));
+        self
+    }
+
+    /// The schema as seen by a request with the given context
+    pub(crate) fn visible_schema(&self, context: &QueryT::Context) -> Cow<SchemaType<'a, S>> {
+        self.visible_schemas.get(
+            &self.schema,
+            self.context_as_any.map(|as_any| as_any(context)),
+        )
+    }
+
+    /// Whether introspection is allowed for a request with the given context
+    pub fn is_introspection_allowed(&self, context: &QueryT::Context) -> bool {
+        match &self.introspection {
+            Introspection::Enabled => true,
+            Introspection::Disabled => false,
+            Introspection::Restricted(predicate) => predicate(context),
         }
     }
 }
 
 impl<'a, S> SchemaType<'a, S> {
     /// Create a new schema.
+    ///
+    /// # Panics
+    ///
+    /// If the root query type isn't an object, or a type is still a placeholder
+    /// after registration. Use `try_new` to check the schema thoroughly instead.
     pub fn new<QueryT, MutationT, SubscriptionT>(
         query_info: &QueryT::TypeInfo,
         mutation_info: &MutationT::TypeInfo,
         subscription_info: &SubscriptionT::TypeInfo,
     ) -> Self
+    where
+        S: ScalarValue + 'a,
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S>,
+        SubscriptionT: GraphQLType<S>,
+    {
+        let schema = Self::build::<QueryT, MutationT, SubscriptionT>(
+            query_info,
+            mutation_info,
+            subscription_info,
+        );
+        if let Some(error) = validate_structure(&schema).into_iter().next() {
+            panic!("{}", error);
+        }
+        schema
+    }
+
+    /// Create a new schema, checking its consistency.
+    ///
+    /// Returns all the problems found in the schema, rather than panicking.
+    pub fn try_new<QueryT, MutationT, SubscriptionT>(
+        query_info: &QueryT::TypeInfo,
+        mutation_info: &MutationT::TypeInfo,
+        subscription_info: &SubscriptionT::TypeInfo,
+    ) -> Result<Self, Vec<SchemaError>>
+    where
+        S: ScalarValue + 'a,
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S>,
+        SubscriptionT: GraphQLType<S>,
+    {
+        let schema = Self::build::<QueryT, MutationT, SubscriptionT>(
+            query_info,
+            mutation_info,
+            subscription_info,
+        );
+        let errors = validate_schema(&schema);
+        if errors.is_empty() {
+            Ok(schema)
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn build<QueryT, MutationT, SubscriptionT>(
+        query_info: &QueryT::TypeInfo,
+        mutation_info: &MutationT::TypeInfo,
+        subscription_info: &SubscriptionT::TypeInfo,
+    ) -> Self
     where
         S: ScalarValue + 'a,
         QueryT: GraphQLType<S>,
@@ -203,25 +557,17 @@ impl<'a, S> SchemaType<'a, S> {
         let mut meta_fields = vec![
             registry.field::<SchemaType<S>>("__schema", &()),
             registry
-                .field::<TypeType<S>>("__type", &())
+                .field::<Option<TypeType<S>>>("__type", &())
                 .argument(registry.arg::<String>("name", &())),
         ];
 
-        if let Some(root_type) = registry.types.get_mut(&query_type_name) {
-            if let MetaType::Object(ObjectMeta { ref mut fields, .. }) = *root_type {
-                fields.append(&mut meta_fields);
-            } else {
-                panic!("Root type is not an object");
-            }
-        } else {
-            panic!("Root type not found");
+        // Missing or non-object root types are reported by the validation.
+        if let Some(MetaType::Object(ObjectMeta { ref mut fields, .. })) =
+            registry.types.get_mut(&query_type_name)
+        {
+            fields.append(&mut meta_fields);
         }
 
-        for meta_type in registry.types.values() {
-            if let MetaType::Placeholder(PlaceholderMeta { ref of_type }) = *meta_type {
-                panic!("Type {:?} is still a placeholder type", of_type);
-            }
-        }
         SchemaType {
             types: registry.types,
             query_type_name,
@@ -443,6 +789,25 @@ impl<'a, S> SchemaType<'a, S> {
     }
 }
 
+impl<S> SchemaType<'static, S>
+where
+    S: ScalarValue + 'static,
+{
+    /// Build a schema from the result of an introspection query.
+    ///
+    /// Accepts either the data returned by [`introspect`](fn.introspect.html)
+    /// or a whole response containing it under `data`, e.g. the JSON returned
+    /// by another GraphQL server. The resulting schema can be used to validate
+    /// queries or to [`diff`](diff/fn.diff.html) against another schema, but
+    /// not to execute them.
+    ///
+    /// Custom scalars accept any scalar value, as their actual format isn't
+    /// part of the introspection result.
+    pub fn from_introspection(value: &Value<S>) -> Result<Self, IntrospectionError> {
+        schema_from_introspection(value)
+    }
+}
+
 impl<'a, S> TypeType<'a, S> {
     #[inline]
     pub fn to_concrete(&self) -> Option<&'a MetaType<S>> {
@@ -0,0 +1,816 @@
+//! Consistency checks of a schema as a whole
+//!
+//! Individual types are mostly checked at compile time by the code generators,
+//! but some rules of the [specification](https://spec.graphql.org/June2018/#sec-Type-System)
+//! depend on how types relate to each other. They are checked here when a
+//! schema is built with [`RootNode::try_new`](../struct.RootNode.html#method.try_new).
+
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt,
+};
+
+use crate::{
+    ast::Type,
+    schema::{
+        meta::{
+            Argument, Field, InputObjectMeta, InterfaceMeta, MetaType, ObjectMeta, PlaceholderMeta,
+        },
+        model::SchemaType,
+    },
+    types::name::Name,
+};
+
+/// An inconsistency that makes a schema invalid
+#[derive(Clone, Debug, PartialEq)]
+pub enum SchemaError {
+    /// A root operation type isn't part of the schema.
+    RootTypeNotFound {
+        /// The operation, i.e. `query`, `mutation` or `subscription`.
+        operation: &'static str,
+        /// Name of the missing type.
+        type_name: String,
+    },
+    /// A root operation type isn't an object type.
+    RootTypeNotObject {
+        /// The operation, i.e. `query`, `mutation` or `subscription`.
+        operation: &'static str,
+        /// Name of the offending type.
+        type_name: String,
+    },
+    /// A type has only been registered as a placeholder, without its metadata.
+    Placeholder(String),
+    /// A name doesn't match `/[_A-Za-z][_0-9A-Za-z]*/`.
+    InvalidName {
+        /// Dotted path to the named element, e.g. `User.friends.first`.
+        path: String,
+    },
+    /// A name starts with `__`, which is reserved for introspection.
+    ReservedName {
+        /// Dotted path to the named element, e.g. `User.friends.first`.
+        path: String,
+    },
+    /// An object claims to implement a type that isn't an interface.
+    NotAnInterface {
+        /// Name of the object type.
+        object: String,
+        /// Name of the type that isn't an interface.
+        interface: String,
+    },
+    /// An object doesn't provide a field of an interface it implements.
+    MissingInterfaceField {
+        /// Name of the object type.
+        object: String,
+        /// Name of the interface type.
+        interface: String,
+        /// Name of the missing field.
+        field: String,
+    },
+    /// The type of an object's field isn't a subtype of the interface field's type.
+    InvalidInterfaceFieldType {
+        /// Name of the object type.
+        object: String,
+        /// Name of the interface type.
+        interface: String,
+        /// Name of the field.
+        field: String,
+        /// The type of the interface field.
+        expected: String,
+        /// The type of the object field.
+        found: String,
+    },
+    /// An object's field doesn't accept an argument of the interface field.
+    MissingInterfaceArgument {
+        /// Name of the object type.
+        object: String,
+        /// Name of the interface type.
+        interface: String,
+        /// Name of the field.
+        field: String,
+        /// Name of the missing argument.
+        argument: String,
+    },
+    /// An argument has a different type than on the interface field.
+    InvalidInterfaceArgumentType {
+        /// Name of the object type.
+        object: String,
+        /// Name of the interface type.
+        interface: String,
+        /// Name of the field.
+        field: String,
+        /// Name of the argument.
+        argument: String,
+        /// The type of the interface field's argument.
+        expected: String,
+        /// The type of the object field's argument.
+        found: String,
+    },
+    /// An object's field requires an argument the interface field doesn't define.
+    RequiredArgumentNotInInterface {
+        /// Name of the object type.
+        object: String,
+        /// Name of the interface type.
+        interface: String,
+        /// Name of the field.
+        field: String,
+        /// Name of the required argument.
+        argument: String,
+    },
+    /// A union member isn't an object type.
+    InvalidUnionMember {
+        /// Name of the union type.
+        union: String,
+        /// Name of the member type.
+        member: String,
+    },
+    /// Input objects reference each other through non-null fields, so no
+    /// finite value can be provided for them.
+    InputObjectCycle {
+        /// The fields forming the cycle, e.g. `["A.b", "B.a"]`.
+        fields: Vec<String>,
+    },
+    /// A field has a type that can't be used in output positions.
+    NotAnOutputType {
+        /// Dotted path to the field.
+        path: String,
+        /// Name of the offending type.
+        type_name: String,
+    },
+    /// An argument or input field has a type that can't be used in input positions.
+    NotAnInputType {
+        /// Dotted path to the argument or input field.
+        path: String,
+        /// Name of the offending type.
+        type_name: String,
+    },
+    /// The schema has visibility predicates, but wasn't built with one of the
+    /// `RootNode::*_with_visibility` constructors evaluating them.
+    VisibilityNotEnabled,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SchemaError::RootTypeNotFound {
+                operation,
+                type_name,
+            } => write!(f, "Root {} type {} not found", operation, type_name),
+            SchemaError::RootTypeNotObject {
+                operation,
+                type_name,
+            } => write!(f, "Root {} type {} is not an object", operation, type_name),
+            SchemaError::Placeholder(name) => {
+                write!(f, "Type {} is still a placeholder type", name)
+            }
+            SchemaError::InvalidName { path } => {
+                write!(f, "Name of {} must match /^[_a-zA-Z][_a-zA-Z0-9]*$/", path,)
+            }
+            SchemaError::ReservedName { path } => write!(
+                f,
+                "Name of {} must not begin with \"__\", which is reserved by GraphQL introspection",
+                path,
+            ),
+            SchemaError::NotAnInterface { object, interface } => write!(
+                f,
+                "Type {} can only implement interfaces, but {} is not an interface",
+                object, interface,
+            ),
+            SchemaError::MissingInterfaceField {
+                object,
+                interface,
+                field,
+            } => write!(
+                f,
+                "Interface field {}.{} expected but {} does not provide it",
+                interface, field, object,
+            ),
+            SchemaError::InvalidInterfaceFieldType {
+                object,
+                interface,
+                field,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Interface field {}.{} expects type {} but {}.{} is type {}",
+                interface, field, expected, object, field, found,
+            ),
+            SchemaError::MissingInterfaceArgument {
+                object,
+                interface,
+                field,
+                argument,
+            } => write!(
+                f,
+                "Interface field argument {}.{}({}:) expected but {}.{} does not provide it",
+                interface, field, argument, object, field,
+            ),
+            SchemaError::InvalidInterfaceArgumentType {
+                object,
+                interface,
+                field,
+                argument,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Interface field argument {}.{}({}:) expects type {} but {}.{}({}:) is type {}",
+                interface, field, argument, expected, object, field, argument, found,
+            ),
+            SchemaError::RequiredArgumentNotInInterface {
+                object,
+                interface,
+                field,
+                argument,
+            } => write!(
+                f,
+                "Object field {}.{} includes required argument {} that is missing from \
+                 the interface field {}.{}",
+                object, field, argument, interface, field,
+            ),
+            SchemaError::InvalidUnionMember { union, member } => write!(
+                f,
+                "Union type {} can only include object types, it cannot include {}",
+                union, member,
+            ),
+            SchemaError::InputObjectCycle { fields } => write!(
+                f,
+                "Cannot reference input object within itself through a series of \
+                 non-null fields: {}",
+                fields.join(", "),
+            ),
+            SchemaError::NotAnOutputType { path, type_name } => write!(
+                f,
+                "The type of {} must be an output type but got {}",
+                path, type_name,
+            ),
+            SchemaError::NotAnInputType { path, type_name } => write!(
+                f,
+                "The type of {} must be an input type but got {}",
+                path, type_name,
+            ),
+            SchemaError::VisibilityNotEnabled => write!(
+                f,
+                "The schema has visibility predicates, build it with \
+                 `RootNode::new_with_visibility` to evaluate them",
+            ),
+        }
+    }
+}
+
+impl Error for SchemaError {}
+
+/// Types defined by juniper for introspection, which may use reserved names.
+const INTROSPECTION_TYPES: &[&str] = &[
+    "__Schema",
+    "__Type",
+    "__TypeKind",
+    "__Field",
+    "__InputValue",
+    "__EnumValue",
+    "__Directive",
+    "__DirectiveLocation",
+];
+
+/// Fields injected by juniper, which may use reserved names.
+const INTROSPECTION_FIELDS: &[&str] = &["__typename", "__schema", "__type"];
+
+/// Errors which make a schema unusable, even for lenient construction.
+pub(crate) fn validate_structure<S>(schema: &SchemaType<S>) -> Vec<SchemaError> {
+    let mut errors = vec![];
+
+    let roots = [
+        ("query", Some(&schema.query_type_name)),
+        ("mutation", schema.mutation_type_name.as_ref()),
+        ("subscription", schema.subscription_type_name.as_ref()),
+    ];
+    for &(operation, name) in &roots {
+        if let Some(name) = name {
+            match schema.types.get(name.as_str()) {
+                Some(MetaType::Object(_)) => {}
+                Some(_) => errors.push(SchemaError::RootTypeNotObject {
+                    operation,
+                    type_name: name.clone(),
+                }),
+                None => errors.push(SchemaError::RootTypeNotFound {
+                    operation,
+                    type_name: name.clone(),
+                }),
+            }
+        }
+    }
+
+    for name in sorted_type_names(schema) {
+        match schema.types[name] {
+            MetaType::Placeholder(PlaceholderMeta { ref of_type }) => errors.push(
+                SchemaError::Placeholder(of_type.innermost_name().to_owned()),
+            ),
+            _ if !Name::is_valid(name) => errors.push(SchemaError::InvalidName {
+                path: name.to_owned(),
+            }),
+            _ => {}
+        }
+    }
+
+    errors
+}
+
+/// Checks the whole schema and returns all the inconsistencies found.
+pub(crate) fn validate_schema<S>(schema: &SchemaType<S>) -> Vec<SchemaError> {
+    let mut errors = validate_structure(schema);
+
+    for name in sorted_type_names(schema) {
+        let meta = &schema.types[name];
+        let is_introspection_type = INTROSPECTION_TYPES.contains(&name);
+
+        if !is_introspection_type && name.starts_with("__") {
+            errors.push(SchemaError::ReservedName {
+                path: name.to_owned(),
+            });
+        }
+
+        match meta {
+            MetaType::Object(ObjectMeta { fields, .. })
+            | MetaType::Interface(InterfaceMeta { fields, .. }) => {
+                for field in fields {
+                    let path = format!("{}.{}", name, field.name);
+                    if !is_introspection_type && !INTROSPECTION_FIELDS.contains(&&*field.name) {
+                        check_name(&mut errors, &field.name, &path);
+                    }
+                    if let Some(t) = schema.types.get(field.field_type.innermost_name()) {
+                        if t.is_input() && !t.is_leaf() {
+                            errors.push(SchemaError::NotAnOutputType {
+                                path: path.clone(),
+                                type_name: field.field_type.to_string(),
+                            });
+                        }
+                    }
+                    for arg in field.arguments.iter().flatten() {
+                        check_input_value(&mut errors, schema, arg, &path);
+                    }
+                }
+            }
+            MetaType::InputObject(InputObjectMeta { input_fields, .. }) => {
+                for field in input_fields {
+                    check_input_value(&mut errors, schema, field, name);
+                }
+            }
+            MetaType::Enum(e) => {
+                for value in &e.values {
+                    let path = format!("{}.{}", name, value.name);
+                    if !is_introspection_type {
+                        check_name(&mut errors, &value.name, &path);
+                    }
+                    if value.name == "true" || value.name == "false" || value.name == "null" {
+                        errors.push(SchemaError::InvalidName { path });
+                    }
+                }
+            }
+            MetaType::Union(u) => {
+                for member in &u.of_type_names {
+                    match schema.types.get(member.as_str()) {
+                        Some(MetaType::Object(_)) => {}
+                        _ => errors.push(SchemaError::InvalidUnionMember {
+                            union: name.to_owned(),
+                            member: member.clone(),
+                        }),
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if let MetaType::Object(object) = meta {
+            check_interfaces(&mut errors, schema, object);
+        }
+    }
+
+    check_input_object_cycles(&mut errors, schema);
+
+    for directive in schema.directive_list() {
+        let path = format!("@{}", directive.name);
+        check_name(&mut errors, &directive.name, &path);
+        for arg in &directive.arguments {
+            check_input_value(&mut errors, schema, arg, &path);
+        }
+    }
+
+    errors
+}
+
+fn check_name(errors: &mut Vec<SchemaError>, name: &str, path: &str) {
+    if !Name::is_valid(name) {
+        errors.push(SchemaError::InvalidName {
+            path: path.to_owned(),
+        });
+    } else if name.starts_with("__") {
+        errors.push(SchemaError::ReservedName {
+            path: path.to_owned(),
+        });
+    }
+}
+
+fn check_input_value<S>(
+    errors: &mut Vec<SchemaError>,
+    schema: &SchemaType<S>,
+    arg: &Argument<S>,
+    parent_path: &str,
+) {
+    let path = format!("{}.{}", parent_path, arg.name);
+    if !parent_path.starts_with("__") {
+        check_name(errors, &arg.name, &path);
+    }
+    if let Some(t) = schema.types.get(arg.arg_type.innermost_name()) {
+        if !t.is_input() {
+            errors.push(SchemaError::NotAnInputType {
+                path,
+                type_name: arg.arg_type.to_string(),
+            });
+        }
+    }
+}
+
+fn check_interfaces<S>(
+    errors: &mut Vec<SchemaError>,
+    schema: &SchemaType<S>,
+    object: &ObjectMeta<S>,
+) {
+    for interface_name in &object.interface_names {
+        let interface = match schema.types.get(interface_name.as_str()) {
+            Some(MetaType::Interface(i)) => i,
+            _ => {
+                errors.push(SchemaError::NotAnInterface {
+                    object: object.name.to_string(),
+                    interface: interface_name.clone(),
+                });
+                continue;
+            }
+        };
+
+        for iface_field in &interface.fields {
+            let field = match object.fields.iter().find(|f| f.name == iface_field.name) {
+                Some(f) => f,
+                None => {
+                    errors.push(SchemaError::MissingInterfaceField {
+                        object: object.name.to_string(),
+                        interface: interface_name.clone(),
+                        field: iface_field.name.clone(),
+                    });
+                    continue;
+                }
+            };
+
+            if !schema.is_subtype(&field.field_type, &iface_field.field_type) {
+                errors.push(SchemaError::InvalidInterfaceFieldType {
+                    object: object.name.to_string(),
+                    interface: interface_name.clone(),
+                    field: field.name.clone(),
+                    expected: iface_field.field_type.to_string(),
+                    found: field.field_type.to_string(),
+                });
+            }
+
+            check_interface_arguments(errors, &object.name, interface_name, field, iface_field);
+        }
+    }
+}
+
+fn check_interface_arguments<S>(
+    errors: &mut Vec<SchemaError>,
+    object: &str,
+    interface: &str,
+    field: &Field<S>,
+    iface_field: &Field<S>,
+) {
+    let args = field.arguments.as_deref().unwrap_or(&[]);
+    let iface_args = iface_field.arguments.as_deref().unwrap_or(&[]);
+
+    for iface_arg in iface_args {
+        match args.iter().find(|a| a.name == iface_arg.name) {
+            None => errors.push(SchemaError::MissingInterfaceArgument {
+                object: object.to_owned(),
+                interface: interface.to_owned(),
+                field: field.name.clone(),
+                argument: iface_arg.name.clone(),
+            }),
+            Some(arg) if arg.arg_type != iface_arg.arg_type => {
+                errors.push(SchemaError::InvalidInterfaceArgumentType {
+                    object: object.to_owned(),
+                    interface: interface.to_owned(),
+                    field: field.name.clone(),
+                    argument: arg.name.clone(),
+                    expected: iface_arg.arg_type.to_string(),
+                    found: arg.arg_type.to_string(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    for arg in args {
+        let is_required = arg.arg_type.is_non_null() && arg.default_value.is_none();
+        if is_required && !iface_args.iter().any(|a| a.name == arg.name) {
+            errors.push(SchemaError::RequiredArgumentNotInInterface {
+                object: object.to_owned(),
+                interface: interface.to_owned(),
+                field: field.name.clone(),
+                argument: arg.name.clone(),
+            });
+        }
+    }
+}
+
+fn check_input_object_cycles<S>(errors: &mut Vec<SchemaError>, schema: &SchemaType<S>) {
+    struct CycleDetector<'s, S> {
+        schema: &'s SchemaType<'s, S>,
+        visited: HashSet<&'s str>,
+        field_path: Vec<String>,
+        path_index: HashMap<&'s str, usize>,
+        errors: Vec<SchemaError>,
+    }
+
+    impl<'s, S> CycleDetector<'s, S> {
+        fn detect(&mut self, name: &'s str) {
+            if !self.visited.insert(name) {
+                return;
+            }
+            let input_fields = match self.schema.types.get(name) {
+                Some(MetaType::InputObject(InputObjectMeta { input_fields, .. })) => input_fields,
+                _ => return,
+            };
+
+            self.path_index.insert(name, self.field_path.len());
+            for field in input_fields {
+                // Lists and nullable fields can always be satisfied by an empty value.
+                let field_type_name = match field.arg_type {
+                    Type::NonNullNamed(ref n) => n.as_ref(),
+                    _ => continue,
+                };
+                let field_type_name = match self.schema.types.get_key_value(field_type_name) {
+                    Some((key, MetaType::InputObject(_))) => {
+                        let key: &'s str = std::borrow::Borrow::borrow(key);
+                        key
+                    }
+                    _ => continue,
+                };
+
+                self.field_path.push(format!("{}.{}", name, field.name));
+                match self.path_index.get(field_type_name) {
+                    None => self.detect(field_type_name),
+                    Some(&start) => self.errors.push(SchemaError::InputObjectCycle {
+                        fields: self.field_path[start..].to_vec(),
+                    }),
+                }
+                self.field_path.pop();
+            }
+            self.path_index.remove(name);
+        }
+    }
+
+    let mut detector = CycleDetector {
+        schema,
+        visited: HashSet::new(),
+        field_path: vec![],
+        path_index: HashMap::new(),
+        errors: vec![],
+    };
+    for name in sorted_type_names(schema) {
+        detector.detect(name);
+    }
+    errors.append(&mut detector.errors);
+}
+
+fn sorted_type_names<'s, S>(schema: &'s SchemaType<S>) -> Vec<&'s str> {
+    let mut names = schema
+        .types
+        .keys()
+        .map(std::borrow::Borrow::borrow)
+        .collect::<Vec<&str>>();
+    names.sort_unstable();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::{
+        ast::Type,
+        schema::{
+            meta::{
+                Argument, DeprecationStatus, EnumMeta, EnumValue, Field, InputObjectMeta,
+                InterfaceMeta, MetaType, ObjectMeta, UnionMeta,
+            },
+            model::{RootNode, SchemaType},
+        },
+        tests::fixtures::starwars::schema::{Database, Query},
+        types::scalars::{EmptyMutation, EmptySubscription},
+        value::DefaultScalarValue,
+    };
+
+    use super::{validate_schema, SchemaError};
+
+    fn named(name: &'static str) -> Type<'static> {
+        Type::Named(Cow::Borrowed(name))
+    }
+
+    fn non_null(name: &'static str) -> Type<'static> {
+        Type::NonNullNamed(Cow::Borrowed(name))
+    }
+
+    fn field(name: &str, field_type: Type<'static>) -> Field<'static, DefaultScalarValue> {
+        Field {
+            name: name.to_owned(),
+            description: None,
+            arguments: None,
+            field_type,
+            deprecation_status: DeprecationStatus::Current,
+            visibility: None,
+            cache_control: None,
+        }
+    }
+
+    /// Builds the Star Wars schema extended with the given types.
+    fn errors_with(types: Vec<MetaType<'static, DefaultScalarValue>>) -> Vec<SchemaError> {
+        let mut schema = SchemaType::new::<
+            Query,
+            EmptyMutation<Database>,
+            EmptySubscription<Database>,
+        >(&(), &(), &());
+        for meta in types {
+            let name = meta.name().unwrap().parse().unwrap();
+            schema.types.insert(name, meta);
+        }
+        validate_schema(&schema)
+    }
+
+    #[test]
+    fn valid_schema() {
+        let schema = RootNode::try_new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+
+        assert!(schema.is_ok());
+    }
+
+    #[test]
+    fn interface_implementations() {
+        let named_iface = MetaType::Interface(InterfaceMeta::new(
+            "Named".into(),
+            &[
+                field("name", non_null("String")),
+                field("friends", named("Named")).argument(Argument::new("first", named("Int"))),
+            ],
+        ));
+        let cat = MetaType::Object(
+            ObjectMeta::new("Cat".into(), &[field("name", named("String"))])
+                .interfaces(&[non_null("Named")]),
+        );
+        let dog = MetaType::Object(
+            ObjectMeta::new(
+                "Dog".into(),
+                &[
+                    field("name", non_null("String")),
+                    field("friends", non_null("Dog"))
+                        .argument(Argument::new("first", non_null("Int")))
+                        .argument(Argument::new("after", non_null("String"))),
+                ],
+            )
+            .interfaces(&[non_null("Named"), non_null("Episode")]),
+        );
+
+        assert_eq!(
+            errors_with(vec![named_iface, cat, dog]),
+            vec![
+                SchemaError::InvalidInterfaceFieldType {
+                    object: "Cat".into(),
+                    interface: "Named".into(),
+                    field: "name".into(),
+                    expected: "String!".into(),
+                    found: "String".into(),
+                },
+                SchemaError::MissingInterfaceField {
+                    object: "Cat".into(),
+                    interface: "Named".into(),
+                    field: "friends".into(),
+                },
+                SchemaError::InvalidInterfaceArgumentType {
+                    object: "Dog".into(),
+                    interface: "Named".into(),
+                    field: "friends".into(),
+                    argument: "first".into(),
+                    expected: "Int".into(),
+                    found: "Int!".into(),
+                },
+                SchemaError::RequiredArgumentNotInInterface {
+                    object: "Dog".into(),
+                    interface: "Named".into(),
+                    field: "friends".into(),
+                    argument: "after".into(),
+                },
+                SchemaError::NotAnInterface {
+                    object: "Dog".into(),
+                    interface: "Episode".into(),
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn union_members_must_be_objects() {
+        let union = MetaType::Union(UnionMeta::new(
+            "Thing".into(),
+            &[non_null("Human"), non_null("Character")],
+        ));
+
+        assert_eq!(
+            errors_with(vec![union]),
+            vec![SchemaError::InvalidUnionMember {
+                union: "Thing".into(),
+                member: "Character".into(),
+            }],
+        );
+    }
+
+    #[test]
+    fn input_object_cycles() {
+        let a = MetaType::InputObject(InputObjectMeta::new::<i32>(
+            "A".into(),
+            &[Argument::new("b", non_null("B"))],
+        ));
+        let b = MetaType::InputObject(InputObjectMeta::new::<i32>(
+            "B".into(),
+            &[
+                Argument::new("a", non_null("A")),
+                Argument::new("self", named("B")),
+            ],
+        ));
+        let c = MetaType::InputObject(InputObjectMeta::new::<i32>(
+            "C".into(),
+            &[Argument::new(
+                "list",
+                Type::NonNullList(Box::new(non_null("C"))),
+            )],
+        ));
+
+        assert_eq!(
+            errors_with(vec![a, b, c]),
+            vec![SchemaError::InputObjectCycle {
+                fields: vec!["A.b".into(), "B.a".into()],
+            }],
+        );
+    }
+
+    #[test]
+    fn names() {
+        let object = MetaType::Object(ObjectMeta::new(
+            "Robot".into(),
+            &[field("__secret", named("String"))],
+        ));
+        let enumeration = MetaType::Enum(EnumMeta::new::<i32>(
+            "Answer".into(),
+            &[EnumValue::new("YES"), EnumValue::new("null")],
+        ));
+
+        assert_eq!(
+            errors_with(vec![object, enumeration]),
+            vec![
+                SchemaError::InvalidName {
+                    path: "Answer.null".into(),
+                },
+                SchemaError::ReservedName {
+                    path: "Robot.__secret".into(),
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn input_and_output_positions() {
+        let object = MetaType::Object(ObjectMeta::new(
+            "Filtered".into(),
+            &[field("filter", named("Filter")).argument(Argument::new("by", named("Human")))],
+        ));
+        let input = MetaType::InputObject(InputObjectMeta::new::<i32>(
+            "Filter".into(),
+            &[Argument::new("name", named("String"))],
+        ));
+
+        assert_eq!(
+            errors_with(vec![object, input]),
+            vec![
+                SchemaError::NotAnOutputType {
+                    path: "Filtered.filter".into(),
+                    type_name: "Filter".into(),
+                },
+                SchemaError::NotAnInputType {
+                    path: "Filtered.filter.by".into(),
+                    type_name: "Human".into(),
+                },
+            ],
+        );
+    }
+}
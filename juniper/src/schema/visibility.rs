@@ -0,0 +1,605 @@
+use std::{any::Any, borrow::Cow, collections::HashSet, sync::Arc};
+
+use crate::{
+    ast::InputValue,
+    executor::FieldError,
+    schema::{
+        meta::{
+            Argument, EnumMeta, EnumParseFn, Field, InputObjectMeta, InterfaceMeta, MetaType,
+            ObjectMeta, UnionMeta, VisibilityFn,
+        },
+        model::SchemaType,
+    },
+    value::Value,
+};
+
+/// Above this number of distinct visibility predicates, the views of a schema
+/// are filtered for each request instead of being computed upfront
+const MAX_CACHED_PREDICATES: usize = 8;
+
+/// The views of a schema for each combination of the results of its
+/// visibility predicates
+#[derive(Debug)]
+pub(crate) struct VisibleSchemas<'a, S> {
+    predicates: Vec<VisibilityFn>,
+    /// The index in `views` for each combination, `None` if nothing is hidden
+    ///
+    /// Empty until `compute()` is called.
+    combinations: Vec<Option<usize>>,
+    views: Vec<SchemaType<'a, S>>,
+}
+
+impl<'a, S: Clone + 'a> VisibleSchemas<'a, S> {
+    pub(crate) fn new(schema: &SchemaType<'a, S>) -> Self {
+        let mut predicates: Vec<VisibilityFn> = vec![];
+        for predicate in schema.visibility_predicates() {
+            if !predicates.iter().any(|p| *p as usize == predicate as usize) {
+                predicates.push(predicate);
+            }
+        }
+
+        VisibleSchemas {
+            predicates,
+            combinations: vec![],
+            views: vec![],
+        }
+    }
+
+    /// Whether the schema has any visibility predicate
+    pub(crate) fn has_predicates(&self) -> bool {
+        !self.predicates.is_empty()
+    }
+
+    /// Filters the schema upfront for every combination of predicate results
+    pub(crate) fn compute(&mut self, schema: &SchemaType<'a, S>) {
+        if self.predicates.len() > MAX_CACHED_PREDICATES {
+            return;
+        }
+
+        let mut hiddens: Vec<Hidden> = vec![];
+        for combination in 0..1usize << self.predicates.len() {
+            let hidden = Hidden::new(schema, |predicate| {
+                combination & 1 << self.index_of(predicate) != 0
+            });
+            let index = if hidden.is_empty() {
+                None
+            } else if let Some(index) = hiddens.iter().position(|h| *h == hidden) {
+                Some(index)
+            } else {
+                self.views.push(hidden.apply(schema));
+                hiddens.push(hidden);
+                Some(self.views.len() - 1)
+            };
+            self.combinations.push(index);
+        }
+    }
+
+    /// The schema as seen by a request with the given context
+    ///
+    /// `RootNode` refuses schemas having visibility predicates without a
+    /// context to give them, so there is nothing to hide without one.
+    pub(crate) fn get<'s>(
+        &'s self,
+        schema: &'s SchemaType<'a, S>,
+        context: Option<&dyn Any>,
+    ) -> Cow<'s, SchemaType<'a, S>> {
+        let context = match context {
+            Some(context) if self.has_predicates() => context,
+            _ => return Cow::Borrowed(schema),
+        };
+
+        if self.combinations.is_empty() {
+            let hidden = Hidden::new(schema, |predicate| predicate(context));
+            return if hidden.is_empty() {
+                Cow::Borrowed(schema)
+            } else {
+                Cow::Owned(hidden.apply(schema))
+            };
+        }
+        let combination = self
+            .predicates
+            .iter()
+            .enumerate()
+            .filter(|(_, predicate)| predicate(context))
+            .fold(0, |combination, (i, _)| combination | 1 << i);
+        match self.combinations[combination] {
+            Some(index) => Cow::Borrowed(&self.views[index]),
+            None => Cow::Borrowed(schema),
+        }
+    }
+
+    fn index_of(&self, predicate: VisibilityFn) -> usize {
+        self.predicates
+            .iter()
+            .position(|p| *p as usize == predicate as usize)
+            .unwrap_or_default()
+    }
+}
+
+impl<'a, S> SchemaType<'a, S> {
+    /// The visibility predicates of the schema, possibly repeated
+    fn visibility_predicates(&self) -> impl Iterator<Item = VisibilityFn> + '_ {
+        self.types.values().flat_map(|meta| {
+            let nested: Vec<_> = match meta {
+                MetaType::Object(ObjectMeta { fields, .. })
+                | MetaType::Interface(InterfaceMeta { fields, .. }) => {
+                    fields.iter().map(|f| f.visibility).collect()
+                }
+                MetaType::Enum(EnumMeta { values, .. }) => {
+                    values.iter().map(|v| v.visibility).collect()
+                }
+                _ => vec![],
+            };
+            std::iter::once(meta.visibility()).chain(nested).flatten()
+        })
+    }
+}
+
+/// The parts of a schema hidden from a request
+#[derive(PartialEq)]
+struct Hidden<'s> {
+    types: HashSet<&'s str>,
+    fields: HashSet<(&'s str, &'s str)>,
+    values: HashSet<(&'s str, &'s str)>,
+}
+
+impl<'s> Hidden<'s> {
+    /// The parts hidden when the given predicates are satisfied, and
+    /// everything they reference
+    fn new<S>(schema: &'s SchemaType<S>, is_satisfied: impl Fn(VisibilityFn) -> bool) -> Self {
+        let is_visible = |predicate: Option<VisibilityFn>| predicate.map_or(true, &is_satisfied);
+
+        let mut hidden = Hidden {
+            types: HashSet::new(),
+            fields: HashSet::new(),
+            values: HashSet::new(),
+        };
+        for meta in schema.types.values() {
+            let name = match meta.name() {
+                Some(name) => name,
+                None => continue,
+            };
+            if name != schema.query_type_name && !is_visible(meta.visibility()) {
+                hidden.types.insert(name);
+            }
+            match meta {
+                MetaType::Object(ObjectMeta { fields, .. })
+                | MetaType::Interface(InterfaceMeta { fields, .. }) => {
+                    for field in fields.iter().filter(|f| !is_visible(f.visibility)) {
+                        hidden.fields.insert((name, &field.name));
+                    }
+                }
+                MetaType::Enum(EnumMeta { values, .. }) => {
+                    for value in values.iter().filter(|v| !is_visible(v.visibility)) {
+                        hidden.values.insert((name, &value.name));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if !hidden.is_empty() {
+            while hidden.propagate(schema) {}
+        }
+        hidden
+    }
+
+    /// The schema without the hidden parts
+    fn apply<'a, S: Clone + 'a>(&self, schema: &SchemaType<'a, S>) -> SchemaType<'a, S> {
+        let types = schema
+            .types
+            .iter()
+            .filter(|(_, meta)| !meta.name().map_or(false, |n| self.has_type(n)))
+            .map(|(name, meta)| (name.clone(), self.filter_type(meta)))
+            .collect();
+        let is_visible = |name: &String| !self.has_type(name);
+
+        SchemaType {
+            types,
+            query_type_name: schema.query_type_name.clone(),
+            mutation_type_name: schema.mutation_type_name.clone().filter(is_visible),
+            subscription_type_name: schema.subscription_type_name.clone().filter(is_visible),
+            directives: schema.directives.clone(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.types.is_empty() && self.fields.is_empty() && self.values.is_empty()
+    }
+
+    fn has_type(&self, name: &str) -> bool {
+        self.types.contains(name)
+    }
+
+    /// Hides the fields referencing hidden types, and the types left empty
+    ///
+    /// Returns whether anything new was hidden.
+    fn propagate<S>(&mut self, schema: &'s SchemaType<S>) -> bool {
+        let mut changed = false;
+        for meta in schema.types.values() {
+            let name = match meta.name() {
+                Some(name) if !self.has_type(name) => name,
+                _ => continue,
+            };
+            let hide_type = match meta {
+                MetaType::Object(ObjectMeta { fields, .. })
+                | MetaType::Interface(InterfaceMeta { fields, .. }) => {
+                    for field in fields {
+                        if !self.fields.contains(&(name, field.name.as_str()))
+                            && self.references_hidden_type(field)
+                        {
+                            self.fields.insert((name, &field.name));
+                            changed = true;
+                        }
+                    }
+                    name != schema.query_type_name
+                        && fields.iter().all(|f| {
+                            f.is_builtin() || self.fields.contains(&(name, f.name.as_str()))
+                        })
+                }
+                MetaType::Union(UnionMeta { of_type_names, .. }) => {
+                    of_type_names.iter().all(|n| self.has_type(n))
+                }
+                MetaType::InputObject(InputObjectMeta { input_fields, .. }) => input_fields
+                    .iter()
+                    .any(|f| self.has_type(f.arg_type.innermost_name())),
+                MetaType::Enum(EnumMeta { values, .. }) => values
+                    .iter()
+                    .all(|v| self.values.contains(&(name, v.name.as_str()))),
+                _ => false,
+            };
+            if hide_type {
+                self.types.insert(name);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    fn references_hidden_type<S>(&self, field: &Field<S>) -> bool {
+        self.has_type(field.field_type.innermost_name())
+            || field
+                .arguments
+                .iter()
+                .flatten()
+                .any(|a: &Argument<S>| self.has_type(a.arg_type.innermost_name()))
+    }
+
+    fn filter_type<'a, S: Clone + 'a>(&self, meta: &MetaType<'a, S>) -> MetaType<'a, S> {
+        let name = meta.name().unwrap_or_default();
+        let mut meta = meta.clone();
+        match &mut meta {
+            MetaType::Object(ObjectMeta {
+                fields,
+                interface_names,
+                ..
+            }) => {
+                fields.retain(|f| !self.fields.contains(&(name, f.name.as_str())));
+                interface_names.retain(|n| !self.has_type(n));
+            }
+            MetaType::Interface(InterfaceMeta { fields, .. }) => {
+                fields.retain(|f| !self.fields.contains(&(name, f.name.as_str())));
+            }
+            MetaType::Union(UnionMeta { of_type_names, .. }) => {
+                of_type_names.retain(|n| !self.has_type(n));
+            }
+            MetaType::Enum(EnumMeta {
+                values,
+                try_parse_fn,
+                ..
+            }) => {
+                let (visible, hidden): (Vec<_>, Vec<_>) = values
+                    .drain(..)
+                    .partition(|v| !self.values.contains(&(name, v.name.as_str())));
+                *values = visible;
+                if !hidden.is_empty() {
+                    *try_parse_fn = reject_values(
+                        name.to_owned(),
+                        hidden.into_iter().map(|v| v.name).collect(),
+                        try_parse_fn.clone(),
+                    );
+                }
+            }
+            _ => {}
+        }
+        meta
+    }
+}
+
+/// Rejects the given values of an enum, before parsing the others as usual
+fn reject_values<'a, S: 'a>(
+    name: String,
+    hidden: HashSet<String>,
+    try_parse_fn: EnumParseFn<'a, S>,
+) -> EnumParseFn<'a, S> {
+    Arc::new(move |value| match value {
+        InputValue::Enum(v) if hidden.contains(v) => Err(FieldError::new(
+            format!(r#"Expected "{}", found {}"#, name, v),
+            Value::Null,
+        )),
+        _ => try_parse_fn(value),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::{
+        executor::Variables,
+        schema::{model::RootNode, validation::SchemaError},
+        types::scalars::{EmptyMutation, EmptySubscription},
+        value::{DefaultScalarValue, Value},
+        GraphQLError,
+    };
+
+    struct Context {
+        internal: bool,
+    }
+
+    impl crate::Context for Context {}
+
+    struct Query;
+
+    #[crate::graphql_object(context = Context)]
+    impl Query {
+        fn public() -> i32 {
+            1
+        }
+
+        #[graphql(visible = "is_internal")]
+        fn secret() -> i32 {
+            2
+        }
+
+        fn audit() -> Audit {
+            Audit
+        }
+
+        fn access(level: Level) -> Level {
+            level
+        }
+
+        fn node() -> NodeValue {
+            Audit.into()
+        }
+
+        fn record() -> Record {
+            Record::Audit(Audit)
+        }
+    }
+
+    struct Audit;
+
+    #[crate::graphql_object(
+        context = Context,
+        visible = "is_internal",
+        interfaces = NodeValue,
+        scalar = DefaultScalarValue,
+    )]
+    impl Audit {
+        fn id() -> i32 {
+            0
+        }
+
+        fn owner() -> i32 {
+            4
+        }
+
+        fn entries() -> i32 {
+            3
+        }
+    }
+
+    #[crate::graphql_interface(for = Audit, context = Context, scalar = DefaultScalarValue)]
+    trait Node {
+        fn id(&self) -> i32;
+
+        #[graphql_interface(visible = "is_internal")]
+        fn owner(&self) -> i32;
+    }
+
+    #[crate::graphql_interface(scalar = DefaultScalarValue)]
+    impl Node for Audit {
+        fn id(&self) -> i32 {
+            0
+        }
+
+        fn owner(&self) -> i32 {
+            4
+        }
+    }
+
+    #[derive(crate::GraphQLUnion)]
+    #[graphql(context = Context, scalar = DefaultScalarValue, visible = "is_internal")]
+    enum Record {
+        Audit(Audit),
+    }
+
+    #[derive(crate::GraphQLEnum, Clone, Copy)]
+    #[graphql(context = Context)]
+    enum Level {
+        Read,
+        #[graphql(visible = "is_internal")]
+        Admin,
+    }
+
+    fn is_internal(context: &Context) -> bool {
+        context.internal
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Context>, EmptySubscription<Context>>;
+
+    fn schema() -> Schema {
+        RootNode::new_with_visibility(Query, EmptyMutation::new(), EmptySubscription::new())
+    }
+
+    fn execute(query: &str, internal: bool) -> Result<Value, Vec<String>> {
+        let schema = schema();
+        crate::execute_sync(
+            query,
+            None,
+            &schema,
+            &Variables::new(),
+            &Context { internal },
+        )
+        .map(|(value, errors)| {
+            assert_eq!(errors, vec![]);
+            value
+        })
+        .map_err(|e| match e {
+            GraphQLError::ValidationError(errors) => {
+                errors.iter().map(|e| e.message().to_owned()).collect()
+            }
+            e => panic!("Unexpected error {:?}", e),
+        })
+    }
+
+    #[test]
+    fn hides_fields_and_types_from_validation() {
+        let query = "{ public secret audit { entries } }";
+
+        assert_eq!(
+            execute(query, true),
+            Ok(graphql_value!({
+                "public": 1,
+                "secret": 2,
+                "audit": { "entries": 3 },
+            })),
+        );
+        assert_eq!(
+            execute(query, false),
+            Err(vec![
+                r#"Unknown field "secret" on type "Query""#.to_owned(),
+                r#"Unknown field "audit" on type "Query""#.to_owned(),
+            ]),
+        );
+    }
+
+    #[test]
+    fn hides_enum_values_from_validation() {
+        let query = "{ access(level: ADMIN) }";
+
+        assert_eq!(
+            execute(query, true),
+            Ok(graphql_value!({ "access": "ADMIN" })),
+        );
+        assert!(execute(query, false).is_err());
+        assert_eq!(
+            execute("{ access(level: READ) }", false),
+            Ok(graphql_value!({ "access": "READ" })),
+        );
+    }
+
+    #[test]
+    fn hides_from_introspection() {
+        let query = r#"{
+            query: __type(name: "Query") { fields { name } }
+            audit: __type(name: "Audit") { name }
+            level: __type(name: "Level") { enumValues { name } }
+        }"#;
+
+        assert_eq!(
+            execute(query, false),
+            Ok(graphql_value!({
+                "query": { "fields": [
+                    { "name": "public" },
+                    { "name": "access" },
+                    { "name": "node" },
+                ] },
+                "audit": None,
+                "level": { "enumValues": [{ "name": "READ" }] },
+            })),
+        );
+        assert_eq!(
+            execute(query, true),
+            Ok(graphql_value!({
+                "query": { "fields": [
+                    { "name": "public" },
+                    { "name": "secret" },
+                    { "name": "audit" },
+                    { "name": "access" },
+                    { "name": "node" },
+                    { "name": "record" },
+                ] },
+                "audit": { "name": "Audit" },
+                "level": { "enumValues": [{ "name": "READ" }, { "name": "ADMIN" }] },
+            })),
+        );
+    }
+
+    #[test]
+    fn requires_enabling_visibility() {
+        let schema = RootNode::try_new(
+            Query,
+            EmptyMutation::<Context>::new(),
+            EmptySubscription::<Context>::new(),
+        );
+
+        assert_eq!(schema.err(), Some(vec![SchemaError::VisibilityNotEnabled]));
+    }
+
+    #[test]
+    #[should_panic(expected = "build it with `RootNode::new_with_visibility`")]
+    fn panics_without_enabling_visibility() {
+        RootNode::new(
+            Query,
+            EmptyMutation::<Context>::new(),
+            EmptySubscription::<Context>::new(),
+        );
+    }
+
+    #[test]
+    fn computes_views_once() {
+        let schema = schema();
+        let public = schema.visible_schema(&Context { internal: false });
+        let internal = schema.visible_schema(&Context { internal: true });
+
+        assert!(matches!(public, Cow::Borrowed(_)));
+        assert!(matches!(internal, Cow::Borrowed(s) if std::ptr::eq(s, &schema.schema)));
+        assert!(public.concrete_type_by_name("Audit").is_none());
+        assert!(std::ptr::eq(
+            &*public,
+            &*schema.visible_schema(&Context { internal: false }),
+        ));
+    }
+
+    #[test]
+    fn hides_interfaces_and_unions() {
+        let query = r#"{
+            node: __type(name: "Node") { fields { name } possibleTypes { name } }
+            record: __type(name: "Record") { name }
+        }"#;
+
+        assert_eq!(
+            execute(query, false),
+            Ok(graphql_value!({
+                "node": { "fields": [{ "name": "id" }], "possibleTypes": [] },
+                "record": None,
+            })),
+        );
+        assert_eq!(
+            execute("{ node { owner } }", true),
+            Ok(graphql_value!({ "node": { "owner": 4 } })),
+        );
+        assert_eq!(
+            execute("{ node { owner } }", false),
+            Err(vec![r#"Unknown field "owner" on type "Node""#.to_owned()]),
+        );
+    }
+
+    #[cfg(feature = "schema-language")]
+    #[test]
+    fn hides_from_schema_language() {
+        let schema = schema();
+        let public = schema.as_schema_language_for(&Context { internal: false });
+        let internal = schema.as_schema_language_for(&Context { internal: true });
+
+        assert!(!public.contains("Audit"));
+        assert!(!public.contains("secret"));
+        assert!(!public.contains("ADMIN"));
+        assert!(internal.contains("type Audit"));
+        assert!(internal.contains("secret: Int!"));
+        assert!(internal.contains("ADMIN"));
+    }
+}
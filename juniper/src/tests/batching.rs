@@ -0,0 +1,153 @@
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use serde_json::{json, Value as Json};
+
+use crate::{
+    http::{BatchLimits, GraphQLBatchRequest, HttpConfig, ResponseContentType},
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    Context,
+};
+
+/// Keeps track of the operations running at once
+#[derive(Default)]
+struct Tracker {
+    running: AtomicUsize,
+    max_running: AtomicUsize,
+}
+
+impl Context for Tracker {}
+
+struct Query;
+
+#[crate::graphql_object(context = Tracker)]
+impl Query {
+    async fn echo(n: i32, context: &Tracker) -> i32 {
+        let running = context.running.fetch_add(1, Ordering::SeqCst) + 1;
+        context.max_running.fetch_max(running, Ordering::SeqCst);
+        tokio::time::delay_for(Duration::from_millis(10)).await;
+        context.running.fetch_sub(1, Ordering::SeqCst);
+        n
+    }
+}
+
+type Schema = RootNode<'static, Query, EmptyMutation<Tracker>, EmptySubscription<Tracker>>;
+
+fn schema() -> Schema {
+    RootNode::new(
+        Query,
+        EmptyMutation::<Tracker>::new(),
+        EmptySubscription::<Tracker>::new(),
+    )
+}
+
+fn config(limits: BatchLimits) -> HttpConfig<Tracker> {
+    HttpConfig::new().limit_batches(limits)
+}
+
+fn batch(size: i32) -> GraphQLBatchRequest {
+    let requests: Vec<_> = (1..=size)
+        .map(|n| json!({ "query": format!("{{ echo(n: {}) }}", n) }))
+        .collect();
+    serde_json::from_value(Json::Array(requests)).expect("Invalid batch")
+}
+
+/// Executes a batch of `size` operations, returning the response and the
+/// maximum number of operations that ran at once
+async fn execute(config: &HttpConfig<Tracker>, size: i32) -> (Json, u16, usize) {
+    let schema = schema();
+    let tracker = Tracker::default();
+    let request = batch(size);
+    let response = request.execute_with_config(&schema, &tracker, config).await;
+    (
+        serde_json::to_value(&response).unwrap(),
+        response.status_code(ResponseContentType::Json),
+        tracker.max_running.load(Ordering::SeqCst),
+    )
+}
+
+fn echoes(size: i32) -> Json {
+    (1..=size).map(|n| json!({"data": {"echo": n}})).collect()
+}
+
+#[tokio::test]
+async fn executes_all_operations_concurrently_by_default() {
+    let (response, status, max_running) = execute(&config(BatchLimits::new()), 4).await;
+
+    assert_eq!(response, echoes(4));
+    assert_eq!(status, 200);
+    assert_eq!(max_running, 4);
+}
+
+#[tokio::test]
+async fn bounds_concurrency() {
+    let config = config(BatchLimits::new().max_concurrency(2));
+
+    let (response, _, max_running) = execute(&config, 5).await;
+
+    assert_eq!(response, echoes(5));
+    assert_eq!(max_running, 2);
+}
+
+#[tokio::test]
+async fn executes_sequentially() {
+    let config = config(BatchLimits::new().sequential());
+
+    let (response, _, max_running) = execute(&config, 3).await;
+
+    assert_eq!(response, echoes(3));
+    assert_eq!(max_running, 1);
+}
+
+#[tokio::test]
+async fn rejects_oversized_batches() {
+    let config = config(BatchLimits::new().max_size(2));
+
+    let (response, status, max_running) = execute(&config, 3).await;
+    assert_eq!(
+        response,
+        json!({"errors": [{
+            "message": "Batch of 3 operations exceeds the maximum of 2",
+            "locations": [],
+        }]}),
+    );
+    assert_eq!(status, 400);
+    assert_eq!(max_running, 0);
+
+    let (response, status, _) = execute(&config, 2).await;
+    assert_eq!(response, echoes(2));
+    assert_eq!(status, 200);
+}
+
+#[test]
+fn rejects_oversized_batches_synchronously() {
+    let (schema, config) = (schema(), config(BatchLimits::new().max_size(2)));
+    let tracker = Tracker::default();
+
+    let request = batch(3);
+    let response = request.execute_sync_with_config(&schema, &tracker, &config);
+
+    assert_eq!(response.status_code(ResponseContentType::Json), 400);
+    assert_eq!(tracker.max_running.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn does_not_limit_single_requests() {
+    let (schema, config) = (schema(), config(BatchLimits::new().max_size(0)));
+    let request: GraphQLBatchRequest =
+        serde_json::from_value(json!({"query": "{ echo(n: 1) }"})).unwrap();
+
+    let tracker = Tracker::default();
+
+    let response = request
+        .execute_with_config(&schema, &tracker, &config)
+        .await;
+
+    assert_eq!(
+        serde_json::to_value(&response).unwrap(),
+        json!({"data": {"echo": 1}}),
+    );
+}
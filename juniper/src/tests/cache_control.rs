@@ -0,0 +1,220 @@
+use serde_json::{json, Value as Json};
+
+use crate::{
+    executor::{CacheHint, CachePolicy, CacheScope},
+    http::{GraphQLBatchRequest, GraphQLRequest},
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    DefaultScalarValue, FieldResult,
+};
+
+struct Query;
+
+#[crate::graphql_object]
+impl Query {
+    #[graphql(cache_control(max_age = 60))]
+    fn count() -> i32 {
+        1
+    }
+
+    fn uncached() -> i32 {
+        2
+    }
+
+    fn book() -> Book {
+        Book
+    }
+
+    #[graphql(cache_control(max_age = 120))]
+    fn bestseller() -> Book {
+        Book
+    }
+
+    #[graphql(cache_control(max_age = 60))]
+    fn node() -> NodeValue {
+        Book.into()
+    }
+
+    #[graphql(cache_control(max_age = 60))]
+    fn search() -> SearchResult {
+        SearchResult::Book(Book)
+    }
+
+    #[graphql(cache_control(max_age = 60))]
+    fn dynamic(executor: &Executor) -> i32 {
+        executor.set_cache_hint(CacheHint {
+            max_age: Some(5),
+            scope: None,
+        });
+        3
+    }
+
+    #[graphql(cache_control(max_age = 60))]
+    fn failing() -> FieldResult<i32> {
+        Err("Failed".into())
+    }
+}
+
+struct Book;
+
+#[crate::graphql_object(
+    cache_control(max_age = 30),
+    interfaces = NodeValue,
+    scalar = DefaultScalarValue,
+)]
+impl Book {
+    fn id() -> i32 {
+        1
+    }
+
+    fn title() -> &str {
+        "Juniper"
+    }
+
+    #[graphql(cache_control(scope = "PRIVATE"))]
+    fn bookmarked() -> bool {
+        true
+    }
+
+    fn author() -> Author {
+        Author
+    }
+}
+
+struct Author;
+
+#[crate::graphql_object]
+impl Author {
+    fn name() -> &str {
+        "Ferris"
+    }
+}
+
+#[crate::graphql_interface(for = Book, scalar = DefaultScalarValue)]
+trait Node {
+    #[graphql_interface(cache_control(max_age = 10))]
+    fn id(&self) -> i32;
+}
+
+#[crate::graphql_interface(scalar = DefaultScalarValue)]
+impl Node for Book {
+    fn id(&self) -> i32 {
+        1
+    }
+}
+
+#[derive(crate::GraphQLUnion)]
+#[graphql(cache_control(max_age = 90, scope = "PRIVATE"), scalar = DefaultScalarValue)]
+enum SearchResult {
+    Book(Book),
+}
+
+type Schema = RootNode<'static, Query, EmptyMutation<()>, EmptySubscription<()>>;
+
+fn schema() -> Schema {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+fn cache_policy(query: &str) -> Option<CachePolicy> {
+    let request = GraphQLRequest::<DefaultScalarValue>::new(query.to_owned(), None, None);
+    request.execute_sync(&schema(), &()).cache_policy()
+}
+
+fn header_value(query: &str) -> Option<String> {
+    cache_policy(query)?.header_value()
+}
+
+async fn batch_cache_policy(batch: Json) -> Option<CachePolicy> {
+    let request: GraphQLBatchRequest = serde_json::from_value(batch).unwrap();
+    request.execute(&schema(), &()).await.cache_policy()
+}
+
+#[test]
+fn uses_field_hints() {
+    assert_eq!(
+        header_value("{ count }").as_deref(),
+        Some("max-age=60, public"),
+    );
+}
+
+#[test]
+fn does_not_cache_root_fields_without_hints() {
+    assert_eq!(
+        cache_policy("{ count uncached }"),
+        Some(CachePolicy {
+            max_age: Some(0),
+            scope: CacheScope::Public,
+        }),
+    );
+    assert_eq!(header_value("{ count uncached }"), None);
+}
+
+#[test]
+fn uses_type_hints_of_fields_without_max_age() {
+    assert_eq!(
+        header_value("{ book { title } }").as_deref(),
+        Some("max-age=30, public"),
+    );
+    assert_eq!(
+        header_value("{ bestseller { title } }").as_deref(),
+        Some("max-age=120, public"),
+    );
+}
+
+#[test]
+fn does_not_cache_objects_without_hints() {
+    assert_eq!(header_value("{ bestseller { author { name } } }"), None);
+}
+
+#[test]
+fn uses_the_most_restrictive_scope() {
+    assert_eq!(
+        header_value("{ book { title bookmarked } }").as_deref(),
+        Some("max-age=30, private"),
+    );
+    assert_eq!(
+        header_value("{ count search { ... on Book { title } } }").as_deref(),
+        Some("max-age=60, private"),
+    );
+}
+
+#[test]
+fn uses_hints_of_interface_fields() {
+    assert_eq!(
+        header_value("{ node { id } }").as_deref(),
+        Some("max-age=10, public"),
+    );
+}
+
+#[test]
+fn restricts_policy_dynamically() {
+    assert_eq!(
+        header_value("{ count dynamic }").as_deref(),
+        Some("max-age=5, public"),
+    );
+}
+
+#[test]
+fn does_not_cache_errors() {
+    assert_eq!(cache_policy("{ failing }"), None);
+    assert_eq!(cache_policy("{ unknown }"), None);
+}
+
+#[tokio::test]
+async fn restricts_batches_to_all_responses() {
+    assert_eq!(
+        batch_cache_policy(json!([
+            {"query": "{ count }"},
+            {"query": "{ book { bookmarked } }"},
+        ]))
+        .await,
+        Some(CachePolicy {
+            max_age: Some(30),
+            scope: CacheScope::Private,
+        }),
+    );
+    assert_eq!(
+        batch_cache_policy(json!([{"query": "{ count }"}, {"query": "{ failing }"}])).await,
+        None,
+    );
+}
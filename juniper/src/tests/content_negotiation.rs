@@ -0,0 +1,141 @@
+use serde_json::json;
+
+use crate::{
+    http::{GraphQLBatchRequest, ResponseContentType},
+    schema::model::RootNode,
+    tests::fixtures::starwars::schema::{Database, Query},
+    types::scalars::{EmptyMutation, EmptySubscription},
+};
+
+fn negotiate(accept: &str) -> Option<ResponseContentType> {
+    ResponseContentType::negotiate(Some(accept))
+}
+
+#[test]
+fn defaults_to_json() {
+    assert_eq!(
+        ResponseContentType::negotiate(None),
+        Some(ResponseContentType::Json),
+    );
+    assert_eq!(negotiate(""), Some(ResponseContentType::Json));
+    assert_eq!(negotiate("*/*"), Some(ResponseContentType::Json));
+    assert_eq!(negotiate("application/*"), Some(ResponseContentType::Json));
+    assert_eq!(
+        negotiate("text/html, application/xhtml+xml, */*;q=0.8"),
+        Some(ResponseContentType::Json),
+    );
+}
+
+#[test]
+fn honors_explicit_media_types() {
+    assert_eq!(
+        negotiate("application/graphql-response+json"),
+        Some(ResponseContentType::GraphQLResponseJson),
+    );
+    assert_eq!(
+        negotiate("application/graphql-response+json, application/json"),
+        Some(ResponseContentType::GraphQLResponseJson),
+    );
+    assert_eq!(
+        negotiate("Application/GraphQL-Response+JSON; charset=utf-8"),
+        Some(ResponseContentType::GraphQLResponseJson),
+    );
+    assert_eq!(
+        negotiate("application/json"),
+        Some(ResponseContentType::Json),
+    );
+}
+
+#[test]
+fn honors_quality_values() {
+    assert_eq!(
+        negotiate("application/json;q=0.9, application/graphql-response+json"),
+        Some(ResponseContentType::GraphQLResponseJson),
+    );
+    assert_eq!(
+        negotiate("application/json, application/graphql-response+json;q=0.9"),
+        Some(ResponseContentType::Json),
+    );
+    assert_eq!(
+        negotiate("*/*, application/json;q=0"),
+        Some(ResponseContentType::GraphQLResponseJson),
+    );
+    assert_eq!(negotiate("application/json;q=0"), None);
+}
+
+#[test]
+fn rejects_unsupported_media_types() {
+    assert_eq!(negotiate("text/html"), None);
+    assert_eq!(negotiate("application/xml, text/*"), None);
+}
+
+#[test]
+fn status_codes() {
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    );
+    let database = Database::new();
+
+    for (request, json, graphql_response_json) in vec![
+        (json!({"query": "{ hero { name } }"}), 200, 200),
+        (json!({"query": "{ hero { blah } }"}), 400, 400),
+        (json!({"query": "{ hero { name }"}), 400, 400),
+        (
+            json!({"query": "query A { hero { name } }", "operationName": "B"}),
+            400,
+            400,
+        ),
+        (
+            json!([{"query": "{ hero { name } }"}, {"query": "{ hero { blah } }"}]),
+            400,
+            400,
+        ),
+        (
+            json!({"extensions": {"persistedQuery": {"version": 1, "sha256Hash": "abc"}}}),
+            200,
+            400,
+        ),
+        (
+            json!([
+                {"query": "{ hero { name } }"},
+                {"extensions": {"persistedQuery": {"version": 1, "sha256Hash": "abc"}}},
+            ]),
+            200,
+            400,
+        ),
+    ] {
+        let request: GraphQLBatchRequest =
+            serde_json::from_value(request).expect("Invalid request");
+        let response = request.execute_sync(&schema, &database);
+
+        assert_eq!(response.status_code(ResponseContentType::Json), json);
+        assert_eq!(
+            response.status_code(ResponseContentType::GraphQLResponseJson),
+            graphql_response_json,
+        );
+    }
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn rejects_safelisted_mutations_over_get_with_405() {
+    use crate::http::{parse::parse_get_request, safelist::Safelist, HttpConfig};
+
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    );
+    let config = HttpConfig::new()
+        .enable_safelist(Safelist::new(vec![("AddHero", "mutation AddHero { a }")]));
+    let request = GraphQLBatchRequest::Single(parse_get_request("id=AddHero").unwrap());
+    let response = request.execute_sync_with_config(&schema, &Database::new(), &config);
+
+    assert_eq!(response.status_code(ResponseContentType::Json), 200);
+    assert_eq!(
+        response.status_code(ResponseContentType::GraphQLResponseJson),
+        405,
+    );
+}
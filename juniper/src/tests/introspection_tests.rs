@@ -1,11 +1,19 @@
 use std::collections::HashSet;
 
 use crate::{
+    ast::InputValue,
     executor::Variables,
-    introspection::IntrospectionFormat,
-    schema::model::RootNode,
+    introspection::{IntrospectionError, IntrospectionFormat},
+    parser::parse_document_source,
+    schema::{
+        diff::diff,
+        model::{RootNode, SchemaType},
+    },
     tests::fixtures::starwars::schema::{Database, Query},
     types::scalars::{EmptyMutation, EmptySubscription},
+    validation::{visit_all_rules, ValidatorContext},
+    value::{DefaultScalarValue, Value},
+    GraphQLError,
 };
 
 use super::schema_introspection::*;
@@ -295,3 +303,246 @@ async fn test_builtin_introspection_query_without_descriptions() {
 
     assert_eq!(result, (expected, vec![]));
 }
+
+#[test]
+fn test_schema_from_introspection_round_trip() {
+    let database = Database::new();
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    );
+
+    let (result, errors) =
+        crate::introspect(&schema, &database, IntrospectionFormat::default()).unwrap();
+    assert_eq!(errors, vec![]);
+
+    let loaded = SchemaType::from_introspection(&result).unwrap();
+
+    assert_eq!(diff(&schema.schema, &loaded).changes, vec![]);
+    assert_eq!(loaded.mutation_type_name, None);
+}
+
+#[test]
+fn test_schema_from_introspection_validates_queries() {
+    let database = Database::new();
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    );
+    let (result, _) =
+        crate::introspect(&schema, &database, IntrospectionFormat::default()).unwrap();
+    let loaded = SchemaType::from_introspection(&result).unwrap();
+
+    let validate = |query| {
+        let document = parse_document_source(query, &loaded).unwrap();
+        let mut ctx = ValidatorContext::new(&loaded, &document);
+        visit_all_rules(&mut ctx, &document);
+        ctx.into_errors()
+    };
+
+    assert_eq!(
+        validate(r#"{ hero(episode: EMPIRE) { name ... on Human { homePlanet } } }"#),
+        vec![],
+    );
+    assert_eq!(
+        validate(r#"{ __schema { queryType { name } } __typename }"#),
+        vec![],
+    );
+    assert_eq!(validate(r#"{ hero { mass } }"#).len(), 1);
+    assert_eq!(validate(r#"{ hero(episode: CLONES) { name } }"#).len(), 1);
+    assert_eq!(
+        validate(r#"query ($e: Episode = CLONES) { hero(episode: $e) { name } }"#).len(),
+        1,
+    );
+    assert_eq!(
+        validate(r#"query ($e: Episode = JEDI) { hero(episode: $e) { name } }"#),
+        vec![],
+    );
+    let parse_episode = loaded
+        .concrete_type_by_name("Episode")
+        .and_then(|t| t.input_value_parse_fn())
+        .unwrap();
+    assert!(parse_episode(&InputValue::enum_value("JEDI")).is_ok());
+    assert!(parse_episode(&InputValue::scalar("JEDI")).is_ok());
+    assert!(parse_episode(&InputValue::enum_value("CLONES")).is_err());
+}
+
+#[test]
+fn test_schema_from_introspection_json() {
+    let json = r#"{
+      "data": {
+        "__schema": {
+          "queryType": { "name": "Query" },
+          "mutationType": null,
+          "subscriptionType": null,
+          "types": [
+            {
+              "kind": "OBJECT",
+              "name": "Query",
+              "description": null,
+              "fields": [
+                {
+                  "name": "users",
+                  "description": "Lists users.",
+                  "args": [
+                    {
+                      "name": "first",
+                      "description": null,
+                      "type": { "kind": "SCALAR", "name": "Int", "ofType": null },
+                      "defaultValue": "10"
+                    }
+                  ],
+                  "type": {
+                    "kind": "NON_NULL",
+                    "name": null,
+                    "ofType": {
+                      "kind": "LIST",
+                      "name": null,
+                      "ofType": { "kind": "SCALAR", "name": "DateTime", "ofType": null }
+                    }
+                  },
+                  "isDeprecated": false,
+                  "deprecationReason": null
+                }
+              ],
+              "inputFields": null,
+              "interfaces": [],
+              "enumValues": null,
+              "possibleTypes": null
+            },
+            { "kind": "SCALAR", "name": "DateTime", "description": "RFC3339 timestamp" },
+            { "kind": "SCALAR", "name": "Int", "description": null }
+          ],
+          "directives": []
+        }
+      }
+    }"#;
+    let value: Value<DefaultScalarValue> = serde_json::from_str(json).unwrap();
+
+    let schema = SchemaType::from_introspection(&value).unwrap();
+
+    let users = schema
+        .concrete_query_type()
+        .field_by_name("users")
+        .expect("users field");
+    assert_eq!(users.field_type.to_string(), "[DateTime]!");
+    assert_eq!(users.description.as_deref(), Some("Lists users."));
+    assert_eq!(
+        users.arguments.as_ref().unwrap()[0].default_value,
+        Some(InputValue::scalar(10)),
+    );
+    assert_eq!(
+        schema
+            .concrete_type_by_name("DateTime")
+            .and_then(|t| t.description()),
+        Some(&"RFC3339 timestamp".to_owned()),
+    );
+}
+
+#[test]
+fn test_schema_from_introspection_errors() {
+    let missing: Value<DefaultScalarValue> = serde_json::from_str(r#"{"data": null}"#).unwrap();
+    assert_eq!(
+        SchemaType::from_introspection(&missing).unwrap_err(),
+        IntrospectionError::MissingSchema,
+    );
+
+    let dangling: Value<DefaultScalarValue> = serde_json::from_str(
+        r#"{"__schema": {
+          "queryType": { "name": "Query" },
+          "types": [{
+            "kind": "OBJECT",
+            "name": "Query",
+            "fields": [{
+              "name": "user",
+              "args": [],
+              "type": { "kind": "OBJECT", "name": "User" }
+            }]
+          }]
+        }}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        SchemaType::from_introspection(&dangling).unwrap_err(),
+        IntrospectionError::UnknownType("User".into()),
+    );
+}
+
+#[tokio::test]
+async fn test_disabled_introspection() {
+    let database = Database::new();
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    )
+    .disable_introspection();
+
+    assert!(!schema.is_introspection_allowed(&database));
+
+    let result = crate::execute(
+        "{ __schema { queryType { name } } }",
+        None,
+        &schema,
+        &Variables::new(),
+        &database,
+    )
+    .await;
+    match result {
+        Err(GraphQLError::ValidationError(errors)) => {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(
+                errors[0].message(),
+                r#"GraphQL introspection is not allowed, but the query contained "__schema""#,
+            );
+        }
+        _ => panic!("Expected a validation error, got {:?}", result),
+    }
+
+    assert!(crate::introspect(&schema, &database, IntrospectionFormat::default()).is_err());
+
+    assert_eq!(
+        crate::execute(
+            r#"{ human(id: "1000") { __typename name } }"#,
+            None,
+            &schema,
+            &Variables::new(),
+            &database,
+        )
+        .await,
+        Ok((
+            graphql_value!({
+                "human": {
+                    "__typename": "Human",
+                    "name": "Luke Skywalker",
+                },
+            }),
+            vec![],
+        )),
+    );
+}
+
+#[tokio::test]
+async fn test_restricted_introspection() {
+    let database = Database::new();
+    let empty_database = Database::default();
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    )
+    .restrict_introspection(|db: &Database| db.get_human("1000").is_some());
+
+    let doc = r#"{ __type(name: "Droid") { name } }"#;
+
+    assert_eq!(
+        crate::execute(doc, None, &schema, &Variables::new(), &database).await,
+        Ok((graphql_value!({ "__type": { "name": "Droid" } }), vec![])),
+    );
+    assert!(matches!(
+        crate::execute(doc, None, &schema, &Variables::new(), &empty_database).await,
+        Err(GraphQLError::ValidationError(_)),
+    ));
+}
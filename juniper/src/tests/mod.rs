@@ -1,13 +1,27 @@
 //! Library tests and fixtures
 
+#[cfg(test)]
+mod batching;
+#[cfg(test)]
+mod cache_control;
+#[cfg(test)]
+mod content_negotiation;
 pub mod fixtures;
 #[cfg(test)]
 mod introspection_tests;
 #[cfg(test)]
+mod persisted_queries;
+#[cfg(test)]
 mod query_tests;
 #[cfg(test)]
+mod response_cache;
+#[cfg(test)]
+mod safelist;
+#[cfg(test)]
 mod schema_introspection;
 #[cfg(test)]
 mod subscriptions;
 #[cfg(test)]
 mod type_info_tests;
+#[cfg(all(test, feature = "serde_json"))]
+mod uploads;
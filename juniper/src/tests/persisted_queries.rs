@@ -0,0 +1,151 @@
+use serde_json::{json, Value as Json};
+
+use crate::{
+    http::{
+        persisted_queries::{sha256_hex, InMemoryPersistedQueryStore},
+        GraphQLBatchRequest, GraphQLRequest, HttpConfig,
+    },
+    schema::model::RootNode,
+    tests::fixtures::starwars::schema::{Database, Query},
+    types::scalars::{EmptyMutation, EmptySubscription},
+};
+
+type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+
+const QUERY: &str = "{ hero { name } }";
+
+fn schema() -> Schema {
+    RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    )
+}
+
+fn persisted_request(query: Option<&str>, hash: &str) -> GraphQLRequest {
+    let mut request = json!({
+        "extensions": {"persistedQuery": {"version": 1, "sha256Hash": hash}},
+    });
+    if let Some(query) = query {
+        request["query"] = json!(query);
+    }
+    serde_json::from_value(request).expect("Invalid request")
+}
+
+fn config() -> HttpConfig<Database> {
+    HttpConfig::new().enable_persisted_queries(InMemoryPersistedQueryStore::default())
+}
+
+fn execute(config: &HttpConfig<Database>, request: &GraphQLRequest) -> (bool, Json) {
+    let (schema, database) = (schema(), Database::new());
+    let response = request.execute_sync_with_config(&schema, &database, config);
+    (response.is_ok(), serde_json::to_value(&response).unwrap())
+}
+
+#[test]
+fn registers_and_executes_persisted_queries() {
+    let config = config();
+    let hash = sha256_hex(QUERY);
+
+    let (ok, response) = execute(&config, &persisted_request(None, &hash));
+    assert!(ok);
+    assert_eq!(response["data"], Json::Null);
+    assert_eq!(response["errors"][0]["message"], "PersistedQueryNotFound");
+    assert_eq!(
+        response["errors"][0]["extensions"]["code"],
+        "PERSISTED_QUERY_NOT_FOUND",
+    );
+
+    let expected = json!({"data": {"hero": {"name": "R2-D2"}}});
+    assert_eq!(
+        execute(&config, &persisted_request(Some(QUERY), &hash)),
+        (true, expected.clone()),
+    );
+    assert_eq!(
+        execute(&config, &persisted_request(None, &hash)),
+        (true, expected),
+    );
+}
+
+#[test]
+fn rejects_mismatching_hashes() {
+    let config = config();
+    let hash = sha256_hex("{ hero { id } }");
+
+    let (_, response) = execute(&config, &persisted_request(Some(QUERY), &hash));
+    assert_eq!(
+        response["errors"][0]["message"],
+        "provided sha does not match query",
+    );
+
+    let (_, response) = execute(&config, &persisted_request(None, &hash));
+    assert_eq!(response["errors"][0]["message"], "PersistedQueryNotFound");
+}
+
+#[test]
+fn reports_parse_errors_of_persisted_queries() {
+    let config = config();
+    let query = "{ hero { name }";
+    let hash = sha256_hex(query);
+
+    let (ok, registered) = execute(&config, &persisted_request(Some(query), &hash));
+    assert!(!ok);
+    assert_eq!(
+        execute(&config, &persisted_request(None, &hash)),
+        (false, registered),
+    );
+}
+
+#[test]
+fn persisted_queries_are_not_supported_by_default() {
+    let config = HttpConfig::new();
+    let hash = sha256_hex(QUERY);
+
+    let (ok, response) = execute(&config, &persisted_request(None, &hash));
+    assert!(ok);
+    assert_eq!(
+        response["errors"][0]["message"],
+        "PersistedQueryNotSupported"
+    );
+
+    let (ok, response) = execute(&config, &persisted_request(Some(QUERY), &hash));
+    assert!(ok);
+    assert_eq!(response, json!({"data": {"hero": {"name": "R2-D2"}}}));
+}
+
+#[test]
+fn requests_without_query_are_rejected() {
+    let request: GraphQLRequest = serde_json::from_value(json!({})).unwrap();
+
+    let (ok, response) = execute(&config(), &request);
+    assert!(!ok);
+    assert_eq!(
+        response["errors"][0]["message"],
+        "Must provide an operation"
+    );
+}
+
+#[tokio::test]
+async fn batches_resolve_persisted_queries_per_item() {
+    let config = config();
+    let hash = sha256_hex(QUERY);
+    let request: GraphQLBatchRequest = serde_json::from_value(json!([
+        {"query": QUERY, "extensions": {"persistedQuery": {"version": 1, "sha256Hash": hash}}},
+        {"extensions": {"persistedQuery": {"version": 1, "sha256Hash": hash}}},
+    ]))
+    .unwrap();
+    let (schema, database) = (schema(), Database::new());
+
+    let response = request
+        .execute_with_config(&schema, &database, &config)
+        .await;
+
+    assert!(response.is_ok());
+    assert_eq!(
+        serde_json::to_value(&response).unwrap(),
+        json!([
+            {"data": {"hero": {"name": "R2-D2"}}},
+            {"data": {"hero": {"name": "R2-D2"}}},
+        ]),
+    );
+}
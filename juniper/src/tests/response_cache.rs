@@ -0,0 +1,208 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use serde_json::{json, Value as Json};
+
+use crate::{
+    http::{response_cache::InMemoryResponseCache, GraphQLRequest, HttpConfig},
+    schema::model::RootNode,
+    types::scalars::EmptySubscription,
+    Context, FieldResult, InputValue,
+};
+
+/// The user sending a request, counting how many fields were resolved
+struct User {
+    id: Option<i32>,
+    resolved: Arc<AtomicUsize>,
+}
+
+impl Context for User {}
+
+impl User {
+    fn resolve<T>(&self, value: T) -> T {
+        self.resolved.fetch_add(1, Ordering::SeqCst);
+        value
+    }
+}
+
+struct Query;
+
+#[crate::graphql_object(context = User)]
+impl Query {
+    #[graphql(cache_control(max_age = 60))]
+    fn answer(n: Option<i32>, context: &User) -> i32 {
+        context.resolve(n.unwrap_or(42))
+    }
+
+    fn uncached(context: &User) -> i32 {
+        context.resolve(0)
+    }
+
+    #[graphql(cache_control(max_age = 60))]
+    fn failing(context: &User) -> FieldResult<i32> {
+        context.resolve(Err("Failed".into()))
+    }
+}
+
+struct Mutation;
+
+#[crate::graphql_object(context = User)]
+impl Mutation {
+    fn bump(context: &User) -> i32 {
+        context.resolve(1)
+    }
+}
+
+type Schema = RootNode<'static, Query, Mutation, EmptySubscription<User>>;
+
+fn schema() -> Schema {
+    RootNode::new(Query, Mutation, EmptySubscription::new())
+}
+
+fn config(cache: InMemoryResponseCache) -> HttpConfig<User> {
+    HttpConfig::new().enable_response_cache(cache, |user: &User| match user.id {
+        Some(id) if id < 0 => None,
+        Some(id) => Some(format!("user:{}", id)),
+        None => Some("public".to_owned()),
+    })
+}
+
+fn cache() -> InMemoryResponseCache {
+    InMemoryResponseCache::new(10, Duration::from_secs(60))
+}
+
+/// Executes the given query as the given user, returning the response and
+/// how many fields were resolved
+fn execute(
+    config: &HttpConfig<User>,
+    query: &str,
+    variables: Option<Json>,
+    user: Option<i32>,
+) -> (Json, usize) {
+    let variables = variables.map(|v| serde_json::from_value::<InputValue>(v).unwrap());
+    let request = GraphQLRequest::new(query.to_owned(), None, variables);
+    let user = User {
+        id: user,
+        resolved: Arc::default(),
+    };
+    let schema = schema();
+    let response = request.execute_sync_with_config(&schema, &user, config);
+    (
+        serde_json::to_value(&response).unwrap(),
+        user.resolved.load(Ordering::SeqCst),
+    )
+}
+
+#[test]
+fn caches_identical_queries() {
+    let config = config(cache());
+    let expected = json!({"data": {"answer": 42}});
+
+    assert_eq!(
+        execute(&config, "{ answer }", None, None),
+        (expected.clone(), 1)
+    );
+    assert_eq!(
+        execute(&config, "{ answer }", None, None),
+        (expected.clone(), 0)
+    );
+    assert_eq!(
+        execute(&config, "# Again\nquery {\n  answer,\n}", None, None),
+        (expected, 0),
+    );
+}
+
+#[test]
+fn keys_responses_by_variables() {
+    let config = config(cache());
+    let query = "query ($n: Int) { answer(n: $n) }";
+
+    assert_eq!(
+        execute(&config, query, Some(json!({"n": 1})), None),
+        (json!({"data": {"answer": 1}}), 1),
+    );
+    assert_eq!(
+        execute(&config, query, Some(json!({"n": 2})), None),
+        (json!({"data": {"answer": 2}}), 1),
+    );
+    assert_eq!(
+        execute(&config, query, Some(json!({"n": 1})), None),
+        (json!({"data": {"answer": 1}}), 0),
+    );
+}
+
+#[test]
+fn keys_responses_by_scope() {
+    let config = config(cache());
+
+    assert_eq!(execute(&config, "{ answer }", None, Some(1)).1, 1);
+    assert_eq!(execute(&config, "{ answer }", None, Some(2)).1, 1);
+    assert_eq!(execute(&config, "{ answer }", None, None).1, 1);
+    assert_eq!(execute(&config, "{ answer }", None, Some(1)).1, 0);
+}
+
+#[test]
+fn bypasses_cache_without_scope() {
+    let config = config(cache());
+
+    assert_eq!(execute(&config, "{ answer }", None, Some(-1)).1, 1);
+    assert_eq!(execute(&config, "{ answer }", None, Some(-1)).1, 1);
+    assert!(config.response_cache().is_some());
+}
+
+#[test]
+fn does_not_cache_errors_nor_mutations() {
+    let config = config(cache());
+
+    for _ in 0..2 {
+        let (response, resolved) = execute(&config, "{ failing }", None, None);
+        assert_eq!(response["errors"][0]["message"], "Failed");
+        assert_eq!(resolved, 1);
+
+        assert_eq!(
+            execute(&config, "mutation { bump }", None, None),
+            (json!({"data": {"bump": 1}}), 1),
+        );
+    }
+}
+
+#[test]
+fn only_caches_cacheable_responses_if_asked() {
+    let config = config(cache().only_cacheable());
+
+    assert_eq!(execute(&config, "{ answer uncached }", None, None).1, 2);
+    assert_eq!(execute(&config, "{ answer uncached }", None, None).1, 2);
+
+    assert_eq!(execute(&config, "{ answer }", None, None).1, 1);
+    assert_eq!(execute(&config, "{ answer }", None, None).1, 0);
+}
+
+#[test]
+fn keeps_cache_policy_of_cached_responses() {
+    let config = config(cache());
+    let user = User {
+        id: None,
+        resolved: Arc::default(),
+    };
+    let request = GraphQLRequest::new("{ answer }".to_owned(), None, None);
+
+    let schema = schema();
+    let executed = request
+        .execute_sync_with_config(&schema, &user, &config)
+        .cache_policy();
+    let cached = request
+        .execute_sync_with_config(&schema, &user, &config)
+        .cache_policy();
+
+    assert_eq!(user.resolved.load(Ordering::SeqCst), 1);
+    assert_eq!(executed, cached);
+    assert_eq!(
+        cached.and_then(|p| p.header_value()).as_deref(),
+        Some("max-age=60, public"),
+    );
+}
@@ -0,0 +1,125 @@
+use serde_json::{json, Value as Json};
+
+use crate::{
+    http::{
+        persisted_queries::{sha256_hex, InMemoryPersistedQueryStore},
+        safelist::Safelist,
+        GraphQLBatchRequest, GraphQLRequest, HttpConfig,
+    },
+    schema::model::RootNode,
+    tests::fixtures::starwars::schema::{Database, Query},
+    types::scalars::{EmptyMutation, EmptySubscription},
+};
+
+type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+
+const QUERY: &str = "query HeroName { hero { name } }";
+
+fn schema() -> Schema {
+    RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    )
+}
+
+fn config() -> HttpConfig<Database> {
+    let safelist: Safelist = serde_json::from_value(json!({ "HeroName": QUERY })).unwrap();
+
+    HttpConfig::new().enable_safelist(safelist)
+}
+
+fn execute(config: &HttpConfig<Database>, request: Json) -> Json {
+    let request: GraphQLRequest = serde_json::from_value(request).expect("Invalid request");
+    let (schema, database) = (schema(), Database::new());
+    let response = request.execute_sync_with_config(&schema, &database, config);
+    serde_json::to_value(&response).unwrap()
+}
+
+#[test]
+fn executes_safelisted_operations() {
+    let config = config();
+    let expected = json!({"data": {"hero": {"name": "R2-D2"}}});
+
+    for request in vec![
+        json!({ "id": "HeroName" }),
+        json!({ "id": "HeroName", "query": QUERY }),
+        json!({ "query": QUERY }),
+        json!({
+            "extensions": {"persistedQuery": {"version": 1, "sha256Hash": sha256_hex(QUERY)}},
+        }),
+    ] {
+        assert_eq!(execute(&config, request.clone()), expected, "{}", request);
+    }
+}
+
+#[test]
+fn rejects_unknown_documents() {
+    let config = config();
+
+    for request in vec![
+        json!({ "id": "HeroFriends" }),
+        json!({ "id": "HeroName", "query": "{ hero { id } }" }),
+        json!({ "query": "{ hero { name } }" }),
+        json!({
+            "extensions": {"persistedQuery": {"version": 1, "sha256Hash": sha256_hex("{ a }")}},
+        }),
+    ] {
+        let response = execute(&config, request.clone());
+
+        assert_eq!(
+            response["errors"][0]["message"], "Operation is not in the safelist",
+            "{}",
+            request,
+        );
+        assert_eq!(
+            response["errors"][0]["extensions"]["code"],
+            "OPERATION_NOT_SAFELISTED",
+        );
+    }
+}
+
+#[test]
+fn persisted_queries_cannot_register_operations() {
+    let config = config().enable_persisted_queries(InMemoryPersistedQueryStore::default());
+    let query = "{ hero { id } }";
+    let hash = sha256_hex(query);
+
+    for request in vec![
+        json!({
+            "query": query,
+            "extensions": {"persistedQuery": {"version": 1, "sha256Hash": hash}},
+        }),
+        json!({ "extensions": {"persistedQuery": {"version": 1, "sha256Hash": hash}} }),
+    ] {
+        let response = execute(&config, request);
+
+        assert_eq!(
+            response["errors"][0]["message"],
+            "Operation is not in the safelist",
+        );
+    }
+}
+
+#[tokio::test]
+async fn checks_batches_per_item() {
+    let schema = schema();
+    let request: GraphQLBatchRequest = serde_json::from_value(json!([
+        { "id": "HeroName" },
+        { "query": "{ hero { id } }" },
+    ]))
+    .unwrap();
+    let (config, database) = (config(), Database::new());
+
+    let response = request
+        .execute_with_config(&schema, &database, &config)
+        .await;
+    let response = serde_json::to_value(&response).unwrap();
+
+    assert_eq!(response[0], json!({"data": {"hero": {"name": "R2-D2"}}}));
+    assert_eq!(response[1]["data"], Json::Null);
+    assert_eq!(
+        response[1]["errors"][0]["message"],
+        "Operation is not in the safelist",
+    );
+}
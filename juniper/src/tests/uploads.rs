@@ -0,0 +1,202 @@
+use futures::StreamExt as _;
+use serde_json::{json, Value as Json};
+
+use crate::{
+    http::{parse::parse_post_request, upload::Upload, GraphQLBatchRequest},
+    schema::model::RootNode,
+    types::scalars::EmptySubscription,
+    DefaultScalarValue,
+};
+
+struct Query;
+
+#[crate::graphql_object]
+impl Query {
+    fn ping() -> bool {
+        true
+    }
+}
+
+struct Mutation;
+
+#[crate::graphql_object]
+impl Mutation {
+    fn upload(file: Upload) -> String {
+        format!(
+            "{} ({}): {}",
+            file.filename().unwrap_or("unnamed"),
+            file.content_type().unwrap_or("unknown"),
+            String::from_utf8_lossy(file.as_bytes()),
+        )
+    }
+
+    fn sizes(files: Vec<Upload>) -> Vec<i32> {
+        files.iter().map(|file| file.len() as i32).collect()
+    }
+
+    async fn chunks(file: Upload) -> Vec<i32> {
+        file.stream()
+            .map(|chunk| chunk.len() as i32)
+            .collect()
+            .await
+    }
+}
+
+type Schema = RootNode<'static, Query, Mutation, EmptySubscription<()>>;
+
+fn schema() -> Schema {
+    RootNode::new(Query, Mutation, EmptySubscription::<()>::new())
+}
+
+/// A `multipart/form-data` body with the given `operations` and `map` fields,
+/// followed by the given files
+fn multipart(operations: Json, map: Json, files: &[(&str, &str, &[u8])]) -> Vec<u8> {
+    let mut body = Vec::new();
+    for (name, content) in &[("operations", operations), ("map", map)] {
+        body.extend_from_slice(
+            format!(
+                "--boundary\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                name, content,
+            )
+            .as_bytes(),
+        );
+    }
+    for (name, filename, content) in files {
+        body.extend_from_slice(
+            format!(
+                "--boundary\r\n\
+                 Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\
+                 Content-Type: text/plain\r\n\r\n",
+                name, filename,
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(content);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(b"--boundary--\r\n");
+    body
+}
+
+fn parse(body: &[u8]) -> GraphQLBatchRequest<DefaultScalarValue> {
+    parse_post_request(Some("multipart/form-data; boundary=boundary"), body)
+        .expect("Invalid multipart request")
+}
+
+fn execute(body: &[u8]) -> Json {
+    let schema = schema();
+    let request = parse(body);
+    let response = request.execute_sync(&schema, &());
+    serde_json::to_value(&response).unwrap()
+}
+
+#[test]
+fn uploads_files() {
+    let body = multipart(
+        json!({
+            "query": "mutation ($file: Upload!) { upload(file: $file) }",
+            "variables": {"file": null},
+        }),
+        json!({"0": ["variables.file"]}),
+        &[("0", "avatar.txt", &b"hello"[..])],
+    );
+
+    assert_eq!(
+        execute(&body),
+        json!({"data": {"upload": "avatar.txt (text/plain): hello"}}),
+    );
+}
+
+#[test]
+fn uploads_lists_of_files() {
+    let body = multipart(
+        json!({
+            "query": "mutation ($files: [Upload!]!) { sizes(files: $files) }",
+            "variables": {"files": [null, null]},
+        }),
+        json!({"a": ["variables.files.0"], "b": ["variables.files.1"]}),
+        &[("a", "a.txt", &b"a"[..]), ("b", "b.txt", &b"bbb"[..])],
+    );
+
+    assert_eq!(execute(&body), json!({"data": {"sizes": [1, 3]}}));
+}
+
+#[test]
+fn shares_files_between_batched_operations() {
+    let query = "mutation ($file: Upload!) { upload(file: $file) }";
+    let body = multipart(
+        json!([
+            {"query": query, "variables": {"file": null}},
+            {"query": query, "variables": {"file": null}},
+        ]),
+        json!({"0": ["0.variables.file", "1.variables.file"]}),
+        &[("0", "doc.txt", &b"shared"[..])],
+    );
+    let expected = json!({"data": {"upload": "doc.txt (text/plain): shared"}});
+
+    assert_eq!(execute(&body), json!([expected, expected]));
+}
+
+#[test]
+fn rejects_unknown_files() {
+    let body = multipart(
+        json!({
+            "query": r#"mutation { upload(file: "0") }"#,
+        }),
+        json!({}),
+        &[],
+    );
+    assert!(execute(&body)["errors"].is_array());
+
+    let body = multipart(
+        json!({
+            "query": "mutation ($file: Upload!) { upload(file: $file) }",
+            "variables": {"file": "0"},
+        }),
+        json!({}),
+        &[],
+    );
+    assert!(execute(&body)["errors"].is_array());
+}
+
+#[test]
+fn does_not_share_files_between_requests() {
+    let query = "mutation ($file: Upload!) { upload(file: $file) }";
+    let uploading = parse(&multipart(
+        json!({"query": query, "variables": {"file": null}}),
+        json!({"0": ["variables.file"]}),
+        &[("0", "secret.txt", &b"secret"[..])],
+    ));
+    let body = json!({"query": query, "variables": {"file": "0"}}).to_string();
+    let request =
+        parse_post_request::<DefaultScalarValue>(Some("application/json"), body.as_bytes())
+            .unwrap();
+    let schema = schema();
+
+    let response = serde_json::to_value(&request.execute_sync(&schema, &())).unwrap();
+
+    assert!(response["errors"].is_array());
+    drop(uploading);
+}
+
+#[tokio::test]
+async fn streams_files() {
+    let content = vec![0; 150 * 1024];
+    let body = multipart(
+        json!({
+            "query": "mutation ($file: Upload!) { chunks(file: $file) }",
+            "variables": {"file": null},
+        }),
+        json!({"0": ["variables.file"]}),
+        &[("0", "large.bin", &content[..])],
+    );
+    let schema = schema();
+    let request = parse(&body);
+
+    let response = request.execute(&schema, &()).await;
+
+    assert_eq!(
+        serde_json::to_value(&response).unwrap(),
+        json!({"data": {"chunks": [65536, 65536, 22528]}}),
+    );
+}
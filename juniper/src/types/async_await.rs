@@ -238,6 +238,8 @@ where
                     ))
                 });
 
+                executor.set_field_cache_hint(meta_type, meta_field);
+
                 let exec_vars = executor.variables();
 
                 let sub_exec = executor.field_sub_executor(
@@ -250,7 +252,9 @@ where
                     f.arguments.as_ref().map(|m| {
                         m.item
                             .iter()
-                            .map(|&(ref k, ref v)| (k.item, v.item.clone().into_const(exec_vars)))
+                            .filter_map(|&(ref k, ref v)| {
+                                v.item.clone().into_const(exec_vars).map(|v| (k.item, v))
+                            })
                             .collect()
                     }),
                     &meta_field.arguments,
@@ -91,8 +91,6 @@ where
                 if !args.contains_key(arg.name.as_str()) || args[arg.name.as_str()].is_null() {
                     if let Some(ref default_value) = arg.default_value {
                         args.insert(arg.name.as_str(), default_value.clone());
-                    } else {
-                        args.insert(arg.name.as_str(), InputValue::null());
                     }
                 }
             }
@@ -106,16 +104,19 @@ where
     /// If the argument is found, or a default argument has been provided,
     /// the `InputValue` will be converted into the type `T`.
     ///
-    /// Returns `Some` if the argument is present _and_ type conversion
-    /// succeeeds.
+    /// If the argument is absent and has no default value,
+    /// `FromInputValue::from_implicit_null` is used instead, so types like
+    /// `Nullable` can tell an omitted argument from an explicit `null`.
+    ///
+    /// Returns `Some` if the type conversion succeeeds.
     pub fn get<T>(&self, key: &str) -> Option<T>
     where
         T: FromInputValue<S>,
     {
-        self.args
-            .as_ref()
-            .and_then(|args| args.get(key))
-            .and_then(InputValue::convert)
+        match self.args.as_ref().and_then(|args| args.get(key)) {
+            Some(v) => v.convert().ok(),
+            None => T::from_implicit_null().ok(),
+        }
     }
 }
 
@@ -458,6 +459,8 @@ where
                     ))
                 });
 
+                executor.set_field_cache_hint(meta_type, meta_field);
+
                 let exec_vars = executor.variables();
 
                 let sub_exec = executor.field_sub_executor(
@@ -474,8 +477,8 @@ where
                         f.arguments.as_ref().map(|m| {
                             m.item
                                 .iter()
-                                .map(|&(ref k, ref v)| {
-                                    (k.item, v.item.clone().into_const(exec_vars))
+                                .filter_map(|&(ref k, ref v)| {
+                                    v.item.clone().into_const(exec_vars).map(|v| (k.item, v))
                                 })
                                 .collect()
                         }),
@@ -581,7 +584,8 @@ where
                 .arguments
                 .iter()
                 .flat_map(|m| m.item.get("if"))
-                .flat_map(|v| v.item.clone().into_const(vars).convert())
+                .flat_map(|v| v.item.clone().into_const(vars))
+                .flat_map(|v| v.convert().ok())
                 .next()
                 .unwrap();
 
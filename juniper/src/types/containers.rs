@@ -1,6 +1,15 @@
+use std::{
+    collections::{BTreeSet, HashSet, VecDeque},
+    convert::TryFrom,
+    hash::{BuildHasher, Hash},
+    iter::FromIterator,
+};
+
+use indexmap::IndexSet;
+
 use crate::{
     ast::{FromInputValue, InputValue, Selection, ToInputValue},
-    executor::{ExecutionResult, Executor, Registry},
+    executor::{ExecutionResult, Executor, FieldError, IntoFieldError, Registry},
     schema::meta::MetaType,
     types::{
         async_await::GraphQLValueAsync,
@@ -80,9 +89,11 @@ where
     T: FromInputValue<S>,
     S: ScalarValue,
 {
-    fn from_input_value(v: &InputValue<S>) -> Option<Option<T>> {
+    type Error = T::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Option<T>, T::Error> {
         match v {
-            &InputValue::Null => Some(None),
+            &InputValue::Null => Ok(None),
             v => v.convert().map(Some),
         }
     }
@@ -163,20 +174,10 @@ where
     T: FromInputValue<S>,
     S: ScalarValue,
 {
-    fn from_input_value(v: &InputValue<S>) -> Option<Vec<T>>
-where {
-        match *v {
-            InputValue::List(ref ls) => {
-                let v: Vec<_> = ls.iter().filter_map(|i| i.item.convert()).collect();
-
-                if v.len() == ls.len() {
-                    Some(v)
-                } else {
-                    None
-                }
-            }
-            ref other => other.convert().map(|e| vec![e]),
-        }
+    type Error = T::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Vec<T>, T::Error> {
+        convert_list(v)
     }
 }
 
@@ -257,6 +258,196 @@ where
     }
 }
 
+/// Implements `GraphQLType`, `GraphQLValue`, `GraphQLValueAsync` and
+/// `ToInputValue` for a collection of `$item`, exposed as a GraphQL list.
+///
+/// The collection must have an `iter()` method yielding `&$item` with an exact
+/// size.
+macro_rules! impl_list_type {
+    ([$($generics:tt)*] $ty:ty, $item:ty $(where $($bounds:tt)+)?) => {
+        impl<S, $($generics)*> GraphQLType<S> for $ty
+        where
+            $item: GraphQLType<S>,
+            S: ScalarValue,
+            $($($bounds)+)?
+        {
+            fn name(_: &Self::TypeInfo) -> Option<&'static str> {
+                None
+            }
+
+            fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+            where
+                S: 'r,
+            {
+                registry.build_list_type::<$item>(info).into_meta()
+            }
+        }
+
+        impl<S, $($generics)*> GraphQLValue<S> for $ty
+        where
+            $item: GraphQLValue<S>,
+            S: ScalarValue,
+            $($($bounds)+)?
+        {
+            type Context = <$item as GraphQLValue<S>>::Context;
+            type TypeInfo = <$item as GraphQLValue<S>>::TypeInfo;
+
+            fn type_name(&self, _: &Self::TypeInfo) -> Option<&'static str> {
+                None
+            }
+
+            fn resolve(
+                &self,
+                info: &Self::TypeInfo,
+                _: Option<&[Selection<S>]>,
+                executor: &Executor<Self::Context, S>,
+            ) -> ExecutionResult<S> {
+                resolve_into_list(executor, info, self.iter())
+            }
+        }
+
+        impl<S, $($generics)*> GraphQLValueAsync<S> for $ty
+        where
+            $item: GraphQLValueAsync<S>,
+            <$item as GraphQLValue<S>>::TypeInfo: Sync,
+            <$item as GraphQLValue<S>>::Context: Sync,
+            Self: Sync,
+            S: ScalarValue + Send + Sync,
+            $($($bounds)+)?
+        {
+            fn resolve_async<'a>(
+                &'a self,
+                info: &'a Self::TypeInfo,
+                _: Option<&'a [Selection<S>]>,
+                executor: &'a Executor<Self::Context, S>,
+            ) -> crate::BoxFuture<'a, ExecutionResult<S>> {
+                let f = resolve_into_list_async(executor, info, self.iter());
+                Box::pin(f)
+            }
+        }
+
+        impl<S, $($generics)*> ToInputValue<S> for $ty
+        where
+            $item: ToInputValue<S>,
+            S: ScalarValue,
+            $($($bounds)+)?
+        {
+            fn to_input_value(&self) -> InputValue<S> {
+                InputValue::list(self.iter().map(<$item>::to_input_value).collect())
+            }
+        }
+    };
+}
+
+impl_list_type!([T] VecDeque<T>, T);
+
+impl<S, T> FromInputValue<S> for VecDeque<T>
+where
+    T: FromInputValue<S>,
+    S: ScalarValue,
+{
+    type Error = T::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<VecDeque<T>, T::Error> {
+        convert_list(v)
+    }
+}
+
+impl_list_type!([T, H] HashSet<T, H>, T);
+
+impl<S, T, H> FromInputValue<S> for HashSet<T, H>
+where
+    T: FromInputValue<S> + Eq + Hash,
+    H: BuildHasher + Default,
+    S: ScalarValue,
+{
+    type Error = T::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<HashSet<T, H>, T::Error> {
+        convert_list(v)
+    }
+}
+
+impl_list_type!([T] BTreeSet<T>, T);
+
+impl<S, T> FromInputValue<S> for BTreeSet<T>
+where
+    T: FromInputValue<S> + Ord,
+    S: ScalarValue,
+{
+    type Error = T::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<BTreeSet<T>, T::Error> {
+        convert_list(v)
+    }
+}
+
+impl_list_type!([T, H] IndexSet<T, H>, T);
+
+impl<S, T, H> FromInputValue<S> for IndexSet<T, H>
+where
+    T: FromInputValue<S> + Eq + Hash,
+    H: BuildHasher + Default,
+    S: ScalarValue,
+{
+    type Error = T::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<IndexSet<T, H>, T::Error> {
+        convert_list(v)
+    }
+}
+
+impl_list_type!([T, const N: usize] [T; N], T);
+
+impl<S, T, const N: usize> FromInputValue<S> for [T; N]
+where
+    T: FromInputValue<S>,
+    S: ScalarValue,
+{
+    type Error = FieldError<S>;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<[T; N], FieldError<S>> {
+        let items: Vec<T> = convert_list(v).map_err(IntoFieldError::into_field_error)?;
+        <[T; N]>::try_from(items).map_err(|items| {
+            FieldError::new(
+                format!("Expected list of length {}, found {}", N, items.len()),
+                Value::null(),
+            )
+        })
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl_list_type!([A] smallvec::SmallVec<A>, A::Item where A: smallvec::Array);
+
+#[cfg(feature = "smallvec")]
+impl<S, A> FromInputValue<S> for smallvec::SmallVec<A>
+where
+    A: smallvec::Array,
+    A::Item: FromInputValue<S>,
+    S: ScalarValue,
+{
+    type Error = <A::Item as FromInputValue<S>>::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<smallvec::SmallVec<A>, Self::Error> {
+        convert_list(v)
+    }
+}
+
+/// Converts a list input value into a collection, treating a single non-list
+/// value as a list of one item as the GraphQL spec requires.
+fn convert_list<S, T, C>(v: &InputValue<S>) -> Result<C, T::Error>
+where
+    T: FromInputValue<S>,
+    S: ScalarValue,
+    C: FromIterator<T>,
+{
+    match *v {
+        InputValue::List(ref ls) => ls.iter().map(|i| i.item.convert()).collect(),
+        ref other => other.convert().map(|e| std::iter::once(e).collect()),
+    }
+}
+
 fn resolve_into_list<'t, S, T, I>(
     executor: &Executor<T::Context, S>,
     info: &T::TypeInfo,
@@ -299,7 +490,6 @@ where
     S: ScalarValue + Send + Sync,
 {
     use futures::stream::{FuturesOrdered, StreamExt as _};
-    use std::iter::FromIterator;
 
     let stop_on_null = executor
         .current_type()
@@ -0,0 +1,215 @@
+//! `Duration` scalar, represented as an [ISO 8601 duration][1] string, e.g. `PT1H30M`.
+//!
+//! Only components of a fixed length are supported: weeks and days (as 7 and
+//! 24 hours) in the date part, and hours, minutes and (fractional) seconds in
+//! the time part. Durations are always formatted in hours, minutes and seconds.
+//!
+//! [1]: https://en.wikipedia.org/wiki/ISO_8601#Durations
+
+use std::time::Duration;
+
+use crate::{
+    parser::{ParseError, ScalarToken, Token},
+    value::ParseScalarResult,
+    Value,
+};
+
+/// Formats a duration of `secs` seconds and `nanos` nanoseconds as an ISO 8601
+/// duration string.
+pub(crate) fn format_iso8601(negative: bool, secs: u64, nanos: u32) -> String {
+    let mut out = String::from(if negative { "-PT" } else { "PT" });
+
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        out.push_str(&format!("{}H", hours));
+    }
+    if minutes > 0 {
+        out.push_str(&format!("{}M", minutes));
+    }
+    if seconds > 0 || nanos > 0 || (hours == 0 && minutes == 0) {
+        out.push_str(&seconds.to_string());
+        if nanos > 0 {
+            out.push_str(format!(".{:09}", nanos).trim_end_matches('0'));
+        }
+        out.push('S');
+    }
+    out
+}
+
+/// Parses an ISO 8601 duration string into its sign, seconds and nanoseconds.
+pub(crate) fn parse_iso8601(s: &str) -> Result<(bool, u64, u32), String> {
+    let (negative, rest) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let rest = rest
+        .strip_prefix('P')
+        .ok_or_else(|| "missing the leading `P`".to_owned())?;
+    let (date, time) = match rest.find('T') {
+        Some(i) if i + 1 == rest.len() => return Err("empty time part".into()),
+        Some(i) => (&rest[..i], Some(&rest[i + 1..])),
+        None => (rest, None),
+    };
+    if date.is_empty() && time.is_none() {
+        return Err("no components".into());
+    }
+
+    let mut secs: u64 = 0;
+    let mut nanos: u32 = 0;
+    let mut add = |value: u64, unit: u64| -> Result<(), String> {
+        secs = value
+            .checked_mul(unit)
+            .and_then(|v| v.checked_add(secs))
+            .ok_or_else(|| "out of range".to_owned())?;
+        Ok(())
+    };
+
+    for (part, units) in [
+        (date, &[('W', 7 * 24 * 3600), ('D', 24 * 3600)][..]),
+        (time.unwrap_or(""), &[('H', 3600), ('M', 60), ('S', 1)][..]),
+    ] {
+        let is_date = units[0].0 == 'W';
+        let mut units = units.iter();
+        let mut rest = part;
+        while !rest.is_empty() {
+            let end = rest
+                .find(|c: char| !c.is_ascii_digit() && c != '.' && c != ',')
+                .ok_or_else(|| format!("missing a designator after `{}`", rest))?;
+            let (number, designator) = (&rest[..end], rest[end..].chars().next().unwrap());
+            rest = &rest[end + designator.len_utf8()..];
+
+            if designator == 'Y' || (designator == 'M' && is_date) {
+                return Err("years and months have no fixed length".into());
+            }
+            let unit = units
+                .by_ref()
+                .find(|(d, _)| *d == designator)
+                .map(|(_, unit)| *unit)
+                .ok_or_else(|| format!("unexpected designator `{}`", designator))?;
+
+            let mut split = number.splitn(2, ['.', ',']);
+            let whole = split.next().unwrap_or_default();
+            let fraction = split.next();
+            if whole.is_empty() {
+                return Err(format!("missing a number before `{}`", designator));
+            }
+            add(whole.parse().map_err(|_| "out of range".to_owned())?, unit)?;
+
+            if let Some(fraction) = fraction {
+                if unit != 1 || !rest.is_empty() {
+                    return Err("only seconds may have a fraction".into());
+                }
+                if fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(format!("invalid fraction `{}`", fraction));
+                }
+                // Digits beyond nanoseconds are truncated.
+                let digits = &fraction[..fraction.len().min(9)];
+                nanos = digits.parse::<u32>().unwrap() * 10u32.pow(9 - digits.len() as u32);
+            }
+        }
+    }
+
+    Ok((negative, secs, nanos))
+}
+
+#[crate::graphql_scalar(
+    name = "Duration",
+    description = "An ISO 8601 duration, e.g. `PT1H30M`"
+)]
+impl<S> GraphQLScalar for Duration
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(format_iso8601(false, self.as_secs(), self.subsec_nanos()))
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Duration, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
+            .and_then(|s| {
+                parse_iso8601(s)
+                    .and_then(|(negative, secs, nanos)| match negative {
+                        false => Ok(Duration::new(secs, nanos)),
+                        true if secs == 0 && nanos == 0 => Ok(Duration::default()),
+                        true => Err("negative durations are not supported".into()),
+                    })
+                    .map_err(|e| format!(r#"Expected ISO 8601 duration, found "{}": {}"#, s, e))
+            })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::{DefaultScalarValue, FromInputValue, InputValue, ToInputValue};
+
+    use super::{format_iso8601, parse_iso8601};
+
+    #[test]
+    fn formats() {
+        for (negative, secs, nanos, expected) in vec![
+            (false, 0, 0, "PT0S"),
+            (false, 59, 0, "PT59S"),
+            (false, 60, 0, "PT1M"),
+            (false, 3600, 0, "PT1H"),
+            (false, 93_784, 500_000_000, "PT26H3M4.5S"),
+            (false, 0, 1, "PT0.000000001S"),
+            (true, 3_605, 0, "-PT1H5S"),
+        ] {
+            assert_eq!(format_iso8601(negative, secs, nanos), expected);
+        }
+    }
+
+    #[test]
+    fn parses() {
+        for (input, expected) in vec![
+            ("PT0S", (false, 0, 0)),
+            ("P1D", (false, 86_400, 0)),
+            ("P1W", (false, 604_800, 0)),
+            ("P1DT2H", (false, 93_600, 0)),
+            ("PT26H3M4.5S", (false, 93_784, 500_000_000)),
+            ("PT0,25S", (false, 0, 250_000_000)),
+            ("PT1.1234567891S", (false, 1, 123_456_789)),
+            ("-PT1M", (true, 60, 0)),
+            ("+PT90M", (false, 5_400, 0)),
+        ] {
+            assert_eq!(parse_iso8601(input), Ok(expected), "{}", input);
+        }
+    }
+
+    #[test]
+    fn rejects() {
+        for input in vec![
+            "", "P", "PT", "1H", "PT1", "P1Y", "P1M", "P1.5D", "PT1.5M2S", "PT2S1M", "PTH", "P1DT",
+            "P1MT1M", "PT1.S", "PT1S2S",
+        ] {
+            assert!(parse_iso8601(input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn roundtrips() {
+        let duration = Duration::new(93_784, 5_000_000);
+        let input: InputValue<DefaultScalarValue> = duration.to_input_value();
+
+        assert_eq!(input, InputValue::scalar("PT26H3M4.005S"));
+        assert_eq!(Duration::from_input_value(&input), Ok(duration));
+    }
+
+    #[test]
+    fn rejects_negative() {
+        let input: InputValue<DefaultScalarValue> = InputValue::scalar("-PT1S");
+
+        assert!(Duration::from_input_value(&input).is_err());
+    }
+}
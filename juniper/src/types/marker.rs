@@ -5,7 +5,16 @@
 //! traits are used. Encountering an error where one of these traits
 //! is involved implies that the construct is not valid in GraphQL.
 
-use crate::{GraphQLType, ScalarValue};
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, HashSet, VecDeque},
+    rc::Rc,
+    sync::Arc,
+};
+
+use indexmap::IndexSet;
+
+use crate::{GraphQLType, Nullable, ScalarValue};
 
 /// Maker object for GraphQL objects.
 ///
@@ -78,7 +87,7 @@ pub trait GraphQLUnion<S: ScalarValue>: GraphQLType<S> {
 /// implement this trait. The specification defines enum, scalar,
 /// object, union, and interface as output types.
 // TODO: Re-enable GraphQLType requirement in #682
-pub trait IsOutputType<S: ScalarValue> /*: GraphQLType<S>*/ {
+pub trait IsOutputType<S: ScalarValue> {
     /// An arbitrary function without meaning.
     ///
     /// May contain compile timed check logic which ensures that types
@@ -114,6 +123,20 @@ where
 {
 }
 
+impl<S, T> IsInputType<S> for Nullable<T>
+where
+    T: IsInputType<S>,
+    S: ScalarValue,
+{
+}
+
+impl<S, T> IsOutputType<S> for Nullable<T>
+where
+    T: IsOutputType<S>,
+    S: ScalarValue,
+{
+}
+
 impl<S, T> IsOutputType<S> for Vec<T>
 where
     T: IsOutputType<S>,
@@ -168,5 +191,126 @@ where
 {
 }
 
+impl<S, T> IsInputType<S> for Arc<T>
+where
+    T: IsInputType<S> + ?Sized,
+    S: ScalarValue,
+{
+}
+impl<S, T> IsOutputType<S> for Arc<T>
+where
+    T: IsOutputType<S> + ?Sized,
+    S: ScalarValue,
+{
+}
+
+impl<S, T> IsInputType<S> for Rc<T>
+where
+    T: IsInputType<S> + ?Sized,
+    S: ScalarValue,
+{
+}
+impl<S, T> IsOutputType<S> for Rc<T>
+where
+    T: IsOutputType<S> + ?Sized,
+    S: ScalarValue,
+{
+}
+
+impl<'c, S, T> IsInputType<S> for Cow<'c, T>
+where
+    T: IsInputType<S> + ToOwned + ?Sized,
+    S: ScalarValue,
+{
+}
+impl<'c, S, T> IsOutputType<S> for Cow<'c, T>
+where
+    T: IsOutputType<S> + ToOwned + ?Sized,
+    S: ScalarValue,
+{
+}
+
+impl<S, T> IsInputType<S> for VecDeque<T>
+where
+    T: IsInputType<S>,
+    S: ScalarValue,
+{
+}
+impl<S, T> IsOutputType<S> for VecDeque<T>
+where
+    T: IsOutputType<S>,
+    S: ScalarValue,
+{
+}
+
+impl<S, T, H> IsInputType<S> for HashSet<T, H>
+where
+    T: IsInputType<S>,
+    S: ScalarValue,
+{
+}
+impl<S, T, H> IsOutputType<S> for HashSet<T, H>
+where
+    T: IsOutputType<S>,
+    S: ScalarValue,
+{
+}
+
+impl<S, T> IsInputType<S> for BTreeSet<T>
+where
+    T: IsInputType<S>,
+    S: ScalarValue,
+{
+}
+impl<S, T> IsOutputType<S> for BTreeSet<T>
+where
+    T: IsOutputType<S>,
+    S: ScalarValue,
+{
+}
+
+impl<S, T, H> IsInputType<S> for IndexSet<T, H>
+where
+    T: IsInputType<S>,
+    S: ScalarValue,
+{
+}
+impl<S, T, H> IsOutputType<S> for IndexSet<T, H>
+where
+    T: IsOutputType<S>,
+    S: ScalarValue,
+{
+}
+
+impl<S, T, const N: usize> IsInputType<S> for [T; N]
+where
+    T: IsInputType<S>,
+    S: ScalarValue,
+{
+}
+impl<S, T, const N: usize> IsOutputType<S> for [T; N]
+where
+    T: IsOutputType<S>,
+    S: ScalarValue,
+{
+}
+
+#[cfg(feature = "smallvec")]
+impl<S, A> IsInputType<S> for smallvec::SmallVec<A>
+where
+    A: smallvec::Array,
+    A::Item: IsInputType<S>,
+    S: ScalarValue,
+{
+}
+#[cfg(feature = "smallvec")]
+impl<S, A> IsOutputType<S> for smallvec::SmallVec<A>
+where
+    A: smallvec::Array,
+    A::Item: IsOutputType<S>,
+    S: ScalarValue,
+{
+}
+
 impl<'a, S> IsInputType<S> for str where S: ScalarValue {}
 impl<'a, S> IsOutputType<S> for str where S: ScalarValue {}
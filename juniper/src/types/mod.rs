@@ -1,8 +1,10 @@
 pub mod async_await;
 pub mod base;
 pub mod containers;
+pub mod duration;
 pub mod marker;
 pub mod name;
+pub mod nullable;
 pub mod pointers;
 pub mod scalars;
 pub mod subscriptions;
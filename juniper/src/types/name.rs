@@ -46,6 +46,14 @@ impl Error for NameParseError {
     }
 }
 
+impl Name {
+    /// Wraps a name without validating it, so invalid names can be reported
+    /// by schema validation instead of panicking during type registration.
+    pub(crate) fn new_unchecked(name: &str) -> Self {
+        Name(name.to_owned())
+    }
+}
+
 impl FromStr for Name {
     type Err = NameParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
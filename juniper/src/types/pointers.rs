@@ -1,4 +1,4 @@
-use std::{fmt, sync::Arc};
+use std::{borrow::Cow, fmt, rc::Rc, sync::Arc};
 
 use crate::{
     ast::{FromInputValue, InputValue, Selection, ToInputValue},
@@ -93,11 +93,10 @@ where
     S: ScalarValue,
     T: FromInputValue<S>,
 {
-    fn from_input_value(v: &InputValue<S>) -> Option<Box<T>> {
-        match <T as FromInputValue<S>>::from_input_value(v) {
-            Some(v) => Some(Box::new(v)),
-            None => None,
-        }
+    type Error = T::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Box<T>, T::Error> {
+        <T as FromInputValue<S>>::from_input_value(v).map(Box::new)
     }
 }
 
@@ -283,6 +282,18 @@ where
     }
 }
 
+impl<T, S> FromInputValue<S> for Arc<T>
+where
+    S: ScalarValue,
+    T: FromInputValue<S>,
+{
+    type Error = T::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Arc<T>, T::Error> {
+        <T as FromInputValue<S>>::from_input_value(v).map(Arc::new)
+    }
+}
+
 impl<T, S> ToInputValue<S> for Arc<T>
 where
     S: fmt::Debug,
@@ -292,3 +303,187 @@ where
         (**self).to_input_value()
     }
 }
+
+impl<S, T> GraphQLType<S> for Rc<T>
+where
+    S: ScalarValue,
+    T: GraphQLType<S> + ?Sized,
+{
+    fn name(info: &Self::TypeInfo) -> Option<&str> {
+        T::name(info)
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        T::meta(info, registry)
+    }
+}
+
+impl<S, T> GraphQLValue<S> for Rc<T>
+where
+    S: ScalarValue,
+    T: GraphQLValue<S> + ?Sized,
+{
+    type Context = T::Context;
+    type TypeInfo = T::TypeInfo;
+
+    fn type_name<'i>(&self, info: &'i Self::TypeInfo) -> Option<&'i str> {
+        (**self).type_name(info)
+    }
+
+    fn resolve_into_type(
+        &self,
+        info: &Self::TypeInfo,
+        name: &str,
+        selection_set: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        (**self).resolve_into_type(info, name, selection_set, executor)
+    }
+
+    fn resolve_field(
+        &self,
+        info: &Self::TypeInfo,
+        field: &str,
+        args: &Arguments<S>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        (**self).resolve_field(info, field, args, executor)
+    }
+
+    fn resolve(
+        &self,
+        info: &Self::TypeInfo,
+        selection_set: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        (**self).resolve(info, selection_set, executor)
+    }
+}
+
+// `Rc` is not `Sync`, so it can't implement `GraphQLValueAsync`.
+
+impl<T, S> FromInputValue<S> for Rc<T>
+where
+    S: ScalarValue,
+    T: FromInputValue<S>,
+{
+    type Error = T::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Rc<T>, T::Error> {
+        <T as FromInputValue<S>>::from_input_value(v).map(Rc::new)
+    }
+}
+
+impl<T, S> ToInputValue<S> for Rc<T>
+where
+    S: fmt::Debug,
+    T: ToInputValue<S>,
+{
+    fn to_input_value(&self) -> InputValue<S> {
+        (**self).to_input_value()
+    }
+}
+
+impl<'c, S, T> GraphQLType<S> for Cow<'c, T>
+where
+    S: ScalarValue,
+    T: GraphQLType<S> + ToOwned + ?Sized,
+{
+    fn name(info: &Self::TypeInfo) -> Option<&str> {
+        T::name(info)
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        T::meta(info, registry)
+    }
+}
+
+impl<'c, S, T> GraphQLValue<S> for Cow<'c, T>
+where
+    S: ScalarValue,
+    T: GraphQLValue<S> + ToOwned + ?Sized,
+{
+    type Context = T::Context;
+    type TypeInfo = T::TypeInfo;
+
+    fn type_name<'i>(&self, info: &'i Self::TypeInfo) -> Option<&'i str> {
+        (**self).type_name(info)
+    }
+
+    fn resolve_into_type(
+        &self,
+        info: &Self::TypeInfo,
+        name: &str,
+        selection_set: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        (**self).resolve_into_type(info, name, selection_set, executor)
+    }
+
+    fn resolve_field(
+        &self,
+        info: &Self::TypeInfo,
+        field: &str,
+        args: &Arguments<S>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        (**self).resolve_field(info, field, args, executor)
+    }
+
+    fn resolve(
+        &self,
+        info: &Self::TypeInfo,
+        selection_set: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        (**self).resolve(info, selection_set, executor)
+    }
+}
+
+impl<'c, S, T> GraphQLValueAsync<S> for Cow<'c, T>
+where
+    T: GraphQLValueAsync<S> + ToOwned + ?Sized,
+    T::Owned: Sync,
+    T::TypeInfo: Sync,
+    T::Context: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    fn resolve_async<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> BoxFuture<'a, ExecutionResult<S>> {
+        (**self).resolve_async(info, selection_set, executor)
+    }
+}
+
+impl<'c, T, S> FromInputValue<S> for Cow<'c, T>
+where
+    S: ScalarValue,
+    T: ToOwned + ?Sized,
+    T::Owned: FromInputValue<S>,
+{
+    type Error = <T::Owned as FromInputValue<S>>::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Cow<'c, T>, Self::Error> {
+        <T::Owned as FromInputValue<S>>::from_input_value(v).map(Cow::Owned)
+    }
+}
+
+impl<'c, T, S> ToInputValue<S> for Cow<'c, T>
+where
+    S: fmt::Debug,
+    T: ToOwned + ?Sized,
+    for<'r> &'r T: ToInputValue<S>,
+{
+    fn to_input_value(&self) -> InputValue<S> {
+        (&**self).to_input_value()
+    }
+}
@@ -1,20 +1,30 @@
 use std::{
-    char, convert::From, fmt, marker::PhantomData, ops::Deref, rc::Rc, thread::JoinHandle, u32,
+    char,
+    convert::{From, TryFrom},
+    fmt,
+    marker::PhantomData,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    ops::Deref,
+    path::PathBuf,
+    rc::Rc,
+    thread::JoinHandle,
+    u32,
 };
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    ast::{InputValue, Selection, ToInputValue},
+    ast::{FromInputValue, InputValue, Selection, ToInputValue},
     executor::{ExecutionResult, Executor, Registry},
     parser::{LexerError, ParseError, ScalarToken, Token},
     schema::meta::MetaType,
     types::{
         async_await::GraphQLValueAsync,
         base::{GraphQLType, GraphQLValue},
+        marker::{IsInputType, IsOutputType},
         subscriptions::GraphQLSubscriptionValue,
     },
-    value::{ParseScalarResult, ScalarValue, Value},
+    value::{LongScalarValue, ParseScalarResult, ParseScalarValue, ScalarValue, Value},
 };
 
 /// An ID as defined by the GraphQL specification
@@ -59,7 +69,7 @@ where
         Value::scalar(self.0.clone())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<ID> {
+    fn from_input_value(v: &InputValue) -> Result<ID, String> {
         match *v {
             InputValue::Scalar(ref s) => s
                 .as_string()
@@ -67,6 +77,7 @@ where
                 .map(ID),
             _ => None,
         }
+        .ok_or_else(|| format!(r#"Expected "ID", found {}"#, v))
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -86,11 +97,12 @@ where
         Value::scalar(self.clone())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<String> {
+    fn from_input_value(v: &InputValue) -> Result<String, String> {
         match *v {
             InputValue::Scalar(ref s) => s.as_string(),
             _ => None,
         }
+        .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -275,11 +287,12 @@ where
         Value::scalar(*self)
     }
 
-    fn from_input_value(v: &InputValue) -> Option<bool> {
+    fn from_input_value(v: &InputValue) -> Result<bool, String> {
         match *v {
             InputValue::Scalar(ref b) => b.as_boolean(),
             _ => None,
         }
+        .ok_or_else(|| format!(r#"Expected "Boolean", found {}"#, v))
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -297,11 +310,12 @@ where
         Value::scalar(*self)
     }
 
-    fn from_input_value(v: &InputValue) -> Option<i32> {
+    fn from_input_value(v: &InputValue) -> Result<i32, String> {
         match *v {
             InputValue::Scalar(ref i) => i.as_int(),
             _ => None,
         }
+        .ok_or_else(|| format!(r#"Expected "Int", found {}"#, v))
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -324,11 +338,12 @@ where
         Value::scalar(*self)
     }
 
-    fn from_input_value(v: &InputValue) -> Option<f64> {
+    fn from_input_value(v: &InputValue) -> Result<f64, String> {
         match *v {
             InputValue::Scalar(ref s) => s.as_float(),
             _ => None,
         }
+        .ok_or_else(|| format!(r#"Expected "Float", found {}"#, v))
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -346,6 +361,285 @@ where
     }
 }
 
+/// Implements a 64 bit integer scalar for the scalar values implementing
+/// [`LongScalarValue`].
+///
+/// It's written by hand, as `graphql_scalar` can only implement scalars for all
+/// scalar values, which would conflict with the `i64` and `u64` scalars defined
+/// along with custom scalar values.
+macro_rules! long_scalar {
+    ($ty:ident, $name:literal, $description:literal, $as:ident, $from:ident) => {
+        impl<S> GraphQLType<S> for $ty
+        where
+            S: LongScalarValue,
+        {
+            fn name(_: &()) -> Option<&'static str> {
+                Some($name)
+            }
+
+            fn meta<'r>(info: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+            where
+                S: 'r,
+            {
+                registry
+                    .build_scalar_type::<Self>(info)
+                    .description($description)
+                    .into_meta()
+            }
+        }
+
+        impl<S> GraphQLValue<S> for $ty
+        where
+            S: LongScalarValue,
+        {
+            type Context = ();
+            type TypeInfo = ();
+
+            fn type_name<'i>(&self, info: &'i Self::TypeInfo) -> Option<&'i str> {
+                <Self as GraphQLType<S>>::name(info)
+            }
+
+            fn resolve(
+                &self,
+                _: &(),
+                _: Option<&[Selection<S>]>,
+                _: &Executor<Self::Context, S>,
+            ) -> ExecutionResult<S> {
+                Ok(Value::Scalar(S::$from(*self)))
+            }
+        }
+
+        impl<S> GraphQLValueAsync<S> for $ty
+        where
+            S: LongScalarValue + Send + Sync,
+        {
+            fn resolve_async<'a>(
+                &'a self,
+                info: &'a Self::TypeInfo,
+                selection_set: Option<&'a [Selection<S>]>,
+                executor: &'a Executor<Self::Context, S>,
+            ) -> crate::BoxFuture<'a, crate::ExecutionResult<S>> {
+                use futures::future;
+                Box::pin(future::ready(self.resolve(info, selection_set, executor)))
+            }
+        }
+
+        impl<S> ToInputValue<S> for $ty
+        where
+            S: LongScalarValue,
+        {
+            fn to_input_value(&self) -> InputValue<S> {
+                InputValue::Scalar(S::$from(*self))
+            }
+        }
+
+        impl<S> FromInputValue<S> for $ty
+        where
+            S: LongScalarValue,
+        {
+            type Error = String;
+
+            fn from_input_value(v: &InputValue<S>) -> Result<$ty, String> {
+                match *v {
+                    InputValue::Scalar(ref s) => {
+                        s.$as().or_else(|| s.as_str().and_then(|s| s.parse().ok()))
+                    }
+                    _ => None,
+                }
+                .ok_or_else(|| format!(r#"Expected "{}", found {}"#, $name, v))
+            }
+        }
+
+        impl<S> ParseScalarValue<S> for $ty
+        where
+            S: LongScalarValue,
+        {
+            fn from_str(value: ScalarToken<'_>) -> ParseScalarResult<'_, S> {
+                match value {
+                    ScalarToken::Int(v) => v
+                        .parse()
+                        .map_err(|_| ParseError::UnexpectedToken(Token::Scalar(value)))
+                        .map(S::$from),
+                    ScalarToken::String(v) => Ok(S::from(v.to_owned())),
+                    ScalarToken::Float(_) => Err(ParseError::UnexpectedToken(Token::Scalar(value))),
+                }
+            }
+        }
+
+        impl<S> IsInputType<S> for $ty where S: LongScalarValue {}
+
+        impl<S> IsOutputType<S> for $ty where S: LongScalarValue {}
+    };
+}
+
+long_scalar!(
+    i64,
+    "Long",
+    "A signed 64 bit integer. Accepted as a number or a string of digits.",
+    as_i64,
+    from_i64
+);
+long_scalar!(
+    u64,
+    "UnsignedLong",
+    "An unsigned 64 bit integer. Accepted as a number or a string of digits.",
+    as_u64,
+    from_u64
+);
+
+/// Implements an integer scalar narrower than (or, for `u32`, as wide as) `Int`.
+///
+/// Values are represented as `Int`s where they fit, and out-of-range inputs are
+/// rejected instead of being truncated.
+macro_rules! small_int_scalar {
+    ($ty:ident, $name:literal, $description:literal) => {
+        #[crate::graphql_scalar(name = $name, description = $description)]
+        impl<S> GraphQLScalar for $ty
+        where
+            S: ScalarValue,
+        {
+            fn resolve(&self) -> Value {
+                Value::Scalar(
+                    i32::try_from(*self).map_or_else(|_| S::from_i64(i64::from(*self)), S::from),
+                )
+            }
+
+            fn from_input_value(v: &InputValue) -> Result<$ty, String> {
+                let i = match *v {
+                    InputValue::Scalar(ref s) => s.as_i64(),
+                    _ => None,
+                }
+                .ok_or_else(|| format!(r#"Expected "{}", found {}"#, $name, v))?;
+                <$ty>::try_from(i).map_err(|_| {
+                    format!(
+                        r#"Expected "{}" between {} and {}, found {}"#,
+                        $name,
+                        <$ty>::MIN,
+                        <$ty>::MAX,
+                        i,
+                    )
+                })
+            }
+
+            fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+                if let ScalarToken::Int(v) = value {
+                    v.parse::<i64>()
+                        .map_err(|_| ParseError::UnexpectedToken(Token::Scalar(value)))
+                        .map(|i| i32::try_from(i).map_or_else(|_| S::from_i64(i), S::from))
+                } else {
+                    Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+                }
+            }
+        }
+    };
+}
+
+small_int_scalar!(i8, "Byte", "A signed 8 bit integer.");
+small_int_scalar!(u8, "UnsignedByte", "An unsigned 8 bit integer.");
+small_int_scalar!(i16, "Short", "A signed 16 bit integer.");
+small_int_scalar!(u16, "UnsignedShort", "An unsigned 16 bit integer.");
+small_int_scalar!(u32, "UnsignedInt", "An unsigned 32 bit integer.");
+
+#[crate::graphql_scalar(name = "Char", description = "A single Unicode character.")]
+impl<S> GraphQLScalar for char
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(self.to_string())
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<char, String> {
+        let mut chars = v
+            .as_string_value()
+            .ok_or_else(|| format!(r#"Expected "Char", found {}"#, v))?
+            .chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(format!(r#"Expected a single character, found {}"#, v)),
+        }
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        <String as ParseScalarValue<S>>::from_str(value)
+    }
+}
+
+/// Implements a string scalar for a type parsed with `FromStr` and formatted
+/// with `Display`.
+macro_rules! from_str_scalar {
+    ($ty:ident, $name:literal, $description:literal, $expected:literal) => {
+        #[crate::graphql_scalar(name = $name, description = $description)]
+        impl<S> GraphQLScalar for $ty
+        where
+            S: ScalarValue,
+        {
+            fn resolve(&self) -> Value {
+                Value::scalar(self.to_string())
+            }
+
+            fn from_input_value(v: &InputValue) -> Result<$ty, String> {
+                v.as_string_value()
+                    .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
+                    .and_then(|s| {
+                        s.parse()
+                            .map_err(|e| format!(r#"Expected {}, found "{}": {}"#, $expected, s, e))
+                    })
+            }
+
+            fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+                <String as ParseScalarValue<S>>::from_str(value)
+            }
+        }
+    };
+}
+
+from_str_scalar!(
+    IpAddr,
+    "IpAddr",
+    "An IPv4 or IPv6 address, e.g. `192.168.0.1` or `::1`.",
+    "IP address"
+);
+from_str_scalar!(
+    Ipv4Addr,
+    "Ipv4Addr",
+    "An IPv4 address, e.g. `192.168.0.1`.",
+    "IPv4 address"
+);
+from_str_scalar!(
+    Ipv6Addr,
+    "Ipv6Addr",
+    "An IPv6 address, e.g. `::1`.",
+    "IPv6 address"
+);
+from_str_scalar!(
+    SocketAddr,
+    "SocketAddr",
+    "An IP address with a port, e.g. `192.168.0.1:8080` or `[::1]:8080`.",
+    "socket address"
+);
+
+#[crate::graphql_scalar(name = "Path", description = "A file system path.")]
+impl<S> GraphQLScalar for PathBuf
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        // Paths aren't guaranteed to be valid UTF-8.
+        Value::scalar(self.to_string_lossy().into_owned())
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<PathBuf, String> {
+        v.as_string_value()
+            .map(PathBuf::from)
+            .ok_or_else(|| format!(r#"Expected "String", found {}"#, v))
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        <String as ParseScalarValue<S>>::from_str(value)
+    }
+}
+
 /// Utility type to define read-only schemas
 ///
 /// If you instantiate `RootNode` with this as the mutation, no mutation will be
@@ -8,27 +8,33 @@ use crate::{
 };
 use std::collections::HashSet;
 
-pub fn is_valid_literal_value<S>(
+/// Checks whether `arg_value` is a valid literal of the type `arg_type`
+///
+/// An invalid value is reported as `Err(Some(reason))` when it was rejected by
+/// a `FromInputValue` implementation, and as `Err(None)` when it doesn't have
+/// the shape of the type at all.
+pub fn validate_literal_value<S>(
     schema: &SchemaType<S>,
     arg_type: &TypeType<S>,
     arg_value: &InputValue<S>,
-) -> bool
+) -> Result<(), Option<String>>
 where
     S: ScalarValue,
 {
     match *arg_type {
         TypeType::NonNull(ref inner) => {
             if arg_value.is_null() {
-                false
+                Err(None)
             } else {
-                is_valid_literal_value(schema, inner, arg_value)
+                validate_literal_value(schema, inner, arg_value)
             }
         }
         TypeType::List(ref inner) => match *arg_value {
-            InputValue::List(ref items) => items
-                .iter()
-                .all(|i| is_valid_literal_value(schema, inner, &i.item)),
-            ref v => is_valid_literal_value(schema, inner, v),
+            InputValue::List(ref items) => items.iter().enumerate().try_for_each(|(i, item)| {
+                validate_literal_value(schema, inner, &item.item)
+                    .map_err(|e| e.map(|reason| format!("In element #{}: {}", i, reason)))
+            }),
+            ref v => validate_literal_value(schema, inner, v),
         },
         TypeType::Concrete(t) => {
             // Even though InputValue::String can be parsed into an enum, they
@@ -36,19 +42,29 @@ where
             if let (&InputValue::Scalar(_), Some(&MetaType::Enum(EnumMeta { .. }))) =
                 (arg_value, arg_type.to_concrete())
             {
-                return false;
+                return Err(None);
             }
 
             match *arg_value {
-                InputValue::Null | InputValue::Variable(_) => true,
+                InputValue::Null | InputValue::Variable(_) => Ok(()),
                 ref v @ InputValue::Scalar(_) | ref v @ InputValue::Enum(_) => {
-                    if let Some(parse_fn) = t.input_value_parse_fn() {
-                        parse_fn(v)
-                    } else {
-                        false
+                    match t.input_value_parse_fn() {
+                        Some(parse_fn) => parse_fn(v).map_err(|e| Some(e.message().to_owned())),
+                        None => Err(None),
+                    }
+                }
+                // Scalars may accept structured values, e.g. `serde_json::Value`.
+                // Literals with variables are checked once they are substituted.
+                ref v @ InputValue::List(_) | ref v @ InputValue::Object(_)
+                    if matches!(*t, MetaType::Scalar(_)) =>
+                {
+                    match t.input_value_parse_fn() {
+                        Some(_) if !v.referenced_variables().is_empty() => Ok(()),
+                        Some(parse_fn) => parse_fn(v).map_err(|_| None),
+                        None => Err(None),
                     }
                 }
-                InputValue::List(_) => false,
+                InputValue::List(_) => Err(None),
                 InputValue::Object(ref obj) => {
                     if let MetaType::InputObject(InputObjectMeta {
                         ref input_fields, ..
@@ -65,7 +81,7 @@ where
                             })
                             .collect::<HashSet<_>>();
 
-                        let all_types_ok = obj.iter().all(|&(ref key, ref value)| {
+                        obj.iter().try_for_each(|&(ref key, ref value)| {
                             remaining_required_fields.remove(&key.item);
                             if let Some(ref arg_type) = input_fields
                                 .iter()
@@ -73,15 +89,23 @@ where
                                 .map(|f| schema.make_type(&f.arg_type))
                                 .next()
                             {
-                                is_valid_literal_value(schema, arg_type, &value.item)
+                                validate_literal_value(schema, arg_type, &value.item).map_err(|e| {
+                                    e.map(|reason| {
+                                        format!(r#"In field "{}": {}"#, key.item, reason)
+                                    })
+                                })
                             } else {
-                                false
+                                Err(None)
                             }
-                        });
+                        })?;
 
-                        all_types_ok && remaining_required_fields.is_empty()
+                        if remaining_required_fields.is_empty() {
+                            Ok(())
+                        } else {
+                            Err(None)
+                        }
                     } else {
-                        false
+                        Err(None)
                     }
                 }
             }
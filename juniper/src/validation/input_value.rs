@@ -148,14 +148,16 @@ where
                     if e.is_empty() {
                         // All the fields didn't have errors, see if there is an
                         // overall error when parsing the input value.
-                        if !(iom.try_parse_fn)(value) {
+                        if let Err(e) = (iom.try_parse_fn)(value) {
                             errors.push(unification_error(
                                 var_name,
                                 var_pos,
                                 &path,
                                 &format!(
-                                    r#"Expected input of type "{}". Got: "{}""#,
-                                    iom.name, value
+                                    r#"Expected input of type "{}". Got: "{}": {}"#,
+                                    iom.name,
+                                    value,
+                                    e.message(),
                                 ),
                             ));
                         }
@@ -180,33 +182,12 @@ fn unify_scalar<'a, S>(
 where
     S: fmt::Debug,
 {
-    let mut errors: Vec<RuleError> = vec![];
-
-    if !(meta.try_parse_fn)(value) {
-        return vec![unification_error(
-            var_name,
-            var_pos,
-            path,
-            &format!(r#"Expected "{}""#, meta.name),
-        )];
+    // Lists and objects are left to the scalar as well, as some scalars (e.g.
+    // `serde_json::Value`) accept structured values.
+    match (meta.try_parse_fn)(value) {
+        Ok(()) => vec![],
+        Err(e) => vec![unification_error(var_name, var_pos, path, e.message())],
     }
-
-    match *value {
-        InputValue::List(_) => errors.push(unification_error(
-            var_name,
-            var_pos,
-            path,
-            &format!(r#"Expected "{}", found list"#, meta.name),
-        )),
-        InputValue::Object(_) => errors.push(unification_error(
-            var_name,
-            var_pos,
-            path,
-            &format!(r#"Expected "{}", found object"#, meta.name),
-        )),
-        _ => (),
-    }
-    errors
 }
 
 fn unify_enum<'a, S>(
@@ -10,7 +10,7 @@ mod visitor;
 #[cfg(test)]
 pub(crate) mod test_harness;
 
-pub(crate) use self::rules::visit_all_rules;
+pub(crate) use self::rules::{visit_all_rules, visit_disable_introspection};
 pub use self::{
     context::{RuleError, ValidatorContext},
     input_value::validate_input_values,
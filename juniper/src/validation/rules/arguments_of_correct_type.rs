@@ -2,7 +2,7 @@ use crate::{
     ast::{Directive, Field, InputValue},
     parser::Spanning,
     schema::meta::Argument,
-    types::utilities::is_valid_literal_value,
+    types::utilities::validate_literal_value,
     validation::{ValidatorContext, Visitor},
     value::ScalarValue,
 };
@@ -57,9 +57,13 @@ where
         {
             let meta_type = ctx.schema.make_type(&argument_meta.arg_type);
 
-            if !is_valid_literal_value(ctx.schema, &meta_type, &arg_value.item) {
+            if let Err(reason) = validate_literal_value(ctx.schema, &meta_type, &arg_value.item) {
                 ctx.report_error(
-                    &error_message(arg_name.item, &format!("{}", argument_meta.arg_type)),
+                    &error_message(
+                        arg_name.item,
+                        &format!("{}", argument_meta.arg_type),
+                        reason.as_deref(),
+                    ),
                     &[arg_value.start],
                 );
             }
@@ -67,11 +71,15 @@ where
     }
 }
 
-fn error_message(arg_name: &str, type_name: &str) -> String {
-    format!(
+fn error_message(arg_name: &str, type_name: &str, reason: Option<&str>) -> String {
+    let message = format!(
         "Invalid value for argument \"{}\", expected type \"{}\"",
         arg_name, type_name
-    )
+    );
+    match reason {
+        Some(reason) => format!("{}: {}", message, reason),
+        None => message,
+    }
 }
 
 #[cfg(test)]
@@ -110,7 +118,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("nonNullIntArg", "Int!"),
+                &error_message("nonNullIntArg", "Int!", None),
                 &[SourcePosition::new(97, 3, 50)],
             )],
         );
@@ -240,7 +248,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("stringArg", "String"),
+                &error_message("stringArg", "String", Some(r#"Expected "String", found 1"#)),
                 &[SourcePosition::new(89, 3, 42)],
             )],
         );
@@ -258,7 +266,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("stringArg", "String"),
+                &error_message("stringArg", "String", Some(r#"Expected "String", found 1"#)),
                 &[SourcePosition::new(89, 3, 42)],
             )],
         );
@@ -276,7 +284,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("stringArg", "String"),
+                &error_message(
+                    "stringArg",
+                    "String",
+                    Some(r#"Expected "String", found true"#),
+                ),
                 &[SourcePosition::new(89, 3, 42)],
             )],
         );
@@ -294,7 +306,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("stringArg", "String"),
+                &error_message(
+                    "stringArg",
+                    "String",
+                    Some(r#"Expected "String", found BAR"#),
+                ),
                 &[SourcePosition::new(89, 3, 42)],
             )],
         );
@@ -312,7 +328,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("intArg", "Int"),
+                &error_message("intArg", "Int", Some(r#"Expected "Int", found "3""#)),
                 &[SourcePosition::new(83, 3, 36)],
             )],
         );
@@ -330,7 +346,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("intArg", "Int"),
+                &error_message("intArg", "Int", Some(r#"Expected "Int", found FOO"#)),
                 &[SourcePosition::new(83, 3, 36)],
             )],
         );
@@ -348,7 +364,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("intArg", "Int"),
+                &error_message("intArg", "Int", Some(r#"Expected "Int", found 3"#)),
                 &[SourcePosition::new(83, 3, 36)],
             )],
         );
@@ -366,7 +382,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("intArg", "Int"),
+                &error_message("intArg", "Int", Some(r#"Expected "Int", found 3.333"#)),
                 &[SourcePosition::new(83, 3, 36)],
             )],
         );
@@ -384,7 +400,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("floatArg", "Float"),
+                &error_message(
+                    "floatArg",
+                    "Float",
+                    Some(r#"Expected "Float", found "3.333""#),
+                ),
                 &[SourcePosition::new(87, 3, 40)],
             )],
         );
@@ -402,7 +422,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("floatArg", "Float"),
+                &error_message("floatArg", "Float", Some(r#"Expected "Float", found true"#)),
                 &[SourcePosition::new(87, 3, 40)],
             )],
         );
@@ -420,7 +440,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("floatArg", "Float"),
+                &error_message("floatArg", "Float", Some(r#"Expected "Float", found FOO"#)),
                 &[SourcePosition::new(87, 3, 40)],
             )],
         );
@@ -438,7 +458,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("booleanArg", "Boolean"),
+                &error_message(
+                    "booleanArg",
+                    "Boolean",
+                    Some(r#"Expected "Boolean", found 2"#),
+                ),
                 &[SourcePosition::new(91, 3, 44)],
             )],
         );
@@ -456,7 +480,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("booleanArg", "Boolean"),
+                &error_message(
+                    "booleanArg",
+                    "Boolean",
+                    Some(r#"Expected "Boolean", found 1"#),
+                ),
                 &[SourcePosition::new(91, 3, 44)],
             )],
         );
@@ -474,7 +502,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("booleanArg", "Boolean"),
+                &error_message(
+                    "booleanArg",
+                    "Boolean",
+                    Some(r#"Expected "Boolean", found "true""#),
+                ),
                 &[SourcePosition::new(91, 3, 44)],
             )],
         );
@@ -492,7 +524,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("booleanArg", "Boolean"),
+                &error_message(
+                    "booleanArg",
+                    "Boolean",
+                    Some(r#"Expected "Boolean", found TRUE"#),
+                ),
                 &[SourcePosition::new(91, 3, 44)],
             )],
         );
@@ -510,7 +546,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("idArg", "ID"),
+                &error_message("idArg", "ID", Some(r#"Expected "ID", found 1"#)),
                 &[SourcePosition::new(81, 3, 34)],
             )],
         );
@@ -528,7 +564,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("idArg", "ID"),
+                &error_message("idArg", "ID", Some(r#"Expected "ID", found true"#)),
                 &[SourcePosition::new(81, 3, 34)],
             )],
         );
@@ -546,7 +582,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("idArg", "ID"),
+                &error_message("idArg", "ID", Some(r#"Expected "ID", found SOMETHING"#)),
                 &[SourcePosition::new(81, 3, 34)],
             )],
         );
@@ -564,7 +600,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("dogCommand", "DogCommand"),
+                &error_message("dogCommand", "DogCommand", None),
                 &[SourcePosition::new(79, 3, 44)],
             )],
         );
@@ -582,7 +618,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("dogCommand", "DogCommand"),
+                &error_message("dogCommand", "DogCommand", None),
                 &[SourcePosition::new(79, 3, 44)],
             )],
         );
@@ -600,7 +636,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("dogCommand", "DogCommand"),
+                &error_message("dogCommand", "DogCommand", None),
                 &[SourcePosition::new(79, 3, 44)],
             )],
         );
@@ -618,7 +654,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("dogCommand", "DogCommand"),
+                &error_message("dogCommand", "DogCommand", None),
                 &[SourcePosition::new(79, 3, 44)],
             )],
         );
@@ -636,7 +672,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("dogCommand", "DogCommand"),
+                &error_message(
+                    "dogCommand",
+                    "DogCommand",
+                    Some(r#"Expected "DogCommand", found JUGGLE"#),
+                ),
                 &[SourcePosition::new(79, 3, 44)],
             )],
         );
@@ -654,7 +694,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("dogCommand", "DogCommand"),
+                &error_message(
+                    "dogCommand",
+                    "DogCommand",
+                    Some(r#"Expected "DogCommand", found sit"#),
+                ),
                 &[SourcePosition::new(79, 3, 44)],
             )],
         );
@@ -714,7 +758,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("stringListArg", "[String]"),
+                &error_message(
+                    "stringListArg",
+                    "[String]",
+                    Some(r#"In element #1: Expected "String", found 2"#),
+                ),
                 &[SourcePosition::new(97, 3, 50)],
             )],
         );
@@ -732,7 +780,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("stringListArg", "[String]"),
+                &error_message(
+                    "stringListArg",
+                    "[String]",
+                    Some(r#"Expected "String", found 1"#),
+                ),
                 &[SourcePosition::new(97, 3, 50)],
             )],
         );
@@ -891,11 +943,11 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("req2", "Int!"),
+                    &error_message("req2", "Int!", Some(r#"Expected "Int", found "two""#)),
                     &[SourcePosition::new(82, 3, 35)],
                 ),
                 RuleError::new(
-                    &error_message("req1", "Int!"),
+                    &error_message("req1", "Int!", Some(r#"Expected "Int", found "one""#)),
                     &[SourcePosition::new(95, 3, 48)],
                 ),
             ],
@@ -914,7 +966,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("req1", "Int!"),
+                &error_message("req1", "Int!", Some(r#"Expected "Int", found "one""#)),
                 &[SourcePosition::new(82, 3, 35)],
             )],
         );
@@ -1028,7 +1080,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("complexArg", "ComplexInput"),
+                &error_message("complexArg", "ComplexInput", None),
                 &[SourcePosition::new(91, 3, 44)],
             )],
         );
@@ -1049,7 +1101,13 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("complexArg", "ComplexInput"),
+                &error_message(
+                    "complexArg",
+                    "ComplexInput",
+                    Some(
+                        r#"In field "stringListField": In element #1: Expected "String", found 2"#,
+                    ),
+                ),
                 &[SourcePosition::new(91, 3, 44)],
             )],
         );
@@ -1070,7 +1128,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("complexArg", "ComplexInput"),
+                &error_message("complexArg", "ComplexInput", None),
                 &[SourcePosition::new(91, 3, 44)],
             )],
         );
@@ -1106,11 +1164,11 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("if", "Boolean!"),
+                    &error_message("if", "Boolean!", Some(r#"Expected "Boolean", found "yes""#)),
                     &[SourcePosition::new(38, 2, 27)],
                 ),
                 RuleError::new(
-                    &error_message("if", "Boolean!"),
+                    &error_message("if", "Boolean!", Some(r#"Expected "Boolean", found ENUM"#)),
                     &[SourcePosition::new(74, 3, 27)],
                 ),
             ],
@@ -1,7 +1,7 @@
 use crate::{
     ast::VariableDefinition,
     parser::Spanning,
-    types::utilities::is_valid_literal_value,
+    types::utilities::validate_literal_value,
     validation::{ValidatorContext, Visitor},
     value::ScalarValue,
 };
@@ -35,9 +35,13 @@ where
             } else {
                 let meta_type = ctx.schema.make_type(&var_def.var_type.item);
 
-                if !is_valid_literal_value(ctx.schema, &meta_type, var_value) {
+                if let Err(reason) = validate_literal_value(ctx.schema, &meta_type, var_value) {
                     ctx.report_error(
-                        &type_error_message(var_name.item, &format!("{}", var_def.var_type.item)),
+                        &type_error_message(
+                            var_name.item,
+                            &format!("{}", var_def.var_type.item),
+                            reason.as_deref(),
+                        ),
                         &[*start],
                     );
                 }
@@ -46,11 +50,15 @@ where
     }
 }
 
-fn type_error_message(arg_name: &str, type_name: &str) -> String {
-    format!(
+fn type_error_message(arg_name: &str, type_name: &str, reason: Option<&str>) -> String {
+    let message = format!(
         "Invalid default value for argument \"{}\", expected type \"{}\"",
         arg_name, type_name
-    )
+    );
+    match reason {
+        Some(reason) => format!("{}: {}", message, reason),
+        None => message,
+    }
 }
 
 fn non_null_error_message(arg_name: &str, type_name: &str) -> String {
@@ -147,15 +155,19 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &type_error_message("a", "Int"),
+                    &type_error_message("a", "Int", Some(r#"Expected "Int", found "one""#)),
                     &[SourcePosition::new(61, 2, 22)],
                 ),
                 RuleError::new(
-                    &type_error_message("b", "String"),
+                    &type_error_message("b", "String", Some(r#"Expected "String", found 4"#)),
                     &[SourcePosition::new(93, 3, 25)],
                 ),
                 RuleError::new(
-                    &type_error_message("c", "ComplexInput"),
+                    &type_error_message(
+                        "c",
+                        "ComplexInput",
+                        Some(r#"Expected "ComplexInput", found "notverycomplex""#),
+                    ),
                     &[SourcePosition::new(127, 4, 31)],
                 ),
             ],
@@ -172,7 +184,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &type_error_message("a", "ComplexInput"),
+                &type_error_message("a", "ComplexInput", None),
                 &[SourcePosition::new(57, 1, 56)],
             )],
         );
@@ -188,7 +200,11 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &type_error_message("a", "[String]"),
+                &type_error_message(
+                    "a",
+                    "[String]",
+                    Some(r#"In element #1: Expected "String", found 2"#),
+                ),
                 &[SourcePosition::new(44, 1, 43)],
             )],
         );
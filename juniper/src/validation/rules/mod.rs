@@ -1,5 +1,6 @@
 mod arguments_of_correct_type;
 mod default_values_of_correct_type;
+mod disable_introspection;
 mod fields_on_correct_type;
 mod fragments_on_composite_types;
 mod known_argument_names;
@@ -62,3 +63,14 @@ where
 
     visit(&mut mv, ctx, doc)
 }
+
+pub(crate) fn visit_disable_introspection<'a, S: Debug>(
+    ctx: &mut ValidatorContext<'a, S>,
+    doc: &'a Document<S>,
+) where
+    S: ScalarValue,
+{
+    let mut mv = MultiVisitorNil.with(self::disable_introspection::factory());
+
+    visit(&mut mv, ctx, doc)
+}
@@ -8,7 +8,9 @@ mod scalar;
 
 pub use self::object::Object;
 
-pub use self::scalar::{DefaultScalarValue, ParseScalarResult, ParseScalarValue, ScalarValue};
+pub use self::scalar::{
+    DefaultScalarValue, LongScalarValue, ParseScalarResult, ParseScalarValue, ScalarValue,
+};
 
 /// Serializable value returned from query and field execution.
 ///
@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{convert::TryFrom, fmt};
 
 use serde::{de, ser::Serialize};
 
@@ -23,10 +23,13 @@ pub trait ParseScalarValue<S = DefaultScalarValue> {
 /// replace the default representation with something that better fits their
 /// needs.
 /// There is a custom derive (`#[derive(juniper::GraphQLScalarValue)]`) available that implements
-/// most of the required traits automatically for a enum representing a scalar value.
-/// This derives needs a additional annotation of the form
-/// `#[juniper(visitor = "VisitorType")]` to specify a type that implements
-/// `serde::de::Visitor` and that is used to deserialize the value.
+/// the required traits automatically for a enum representing a scalar value.
+/// Each variant has to contain exactly one value, and `#[graphql(...)]` attributes mark the variants
+/// holding an `int` (`i32`), a `float` (`f64`), a `string` (`String`) and a `boolean` (`bool`).
+/// Variants holding a `long` (`i64`) and an `unsigned_long` (`u64`) may be marked as well. The
+/// derive then generates the whole `ScalarValue` implementation along with a `serde` visitor
+/// named after the enum (e.g. `MyScalarValueVisitor`) deserializing it. Variants marked as
+/// `as_string`, e.g. `#[graphql(long, as_string)]`, are serialized as strings.
 ///
 /// # Implementing a new scalar value representation
 /// The preferred way to define a new scalar value representation is
@@ -35,136 +38,28 @@ pub trait ParseScalarValue<S = DefaultScalarValue> {
 /// The following example introduces an new variant that is able to store 64 bit integers.
 ///
 /// ```
-/// # use std::fmt;
-/// # use serde::{de, Deserialize, Deserializer};
 /// # use juniper::ScalarValue;
 /// #
 /// #[derive(Debug, Clone, PartialEq, juniper::GraphQLScalarValue)]
 /// enum MyScalarValue {
+///     #[graphql(int)]
 ///     Int(i32),
+///     #[graphql(long)]
 ///     Long(i64),
+///     #[graphql(float)]
 ///     Float(f64),
+///     #[graphql(string)]
 ///     String(String),
+///     #[graphql(boolean)]
 ///     Boolean(bool),
 /// }
 ///
-/// impl ScalarValue for MyScalarValue {
-///     type Visitor = MyScalarValueVisitor;
-///
-///      fn as_int(&self) -> Option<i32> {
-///        match *self {
-///            MyScalarValue::Int(ref i) => Some(*i),
-///            _ => None,
-///        }
-///    }
-///
-///    fn as_string(&self) -> Option<String> {
-///        match *self {
-///            MyScalarValue::String(ref s) => Some(s.clone()),
-///            _ => None,
-///        }
-///    }
-///
-///    fn as_str(&self) -> Option<&str> {
-///        match *self {
-///            MyScalarValue::String(ref s) => Some(s.as_str()),
-///            _ => None,
-///        }
-///    }
-///
-///    fn as_float(&self) -> Option<f64> {
-///        match *self {
-///            MyScalarValue::Int(ref i) => Some(*i as f64),
-///            MyScalarValue::Float(ref f) => Some(*f),
-///            _ => None,
-///        }
-///    }
-///
-///    fn as_boolean(&self) -> Option<bool> {
-///        match *self {
-///            MyScalarValue::Boolean(ref b) => Some(*b),
-///            _ => None,
-///        }
-///    }
-/// }
-///
-/// #[derive(Default)]
-/// struct MyScalarValueVisitor;
-///
-/// impl<'de> de::Visitor<'de> for MyScalarValueVisitor {
-///     type Value = MyScalarValue;
-///
-///     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-///         formatter.write_str("a valid input value")
-///     }
-///
-///     fn visit_bool<E>(self, value: bool) -> Result<MyScalarValue, E> {
-///         Ok(MyScalarValue::Boolean(value))
-///     }
-///
-///     fn visit_i32<E>(self, value: i32) -> Result<MyScalarValue, E>
-///     where
-///         E: de::Error,
-///     {
-///         Ok(MyScalarValue::Int(value))
-///     }
-///
-///     fn visit_i64<E>(self, value: i64) -> Result<MyScalarValue, E>
-///     where
-///         E: de::Error,
-///     {
-///         if value <= i32::max_value() as i64 {
-///             self.visit_i32(value as i32)
-///         } else {
-///             Ok(MyScalarValue::Long(value))
-///         }
-///     }
-///
-///     fn visit_u32<E>(self, value: u32) -> Result<MyScalarValue, E>
-///     where
-///         E: de::Error,
-///     {
-///         if value <= i32::max_value() as u32 {
-///             self.visit_i32(value as i32)
-///         } else {
-///             self.visit_u64(value as u64)
-///         }
-///     }
-///
-///     fn visit_u64<E>(self, value: u64) -> Result<MyScalarValue, E>
-///     where
-///         E: de::Error,
-///     {
-///         if value <= i64::max_value() as u64 {
-///             self.visit_i64(value as i64)
-///         } else {
-///             // Browser's JSON.stringify serialize all numbers having no
-///             // fractional part as integers (no decimal point), so we
-///             // must parse large integers as floating point otherwise
-///             // we would error on transferring large floating point
-///             // numbers.
-///             Ok(MyScalarValue::Float(value as f64))
-///         }
-///     }
-///
-///     fn visit_f64<E>(self, value: f64) -> Result<MyScalarValue, E> {
-///         Ok(MyScalarValue::Float(value))
-///     }
-///
-///     fn visit_str<E>(self, value: &str) -> Result<MyScalarValue, E>
-///     where
-///         E: de::Error,
-///     {
-///         self.visit_string(value.into())
-///     }
-///
-///     fn visit_string<E>(self, value: String) -> Result<MyScalarValue, E> {
-///         Ok(MyScalarValue::String(value))
-///     }
-/// }
-///
-/// # fn main() {}
+/// assert_eq!(MyScalarValue::from_i64(1 << 40).as_i64(), Some(1 << 40));
+/// assert_eq!(MyScalarValue::Long(42).as_float(), Some(42.0));
 /// ```
+///
+/// If none of the variants is marked, only the conversions, `Display` and
+/// `Serialize` are derived, and `ScalarValue` has to be implemented manually.
 pub trait ScalarValue:
     fmt::Debug
     + fmt::Display
@@ -205,6 +100,46 @@ pub trait ScalarValue:
     /// types with 32 bit or less to an integer if requested.
     fn as_int(&self) -> Option<i32>;
 
+    /// Convert the given scalar value into a signed 64 bit integer value
+    ///
+    /// This function is used for implementing `GraphQLValue` for `i64` for all
+    /// scalar values. Implementations should convert all supported integer
+    /// types fitting into 64 bit to such an integer if requested. The default
+    /// implementation only handles the values returned by `as_int`.
+    fn as_i64(&self) -> Option<i64> {
+        self.as_int().map(i64::from)
+    }
+
+    /// Convert the given scalar value into an unsigned 64 bit integer value
+    ///
+    /// This function is used for implementing `GraphQLValue` for `u64` for all
+    /// scalar values. The default implementation only handles the non-negative
+    /// values returned by `as_i64`.
+    fn as_u64(&self) -> Option<u64> {
+        self.as_i64().and_then(|i| u64::try_from(i).ok())
+    }
+
+    /// Construct a scalar value from a signed 64 bit integer
+    ///
+    /// This function is used for implementing `GraphQLValue` for `i64` for the
+    /// scalar values implementing [`LongScalarValue`], so it decides whether
+    /// such integers are represented as numbers or as strings. The default
+    /// implementation produces an integer value if `i` fits into 32 bit, and a
+    /// string value otherwise, so no precision is lost for scalar value types
+    /// without 64 bit support.
+    fn from_i64(i: i64) -> Self {
+        i32::try_from(i).map_or_else(|_| Self::from(i.to_string()), Self::from)
+    }
+
+    /// Construct a scalar value from an unsigned 64 bit integer
+    ///
+    /// This function is used for implementing `GraphQLValue` for `u64` for the
+    /// scalar values implementing [`LongScalarValue`]. The default
+    /// implementation behaves like `from_i64`.
+    fn from_u64(u: u64) -> Self {
+        i32::try_from(u).map_or_else(|_| Self::from(u.to_string()), Self::from)
+    }
+
     /// Convert the given scalar value into a string value
     ///
     /// This function is used for implementing `GraphQLValue` for `String` for all
@@ -232,122 +167,66 @@ pub trait ScalarValue:
     fn as_boolean(&self) -> Option<bool>;
 }
 
+/// A scalar value supporting the built-in `Long` and `UnsignedLong` scalars
+///
+/// `i64` and `u64` are only GraphQL scalars for the scalar values implementing
+/// this trait, and are resolved with [`ScalarValue::from_i64`] and
+/// [`ScalarValue::from_u64`]: a scalar value without a `long` variant
+/// represents integers beyond 32 bit as strings, so clients unable to handle
+/// integers beyond 2^53 don't lose precision.
+///
+/// Scalar values defining their own `i64` or `u64` scalar must not implement it,
+/// as both implementations would conflict.
+///
+/// ```
+/// # use juniper::LongScalarValue;
+/// #
+/// #[derive(Debug, Clone, PartialEq, juniper::GraphQLScalarValue)]
+/// enum MyScalarValue {
+///     #[graphql(int)]
+///     Int(i32),
+///     #[graphql(float)]
+///     Float(f64),
+///     #[graphql(string)]
+///     String(String),
+///     #[graphql(boolean)]
+///     Boolean(bool),
+/// }
+///
+/// impl LongScalarValue for MyScalarValue {}
+/// ```
+pub trait LongScalarValue: ScalarValue {}
+
 /// The default scalar value representation in juniper
 ///
 /// This types closely follows the graphql specification.
+///
+/// `Long` and `UnsignedLong` values are serialized as numbers, or as strings
+/// with the `long-as-string` feature, for clients that can't handle integers
+/// beyond 2^53.
 #[derive(Debug, PartialEq, Clone, GraphQLScalarValue)]
 #[allow(missing_docs)]
 pub enum DefaultScalarValue {
+    #[graphql(int)]
     Int(i32),
+    #[cfg_attr(not(feature = "long-as-string"), graphql(long))]
+    #[cfg_attr(feature = "long-as-string", graphql(long, as_string))]
+    Long(i64),
+    #[cfg_attr(not(feature = "long-as-string"), graphql(unsigned_long))]
+    #[cfg_attr(feature = "long-as-string", graphql(unsigned_long, as_string))]
+    UnsignedLong(u64),
+    #[graphql(float)]
     Float(f64),
+    #[graphql(string)]
     String(String),
+    #[graphql(boolean)]
     Boolean(bool),
 }
 
-impl ScalarValue for DefaultScalarValue {
-    type Visitor = DefaultScalarValueVisitor;
-
-    fn as_int(&self) -> Option<i32> {
-        match *self {
-            DefaultScalarValue::Int(ref i) => Some(*i),
-            _ => None,
-        }
-    }
-
-    fn as_float(&self) -> Option<f64> {
-        match *self {
-            DefaultScalarValue::Int(ref i) => Some(*i as f64),
-            DefaultScalarValue::Float(ref f) => Some(*f),
-            _ => None,
-        }
-    }
-
-    fn as_str(&self) -> Option<&str> {
-        match *self {
-            DefaultScalarValue::String(ref s) => Some(s.as_str()),
-            _ => None,
-        }
-    }
-
-    fn as_string(&self) -> Option<String> {
-        match *self {
-            DefaultScalarValue::String(ref s) => Some(s.clone()),
-            _ => None,
-        }
-    }
-
-    fn as_boolean(&self) -> Option<bool> {
-        match *self {
-            DefaultScalarValue::Boolean(ref b) => Some(*b),
-            _ => None,
-        }
-    }
-}
-
 impl<'a> From<&'a str> for DefaultScalarValue {
     fn from(s: &'a str) -> Self {
         DefaultScalarValue::String(s.into())
     }
 }
 
-#[derive(Default, Clone, Copy, Debug)]
-pub struct DefaultScalarValueVisitor;
-
-impl<'de> de::Visitor<'de> for DefaultScalarValueVisitor {
-    type Value = DefaultScalarValue;
-
-    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("a valid input value")
-    }
-
-    fn visit_bool<E>(self, value: bool) -> Result<DefaultScalarValue, E> {
-        Ok(DefaultScalarValue::Boolean(value))
-    }
-
-    fn visit_i64<E>(self, value: i64) -> Result<DefaultScalarValue, E>
-    where
-        E: de::Error,
-    {
-        if value >= i64::from(i32::min_value()) && value <= i64::from(i32::max_value()) {
-            Ok(DefaultScalarValue::Int(value as i32))
-        } else {
-            // Browser's JSON.stringify serialize all numbers having no
-            // fractional part as integers (no decimal point), so we
-            // must parse large integers as floating point otherwise
-            // we would error on transferring large floating point
-            // numbers.
-            Ok(DefaultScalarValue::Float(value as f64))
-        }
-    }
-
-    fn visit_u64<E>(self, value: u64) -> Result<DefaultScalarValue, E>
-    where
-        E: de::Error,
-    {
-        if value <= i32::max_value() as u64 {
-            self.visit_i64(value as i64)
-        } else {
-            // Browser's JSON.stringify serialize all numbers having no
-            // fractional part as integers (no decimal point), so we
-            // must parse large integers as floating point otherwise
-            // we would error on transferring large floating point
-            // numbers.
-            Ok(DefaultScalarValue::Float(value as f64))
-        }
-    }
-
-    fn visit_f64<E>(self, value: f64) -> Result<DefaultScalarValue, E> {
-        Ok(DefaultScalarValue::Float(value))
-    }
-
-    fn visit_str<E>(self, value: &str) -> Result<DefaultScalarValue, E>
-    where
-        E: de::Error,
-    {
-        self.visit_string(value.into())
-    }
-
-    fn visit_string<E>(self, value: String) -> Result<DefaultScalarValue, E> {
-        Ok(DefaultScalarValue::String(value))
-    }
-}
+impl LongScalarValue for DefaultScalarValue {}
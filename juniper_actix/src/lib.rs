@@ -41,52 +41,51 @@ Check the LICENSE file for details.
 #![doc(html_root_url = "https://docs.rs/juniper_actix/0.1.0")]
 
 use actix_web::{
-    error::{ErrorBadRequest, ErrorMethodNotAllowed, ErrorUnsupportedMediaType},
-    http::{header::CONTENT_TYPE, Method},
+    error::{ErrorMethodNotAllowed, ErrorNotAcceptable, InternalError},
+    http::{
+        header::{ACCEPT, CACHE_CONTROL, CONTENT_TYPE},
+        Method, StatusCode,
+    },
     web, Error, FromRequest, HttpRequest, HttpResponse,
 };
 use juniper::{
     http::{
-        graphiql::graphiql_source, playground::playground_source, GraphQLBatchRequest,
-        GraphQLRequest,
+        graphiql::{graphiql_source, GraphiQLSettings},
+        parse::{parse_get_request, parse_post_request_with_limits, ParseRequestError},
+        playground::{playground_source, PlaygroundSettings},
+        HttpConfig, ResponseContentType,
     },
     ScalarValue,
 };
-use serde::Deserialize;
-
-#[derive(Deserialize, Clone, PartialEq, Debug)]
-#[serde(deny_unknown_fields)]
-struct GetGraphQLRequest {
-    query: String,
-    #[serde(rename = "operationName")]
-    operation_name: Option<String>,
-    variables: Option<String>,
-}
 
-impl<S> From<GetGraphQLRequest> for GraphQLRequest<S>
+/// Actix Web GraphQL Handler for GET and POST requests
+pub async fn graphql_handler<Query, Mutation, Subscription, CtxT, S>(
+    schema: &juniper::RootNode<'static, Query, Mutation, Subscription, S>,
+    context: &CtxT,
+    req: HttpRequest,
+    payload: actix_web::web::Payload,
+) -> Result<HttpResponse, Error>
 where
-    S: ScalarValue,
+    Query: juniper::GraphQLTypeAsync<S, Context = CtxT>,
+    Query::TypeInfo: Sync,
+    Mutation: juniper::GraphQLTypeAsync<S, Context = CtxT>,
+    Mutation::TypeInfo: Sync,
+    Subscription: juniper::GraphQLSubscriptionType<S, Context = CtxT>,
+    Subscription::TypeInfo: Sync,
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync,
 {
-    fn from(get_req: GetGraphQLRequest) -> Self {
-        let GetGraphQLRequest {
-            query,
-            operation_name,
-            variables,
-        } = get_req;
-        let variables = match variables {
-            Some(variables) => Some(serde_json::from_str(&variables).unwrap()),
-            None => None,
-        };
-        Self::new(query, operation_name, variables)
-    }
+    graphql_handler_with_config(schema, context, req, payload, &HttpConfig::default()).await
 }
 
-/// Actix Web GraphQL Handler for GET and POST requests
-pub async fn graphql_handler<Query, Mutation, Subscription, CtxT, S>(
+/// Actix Web GraphQL Handler for GET and POST requests, executed along with the given
+/// [`HttpConfig`]
+pub async fn graphql_handler_with_config<Query, Mutation, Subscription, CtxT, S>(
     schema: &juniper::RootNode<'static, Query, Mutation, Subscription, S>,
     context: &CtxT,
     req: HttpRequest,
     payload: actix_web::web::Payload,
+    config: &HttpConfig<CtxT, S>,
 ) -> Result<HttpResponse, Error>
 where
     Query: juniper::GraphQLTypeAsync<S, Context = CtxT>,
@@ -99,8 +98,10 @@ where
     S: ScalarValue + Send + Sync,
 {
     match *req.method() {
-        Method::POST => post_graphql_handler(schema, context, req, payload).await,
-        Method::GET => get_graphql_handler(schema, context, req).await,
+        Method::POST => {
+            post_graphql_handler_with_config(schema, context, req, payload, config).await
+        }
+        Method::GET => get_graphql_handler_with_config(schema, context, req, config).await,
         _ => Err(ErrorMethodNotAllowed(
             "GraphQL requests can only be sent with GET or POST",
         )),
@@ -122,17 +123,38 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
-    let get_req = web::Query::<GetGraphQLRequest>::from_query(req.query_string())?;
-    let req = GraphQLRequest::from(get_req.into_inner());
-    let gql_response = req.execute(schema, context).await;
+    get_graphql_handler_with_config(schema, context, req, &HttpConfig::default()).await
+}
+
+/// Actix GraphQL Handler for GET requests, executed along with the given [`HttpConfig`]
+pub async fn get_graphql_handler_with_config<Query, Mutation, Subscription, CtxT, S>(
+    schema: &juniper::RootNode<'static, Query, Mutation, Subscription, S>,
+    context: &CtxT,
+    req: HttpRequest,
+    config: &HttpConfig<CtxT, S>,
+) -> Result<HttpResponse, Error>
+where
+    Query: juniper::GraphQLTypeAsync<S, Context = CtxT>,
+    Query::TypeInfo: Sync,
+    Mutation: juniper::GraphQLTypeAsync<S, Context = CtxT>,
+    Mutation::TypeInfo: Sync,
+    Subscription: juniper::GraphQLSubscriptionType<S, Context = CtxT>,
+    Subscription::TypeInfo: Sync,
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    let response_content_type = response_content_type(&req)?;
+    let req = parse_get_request::<S>(req.query_string()).map_err(request_error)?;
+    let gql_response = req.execute_with_config(schema, context, config).await;
     let body_response = serde_json::to_string(&gql_response)?;
-    let mut response = match gql_response.is_ok() {
-        true => HttpResponse::Ok(),
-        false => HttpResponse::BadRequest(),
-    };
-    Ok(response
-        .content_type("application/json")
-        .body(body_response))
+    let mut response = HttpResponse::build(response_status(
+        gql_response.status_code(response_content_type),
+    ));
+    response.content_type(response_content_type.as_str());
+    if let Some(cache_control) = gql_response.cache_policy().and_then(|p| p.header_value()) {
+        response.header(CACHE_CONTROL, cache_control);
+    }
+    Ok(response.body(body_response))
 }
 
 /// Actix GraphQL Handler for POST requests
@@ -152,32 +174,67 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
-    let content_type_header = req
+    post_graphql_handler_with_config(schema, context, req, payload, &HttpConfig::default()).await
+}
+
+/// Actix GraphQL Handler for POST requests, executed along with the given [`HttpConfig`]
+pub async fn post_graphql_handler_with_config<Query, Mutation, Subscription, CtxT, S>(
+    schema: &juniper::RootNode<'static, Query, Mutation, Subscription, S>,
+    context: &CtxT,
+    req: HttpRequest,
+    payload: actix_web::web::Payload,
+    config: &HttpConfig<CtxT, S>,
+) -> Result<HttpResponse, Error>
+where
+    Query: juniper::GraphQLTypeAsync<S, Context = CtxT>,
+    Query::TypeInfo: Sync,
+    Mutation: juniper::GraphQLTypeAsync<S, Context = CtxT>,
+    Mutation::TypeInfo: Sync,
+    Subscription: juniper::GraphQLSubscriptionType<S, Context = CtxT>,
+    Subscription::TypeInfo: Sync,
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    let response_content_type = response_content_type(&req)?;
+    let content_type = req
         .headers()
         .get(CONTENT_TYPE)
         .and_then(|hv| hv.to_str().ok());
-    let req = match content_type_header {
-        Some("application/json") => {
-            let body = String::from_request(&req, &mut payload.into_inner()).await?;
-            serde_json::from_str::<GraphQLBatchRequest<S>>(&body).map_err(ErrorBadRequest)
-        }
-        Some("application/graphql") => {
-            let body = String::from_request(&req, &mut payload.into_inner()).await?;
-            Ok(GraphQLBatchRequest::Single(GraphQLRequest::new(
-                body, None, None,
-            )))
-        }
-        _ => Err(ErrorUnsupportedMediaType(
-            "GraphQL requests should have content type `application/json` or `application/graphql`",
-        )),
-    }?;
-    let gql_batch_response = req.execute(schema, context).await;
+    let body = web::Bytes::from_request(&req, &mut payload.into_inner()).await?;
+    let req = parse_post_request_with_limits::<S>(content_type, &body, config.request_limits())
+        .map_err(request_error)?;
+    let gql_batch_response = req.execute_with_config(schema, context, config).await;
     let gql_response = serde_json::to_string(&gql_batch_response)?;
-    let mut response = match gql_batch_response.is_ok() {
-        true => HttpResponse::Ok(),
-        false => HttpResponse::BadRequest(),
-    };
-    Ok(response.content_type("application/json").body(gql_response))
+    let mut response = HttpResponse::build(response_status(
+        gql_batch_response.status_code(response_content_type),
+    ));
+    response.content_type(response_content_type.as_str());
+    if let Some(cache_control) = gql_batch_response
+        .cache_policy()
+        .and_then(|p| p.header_value())
+    {
+        response.header(CACHE_CONTROL, cache_control);
+    }
+    Ok(response.body(gql_response))
+}
+
+fn response_content_type(req: &HttpRequest) -> Result<ResponseContentType, Error> {
+    let accept = req.headers().get(ACCEPT).and_then(|hv| hv.to_str().ok());
+    ResponseContentType::negotiate(accept).ok_or_else(|| {
+        ErrorNotAcceptable(
+            "GraphQL responses are only available as application/graphql-response+json \
+             or application/json",
+        )
+    })
+}
+
+fn response_status(code: u16) -> StatusCode {
+    StatusCode::from_u16(code).unwrap_or(StatusCode::BAD_REQUEST)
+}
+
+fn request_error(err: ParseRequestError) -> Error {
+    let status = StatusCode::from_u16(err.status_code()).unwrap_or(StatusCode::BAD_REQUEST);
+    InternalError::new(err, status).into()
 }
 
 /// Create a handler that replies with an HTML page containing GraphiQL. This does not handle routing, so you can mount it on any endpoint
@@ -202,6 +259,30 @@ pub async fn graphiql_handler(
         .body(html))
 }
 
+/// Create a handler that replies with an HTML page containing GraphiQL, configured with the given settings. This does not handle routing, so you can mount it on any endpoint
+///
+/// For example, to load the assets of GraphiQL from another route instead of public CDNs:
+///
+/// ```
+/// # use juniper::http::graphiql::GraphiQLSettings;
+/// # use juniper_actix::graphiql_handler_with_settings;
+/// # use actix_web::{web, App};
+///
+/// let settings = GraphiQLSettings::new("/graphql").assets_url("/static/graphiql");
+/// let app = App::new()
+///          .route("/", web::get().to(move || {
+///              let settings = settings.clone();
+///              async move { graphiql_handler_with_settings(&settings).await }
+///          }));
+/// ```
+pub async fn graphiql_handler_with_settings(
+    settings: &GraphiQLSettings,
+) -> Result<HttpResponse, Error> {
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(settings.source()))
+}
+
 /// Create a handler that replies with an HTML page containing GraphQL Playground. This does not handle routing, so you cant mount it on any endpoint.
 pub async fn playground_handler(
     graphql_endpoint_url: &str,
@@ -213,6 +294,15 @@ pub async fn playground_handler(
         .body(html))
 }
 
+/// Create a handler that replies with an HTML page containing GraphQL Playground, configured with the given settings. This does not handle routing, so you can mount it on any endpoint.
+pub async fn playground_handler_with_settings(
+    settings: &PlaygroundSettings,
+) -> Result<HttpResponse, Error> {
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(settings.source()))
+}
+
 /// `juniper_actix` subscriptions handler implementation.
 /// Cannot be merged to `juniper_actix` yet as GraphQL over WS[1]
 /// is not fully supported in current implementation.
@@ -479,13 +569,137 @@ pub mod subscriptions {
     impl std::error::Error for Error {}
 }
 
+/// `juniper_actix` handler serving subscriptions over Server-Sent Events, following the
+/// "distinct connections" mode of the GraphQL over SSE protocol[1].
+///
+/// [1]: https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md
+#[cfg(feature = "sse")]
+pub mod sse {
+    use std::sync::Arc;
+
+    use actix_web::{
+        error::{ErrorBadRequest, ErrorMethodNotAllowed},
+        http::{
+            header::{CACHE_CONTROL, CONTENT_TYPE},
+            Method,
+        },
+        web, Error, FromRequest, HttpRequest, HttpResponse,
+    };
+
+    use juniper::{
+        futures::stream::StreamExt,
+        http::{
+            parse::{parse_get_request, parse_post_request_with_limits},
+            GraphQLBatchRequest, HttpConfig,
+        },
+        GraphQLSubscriptionType, GraphQLTypeAsync, RootNode, ScalarValue,
+    };
+    use juniper_graphql_sse::EventStreamConfig;
+
+    use super::request_error;
+
+    /// Serves a subscription over Server-Sent Events.
+    ///
+    /// Subscriptions are sent either as `GET` requests, or as `POST` requests with a single
+    /// operation, and each is served with its own event stream. The `config` argument sets how
+    /// often keep-alives are sent on these streams.
+    pub async fn sse_handler<Query, Mutation, Subscription, CtxT, S>(
+        req: HttpRequest,
+        payload: web::Payload,
+        root_node: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+        context: CtxT,
+        config: &EventStreamConfig,
+    ) -> Result<HttpResponse, Error>
+    where
+        Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Query::TypeInfo: Send + Sync,
+        Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Mutation::TypeInfo: Send + Sync,
+        Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+        Subscription::TypeInfo: Send + Sync,
+        CtxT: Send + Sync + 'static,
+        S: ScalarValue + Send + Sync + 'static,
+    {
+        sse_handler_with_config(req, payload, root_node, context, config, Arc::default()).await
+    }
+
+    /// Serves a subscription over Server-Sent Events, checking it against the safelist of the
+    /// given [`HttpConfig`], if any.
+    ///
+    /// Its [`RequestLimits`](juniper::http::RequestLimits) also apply to the bodies of `POST`
+    /// requests.
+    pub async fn sse_handler_with_config<Query, Mutation, Subscription, CtxT, S>(
+        req: HttpRequest,
+        payload: web::Payload,
+        root_node: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+        context: CtxT,
+        config: &EventStreamConfig,
+        http_config: Arc<HttpConfig<CtxT, S>>,
+    ) -> Result<HttpResponse, Error>
+    where
+        Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Query::TypeInfo: Send + Sync,
+        Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Mutation::TypeInfo: Send + Sync,
+        Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+        Subscription::TypeInfo: Send + Sync,
+        CtxT: Send + Sync + 'static,
+        S: ScalarValue + Send + Sync + 'static,
+    {
+        let request = match *req.method() {
+            Method::GET => parse_get_request::<S>(req.query_string()).map_err(request_error)?,
+            Method::POST => {
+                let content_type = req
+                    .headers()
+                    .get(CONTENT_TYPE)
+                    .and_then(|hv| hv.to_str().ok());
+                let body = web::Bytes::from_request(&req, &mut payload.into_inner()).await?;
+                let limits = http_config.request_limits();
+                match parse_post_request_with_limits::<S>(content_type, &body, limits)
+                    .map_err(request_error)?
+                {
+                    GraphQLBatchRequest::Single(request) => request,
+                    GraphQLBatchRequest::Batch(_) => {
+                        return Err(ErrorBadRequest(
+                            "Batch requests can't be served over Server-Sent Events",
+                        ))
+                    }
+                }
+            }
+            _ => {
+                return Err(ErrorMethodNotAllowed(
+                    "GraphQL requests can only be sent with GET or POST",
+                ))
+            }
+        };
+
+        let frames = juniper_graphql_sse::subscribe_with_config(
+            root_node,
+            request,
+            Arc::new(context),
+            config,
+            http_config,
+        );
+        Ok(HttpResponse::Ok()
+            .content_type(juniper_graphql_sse::CONTENT_TYPE)
+            .header(CACHE_CONTROL, "no-cache")
+            .streaming(frames.map(|frame| Ok::<_, Error>(web::Bytes::from(frame)))))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use actix_web::{dev::ServiceResponse, http, http::header::CONTENT_TYPE, test, App};
     use juniper::{
         futures::stream::StreamExt,
-        http::tests::{run_http_test_suite, HttpIntegration, TestResponse},
+        http::{
+            tests::{
+                run_http_test_suite, run_multipart_test_suite, HttpIntegration,
+                MultipartIntegration, TestResponse,
+            },
+            GraphQLBatchRequest,
+        },
         tests::fixtures::starwars::schema::{Database, Query},
         EmptyMutation, EmptySubscription, RootNode,
     };
@@ -599,6 +813,104 @@ mod tests {
         assert!(body.contains("GraphQLPlayground.init(root, { endpoint: '/dogs-api/graphql', subscriptionEndpoint: '/dogs-api/subscriptions' })"));
     }
 
+    #[actix_web::rt::test]
+    async fn endpoints_with_settings_return_configured_sources() {
+        async fn graphiql() -> Result<HttpResponse, Error> {
+            graphiql_handler_with_settings(
+                &GraphiQLSettings::new("/graphql")
+                    .header("Authorization", "Bearer token")
+                    .assets_url("/static/graphiql"),
+            )
+            .await
+        }
+        async fn playground() -> Result<HttpResponse, Error> {
+            playground_handler_with_settings(
+                &PlaygroundSettings::new("/graphql").assets_url("/static/playground"),
+            )
+            .await
+        }
+        let mut app = test::init_service(
+            App::new()
+                .route("/graphiql", web::get().to(graphiql))
+                .route("/playground", web::get().to(playground)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/graphiql").to_request();
+        let mut resp = test::call_service(&mut app, req).await;
+        let body = take_response_body_string(&mut resp).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert!(body.contains(r#"<script src="/static/graphiql/graphiql.min.js"></script>"#));
+        assert!(body.contains("{'Authorization': 'Bearer token'}"));
+
+        let req = test::TestRequest::get().uri("/playground").to_request();
+        let mut resp = test::call_service(&mut app, req).await;
+        let body = take_response_body_string(&mut resp).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(CONTENT_TYPE).unwrap().to_str().unwrap(),
+            "text/html; charset=utf-8"
+        );
+        assert!(body.contains(r#"<script src="/static/playground/middleware.js"></script>"#));
+    }
+
+    #[cfg(feature = "sse")]
+    #[actix_web::rt::test]
+    async fn sse_handler_serves_event_streams() {
+        use juniper_graphql_sse::EventStreamConfig;
+        use std::sync::Arc;
+
+        async fn stream(
+            req: HttpRequest,
+            payload: web::Payload,
+            schema: web::Data<Arc<Schema>>,
+        ) -> Result<HttpResponse, Error> {
+            let config = EventStreamConfig::new();
+            sse::sse_handler(
+                req,
+                payload,
+                schema.get_ref().clone(),
+                Database::new(),
+                &config,
+            )
+            .await
+        }
+        let schema: Schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+        let mut app = test::init_service(
+            App::new()
+                .data(Arc::new(schema))
+                .route("/stream", web::to(stream)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/stream?query=%7Bhero%7Bname%7D%7D")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(CONTENT_TYPE).unwrap().to_str().unwrap(),
+            "text/event-stream"
+        );
+        assert_eq!(
+            test::read_body(resp).await,
+            "event: next\ndata: {\"errors\":[{\"message\":\"Expected subscription, got query\"}]}\n\n\
+             event: complete\ndata:\n\n",
+        );
+
+        let req = test::TestRequest::post()
+            .header("content-type", "application/json")
+            .set_payload(r#"[{ "query": "{ hero { name } }" }, { "query": "{ hero { id } }" }]"#)
+            .uri("/stream")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+    }
+
     #[actix_web::rt::test]
     async fn graphql_post_works_json_post() {
         let schema: Schema = RootNode::new(
@@ -750,6 +1062,34 @@ mod tests {
                     .uri(url),
             )
         }
+
+        fn get_with_accept(&self, url: &str, accept: &str) -> TestResponse {
+            self.make_request(test::TestRequest::get().header("accept", accept).uri(url))
+        }
+
+        fn post_json_with_accept(&self, url: &str, body: &str, accept: &str) -> TestResponse {
+            self.make_request(
+                test::TestRequest::post()
+                    .header("content-type", "application/json")
+                    .header("accept", accept)
+                    .set_payload(body.to_string())
+                    .uri(url),
+            )
+        }
+    }
+
+    impl MultipartIntegration for TestActixWebIntegration {
+        fn post_multipart(&self, url: &str, boundary: &str, body: &str) -> TestResponse {
+            self.make_request(
+                test::TestRequest::post()
+                    .header(
+                        "content-type",
+                        format!("multipart/form-data; boundary={}", boundary),
+                    )
+                    .set_payload(body.to_string())
+                    .uri(url),
+            )
+        }
     }
 
     async fn make_test_response(mut resp: ServiceResponse) -> TestResponse {
@@ -766,6 +1106,7 @@ mod tests {
     #[test]
     fn test_actix_web_integration() {
         run_http_test_suite(&TestActixWebIntegration);
+        run_multipart_test_suite(&TestActixWebIntegration);
     }
 }
 
@@ -0,0 +1,97 @@
+//! Parsing and code generation of the `cache_control(max_age = 60, scope = "PUBLIC")` argument,
+//! used by this crate.
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens, TokenStreamExt as _};
+use syn::{
+    parse::{Parse, ParseStream},
+    token,
+};
+
+use crate::common::parse::{
+    attr::{err, OptionExt as _},
+    ParseBufferExt as _,
+};
+
+/// Parsed [`CacheHint`] of a [GraphQL type][1] or field.
+///
+/// [`CacheHint`]: juniper::CacheHint
+/// [1]: https://spec.graphql.org/June2018/#sec-Types
+#[derive(Clone, Debug, Default)]
+pub(crate) struct CacheControl {
+    /// Explicitly specified number of seconds the result may be cached for.
+    max_age: Option<syn::LitInt>,
+
+    /// Explicitly specified [`CacheScope`] of the result.
+    ///
+    /// [`CacheScope`]: juniper::CacheScope
+    scope: Option<syn::Ident>,
+}
+
+impl Parse for CacheControl {
+    /// Parses the parenthesized arguments following the `cache_control` identifier.
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut output = Self::default();
+
+        let content;
+        syn::parenthesized!(content in input);
+        while !content.is_empty() {
+            let ident = content.parse_any_ident()?;
+            match ident.to_string().as_str() {
+                "max_age" => {
+                    content.parse::<token::Eq>()?;
+                    let lit = content.parse::<syn::LitInt>()?;
+                    lit.base10_parse::<u32>()?;
+                    output
+                        .max_age
+                        .replace(lit)
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "scope" => {
+                    content.parse::<token::Eq>()?;
+                    let lit = content.parse::<syn::LitStr>()?;
+                    let scope = match lit.value().to_uppercase().as_str() {
+                        "PUBLIC" => syn::Ident::new("Public", lit.span()),
+                        "PRIVATE" => syn::Ident::new("Private", lit.span()),
+                        _ => {
+                            return Err(syn::Error::new(
+                                lit.span(),
+                                "expected `PUBLIC` or `PRIVATE` cache scope",
+                            ))
+                        }
+                    };
+                    output
+                        .scope
+                        .replace(scope)
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                name => {
+                    return Err(err::unknown_arg(&ident, name));
+                }
+            }
+            content.try_parse::<token::Comma>()?;
+        }
+
+        Ok(output)
+    }
+}
+
+impl ToTokens for CacheControl {
+    fn to_tokens(&self, into: &mut TokenStream) {
+        let max_age = match &self.max_age {
+            Some(max_age) => quote! { Some(#max_age) },
+            None => quote! { None },
+        };
+        let scope = match &self.scope {
+            Some(scope) => quote! { Some(::juniper::CacheScope::#scope) },
+            None => quote! { None },
+        };
+
+        into.append_all(&[quote! {
+            ::juniper::CacheHint {
+                max_age: #max_age,
+                scope: #scope,
+            }
+        }]);
+    }
+}
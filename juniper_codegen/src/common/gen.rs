@@ -45,3 +45,18 @@ pub(crate) fn async_resolving_code(ty: Option<&syn::Type>) -> TokenStream {
         }))
     }
 }
+
+/// Generate the [visibility predicate][1] of a [GraphQL type][2], field or enum value, calling the
+/// given `visible` function with the `juniper::Context` of the request.
+///
+/// The request is denied whenever its `juniper::Context` isn't of the given `context` type.
+///
+/// [1]: juniper::meta::VisibilityFn
+/// [2]: https://spec.graphql.org/June2018/#sec-Types
+pub(crate) fn visibility_predicate(visible: &syn::ExprPath, context: &TokenStream) -> TokenStream {
+    quote! {
+        |context: &dyn ::std::any::Any| {
+            context.downcast_ref::<#context>().map_or(false, #visible)
+        }
+    }
+}
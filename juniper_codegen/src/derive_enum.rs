@@ -88,6 +88,13 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
                 );
             }
 
+            if let Some(hint) = field_attrs.cache_control {
+                error.unsupported_attribute_within(
+                    hint.span_ident(),
+                    UnsupportedAttribute::CacheControl,
+                );
+            }
+
             Some(util::GraphQLTypeDefinitionField {
                 name,
                 _type,
@@ -98,6 +105,8 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
                 is_type_inferred: true,
                 is_async: false,
                 default: None,
+                visible: field_attrs.visible.map(SpanContainer::into_inner),
+                cache_control: None,
                 span,
             })
         })
@@ -124,6 +133,10 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
         error.unsupported_attribute(scalar.span_ident(), UnsupportedAttribute::Scalar);
     }
 
+    if let Some(hint) = &attrs.cache_control {
+        error.unsupported_attribute(hint.span_ident(), UnsupportedAttribute::CacheControl);
+    }
+
     if !attrs.is_internal && name.starts_with("__") {
         error.no_double_underscore(if let Some(name) = attrs.name {
             name.span_ident()
@@ -140,6 +153,8 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
         context: attrs.context.map(SpanContainer::into_inner),
         scalar: None,
         description: attrs.description.map(SpanContainer::into_inner),
+        visible: attrs.visible.map(SpanContainer::into_inner),
+        cache_control: None,
         fields,
         // NOTICE: only unit variants allow -> no generics possible
         generics: syn::Generics::default(),
@@ -67,6 +67,17 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
                 )
             }
 
+            if let Some(span) = field_attrs.visible {
+                error.unsupported_attribute_within(span.span_ident(), UnsupportedAttribute::Visible)
+            }
+
+            if let Some(hint) = field_attrs.cache_control {
+                error.unsupported_attribute_within(
+                    hint.span_ident(),
+                    UnsupportedAttribute::CacheControl,
+                );
+            }
+
             if name.starts_with("__") {
                 error.no_double_underscore(if let Some(name) = field_attrs.name {
                     name.span_ident()
@@ -94,6 +105,8 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
                 is_type_inferred: true,
                 is_async: false,
                 default,
+                visible: None,
+                cache_control: None,
                 span,
             })
         })
@@ -117,6 +130,10 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
         });
     }
 
+    if let Some(hint) = &attrs.cache_control {
+        error.unsupported_attribute(hint.span_ident(), UnsupportedAttribute::CacheControl);
+    }
+
     if let Some(duplicates) =
         crate::util::duplicate::Duplicate::find_by_key(&fields, |field| field.name.as_str())
     {
@@ -139,6 +156,8 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
         context: attrs.context.map(SpanContainer::into_inner),
         scalar: attrs.scalar.map(SpanContainer::into_inner),
         description: attrs.description.map(SpanContainer::into_inner),
+        visible: attrs.visible.map(SpanContainer::into_inner),
+        cache_control: None,
         fields,
         generics: ast.generics,
         interfaces: vec![],
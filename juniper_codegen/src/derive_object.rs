@@ -86,6 +86,8 @@ pub fn build_derive_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::R
                 default: None,
                 is_type_inferred: true,
                 is_async: false,
+                visible: field_attrs.visible.map(SpanContainer::into_inner),
+                cache_control: field_attrs.cache_control.map(SpanContainer::into_inner),
                 span,
             })
         })
@@ -121,6 +123,8 @@ pub fn build_derive_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::R
         context: attrs.context.map(SpanContainer::into_inner),
         scalar: attrs.scalar.map(SpanContainer::into_inner),
         description: attrs.description.map(SpanContainer::into_inner),
+        visible: attrs.visible.map(SpanContainer::into_inner),
+        cache_control: attrs.cache_control.map(SpanContainer::into_inner),
         fields,
         generics: ast.generics,
         interfaces: attrs
@@ -63,11 +63,112 @@ impl TransparentAttributes {
     }
 }
 
+/// Role of an enum variant in the derived `ScalarValue` implementation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum VariantRole {
+    Int,
+    Long,
+    UnsignedLong,
+    Float,
+    String,
+    Boolean,
+}
+
+impl VariantRole {
+    fn from_ident(ident: &syn::Ident) -> syn::Result<Self> {
+        Ok(match ident.to_string().as_str() {
+            "int" => Self::Int,
+            "long" => Self::Long,
+            "unsigned_long" => Self::UnsignedLong,
+            "float" => Self::Float,
+            "string" => Self::String,
+            "boolean" => Self::Boolean,
+            _ => return Err(syn::Error::new(ident.span(), "unknown attribute")),
+        })
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Int => "int",
+            Self::Long => "long",
+            Self::UnsignedLong => "unsigned_long",
+            Self::Float => "float",
+            Self::String => "string",
+            Self::Boolean => "boolean",
+        }
+    }
+}
+
+/// Variants of a scalar value enum, by their role in the derived
+/// `ScalarValue` implementation.
+struct VariantRoles<'a> {
+    int: &'a syn::Ident,
+    long: Option<&'a syn::Ident>,
+    unsigned_long: Option<&'a syn::Ident>,
+    float: &'a syn::Ident,
+    string: &'a syn::Ident,
+    boolean: &'a syn::Ident,
+}
+
+impl<'a> VariantRoles<'a> {
+    /// Collects the roles from `#[graphql(...)]` attributes on the variants.
+    ///
+    /// Returns `None` if no variant is marked, in which case `ScalarValue`
+    /// has to be implemented manually.
+    fn from_variants(data: &'a syn::DataEnum, error: &GraphQLScope) -> syn::Result<Option<Self>> {
+        let mut marked: Vec<(VariantRole, &'a syn::Ident)> = vec![];
+        for variant in &data.variants {
+            if let Some(attr) = util::find_graphql_attr(&variant.attrs) {
+                let idents = attr.parse_args_with(
+                    syn::punctuated::Punctuated::<syn::Ident, token::Comma>::parse_terminated,
+                )?;
+                for ident in idents.iter().filter(|ident| *ident != "as_string") {
+                    let role = VariantRole::from_ident(ident)?;
+                    if marked.iter().any(|(r, _)| *r == role) {
+                        return Err(error.custom_error(
+                            ident.span(),
+                            format!("only one variant may be marked as `{}`", role.name()),
+                        ));
+                    }
+                    marked.push((role, &variant.ident));
+                }
+            }
+        }
+
+        if marked.is_empty() {
+            return Ok(None);
+        }
+
+        let find = |role| marked.iter().find(|(r, _)| *r == role).map(|(_, v)| *v);
+        let require = |role: VariantRole| {
+            find(role).ok_or_else(|| {
+                error.custom_error(
+                    data.variants.span(),
+                    format!(
+                        "a variant must be marked as `{}`, e.g. #[graphql({})]",
+                        role.name(),
+                        role.name(),
+                    ),
+                )
+            })
+        };
+
+        Ok(Some(Self {
+            int: require(VariantRole::Int)?,
+            long: find(VariantRole::Long),
+            unsigned_long: find(VariantRole::UnsignedLong),
+            float: require(VariantRole::Float)?,
+            string: require(VariantRole::String)?,
+            boolean: require(VariantRole::Boolean)?,
+        }))
+    }
+}
+
 pub fn impl_scalar_value(ast: &syn::DeriveInput, error: GraphQLScope) -> syn::Result<TokenStream> {
     let ident = &ast.ident;
 
     match ast.data {
-        Data::Enum(ref enum_data) => impl_scalar_enum(ident, enum_data, error),
+        Data::Enum(ref enum_data) => impl_scalar_enum(ident, &ast.vis, enum_data, error),
         Data::Struct(ref struct_data) => impl_scalar_struct(ast, struct_data, error),
         Data::Union(_) => Err(error.custom_error(ast.span(), "may not be applied to unions")),
     }
@@ -178,9 +279,11 @@ fn impl_scalar_struct(
         where
             S: ::juniper::ScalarValue,
         {
-            fn from_input_value(v: &::juniper::InputValue<S>) -> Option<#ident> {
+            type Error = <#inner_ty as ::juniper::FromInputValue<S>>::Error;
+
+            fn from_input_value(v: &::juniper::InputValue<S>) -> Result<#ident, Self::Error> {
                 let inner: #inner_ty = ::juniper::FromInputValue::from_input_value(v)?;
-                Some(#ident(inner))
+                Ok(#ident(inner))
             }
         }
 
@@ -204,6 +307,7 @@ fn impl_scalar_struct(
 
 fn impl_scalar_enum(
     ident: &syn::Ident,
+    vis: &syn::Visibility,
     data: &syn::DataEnum,
     error: GraphQLScope,
 ) -> syn::Result<TokenStream> {
@@ -217,14 +321,192 @@ fn impl_scalar_enum(
 
     let display = derive_display(data.variants.iter(), ident);
 
+    let scalar_value = match VariantRoles::from_variants(data, &error)? {
+        Some(roles) => derive_scalar_value_trait(ident, vis, &roles),
+        None => quote!(),
+    };
+
     Ok(quote! {
         #(#froms)*
 
         #serialize
         #display
+        #scalar_value
     })
 }
 
+fn derive_scalar_value_trait(
+    ident: &Ident,
+    vis: &syn::Visibility,
+    roles: &VariantRoles,
+) -> TokenStream {
+    let visitor = quote::format_ident!("{}Visitor", ident);
+    let visitor_doc = format!("Serde visitor deserializing [`{}`].", ident);
+    let VariantRoles {
+        int,
+        long,
+        unsigned_long,
+        float,
+        string,
+        boolean,
+    } = roles;
+
+    let long_as_i64 = long.map(|v| quote!(#ident::#v(ref i) => Some(*i),));
+    let unsigned_long_as_i64 = unsigned_long
+        .map(|v| quote!(#ident::#v(ref u) => ::std::convert::TryFrom::try_from(*u).ok(),));
+    let long_as_u64 =
+        long.map(|v| quote!(#ident::#v(ref i) => ::std::convert::TryFrom::try_from(*i).ok(),));
+    let unsigned_long_as_u64 = unsigned_long.map(|v| quote!(#ident::#v(ref u) => Some(*u),));
+    let long_as_float = long.map(|v| quote!(#ident::#v(ref i) => Some(*i as f64),));
+    let unsigned_long_as_float =
+        unsigned_long.map(|v| quote!(#ident::#v(ref u) => Some(*u as f64),));
+
+    let from_i64 = long.map(|v| {
+        quote! {
+            fn from_i64(i: i64) -> Self {
+                #ident::#v(i)
+            }
+        }
+    });
+    let from_u64 = unsigned_long.map(|v| {
+        quote! {
+            fn from_u64(u: u64) -> Self {
+                #ident::#v(u)
+            }
+        }
+    });
+
+    // Browser's JSON.stringify serialize all numbers having no fractional part
+    // as integers (no decimal point), so large integers without a dedicated
+    // variant are parsed as floating point numbers.
+    let large_i64 = match long {
+        Some(v) => quote!(Ok(#ident::#v(value))),
+        None => quote!(Ok(#ident::#float(value as f64))),
+    };
+    let large_u64 = match unsigned_long {
+        Some(v) => quote!(Ok(#ident::#v(value))),
+        None => quote!(Ok(#ident::#float(value as f64))),
+    };
+
+    quote! {
+        impl ::juniper::ScalarValue for #ident {
+            type Visitor = #visitor;
+
+            fn as_int(&self) -> Option<i32> {
+                match *self {
+                    #ident::#int(ref i) => Some(*i),
+                    _ => None,
+                }
+            }
+
+            fn as_i64(&self) -> Option<i64> {
+                match *self {
+                    #ident::#int(ref i) => Some(i64::from(*i)),
+                    #long_as_i64
+                    #unsigned_long_as_i64
+                    _ => None,
+                }
+            }
+
+            fn as_u64(&self) -> Option<u64> {
+                match *self {
+                    #ident::#int(ref i) => ::std::convert::TryFrom::try_from(*i).ok(),
+                    #long_as_u64
+                    #unsigned_long_as_u64
+                    _ => None,
+                }
+            }
+
+            #from_i64
+            #from_u64
+
+            fn as_float(&self) -> Option<f64> {
+                match *self {
+                    #ident::#int(ref i) => Some(f64::from(*i)),
+                    #long_as_float
+                    #unsigned_long_as_float
+                    #ident::#float(ref f) => Some(*f),
+                    _ => None,
+                }
+            }
+
+            fn as_str(&self) -> Option<&str> {
+                match *self {
+                    #ident::#string(ref s) => Some(s.as_str()),
+                    _ => None,
+                }
+            }
+
+            fn as_string(&self) -> Option<String> {
+                match *self {
+                    #ident::#string(ref s) => Some(s.clone()),
+                    _ => None,
+                }
+            }
+
+            fn as_boolean(&self) -> Option<bool> {
+                match *self {
+                    #ident::#boolean(ref b) => Some(*b),
+                    _ => None,
+                }
+            }
+        }
+
+        #[doc = #visitor_doc]
+        #[derive(Clone, Copy, Debug, Default)]
+        #vis struct #visitor;
+
+        impl<'de> ::juniper::serde::de::Visitor<'de> for #visitor {
+            type Value = #ident;
+
+            fn expecting(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                f.write_str("a valid input value")
+            }
+
+            fn visit_bool<E>(self, value: bool) -> Result<#ident, E> {
+                Ok(#ident::#boolean(value))
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<#ident, E>
+            where
+                E: ::juniper::serde::de::Error,
+            {
+                if value >= i64::from(i32::min_value()) && value <= i64::from(i32::max_value()) {
+                    Ok(#ident::#int(value as i32))
+                } else {
+                    #large_i64
+                }
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<#ident, E>
+            where
+                E: ::juniper::serde::de::Error,
+            {
+                if value <= i64::max_value() as u64 {
+                    self.visit_i64(value as i64)
+                } else {
+                    #large_u64
+                }
+            }
+
+            fn visit_f64<E>(self, value: f64) -> Result<#ident, E> {
+                Ok(#ident::#float(value))
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<#ident, E>
+            where
+                E: ::juniper::serde::de::Error,
+            {
+                self.visit_string(value.into())
+            }
+
+            fn visit_string<E>(self, value: String) -> Result<#ident, E> {
+                Ok(#ident::#string(value))
+            }
+        }
+    }
+}
+
 fn derive_display<'a, I>(variants: I, ident: &Ident) -> TokenStream
 where
     I: Iterator<Item = &'a Variant>,
@@ -251,7 +533,11 @@ where
 {
     let arms = variants.map(|v| {
         let variant = &v.ident;
-        quote!(#ident::#variant(ref v) => v.serialize(serializer),)
+        if is_serialized_as_string(v) {
+            quote!(#ident::#variant(ref v) => serializer.collect_str(v),)
+        } else {
+            quote!(#ident::#variant(ref v) => v.serialize(serializer),)
+        }
     });
 
     quote! {
@@ -267,6 +553,23 @@ where
     }
 }
 
+/// Whether the variant is marked with `as_string`, e.g. `#[graphql(long, as_string)]`, so its
+/// value is serialized as a string.
+///
+/// Invalid attributes are reported by `VariantRoles::from_variants`.
+fn is_serialized_as_string(variant: &Variant) -> bool {
+    util::find_graphql_attr(&variant.attrs)
+        .and_then(|attr| {
+            attr.parse_args_with(
+                syn::punctuated::Punctuated::<syn::Ident, token::Comma>::parse_terminated,
+            )
+            .ok()
+        })
+        .map_or(false, |idents| {
+            idents.iter().any(|ident| ident == "as_string")
+        })
+}
+
 fn derive_from_variant(
     variant: &Variant,
     ident: &Ident,
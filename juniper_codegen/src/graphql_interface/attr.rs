@@ -204,6 +204,8 @@ pub fn expand_on_trait(
 
         name,
         description: meta.description.map(SpanContainer::into_inner),
+        visible: meta.visible.map(SpanContainer::into_inner),
+        cache_control: meta.cache_control.map(SpanContainer::into_inner),
 
         context,
         scalar: scalar.clone(),
@@ -498,6 +500,8 @@ impl TraitMethod {
             ty,
             description,
             deprecated,
+            visible: meta.visible.map(SpanContainer::into_inner),
+            cache_control: meta.cache_control.map(SpanContainer::into_inner),
             method: method_ident.clone(),
             arguments,
             is_async: method.sig.asyncness.is_some(),
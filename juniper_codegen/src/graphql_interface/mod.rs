@@ -17,6 +17,7 @@ use syn::{
 
 use crate::{
     common::{
+        cache_control::CacheControl,
         gen,
         parse::{
             attr::{err, OptionExt as _},
@@ -48,6 +49,22 @@ struct TraitMeta {
     /// [2]: https://spec.graphql.org/June2018/#sec-Descriptions
     description: Option<SpanContainer<String>>,
 
+    /// Explicitly specified function deciding whether this [GraphQL interface][1] type is visible
+    /// to a request, given its [`Context`].
+    ///
+    /// If absent, then the [interface][1] is visible to all requests.
+    ///
+    /// [`Context`]: juniper::Context
+    /// [1]: https://spec.graphql.org/June2018/#sec-Interfaces
+    visible: Option<SpanContainer<syn::ExprPath>>,
+
+    /// Explicitly specified [`CacheHint`] of this [GraphQL interface][1] type, used by the fields
+    /// returning it.
+    ///
+    /// [`CacheHint`]: juniper::CacheHint
+    /// [1]: https://spec.graphql.org/June2018/#sec-Interfaces
+    cache_control: Option<SpanContainer<CacheControl>>,
+
     /// Explicitly specified identifier of the enum Rust type behind the trait, being an actual
     /// implementation of a [GraphQL interface][1] type.
     ///
@@ -152,6 +169,22 @@ impl Parse for TraitMeta {
                         ))
                         .none_or_else(|_| err::dup_arg(&ident))?
                 }
+                "visible" => {
+                    input.parse::<token::Eq>()?;
+                    let lit = input.parse::<syn::LitStr>()?;
+                    let path = lit.parse::<syn::ExprPath>()?;
+                    output
+                        .visible
+                        .replace(SpanContainer::new(ident.span(), Some(lit.span()), path))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "cache_control" => {
+                    let hint = input.parse::<CacheControl>()?;
+                    output
+                        .cache_control
+                        .replace(SpanContainer::new(ident.span(), None, hint))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
                 "ctx" | "context" | "Context" => {
                     input.parse::<token::Eq>()?;
                     let ctx = input.parse::<syn::Type>()?;
@@ -234,6 +267,8 @@ impl TraitMeta {
         Ok(Self {
             name: try_merge_opt!(name: self, another),
             description: try_merge_opt!(description: self, another),
+            visible: try_merge_opt!(visible: self, another),
+            cache_control: try_merge_opt!(cache_control: self, another),
             context: try_merge_opt!(context: self, another),
             scalar: try_merge_opt!(scalar: self, another),
             implementers: try_merge_hashset!(implementers: self, another => span_joined),
@@ -396,6 +431,20 @@ struct MethodMeta {
     /// [2]: https://spec.graphql.org/June2018/#sec-Deprecation
     deprecated: Option<SpanContainer<Option<syn::LitStr>>>,
 
+    /// Explicitly specified function deciding whether this [GraphQL field][1] is visible to a
+    /// request, given its `juniper::Context`.
+    ///
+    /// If absent, then the [field][1] is visible to all requests.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    visible: Option<SpanContainer<syn::ExprPath>>,
+
+    /// Explicitly specified [`CacheHint`] of this [GraphQL field][1].
+    ///
+    /// [`CacheHint`]: juniper::CacheHint
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    cache_control: Option<SpanContainer<CacheControl>>,
+
     /// Explicitly specified marker indicating that this trait method should be omitted by code
     /// generation and not considered in the [GraphQL interface][1] type definition.
     ///
@@ -452,6 +501,22 @@ impl Parse for MethodMeta {
                         ))
                         .none_or_else(|_| err::dup_arg(&ident))?
                 }
+                "visible" => {
+                    input.parse::<token::Eq>()?;
+                    let lit = input.parse::<syn::LitStr>()?;
+                    let path = lit.parse::<syn::ExprPath>()?;
+                    output
+                        .visible
+                        .replace(SpanContainer::new(ident.span(), Some(lit.span()), path))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "cache_control" => {
+                    let hint = input.parse::<CacheControl>()?;
+                    output
+                        .cache_control
+                        .replace(SpanContainer::new(ident.span(), None, hint))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
                 "ignore" | "skip" => output
                     .ignore
                     .replace(SpanContainer::new(ident.span(), None, ident.clone()))
@@ -478,6 +543,8 @@ impl MethodMeta {
             name: try_merge_opt!(name: self, another),
             description: try_merge_opt!(description: self, another),
             deprecated: try_merge_opt!(deprecated: self, another),
+            visible: try_merge_opt!(visible: self, another),
+            cache_control: try_merge_opt!(cache_control: self, another),
             ignore: try_merge_opt!(ignore: self, another),
             downcast: try_merge_opt!(downcast: self, another),
         })
@@ -494,6 +561,8 @@ impl MethodMeta {
             if meta.name.is_some()
                 || meta.description.is_some()
                 || meta.deprecated.is_some()
+                || meta.visible.is_some()
+                || meta.cache_control.is_some()
                 || meta.downcast.is_some()
             {
                 return Err(syn::Error::new(
@@ -507,6 +576,8 @@ impl MethodMeta {
             if meta.name.is_some()
                 || meta.description.is_some()
                 || meta.deprecated.is_some()
+                || meta.visible.is_some()
+                || meta.cache_control.is_some()
                 || meta.ignore.is_some()
             {
                 return Err(syn::Error::new(
@@ -724,6 +795,21 @@ struct Definition {
     /// [1]: https://spec.graphql.org/June2018/#sec-Interfaces
     description: Option<String>,
 
+    /// Function deciding whether this [GraphQL interface][1] is visible to a request, given its
+    /// [`Context`].
+    ///
+    /// If [`None`] then this [GraphQL interface][1] is visible to all requests.
+    ///
+    /// [`Context`]: juniper::Context
+    /// [1]: https://spec.graphql.org/June2018/#sec-Interfaces
+    visible: Option<syn::ExprPath>,
+
+    /// [`CacheHint`] of this [GraphQL interface][1], used by the fields returning it.
+    ///
+    /// [`CacheHint`]: juniper::CacheHint
+    /// [1]: https://spec.graphql.org/June2018/#sec-Interfaces
+    cache_control: Option<CacheControl>,
+
     /// Rust type of [`Context`] to generate [`GraphQLType`] implementation with for this
     /// [GraphQL interface][1].
     ///
@@ -790,6 +876,20 @@ impl Definition {
             .as_ref()
             .map(|desc| quote! { .description(#desc) });
 
+        let context = self
+            .context
+            .as_ref()
+            .map(|ctx| quote! { #ctx })
+            .unwrap_or_else(|| quote! { () });
+        let visibility = self.visible.as_ref().map(|visible| {
+            let predicate = gen::visibility_predicate(visible, &context);
+            quote! { .visibility(#predicate) }
+        });
+        let cache_control = self
+            .cache_control
+            .as_ref()
+            .map(|hint| quote! { .cache_control(#hint) });
+
         // Sorting is required to preserve/guarantee the order of implementers registered in schema.
         let mut impler_tys: Vec<_> = self.implementers.iter().map(|impler| &impler.ty).collect();
         impler_tys.sort_unstable_by(|a, b| {
@@ -797,7 +897,10 @@ impl Definition {
             a.cmp(&b)
         });
 
-        let fields_meta = self.fields.iter().map(Field::method_meta_tokens);
+        let fields_meta = self
+            .fields
+            .iter()
+            .map(|field| field.method_meta_tokens(&context));
 
         quote! {
             #[automatically_derived]
@@ -821,6 +924,8 @@ impl Definition {
                     ];
                     registry.build_interface_type::<#ty>(info, &fields)
                         #description
+                        #visibility
+                        #cache_control
                         .into_meta()
                 }
             }
@@ -1257,6 +1362,21 @@ struct Field {
     /// [2]: https://spec.graphql.org/June2018/#sec-Language.Fields
     deprecated: Option<Option<String>>,
 
+    /// Function deciding whether this [GraphQL field][2] is visible to a request, given its
+    /// `juniper::Context`.
+    ///
+    /// If [`None`] then this [GraphQL field][2] is visible to all requests.
+    ///
+    /// [2]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    visible: Option<syn::ExprPath>,
+
+    /// [`CacheHint`] of this [GraphQL field][2], taken by the same field of the implementers
+    /// having none.
+    ///
+    /// [`CacheHint`]: juniper::CacheHint
+    /// [2]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    cache_control: Option<CacheControl>,
+
     /// Name of Rust trait method representing this [GraphQL field][2].
     ///
     /// [2]: https://spec.graphql.org/June2018/#sec-Language.Fields
@@ -1281,7 +1401,7 @@ impl Field {
     /// [`GraphQLType::meta`]: juniper::GraphQLType::meta
     /// [`Registry`]: juniper::Registry
     #[must_use]
-    fn method_meta_tokens(&self) -> TokenStream {
+    fn method_meta_tokens(&self, context: &TokenStream) -> TokenStream {
         let (name, ty) = (&self.name, &self.ty);
 
         let description = self
@@ -1297,6 +1417,15 @@ impl Field {
             quote! { .deprecated(#reason) }
         });
 
+        let visibility = self.visible.as_ref().map(|visible| {
+            let predicate = gen::visibility_predicate(visible, context);
+            quote! { .visibility(#predicate) }
+        });
+        let cache_control = self
+            .cache_control
+            .as_ref()
+            .map(|hint| quote! { .cache_control(#hint) });
+
         let arguments = self
             .arguments
             .iter()
@@ -1307,6 +1436,8 @@ impl Field {
                 #( #arguments )*
                 #description
                 #deprecated
+                #visibility
+                #cache_control
         }
     }
 
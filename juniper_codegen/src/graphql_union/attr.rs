@@ -86,6 +86,8 @@ pub fn expand(attr_args: TokenStream, body: TokenStream) -> syn::Result<TokenStr
         ty: parse_quote! { #trait_ident },
         is_trait_object: true,
         description: meta.description.map(SpanContainer::into_inner),
+        visible: meta.visible.map(SpanContainer::into_inner),
+        cache_control: meta.cache_control.map(SpanContainer::into_inner),
         context,
         scalar: meta.scalar.map(SpanContainer::into_inner),
         generics: ast.generics.clone(),
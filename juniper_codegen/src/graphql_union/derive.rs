@@ -80,6 +80,8 @@ fn expand_enum(ast: syn::DeriveInput) -> syn::Result<UnionDefinition> {
         ty: parse_quote! { #enum_ident },
         is_trait_object: false,
         description: meta.description.map(SpanContainer::into_inner),
+        visible: meta.visible.map(SpanContainer::into_inner),
+        cache_control: meta.cache_control.map(SpanContainer::into_inner),
         context: meta.context.map(SpanContainer::into_inner),
         scalar: meta.scalar.map(SpanContainer::into_inner),
         generics: ast.generics,
@@ -208,6 +210,8 @@ fn expand_struct(ast: syn::DeriveInput) -> syn::Result<UnionDefinition> {
         ty: parse_quote! { #struct_ident },
         is_trait_object: false,
         description: meta.description.map(SpanContainer::into_inner),
+        visible: meta.visible.map(SpanContainer::into_inner),
+        cache_control: meta.cache_control.map(SpanContainer::into_inner),
         context: meta.context.map(SpanContainer::into_inner),
         scalar: meta.scalar.map(SpanContainer::into_inner),
         generics: ast.generics,
@@ -17,9 +17,13 @@ use syn::{
 };
 
 use crate::{
-    common::parse::{
-        attr::{err, OptionExt as _},
-        ParseBufferExt as _,
+    common::{
+        cache_control::CacheControl,
+        gen,
+        parse::{
+            attr::{err, OptionExt as _},
+            ParseBufferExt as _,
+        },
     },
     util::{filter_attrs, get_doc_comment, span_container::SpanContainer},
 };
@@ -48,6 +52,21 @@ struct UnionMeta {
     /// [2]: https://spec.graphql.org/June2018/#sec-Descriptions
     pub description: Option<SpanContainer<String>>,
 
+    /// Explicitly specified function deciding whether this [GraphQL union][1] type is visible to
+    /// a request, given its `juniper::Context`.
+    ///
+    /// If absent, then the [union][1] is visible to all requests.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Unions
+    pub visible: Option<SpanContainer<syn::ExprPath>>,
+
+    /// Explicitly specified [`CacheHint`] of this [GraphQL union][1] type, used by the fields
+    /// returning it.
+    ///
+    /// [`CacheHint`]: juniper::CacheHint
+    /// [1]: https://spec.graphql.org/June2018/#sec-Unions
+    pub(crate) cache_control: Option<SpanContainer<CacheControl>>,
+
     /// Explicitly specified type of `juniper::Context` to use for resolving this [GraphQL union][1]
     /// type with.
     ///
@@ -113,6 +132,22 @@ impl Parse for UnionMeta {
                         ))
                         .none_or_else(|_| err::dup_arg(&ident))?
                 }
+                "visible" => {
+                    input.parse::<token::Eq>()?;
+                    let lit = input.parse::<syn::LitStr>()?;
+                    let path = lit.parse::<syn::ExprPath>()?;
+                    output
+                        .visible
+                        .replace(SpanContainer::new(ident.span(), Some(lit.span()), path))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "cache_control" => {
+                    let hint = input.parse::<CacheControl>()?;
+                    output
+                        .cache_control
+                        .replace(SpanContainer::new(ident.span(), None, hint))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
                 "ctx" | "context" | "Context" => {
                     input.parse::<token::Eq>()?;
                     let ctx = input.parse::<syn::Type>()?;
@@ -160,6 +195,8 @@ impl UnionMeta {
         Ok(Self {
             name: try_merge_opt!(name: self, another),
             description: try_merge_opt!(description: self, another),
+            visible: try_merge_opt!(visible: self, another),
+            cache_control: try_merge_opt!(cache_control: self, another),
             context: try_merge_opt!(context: self, another),
             scalar: try_merge_opt!(scalar: self, another),
             external_resolvers: try_merge_hashmap!(
@@ -324,6 +361,20 @@ struct UnionDefinition {
     /// [1]: https://spec.graphql.org/June2018/#sec-Unions
     pub description: Option<String>,
 
+    /// Function deciding whether this [GraphQL union][1] is visible to a request, given its
+    /// `juniper::Context`.
+    ///
+    /// If [`None`] then this [GraphQL union][1] is visible to all requests.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Unions
+    pub visible: Option<syn::ExprPath>,
+
+    /// [`CacheHint`] of this [GraphQL union][1], used by the fields returning it.
+    ///
+    /// [`CacheHint`]: juniper::CacheHint
+    /// [1]: https://spec.graphql.org/June2018/#sec-Unions
+    pub(crate) cache_control: Option<CacheControl>,
+
     /// Rust type of `juniper::Context` to generate `juniper::GraphQLType` implementation with
     /// for this [GraphQL union][1].
     ///
@@ -371,6 +422,15 @@ impl ToTokens for UnionDefinition {
             .as_ref()
             .map(|desc| quote! { .description(#desc) });
 
+        let visibility = self.visible.as_ref().map(|visible| {
+            let predicate = gen::visibility_predicate(visible, &context);
+            quote! { .visibility(#predicate) }
+        });
+        let cache_control = self
+            .cache_control
+            .as_ref()
+            .map(|hint| quote! { .cache_control(#hint) });
+
         let var_types: Vec<_> = self.variants.iter().map(|var| &var.ty).collect();
 
         let all_variants_unique = if var_types.len() > 1 {
@@ -487,6 +547,8 @@ impl ToTokens for UnionDefinition {
                     ];
                     registry.build_union_type::<#ty_full>(info, &types)
                     #description
+                    #visibility
+                    #cache_control
                     .into_meta()
                 }
             }
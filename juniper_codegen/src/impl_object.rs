@@ -176,6 +176,8 @@ fn create(
                 is_type_inferred: false,
                 is_async,
                 default: None,
+                visible: attrs.visible.map(SpanContainer::into_inner),
+                cache_control: attrs.cache_control.map(SpanContainer::into_inner),
                 span,
             })
         })
@@ -211,6 +213,8 @@ fn create(
         scalar: _impl.attrs.scalar.map(SpanContainer::into_inner),
         context: _impl.attrs.context.map(SpanContainer::into_inner),
         description: _impl.description,
+        visible: _impl.attrs.visible.map(SpanContainer::into_inner),
+        cache_control: _impl.attrs.cache_control.map(SpanContainer::into_inner),
         fields,
         generics: _impl.generics.clone(),
         interfaces: _impl
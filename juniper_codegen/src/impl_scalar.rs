@@ -43,6 +43,25 @@ fn get_method_return_type(output: syn::ReturnType) -> Option<syn::Type> {
     }
 }
 
+// Find the error type `E` of a `Result<T, E>` return value
+fn get_result_error_type(return_type: &syn::Type) -> Option<syn::Type> {
+    if let syn::Type::Path(type_path) = return_type {
+        if let Some(path_segment) = type_path.path.segments.last() {
+            if path_segment.ident == "Result" {
+                if let syn::PathArguments::AngleBracketed(generic_args) = &path_segment.arguments {
+                    if let Some(syn::GenericArgument::Type(error_type)) =
+                        generic_args.args.iter().nth(1)
+                    {
+                        return Some(error_type.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
 // Find the enum type by inspecting the type parameter on the return value
 fn get_enum_type(return_type: &Option<syn::Type>) -> Option<syn::PathSegment> {
     if let Some(return_type) = return_type {
@@ -200,6 +219,13 @@ pub fn build_scalar(
             "unable to find return type of `from_input_value` method",
         )
     })?;
+    let from_input_value_error =
+        get_result_error_type(&from_input_value_result).ok_or_else(|| {
+            error.custom_error(
+                from_input_value_result.span(),
+                "`from_input_value` method must return `Result<Self, E>`",
+            )
+        })?;
     let from_str_arg = input.from_str_arg.ok_or_else(|| {
         error.custom_error(body_span, "unable to find argument for `from_str` method")
     })?;
@@ -321,6 +347,8 @@ pub fn build_scalar(
         impl#generic_type_decl ::juniper::FromInputValue<#generic_type> for #impl_for_type
         #generic_type_bound
         {
+            type Error = #from_input_value_error;
+
             fn from_input_value(#from_input_value_arg: &::juniper::InputValue<#generic_type>) -> #from_input_value_result {
                 #from_input_value_body
             }
@@ -194,7 +194,30 @@ pub fn derive_object(input: TokenStream) -> TokenStream {
 ///
 /// ### Base ScalarValue Enum
 ///
-/// TODO: write documentation.
+/// Applied to an enum, the derive implements the conversions, `Display` and
+/// `Serialize` for a custom scalar value representation. If the variants are
+/// marked with their roles, the whole `ScalarValue` trait is implemented as
+/// well, along with a `serde` visitor named `<Enum>Visitor`.
+///
+/// ```rust
+/// #[derive(Clone, Debug, PartialEq, juniper::GraphQLScalarValue)]
+/// enum MyScalarValue {
+///     #[graphql(int)]
+///     Int(i32),
+///     // Optional, `unsigned_long` (`u64`) is supported as well.
+///     #[graphql(long)]
+///     Long(i64),
+///     #[graphql(float)]
+///     Float(f64),
+///     #[graphql(string)]
+///     String(String),
+///     #[graphql(boolean)]
+///     Boolean(bool),
+/// }
+/// ```
+///
+/// A variant may also be marked as `as_string`, e.g. `#[graphql(long, as_string)]`, to serialize
+/// its value as a string, for clients that can't handle integers beyond 2^53.
 ///
 #[proc_macro_error]
 #[proc_macro_derive(GraphQLScalarValue, attributes(graphql))]
@@ -508,8 +531,11 @@ pub fn graphql_object(args: TokenStream, input: TokenStream) -> TokenStream {
 ///         juniper::Value::scalar(self.0.to_owned())
 ///     }
 ///
-///     fn from_input_value(value: &juniper::InputValue) -> Option<UserID> {
-///         value.as_string_value().map(|s| UserID(s.to_owned()))
+///     fn from_input_value(value: &juniper::InputValue) -> Result<UserID, String> {
+///         value
+///             .as_string_value()
+///             .map(|s| UserID(s.to_owned()))
+///             .ok_or_else(|| format!("Expected a string, found {}", value))
 ///     }
 ///
 ///     fn from_str<'a>(value: juniper::ScalarToken<'a>) -> juniper::ParseScalarResult<'a, S> {
@@ -522,7 +548,9 @@ pub fn graphql_object(args: TokenStream, input: TokenStream) -> TokenStream {
 ///
 /// In addition to implementing `GraphQLType` for the type in question,
 /// `FromInputValue` and `ToInputValue` is also implemented. This makes the type
-/// usable as arguments and default values.
+/// usable as arguments and default values. The error type of the `Result`
+/// returned by `from_input_value` becomes `FromInputValue::Error`, and its
+/// message is reported when a value is rejected.
 #[proc_macro_error]
 #[proc_macro_attribute]
 pub fn graphql_scalar(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -18,7 +18,7 @@ use syn::{
     token, Attribute, Lit, Meta, MetaList, MetaNameValue, NestedMeta,
 };
 
-use crate::common::parse::ParseBufferExt as _;
+use crate::common::{cache_control::CacheControl, gen, parse::ParseBufferExt as _};
 
 /// Returns the name of a type.
 /// If the type does not end in a simple ident, `None` is returned.
@@ -339,6 +339,8 @@ pub struct ObjectAttributes {
     pub no_async: Option<SpanContainer<()>>,
     pub is_internal: bool,
     pub rename: Option<RenameRule>,
+    pub visible: Option<SpanContainer<syn::ExprPath>>,
+    pub(crate) cache_control: Option<SpanContainer<CacheControl>>,
 }
 
 impl Parse for ObjectAttributes {
@@ -409,6 +411,16 @@ impl Parse for ObjectAttributes {
                         return Err(syn::Error::new(val.span(), "unknown rename rule"));
                     }
                 }
+                "visible" => {
+                    input.parse::<token::Eq>()?;
+                    let val = input.parse::<syn::LitStr>()?;
+                    let path = val.parse::<syn::ExprPath>()?;
+                    output.visible = Some(SpanContainer::new(ident.span(), Some(val.span()), path));
+                }
+                "cache_control" => {
+                    let hint = input.parse::<CacheControl>()?;
+                    output.cache_control = Some(SpanContainer::new(ident.span(), None, hint));
+                }
                 _ => {
                     return Err(syn::Error::new(ident.span(), "unknown attribute"));
                 }
@@ -500,6 +512,8 @@ enum FieldAttribute {
     Skip(SpanContainer<syn::Ident>),
     Arguments(HashMap<String, FieldAttributeArgument>),
     Default(SpanContainer<Option<syn::Expr>>),
+    Visible(SpanContainer<syn::ExprPath>),
+    CacheControl(SpanContainer<CacheControl>),
 }
 
 impl Parse for FieldAttribute {
@@ -574,6 +588,24 @@ impl Parse for FieldAttribute {
 
                 Ok(FieldAttribute::Default(default_expr))
             }
+            "visible" => {
+                input.parse::<token::Eq>()?;
+                let lit = input.parse::<syn::LitStr>()?;
+                let path = lit.parse::<syn::ExprPath>()?;
+                Ok(FieldAttribute::Visible(SpanContainer::new(
+                    ident.span(),
+                    Some(lit.span()),
+                    path,
+                )))
+            }
+            "cache_control" => {
+                let hint = input.parse::<CacheControl>()?;
+                Ok(FieldAttribute::CacheControl(SpanContainer::new(
+                    ident.span(),
+                    None,
+                    hint,
+                )))
+            }
             _ => Err(syn::Error::new(ident.span(), "unknown attribute")),
         }
     }
@@ -590,6 +622,10 @@ pub struct FieldAttributes {
     pub arguments: HashMap<String, FieldAttributeArgument>,
     /// Only relevant for object input objects.
     pub default: Option<SpanContainer<Option<syn::Expr>>>,
+    /// Not relevant for input objects.
+    pub visible: Option<SpanContainer<syn::ExprPath>>,
+    /// Only relevant for objects.
+    pub(crate) cache_control: Option<SpanContainer<CacheControl>>,
 }
 
 impl Parse for FieldAttributes {
@@ -618,6 +654,12 @@ impl Parse for FieldAttributes {
                 FieldAttribute::Default(expr) => {
                     output.default = Some(expr);
                 }
+                FieldAttribute::Visible(path) => {
+                    output.visible = Some(path);
+                }
+                FieldAttribute::CacheControl(hint) => {
+                    output.cache_control = Some(hint);
+                }
             }
         }
 
@@ -679,6 +721,8 @@ pub struct GraphQLTypeDefinitionField {
     pub is_type_inferred: bool,
     pub is_async: bool,
     pub default: Option<TokenStream>,
+    pub visible: Option<syn::ExprPath>,
+    pub(crate) cache_control: Option<CacheControl>,
     pub span: Span,
 }
 
@@ -704,6 +748,8 @@ pub struct GraphQLTypeDefiniton {
     pub context: Option<syn::Type>,
     pub scalar: Option<syn::Type>,
     pub description: Option<String>,
+    pub visible: Option<syn::ExprPath>,
+    pub(crate) cache_control: Option<CacheControl>,
     pub fields: Vec<GraphQLTypeDefinitionField>,
     pub generics: syn::Generics,
     pub interfaces: Vec<syn::Type>,
@@ -781,6 +827,15 @@ impl GraphQLTypeDefiniton {
                 None => quote!(),
             };
 
+            let visibility = field.visible.as_ref().map(|visible| {
+                let predicate = gen::visibility_predicate(visible, &context);
+                quote!( .visibility(#predicate) )
+            });
+            let cache_control = field
+                .cache_control
+                .as_ref()
+                .map(|hint| quote!( .cache_control(#hint) ));
+
             let field_name = &field.name;
 
             let _type = &field._type;
@@ -790,6 +845,8 @@ impl GraphQLTypeDefiniton {
                     #(#args)*
                     #description
                     #deprecation
+                    #visibility
+                    #cache_control
             }
         });
 
@@ -849,6 +906,14 @@ impl GraphQLTypeDefiniton {
             .description
             .as_ref()
             .map(|description| quote!( .description(#description) ));
+        let visibility = self.visible.as_ref().map(|visible| {
+            let predicate = gen::visibility_predicate(visible, &context);
+            quote!( .visibility(#predicate) )
+        });
+        let cache_control = self
+            .cache_control
+            .as_ref()
+            .map(|hint| quote!( .cache_control(#hint) ));
 
         let interfaces = if !self.interfaces.is_empty() {
             let interfaces_ty = &self.interfaces;
@@ -1071,6 +1136,8 @@ impl GraphQLTypeDefiniton {
                     ];
                     let meta = registry.build_object_type::<#ty>(info, &fields)
                         #description
+                        #visibility
+                        #cache_control
                         #interfaces;
                     meta.into_meta()
                 }
@@ -1195,12 +1262,18 @@ impl GraphQLTypeDefiniton {
                 panic!("Synchronous resolvers are not supported. Specify that this function is async: 'async fn foo()'")
             }
 
+            let visibility = field.visible.as_ref().map(|visible| {
+                let predicate = gen::visibility_predicate(visible, &context);
+                quote!( .visibility(#predicate) )
+            });
+
             quote! {
                 registry
                     .field_convert::<#_type, _, Self::Context>(#field_name, info)
                     #(#args)*
                     #description
                     #deprecation
+                    #visibility
             }
         });
 
@@ -1208,6 +1281,10 @@ impl GraphQLTypeDefiniton {
             .description
             .as_ref()
             .map(|description| quote!( .description(#description) ));
+        let visibility = self.visible.as_ref().map(|visible| {
+            let predicate = gen::visibility_predicate(visible, &context);
+            quote!( .visibility(#predicate) )
+        });
 
         let interfaces = if !self.interfaces.is_empty() {
             let interfaces_ty = &self.interfaces;
@@ -1344,6 +1421,7 @@ impl GraphQLTypeDefiniton {
                         ];
                         let meta = registry.build_object_type::<#ty>(info, &fields)
                             #description
+                            #visibility
                             #interfaces;
                         meta.into_meta()
                     }
@@ -1452,6 +1530,10 @@ impl GraphQLTypeDefiniton {
             .description
             .as_ref()
             .map(|description| quote!( .description(#description) ));
+        let visibility = self.visible.as_ref().map(|visible| {
+            let predicate = gen::visibility_predicate(visible, &context);
+            quote!( .visibility(#predicate) )
+        });
 
         let values = self.fields.iter().map(|variant| {
             let variant_name = &variant.name;
@@ -1471,11 +1553,21 @@ impl GraphQLTypeDefiniton {
                 })
                 .unwrap_or_else(|| quote!(::juniper::meta::DeprecationStatus::Current));
 
+            let visibility = variant
+                .visible
+                .as_ref()
+                .map(|visible| {
+                    let predicate = gen::visibility_predicate(visible, &context);
+                    quote!( Some((#predicate) as ::juniper::meta::VisibilityFn) )
+                })
+                .unwrap_or_else(|| quote!(None));
+
             quote!(
                 ::juniper::meta::EnumValue {
                     name: #variant_name.to_string(),
                     description: #descr,
                     deprecation_status: #depr,
+                    visibility: #visibility,
                 },
             )
         });
@@ -1494,7 +1586,7 @@ impl GraphQLTypeDefiniton {
             let resolver_code = &variant.resolver_code;
 
             quote!(
-                Some(#variant_name) => Some(#resolver_code),
+                Some(#variant_name) => Ok(#resolver_code),
             )
         });
 
@@ -1572,6 +1664,7 @@ impl GraphQLTypeDefiniton {
                         #( #values )*
                     ])
                     #description
+                    #visibility
                     .into_meta()
                 }
             }
@@ -1602,13 +1695,16 @@ impl GraphQLTypeDefiniton {
             impl#impl_generics ::juniper::FromInputValue<#scalar> for #ty
                 #where_clause
             {
-                fn from_input_value(v: &::juniper::InputValue<#scalar>) -> Option<#ty>
-                {
+                type Error = ::std::string::String;
+
+                fn from_input_value(
+                    v: &::juniper::InputValue<#scalar>
+                ) -> Result<#ty, Self::Error> {
                     match v.as_enum_value().or_else(|| {
                         v.as_string_value()
                     }) {
                         #( #from_inputs )*
-                        _ => None,
+                        _ => Err(format!(r#"Expected "{}", found {}"#, #name, v)),
                     }
                 }
             }
@@ -1701,34 +1797,48 @@ impl GraphQLTypeDefiniton {
             })
             .collect::<Vec<_>>();
 
-        let from_inputs = self.fields.iter().map(|field| {
-            let field_ident = &field.resolver_code;
-            let field_name = &field.name;
+        let from_inputs = self
+            .fields
+            .iter()
+            .map(|field| {
+                let field_ident = &field.resolver_code;
+                let field_name = &field.name;
 
-            // Build from_input clause.
-            let from_input_default = match field.default {
-                Some(ref def) => {
-                    quote! {
-                        Some(&&::juniper::InputValue::Null) | None if true => #def,
+                // Build from_input clause.
+                let from_input_default = match field.default {
+                    Some(ref def) => {
+                        quote! {
+                            Some(&&::juniper::InputValue::Null) | None if true => #def,
+                        }
                     }
-                }
-                None => quote! {},
-            };
+                    None => quote! {},
+                };
 
-            quote!(
-                #field_ident: {
-                    // TODO: investigate the unwraps here, they seem dangerous!
-                    match obj.get(#field_name) {
-                        #from_input_default
-                        Some(ref v) => ::juniper::FromInputValue::from_input_value(v).unwrap(),
-                        None => {
-                            ::juniper::FromInputValue::from_input_value(&::juniper::InputValue::<#scalar>::null())
-                            .unwrap()
-                        },
+                let field_error = quote!(
+                    |e| {
+                        let e = ::juniper::IntoFieldError::<#scalar>::into_field_error(e);
+                        ::juniper::FieldError::new(
+                            format!(r#"In field "{}": {}"#, #field_name, e.message()),
+                            e.extensions().clone(),
+                        )
                     }
-                },
-            )
-        }).collect::<Vec<_>>();
+                );
+
+                quote!(
+                    #field_ident: {
+                        match obj.get(#field_name) {
+                            #from_input_default
+                            Some(ref v) => ::juniper::FromInputValue::from_input_value(v)
+                                .map_err(#field_error)?,
+                            None => {
+                                ::juniper::FromInputValue::<#scalar>::from_implicit_null()
+                                    .map_err(#field_error)?
+                            },
+                        }
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
 
         let to_inputs = self
             .fields
@@ -1747,6 +1857,10 @@ impl GraphQLTypeDefiniton {
             .description
             .as_ref()
             .map(|description| quote!( .description(#description) ));
+        let visibility = self.visible.as_ref().map(|visible| {
+            let predicate = gen::visibility_predicate(visible, &context);
+            quote!( .visibility(#predicate) )
+        });
 
         // Preserve the original type_generics before modification,
         // since alteration makes them invalid if self.generic_scalar
@@ -1820,6 +1934,7 @@ impl GraphQLTypeDefiniton {
                     ];
                     registry.build_input_object_type::<#ty>(&(), fields)
                     #description
+                    #visibility
                     .into_meta()
                 }
             }
@@ -1838,16 +1953,22 @@ impl GraphQLTypeDefiniton {
             impl#impl_generics ::juniper::FromInputValue<#scalar> for #ty #type_generics_tokens
                 #where_clause
             {
-                fn from_input_value(value: &::juniper::InputValue<#scalar>) -> Option<Self>
-                {
+                type Error = ::juniper::FieldError<#scalar>;
+
+                fn from_input_value(
+                    value: &::juniper::InputValue<#scalar>
+                ) -> Result<Self, Self::Error> {
                     if let Some(obj) = value.to_object_value() {
                         let item = #ty {
                             #( #from_inputs )*
                         };
-                        Some(item)
+                        Ok(item)
                     }
                     else {
-                        None
+                        Err(::juniper::FieldError::new(
+                            format!(r#"Expected "{}", found {}"#, #name, value),
+                            ::juniper::Value::null(),
+                        ))
                     }
                 }
             }
@@ -0,0 +1,372 @@
+/*!
+
+# juniper_graphql_sse
+
+This crate contains an implementation of the "distinct connections" mode of the
+[GraphQL over Server-Sent Events protocol](https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md),
+for clients unable to use WebSockets.
+
+Each subscription is served over its own HTTP response, with a
+`text/event-stream` body made of:
+- a `next` event for every result of the subscription,
+- a `complete` event once the subscription ends, after which the response ends,
+- keep-alive comments sent in between, so that proxies don't close idle
+  connections.
+
+It is agnostic of the HTTP server: [`subscribe`] and [`event_stream`] give the
+frames to write in the body of the response, as strings.
+
+*/
+
+#![deny(missing_docs)]
+#![deny(warnings)]
+#![doc(html_root_url = "https://docs.rs/juniper_graphql_sse/0.1.0")]
+
+use std::{sync::Arc, time::Duration};
+
+use juniper::{
+    futures::{
+        channel::mpsc,
+        future::{self, FutureExt},
+        sink::SinkExt,
+        stream::{self, BoxStream, Stream, StreamExt},
+    },
+    http::{GraphQLRequest, GraphQLResponse, HttpConfig},
+    DefaultScalarValue, ExecutionOutput, GraphQLError, GraphQLSubscriptionType, GraphQLTypeAsync,
+    RootNode, ScalarValue, SubscriptionConnection,
+};
+use juniper_subscriptions::Connection;
+
+/// The content type of the responses serving subscriptions
+pub const CONTENT_TYPE: &str = "text/event-stream";
+
+/// The comment sent to keep connections alive
+pub const KEEP_ALIVE: &str = ":\n\n";
+
+/// The event sent once a subscription ends
+pub const COMPLETE_EVENT: &str = "event: complete\ndata:\n\n";
+
+/// EventStreamConfig is used to configure the event streams serving subscriptions.
+#[derive(Clone, Debug)]
+pub struct EventStreamConfig {
+    keep_alive_interval: Duration,
+}
+
+impl EventStreamConfig {
+    /// Constructs the default configuration, sending keep-alives every 15 seconds.
+    pub fn new() -> Self {
+        Self {
+            keep_alive_interval: Duration::from_secs(15),
+        }
+    }
+
+    /// Specifies the interval at which to send keep-alives. Specifying a zero duration will
+    /// disable keep-alives. By default, keep-alives are sent every 15 seconds.
+    pub fn with_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.keep_alive_interval = interval;
+        self
+    }
+}
+
+impl Default for EventStreamConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats the `next` event carrying the given result of a subscription.
+pub fn next_event<S: ScalarValue>(output: ExecutionOutput<S>) -> String {
+    event(&GraphQLResponse::from_result(Ok((
+        output.data,
+        output.errors,
+    ))))
+}
+
+/// Formats the `next` event carrying the given error, which prevented a subscription from
+/// starting.
+pub fn error_event(error: GraphQLError) -> String {
+    event(&GraphQLResponse::<DefaultScalarValue>::from_result(Err(
+        error,
+    )))
+}
+
+fn event<S: ScalarValue>(response: &GraphQLResponse<S>) -> String {
+    // Serialized JSON has no line breaks, so it always fits in a single `data` field.
+    let data = serde_json::to_string(response).expect("GraphQL responses are serializable");
+    format!("event: next\ndata: {}\n\n", data)
+}
+
+/// Turns a subscription connection into the frames of an event stream: a `next` event for each
+/// of its results, keep-alives in between and a `complete` event once it ends.
+pub fn event_stream<'a, C, S>(connection: C, config: &EventStreamConfig) -> BoxStream<'a, String>
+where
+    C: SubscriptionConnection<S> + Send + 'a,
+    S: ScalarValue + 'a,
+{
+    frames(connection.map(next_event).boxed(), config)
+}
+
+/// Executes the given subscription request, returning the frames of the event stream serving
+/// it.
+///
+/// Requests which fail to start a subscription, e.g. because of validation errors, are served a
+/// single `next` event with the errors, followed by a `complete` event.
+pub fn subscribe<QueryT, MutationT, SubscriptionT, CtxT, S>(
+    root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
+    request: GraphQLRequest<S>,
+    context: Arc<CtxT>,
+    config: &EventStreamConfig,
+) -> BoxStream<'static, String>
+where
+    QueryT: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    QueryT::TypeInfo: Send + Sync,
+    MutationT: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    MutationT::TypeInfo: Send + Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    SubscriptionT::TypeInfo: Send + Sync,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    subscribe_with_config(root_node, request, context, config, Arc::default())
+}
+
+/// Executes the given subscription request like [`subscribe`], checking it against the safelist
+/// of the given [`HttpConfig`], if any.
+pub fn subscribe_with_config<QueryT, MutationT, SubscriptionT, CtxT, S>(
+    root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
+    request: GraphQLRequest<S>,
+    context: Arc<CtxT>,
+    config: &EventStreamConfig,
+    http_config: Arc<HttpConfig<CtxT, S>>,
+) -> BoxStream<'static, String>
+where
+    QueryT: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    QueryT::TypeInfo: Send + Sync,
+    MutationT: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    MutationT::TypeInfo: Send + Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    SubscriptionT::TypeInfo: Send + Sync,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    // The resolved stream references the root node, request, context and HTTP config, so they
+    // are all owned by the future driving the subscription, which hands the events over through
+    // a channel.
+    let (mut tx, rx) = mpsc::channel(0);
+    let execution = async move {
+        match juniper::http::resolve_into_stream_with_config(
+            &request,
+            &*root_node,
+            &*context,
+            &*http_config,
+        )
+        .await
+        {
+            Ok((stream, errors)) => {
+                let mut connection = Connection::from_stream(stream, errors);
+                while let Some(output) = connection.next().await {
+                    if tx.send(next_event(output)).await.is_err() {
+                        break;
+                    }
+                }
+            }
+            Err(e) => {
+                let _ = tx.send(error_event(e)).await;
+            }
+        }
+    };
+    let execution = execution.into_stream().filter_map(|()| future::ready(None));
+
+    frames(stream::select(rx, execution).boxed(), config)
+}
+
+/// Interleaves keep-alives with the given events until they end, then completes the stream.
+fn frames<'a>(events: BoxStream<'a, String>, config: &EventStreamConfig) -> BoxStream<'a, String> {
+    // `None` marks the end of the events, as keep-alives never end.
+    let events = events.map(Some).chain(stream::once(future::ready(None)));
+    let events = match config.keep_alive_interval {
+        interval if interval > Duration::from_secs(0) => {
+            stream::select(events, keep_alives(interval)).boxed()
+        }
+        _ => events.boxed(),
+    };
+
+    events
+        .take_while(|event| future::ready(event.is_some()))
+        .filter_map(future::ready)
+        .chain(stream::once(future::ready(COMPLETE_EVENT.to_owned())))
+        .boxed()
+}
+
+fn keep_alives(interval: Duration) -> impl Stream<Item = Option<String>> + Send {
+    stream::unfold((), move |()| async move {
+        tokio::time::delay_for(interval).await;
+        Some((Some(KEEP_ALIVE.to_owned()), ()))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use juniper::{
+        graphql_object, graphql_subscription, http::safelist::Safelist, EmptyMutation, FieldError,
+        FieldResult, Value,
+    };
+
+    use super::*;
+
+    struct Context(i32);
+
+    struct Query;
+
+    #[graphql_object(context = Context)]
+    impl Query {
+        /// context just resolves to the current context.
+        async fn context(context: &Context) -> i32 {
+            context.0
+        }
+    }
+
+    struct Subscription;
+
+    #[graphql_subscription(context = Context)]
+    impl Subscription {
+        /// count emits the numbers from 1 to the current context, then ends.
+        async fn count(context: &Context) -> BoxStream<'static, FieldResult<i32>> {
+            stream::iter((1..=context.0).map(Ok)).boxed()
+        }
+
+        /// never never emits anything.
+        async fn never() -> BoxStream<'static, FieldResult<i32>> {
+            stream::pending().boxed()
+        }
+
+        /// error emits an error once, then ends.
+        async fn error() -> BoxStream<'static, FieldResult<i32>> {
+            stream::once(future::ready(Err(FieldError::new(
+                "field error",
+                Value::null(),
+            ))))
+            .boxed()
+        }
+    }
+
+    fn subscribe_to(query: &str, config: &EventStreamConfig) -> BoxStream<'static, String> {
+        subscribe(
+            Arc::new(RootNode::new(Query, EmptyMutation::new(), Subscription)),
+            GraphQLRequest::new(query.to_owned(), None, None),
+            Arc::new(Context(2)),
+            config,
+        )
+    }
+
+    fn without_keep_alives() -> EventStreamConfig {
+        EventStreamConfig::new().with_keep_alive_interval(Duration::from_secs(0))
+    }
+
+    #[tokio::test]
+    async fn test_subscription() {
+        let frames: Vec<_> = subscribe_to("subscription { count }", &without_keep_alives())
+            .collect()
+            .await;
+
+        assert_eq!(
+            frames,
+            vec![
+                "event: next\ndata: {\"data\":{\"count\":1}}\n\n",
+                "event: next\ndata: {\"data\":{\"count\":2}}\n\n",
+                "event: complete\ndata:\n\n",
+            ],
+        );
+    }
+
+    #[tokio::test]
+    async fn test_field_error() {
+        let frames: Vec<_> = subscribe_to("subscription { error }", &without_keep_alives())
+            .collect()
+            .await;
+
+        assert_eq!(frames.len(), 2);
+        assert!(
+            frames[0].starts_with("event: next\ndata: {\"data\":{\"error\":null},\"errors\":[{")
+        );
+        assert!(frames[0].contains("\"message\":\"field error\""));
+        assert_eq!(frames[1], COMPLETE_EVENT);
+    }
+
+    #[tokio::test]
+    async fn test_validation_error() {
+        let frames: Vec<_> = subscribe_to("subscription { unknown }", &without_keep_alives())
+            .collect()
+            .await;
+
+        assert_eq!(frames.len(), 2);
+        assert!(frames[0].starts_with("event: next\ndata: {\"errors\":[{"));
+        assert!(frames[0].contains("Unknown field \\\"unknown\\\""));
+        assert_eq!(frames[1], COMPLETE_EVENT);
+    }
+
+    #[tokio::test]
+    async fn test_safelist() {
+        let safelist = Safelist::new(vec![("Count", "subscription Count { count }")]);
+        let http_config = Arc::new(HttpConfig::new().enable_safelist(safelist));
+        let subscribe_to = |query: &str| {
+            subscribe_with_config(
+                Arc::new(RootNode::new(Query, EmptyMutation::new(), Subscription)),
+                GraphQLRequest::new(query.to_owned(), None, None),
+                Arc::new(Context(2)),
+                &without_keep_alives(),
+                http_config.clone(),
+            )
+            .collect::<Vec<_>>()
+        };
+
+        let frames = subscribe_to("subscription Count { count }").await;
+        assert_eq!(frames.len(), 3);
+
+        let frames = subscribe_to("subscription { never }").await;
+        assert_eq!(frames.len(), 2);
+        assert!(frames[0].contains("Operation is not in the safelist"));
+        assert_eq!(frames[1], COMPLETE_EVENT);
+    }
+
+    #[tokio::test]
+    async fn test_keep_alives() {
+        let config = EventStreamConfig::new().with_keep_alive_interval(Duration::from_millis(10));
+        let frames: Vec<_> = subscribe_to("subscription { never }", &config)
+            .take(2)
+            .collect()
+            .await;
+
+        assert_eq!(frames, vec![KEEP_ALIVE, KEEP_ALIVE]);
+    }
+
+    #[tokio::test]
+    async fn test_event_stream() {
+        let root_node = RootNode::new(Query, EmptyMutation::new(), Subscription);
+        let context = Context(1);
+        let (stream, errors) = juniper::resolve_into_stream(
+            "subscription { count }",
+            None,
+            &root_node,
+            &Default::default(),
+            &context,
+        )
+        .await
+        .unwrap();
+
+        let frames: Vec<_> = event_stream(
+            Connection::from_stream(stream, errors),
+            &without_keep_alives(),
+        )
+        .collect()
+        .await;
+
+        assert_eq!(
+            frames,
+            vec![
+                "event: next\ndata: {\"data\":{\"count\":1}}\n\n",
+                COMPLETE_EVENT,
+            ],
+        );
+    }
+}
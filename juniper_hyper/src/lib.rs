@@ -1,17 +1,27 @@
 #![doc(html_root_url = "https://docs.rs/juniper_hyper/0.2.0")]
 
-use std::{error::Error, fmt, string::FromUtf8Error, sync::Arc};
+#[cfg(feature = "sse")]
+use std::convert::Infallible;
+use std::{fmt, sync::Arc};
 
+#[cfg(feature = "sse")]
+use futures::StreamExt;
 use hyper::{
+    body::HttpBody as _,
     header::{self, HeaderValue},
     Body, Method, Request, Response, StatusCode,
 };
 use juniper::{
-    http::{GraphQLBatchRequest, GraphQLRequest as JuniperGraphQLRequest, GraphQLRequest},
-    GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, InputValue, RootNode, ScalarValue,
+    http::{
+        graphiql::GraphiQLSettings,
+        parse::{parse_request_with_limits, ParseRequestError},
+        playground::PlaygroundSettings,
+        GraphQLBatchRequest, HttpConfig, RequestLimits, ResponseContentType,
+    },
+    GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, RootNode, ScalarValue,
 };
-use serde_json::error::Error as SerdeError;
-use url::form_urlencoded;
+#[cfg(feature = "sse")]
+use juniper_graphql_sse::EventStreamConfig;
 
 pub async fn graphql_sync<CtxT, QueryT, MutationT, SubscriptionT, S>(
     root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
@@ -28,8 +38,29 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
-    Ok(match parse_req(req).await {
-        Ok(req) => execute_request_sync(root_node, context, req).await,
+    graphql_sync_with_config(root_node, context, req, Arc::default()).await
+}
+
+pub async fn graphql_sync_with_config<CtxT, QueryT, MutationT, SubscriptionT, S>(
+    root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
+    context: Arc<CtxT>,
+    req: Request<Body>,
+    config: Arc<HttpConfig<CtxT, S>>,
+) -> Result<Response<Body>, hyper::Error>
+where
+    QueryT: GraphQLType<S, Context = CtxT>,
+    QueryT::TypeInfo: Sync,
+    MutationT: GraphQLType<S, Context = CtxT>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = CtxT>,
+    SubscriptionT::TypeInfo: Sync,
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    Ok(match parse_req(req, config.request_limits()).await {
+        Ok((req, content_type)) => {
+            execute_request_sync(root_node, context, req, content_type, &config).await
+        }
         Err(resp) => resp,
     })
 }
@@ -49,73 +80,159 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
-    Ok(match parse_req(req).await {
-        Ok(req) => execute_request(root_node, context, req).await,
+    graphql_with_config(root_node, context, req, Arc::default()).await
+}
+
+pub async fn graphql_with_config<CtxT, QueryT, MutationT, SubscriptionT, S>(
+    root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
+    context: Arc<CtxT>,
+    req: Request<Body>,
+    config: Arc<HttpConfig<CtxT, S>>,
+) -> Result<Response<Body>, hyper::Error>
+where
+    QueryT: GraphQLTypeAsync<S, Context = CtxT>,
+    QueryT::TypeInfo: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = CtxT>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT>,
+    SubscriptionT::TypeInfo: Sync,
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    Ok(match parse_req(req, config.request_limits()).await {
+        Ok((req, content_type)) => {
+            execute_request(root_node, context, req, content_type, &config).await
+        }
         Err(resp) => resp,
     })
 }
 
-async fn parse_req<S: ScalarValue>(
+#[cfg(feature = "sse")]
+pub async fn graphql_sse<CtxT, QueryT, MutationT, SubscriptionT, S>(
+    root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
+    context: Arc<CtxT>,
     req: Request<Body>,
-) -> Result<GraphQLBatchRequest<S>, Response<Body>> {
-    match *req.method() {
-        Method::GET => parse_get_req(req),
-        Method::POST => {
-            let content_type = req
-                .headers()
-                .get(header::CONTENT_TYPE)
-                .map(HeaderValue::to_str);
-            match content_type {
-                Some(Ok("application/json")) => parse_post_json_req(req.into_body()).await,
-                Some(Ok("application/graphql")) => parse_post_graphql_req(req.into_body()).await,
-                _ => return Err(new_response(StatusCode::BAD_REQUEST)),
-            }
-        }
-        _ => return Err(new_response(StatusCode::METHOD_NOT_ALLOWED)),
-    }
-    .map_err(|e| render_error(e))
+    config: &EventStreamConfig,
+) -> Result<Response<Body>, hyper::Error>
+where
+    QueryT: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    QueryT::TypeInfo: Send + Sync,
+    MutationT: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    MutationT::TypeInfo: Send + Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    SubscriptionT::TypeInfo: Send + Sync,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    graphql_sse_with_config(root_node, context, req, config, Arc::default()).await
 }
 
-fn parse_get_req<S: ScalarValue>(
+#[cfg(feature = "sse")]
+pub async fn graphql_sse_with_config<CtxT, QueryT, MutationT, SubscriptionT, S>(
+    root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
+    context: Arc<CtxT>,
     req: Request<Body>,
-) -> Result<GraphQLBatchRequest<S>, GraphQLRequestError> {
-    req.uri()
-        .query()
-        .map(|q| gql_request_from_get(q).map(GraphQLBatchRequest::Single))
-        .unwrap_or_else(|| {
-            Err(GraphQLRequestError::Invalid(
-                "'query' parameter is missing".to_string(),
-            ))
-        })
+    config: &EventStreamConfig,
+    http_config: Arc<HttpConfig<CtxT, S>>,
+) -> Result<Response<Body>, hyper::Error>
+where
+    QueryT: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    QueryT::TypeInfo: Send + Sync,
+    MutationT: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    MutationT::TypeInfo: Send + Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    SubscriptionT::TypeInfo: Send + Sync,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    let req = read_req(req, http_config.request_limits()).await;
+    Ok(match req {
+        Ok(GraphQLBatchRequest::Single(req)) => {
+            let frames = juniper_graphql_sse::subscribe_with_config(
+                root_node,
+                req,
+                context,
+                config,
+                http_config,
+            );
+            let mut resp = new_response(StatusCode::OK);
+            resp.headers_mut().insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(juniper_graphql_sse::CONTENT_TYPE),
+            );
+            resp.headers_mut()
+                .insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+            *resp.body_mut() = Body::wrap_stream(frames.map(Ok::<_, Infallible>));
+            resp
+        }
+        Ok(GraphQLBatchRequest::Batch(_)) => render_error(
+            StatusCode::BAD_REQUEST,
+            "Batch requests can't be served over Server-Sent Events",
+        ),
+        Err(resp) => resp,
+    })
 }
 
-async fn parse_post_json_req<S: ScalarValue>(
-    body: Body,
-) -> Result<GraphQLBatchRequest<S>, GraphQLRequestError> {
-    let chunk = hyper::body::to_bytes(body)
+async fn parse_req<S: ScalarValue>(
+    req: Request<Body>,
+    limits: &RequestLimits,
+) -> Result<(GraphQLBatchRequest<S>, ResponseContentType), Response<Body>> {
+    let accept = req
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok());
+    let response_content_type = ResponseContentType::negotiate(accept).ok_or_else(|| {
+        render_error(
+            StatusCode::NOT_ACCEPTABLE,
+            "GraphQL responses are only available as application/graphql-response+json \
+             or application/json",
+        )
+    })?;
+    read_req(req, limits)
         .await
-        .map_err(GraphQLRequestError::BodyHyper)?;
-
-    let input = String::from_utf8(chunk.iter().cloned().collect())
-        .map_err(GraphQLRequestError::BodyUtf8)?;
-
-    serde_json::from_str::<GraphQLBatchRequest<S>>(&input)
-        .map_err(GraphQLRequestError::BodyJSONError)
+        .map(|req| (req, response_content_type))
 }
 
-async fn parse_post_graphql_req<S: ScalarValue>(
-    body: Body,
-) -> Result<GraphQLBatchRequest<S>, GraphQLRequestError> {
-    let chunk = hyper::body::to_bytes(body)
-        .await
-        .map_err(GraphQLRequestError::BodyHyper)?;
+async fn read_req<S: ScalarValue>(
+    req: Request<Body>,
+    limits: &RequestLimits,
+) -> Result<GraphQLBatchRequest<S>, Response<Body>> {
+    let (parts, body) = req.into_parts();
+    let body = match parts.method {
+        Method::POST => read_body(body, limits).await?,
+        _ => Default::default(),
+    };
+    let content_type = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|ct| ct.to_str().ok());
+
+    parse_request_with_limits(
+        parts.method.as_str(),
+        content_type,
+        parts.uri.query(),
+        &body,
+        limits,
+    )
+    .map_err(render_parse_error)
+}
 
-    let query = String::from_utf8(chunk.iter().cloned().collect())
-        .map_err(GraphQLRequestError::BodyUtf8)?;
+/// Reads the body of a request, stopping as soon as it exceeds the limits
+async fn read_body(mut body: Body, limits: &RequestLimits) -> Result<Vec<u8>, Response<Body>> {
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|e| render_error(StatusCode::BAD_REQUEST, e))?;
+        bytes.extend_from_slice(&chunk);
+        limits
+            .check_body_size(bytes.len())
+            .map_err(render_parse_error)?;
+    }
+    Ok(bytes)
+}
 
-    Ok(GraphQLBatchRequest::Single(GraphQLRequest::new(
-        query, None, None,
-    )))
+fn render_parse_error(e: ParseRequestError) -> Response<Body> {
+    let status = StatusCode::from_u16(e.status_code()).unwrap_or(StatusCode::BAD_REQUEST);
+    render_error(status, e)
 }
 
 pub async fn graphiql(
@@ -131,6 +248,14 @@ pub async fn graphiql(
     Ok(resp)
 }
 
+pub async fn graphiql_with_settings(
+    settings: &GraphiQLSettings,
+) -> Result<Response<Body>, hyper::Error> {
+    let mut resp = new_html_response(StatusCode::OK);
+    *resp.body_mut() = Body::from(settings.source());
+    Ok(resp)
+}
+
 pub async fn playground(
     graphql_endpoint: &str,
     subscriptions_endpoint: Option<&str>,
@@ -143,9 +268,17 @@ pub async fn playground(
     Ok(resp)
 }
 
-fn render_error(err: GraphQLRequestError) -> Response<Body> {
+pub async fn playground_with_settings(
+    settings: &PlaygroundSettings,
+) -> Result<Response<Body>, hyper::Error> {
+    let mut resp = new_html_response(StatusCode::OK);
+    *resp.body_mut() = Body::from(settings.source());
+    Ok(resp)
+}
+
+fn render_error(status: StatusCode, err: impl fmt::Display) -> Response<Body> {
     let message = format!("{}", err);
-    let mut resp = new_response(StatusCode::BAD_REQUEST);
+    let mut resp = new_response(status);
     *resp.body_mut() = Body::from(message);
     resp
 }
@@ -154,6 +287,8 @@ async fn execute_request_sync<CtxT, QueryT, MutationT, SubscriptionT, S>(
     root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
     context: Arc<CtxT>,
     request: GraphQLBatchRequest<S>,
+    content_type: ResponseContentType,
+    config: &HttpConfig<CtxT, S>,
 ) -> Response<Body>
 where
     QueryT: GraphQLType<S, Context = CtxT>,
@@ -165,18 +300,21 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
-    let res = request.execute_sync(&*root_node, &context);
+    let res = request.execute_sync_with_config(&*root_node, &context, config);
     let body = Body::from(serde_json::to_string_pretty(&res).unwrap());
-    let code = if res.is_ok() {
-        StatusCode::OK
-    } else {
-        StatusCode::BAD_REQUEST
-    };
+    let code =
+        StatusCode::from_u16(res.status_code(content_type)).unwrap_or(StatusCode::BAD_REQUEST);
     let mut resp = new_response(code);
     resp.headers_mut().insert(
         header::CONTENT_TYPE,
-        HeaderValue::from_static("application/json"),
+        HeaderValue::from_static(content_type.as_str()),
     );
+    if let Some(cache_control) = res.cache_policy().and_then(|p| p.header_value()) {
+        resp.headers_mut().insert(
+            header::CACHE_CONTROL,
+            HeaderValue::from_str(&cache_control).expect("Cache-Control is valid"),
+        );
+    }
     *resp.body_mut() = body;
     resp
 }
@@ -185,6 +323,8 @@ async fn execute_request<CtxT, QueryT, MutationT, SubscriptionT, S>(
     root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
     context: Arc<CtxT>,
     request: GraphQLBatchRequest<S>,
+    content_type: ResponseContentType,
+    config: &HttpConfig<CtxT, S>,
 ) -> Response<Body>
 where
     QueryT: GraphQLTypeAsync<S, Context = CtxT>,
@@ -196,71 +336,27 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
-    let res = request.execute(&*root_node, &context).await;
+    let res = request
+        .execute_with_config(&*root_node, &context, config)
+        .await;
     let body = Body::from(serde_json::to_string_pretty(&res).unwrap());
-    let code = if res.is_ok() {
-        StatusCode::OK
-    } else {
-        StatusCode::BAD_REQUEST
-    };
+    let code =
+        StatusCode::from_u16(res.status_code(content_type)).unwrap_or(StatusCode::BAD_REQUEST);
     let mut resp = new_response(code);
     resp.headers_mut().insert(
         header::CONTENT_TYPE,
-        HeaderValue::from_static("application/json"),
+        HeaderValue::from_static(content_type.as_str()),
     );
+    if let Some(cache_control) = res.cache_policy().and_then(|p| p.header_value()) {
+        resp.headers_mut().insert(
+            header::CACHE_CONTROL,
+            HeaderValue::from_str(&cache_control).expect("Cache-Control is valid"),
+        );
+    }
     *resp.body_mut() = body;
     resp
 }
 
-fn gql_request_from_get<S>(input: &str) -> Result<JuniperGraphQLRequest<S>, GraphQLRequestError>
-where
-    S: ScalarValue,
-{
-    let mut query = None;
-    let operation_name = None;
-    let mut variables = None;
-    for (key, value) in form_urlencoded::parse(input.as_bytes()).into_owned() {
-        match key.as_ref() {
-            "query" => {
-                if query.is_some() {
-                    return Err(invalid_err("query"));
-                }
-                query = Some(value)
-            }
-            "operationName" => {
-                if operation_name.is_some() {
-                    return Err(invalid_err("operationName"));
-                }
-            }
-            "variables" => {
-                if variables.is_some() {
-                    return Err(invalid_err("variables"));
-                }
-                match serde_json::from_str::<InputValue<S>>(&value)
-                    .map_err(GraphQLRequestError::Variables)
-                {
-                    Ok(parsed_variables) => variables = Some(parsed_variables),
-                    Err(e) => return Err(e),
-                }
-            }
-            _ => continue,
-        }
-    }
-    match query {
-        Some(query) => Ok(JuniperGraphQLRequest::new(query, operation_name, variables)),
-        None => Err(GraphQLRequestError::Invalid(
-            "'query' parameter is missing".to_string(),
-        )),
-    }
-}
-
-fn invalid_err(parameter_name: &str) -> GraphQLRequestError {
-    GraphQLRequestError::Invalid(format!(
-        "'{}' parameter is specified multiple times",
-        parameter_name
-    ))
-}
-
 fn new_response(code: StatusCode) -> Response<Body> {
     let mut r = Response::new(Body::empty());
     *r.status_mut() = code;
@@ -276,39 +372,6 @@ fn new_html_response(code: StatusCode) -> Response<Body> {
     resp
 }
 
-#[derive(Debug)]
-enum GraphQLRequestError {
-    BodyHyper(hyper::Error),
-    BodyUtf8(FromUtf8Error),
-    BodyJSONError(SerdeError),
-    Variables(SerdeError),
-    Invalid(String),
-}
-
-impl fmt::Display for GraphQLRequestError {
-    fn fmt(&self, mut f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            GraphQLRequestError::BodyHyper(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLRequestError::BodyUtf8(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLRequestError::BodyJSONError(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLRequestError::Variables(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLRequestError::Invalid(ref err) => fmt::Display::fmt(err, &mut f),
-        }
-    }
-}
-
-impl Error for GraphQLRequestError {
-    fn source(&self) -> Option<&(dyn Error + 'static)> {
-        match *self {
-            GraphQLRequestError::BodyHyper(ref err) => Some(err),
-            GraphQLRequestError::BodyUtf8(ref err) => Some(err),
-            GraphQLRequestError::BodyJSONError(ref err) => Some(err),
-            GraphQLRequestError::Variables(ref err) => Some(err),
-            GraphQLRequestError::Invalid(_) => None,
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use hyper::{
@@ -356,6 +419,57 @@ mod tests {
                 .expect(&format!("failed POST {}", url));
             make_test_response(res)
         }
+
+        fn get_with_accept(&self, url: &str, accept: &str) -> http_tests::TestResponse {
+            let url = format!("http://127.0.0.1:{}/graphql{}", self.port, url);
+            let client = reqwest::blocking::Client::new();
+            let res = client
+                .get(&url)
+                .header(reqwest::header::ACCEPT, accept)
+                .send()
+                .expect(&format!("failed GET {}", url));
+            make_test_response(res)
+        }
+
+        fn post_json_with_accept(
+            &self,
+            url: &str,
+            body: &str,
+            accept: &str,
+        ) -> http_tests::TestResponse {
+            let url = format!("http://127.0.0.1:{}/graphql{}", self.port, url);
+            let client = reqwest::blocking::Client::new();
+            let res = client
+                .post(&url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(reqwest::header::ACCEPT, accept)
+                .body(body.to_string())
+                .send()
+                .expect(&format!("failed POST {}", url));
+            make_test_response(res)
+        }
+    }
+
+    impl http_tests::MultipartIntegration for TestHyperIntegration {
+        fn post_multipart(
+            &self,
+            url: &str,
+            boundary: &str,
+            body: &str,
+        ) -> http_tests::TestResponse {
+            let url = format!("http://127.0.0.1:{}/graphql{}", self.port, url);
+            let client = reqwest::blocking::Client::new();
+            let res = client
+                .post(&url)
+                .header(
+                    reqwest::header::CONTENT_TYPE,
+                    format!("multipart/form-data; boundary={}", boundary),
+                )
+                .body(body.to_string())
+                .send()
+                .expect(&format!("failed POST {}", url));
+            make_test_response(res)
+        }
     }
 
     fn make_test_response(response: ReqwestResponse) -> http_tests::TestResponse {
@@ -434,6 +548,7 @@ mod tests {
             thread::sleep(Duration::from_millis(10)); // wait 10ms for server to bind
             let integration = TestHyperIntegration { port };
             http_tests::run_http_test_suite(&integration);
+            http_tests::run_multipart_test_suite(&integration);
             shutdown.abort();
         });
 
@@ -451,4 +566,44 @@ mod tests {
     async fn test_sync_hyper_integration() {
         run_hyper_integration(true).await
     }
+
+    #[cfg(feature = "sse")]
+    #[tokio::test]
+    async fn test_sse_integration() {
+        use hyper::Request;
+        use juniper_graphql_sse::EventStreamConfig;
+
+        let root_node = Arc::new(RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        ));
+        let db = Arc::new(Database::new());
+        let config = EventStreamConfig::new();
+
+        let req = Request::get("/graphql/stream?query=%7Bhero%7Bname%7D%7D")
+            .body(Body::empty())
+            .unwrap();
+        let resp = super::graphql_sse(root_node.clone(), db.clone(), req, &config)
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers()["content-type"], "text/event-stream");
+        assert_eq!(
+            hyper::body::to_bytes(resp.into_body()).await.unwrap(),
+            "event: next\ndata: {\"errors\":[{\"message\":\"Expected subscription, got query\"}]}\n\n\
+             event: complete\ndata:\n\n",
+        );
+
+        let req = Request::post("/graphql/stream")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                r#"[{ "query": "{ hero { name } }" }, { "query": "{ hero { id } }" }]"#,
+            ))
+            .unwrap();
+        let resp = super::graphql_sse(root_node, db, req, &config)
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
 }
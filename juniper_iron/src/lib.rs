@@ -106,33 +106,19 @@ supported.
 
 #![doc(html_root_url = "https://docs.rs/juniper_iron/0.3.0")]
 
-use std::{error::Error, fmt, io::Read, ops::Deref as _};
-
-use iron::{
-    headers::ContentType,
-    itry, method,
-    middleware::Handler,
-    mime::{Mime, TopLevel},
-    prelude::*,
-    status,
-};
+use std::io::Read;
+
+use iron::{headers::ContentType, itry, middleware::Handler, mime::Mime, prelude::*, status};
 use juniper::{
-    http, http::GraphQLBatchRequest, DefaultScalarValue, GraphQLType, InputValue, RootNode,
-    ScalarValue,
+    http::{
+        graphiql::GraphiQLSettings,
+        parse::{parse_request_with_limits, ParseRequestError},
+        playground::PlaygroundSettings,
+        GraphQLBatchRequest, HttpConfig, ResponseContentType,
+    },
+    DefaultScalarValue, GraphQLType, RootNode, ScalarValue,
 };
-use serde_json::error::Error as SerdeError;
-use urlencoded::{UrlDecodingError, UrlEncodedQuery};
-
-/// Handler that executes `GraphQL` queries in the given schema
-///
-/// The handler responds to GET requests and POST requests only. In GET
-/// requests, the query should be supplied in the `query` URL parameter, e.g.
-/// `http://localhost:3000/graphql?query={hero{name}}`.
-///
-/// POST requests support both queries and variables. POST a JSON document to
-/// this endpoint containing the field `"query"` and optionally `"variables"`.
-/// The variables should be a JSON object containing the variable to value
-/// mapping.
+
 pub struct GraphQLHandler<
     'a,
     CtxFactory,
@@ -151,49 +137,17 @@ pub struct GraphQLHandler<
 {
     context_factory: CtxFactory,
     root_node: RootNode<'a, Query, Mutation, Subscription, S>,
+    config: HttpConfig<CtxT, S>,
 }
 
 /// Handler that renders `GraphiQL` - a graphical query editor interface
 pub struct GraphiQLHandler {
-    graphql_url: String,
-    subscription_url: Option<String>,
+    settings: GraphiQLSettings,
 }
 
 /// Handler that renders `GraphQL Playground` - a graphical query editor interface
 pub struct PlaygroundHandler {
-    graphql_url: String,
-    subscription_url: Option<String>,
-}
-
-fn get_single_value<T>(mut values: Vec<T>) -> IronResult<T> {
-    if values.len() == 1 {
-        Ok(values.remove(0))
-    } else {
-        Err(GraphQLIronError::InvalidData("Duplicate URL query parameter").into())
-    }
-}
-
-fn parse_url_param(params: Option<Vec<String>>) -> IronResult<Option<String>> {
-    if let Some(values) = params {
-        get_single_value(values).map(Some)
-    } else {
-        Ok(None)
-    }
-}
-
-fn parse_variable_param<S>(params: Option<Vec<String>>) -> IronResult<Option<InputValue<S>>>
-where
-    S: ScalarValue,
-{
-    if let Some(values) = params {
-        Ok(
-            serde_json::from_str::<InputValue<S>>(get_single_value(values)?.as_ref())
-                .map(Some)
-                .map_err(GraphQLIronError::Serde)?,
-        )
-    } else {
-        Ok(None)
-    }
+    settings: PlaygroundSettings,
 }
 
 impl<'a, CtxFactory, Query, Mutation, Subscription, CtxT, S>
@@ -221,58 +175,55 @@ where
         GraphQLHandler {
             context_factory,
             root_node: RootNode::new(query, mutation, subscription),
+            config: HttpConfig::default(),
         }
     }
 
-    fn handle_get(&self, req: &mut Request) -> IronResult<GraphQLBatchRequest<S>> {
-        let url_query = req
-            .get_mut::<UrlEncodedQuery>()
-            .map_err(GraphQLIronError::Url)?;
-
-        let query = parse_url_param(url_query.remove("query"))?
-            .ok_or_else(|| GraphQLIronError::InvalidData("No query provided"))?;
-        let operation_name = parse_url_param(url_query.remove("operationName"))?;
-        let variables = parse_variable_param(url_query.remove("variables"))?;
-
-        Ok(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
-            query,
-            operation_name,
-            variables,
-        )))
+    /// Execute the requests along with the given [`HttpConfig`], e.g. to
+    /// limit the size of their bodies and files, or to enable persisted
+    /// queries
+    pub fn with_config(mut self, config: HttpConfig<CtxT, S>) -> Self {
+        self.config = config;
+        self
     }
 
-    fn handle_post_json(&self, req: &mut Request) -> IronResult<GraphQLBatchRequest<S>> {
-        let mut payload = String::new();
-        itry!(req.body.read_to_string(&mut payload));
-
-        Ok(
-            serde_json::from_str::<GraphQLBatchRequest<S>>(payload.as_str())
-                .map_err(GraphQLIronError::Serde)?,
+    fn parse(&self, req: &mut Request) -> IronResult<GraphQLBatchRequest<S>> {
+        let limits = self.config.request_limits();
+        let mut body = Vec::new();
+        if req.method == iron::method::Post {
+            // Stop reading as soon as the body exceeds the limits.
+            let mut chunk = [0; 8 * 1024];
+            loop {
+                let read = itry!(req.body.read(&mut chunk));
+                if read == 0 {
+                    break;
+                }
+                body.extend_from_slice(&chunk[..read]);
+                limits.check_body_size(body.len()).map_err(request_error)?;
+            }
+        }
+        let content_type = req.headers.get::<ContentType>().map(ToString::to_string);
+
+        parse_request_with_limits(
+            &req.method.to_string(),
+            content_type.as_deref(),
+            req.url.query(),
+            &body,
+            limits,
         )
-    }
-
-    fn handle_post_graphql(&self, req: &mut Request) -> IronResult<GraphQLBatchRequest<S>> {
-        let mut payload = String::new();
-        itry!(req.body.read_to_string(&mut payload));
-
-        Ok(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
-            payload, None, None,
-        )))
+        .map_err(request_error)
     }
 
     fn execute_sync(
         &self,
         context: &CtxT,
         request: GraphQLBatchRequest<S>,
+        content_type: ResponseContentType,
     ) -> IronResult<Response> {
-        let response = request.execute_sync(&self.root_node, context);
-        let content_type = "application/json".parse::<Mime>().unwrap();
+        let response = request.execute_sync_with_config(&self.root_node, context, &self.config);
+        let status = status::Status::from_u16(response.status_code(content_type));
+        let content_type = content_type.as_str().parse::<Mime>().unwrap();
         let json = serde_json::to_string_pretty(&response).unwrap();
-        let status = if response.is_ok() {
-            status::Ok
-        } else {
-            status::BadRequest
-        };
         Ok(Response::with((content_type, status, json)))
     }
 }
@@ -283,10 +234,17 @@ impl GraphiQLHandler {
     /// The provided URL should point to the URL of the attached `GraphQLHandler`. It can be
     /// relative, so a common value could be `"/graphql"`.
     pub fn new(graphql_url: &str, subscription_url: Option<&str>) -> GraphiQLHandler {
-        GraphiQLHandler {
-            graphql_url: graphql_url.to_owned(),
-            subscription_url: subscription_url.map(|s| s.to_owned()),
+        let mut settings = GraphiQLSettings::new(graphql_url);
+        if let Some(url) = subscription_url {
+            settings = settings.subscriptions_endpoint(url);
         }
+        GraphiQLHandler::with_settings(settings)
+    }
+
+    /// Build a new GraphiQL handler configured with the given settings, e.g. to
+    /// load its assets from another handler instead of public CDNs.
+    pub fn with_settings(settings: GraphiQLSettings) -> GraphiQLHandler {
+        GraphiQLHandler { settings }
     }
 }
 
@@ -296,10 +254,18 @@ impl PlaygroundHandler {
     /// The provided URL should point to the URL of the attached `GraphQLHandler`. It can be
     /// relative, so a common value could be `"/graphql"`.
     pub fn new(graphql_url: &str, subscription_url: Option<&str>) -> PlaygroundHandler {
-        PlaygroundHandler {
-            graphql_url: graphql_url.to_owned(),
-            subscription_url: subscription_url.map(|s| s.to_owned()),
+        let mut settings = PlaygroundSettings::new(graphql_url);
+        if let Some(url) = subscription_url {
+            settings = settings.subscriptions_endpoint(url);
         }
+        PlaygroundHandler::with_settings(settings)
+    }
+
+    /// Build a new GraphQL Playground handler configured with the given
+    /// settings, e.g. to load its assets from another handler instead of a
+    /// public CDN.
+    pub fn with_settings(settings: PlaygroundSettings) -> PlaygroundHandler {
+        PlaygroundHandler { settings }
     }
 }
 
@@ -314,23 +280,24 @@ where
     Subscription: GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
     'a: 'static,
 {
-    fn handle(&self, mut req: &mut Request) -> IronResult<Response> {
+    fn handle(&self, req: &mut Request) -> IronResult<Response> {
+        let accept = req.headers.get_raw("accept").map(|values| {
+            values
+                .iter()
+                .map(|value| String::from_utf8_lossy(value))
+                .collect::<Vec<_>>()
+                .join(",")
+        });
+        let content_type = match ResponseContentType::negotiate(accept.as_deref()) {
+            Some(content_type) => content_type,
+            None => return Ok(not_acceptable_response()),
+        };
+
         let context = (self.context_factory)(req)?;
 
-        let graphql_request = match req.method {
-            method::Get => self.handle_get(&mut req)?,
-            method::Post => match req.headers.get::<ContentType>().map(ContentType::deref) {
-                Some(Mime(TopLevel::Application, sub_lvl, _)) => match sub_lvl.as_str() {
-                    "json" => self.handle_post_json(&mut req)?,
-                    "graphql" => self.handle_post_graphql(&mut req)?,
-                    _ => return Ok(Response::with(status::BadRequest)),
-                },
-                _ => return Ok(Response::with(status::BadRequest)),
-            },
-            _ => return Ok(Response::with(status::MethodNotAllowed)),
-        };
+        let graphql_request = self.parse(req)?;
 
-        self.execute_sync(&context, graphql_request)
+        self.execute_sync(&context, graphql_request, content_type)
     }
 }
 
@@ -341,10 +308,7 @@ impl Handler for GraphiQLHandler {
         Ok(Response::with((
             content_type,
             status::Ok,
-            juniper::http::graphiql::graphiql_source(
-                &self.graphql_url,
-                self.subscription_url.as_deref(),
-            ),
+            self.settings.source(),
         )))
     }
 }
@@ -356,46 +320,26 @@ impl Handler for PlaygroundHandler {
         Ok(Response::with((
             content_type,
             status::Ok,
-            juniper::http::playground::playground_source(
-                &self.graphql_url,
-                self.subscription_url.as_deref(),
-            ),
+            self.settings.source(),
         )))
     }
 }
 
-#[derive(Debug)]
-enum GraphQLIronError {
-    Serde(SerdeError),
-    Url(UrlDecodingError),
-    InvalidData(&'static str),
-}
-
-impl fmt::Display for GraphQLIronError {
-    fn fmt(&self, mut f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            GraphQLIronError::Serde(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLIronError::Url(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLIronError::InvalidData(err) => fmt::Display::fmt(err, &mut f),
-        }
-    }
-}
+fn not_acceptable_response() -> Response {
+    let content_type = "text/plain; charset=utf-8".parse::<Mime>().unwrap();
 
-impl Error for GraphQLIronError {
-    fn cause(&self) -> Option<&dyn Error> {
-        match *self {
-            GraphQLIronError::Serde(ref err) => Some(err),
-            GraphQLIronError::Url(ref err) => Some(err),
-            GraphQLIronError::InvalidData(_) => None,
-        }
-    }
+    Response::with((
+        content_type,
+        status::NotAcceptable,
+        "GraphQL responses are only available as application/graphql-response+json \
+         or application/json",
+    ))
 }
 
-impl From<GraphQLIronError> for IronError {
-    fn from(err: GraphQLIronError) -> IronError {
-        let message = format!("{}", err);
-        IronError::new(err, (status::BadRequest, message))
-    }
+fn request_error(err: ParseRequestError) -> IronError {
+    let status = status::Status::from_u16(err.status_code());
+    let message = err.to_string();
+    IronError::new(err, (status, message))
 }
 
 #[cfg(test)]
@@ -465,6 +409,46 @@ mod tests {
                 .map(make_test_response)
                 .unwrap_or_else(make_test_error_response)
         }
+
+        fn get_with_accept(&self, url: &str, accept: &str) -> http_tests::TestResponse {
+            let mut headers = Headers::new();
+            headers.set_raw("accept", vec![accept.as_bytes().to_vec()]);
+            request::get(&fixup_url(url), headers, &make_handler())
+                .map(make_test_response)
+                .unwrap_or_else(make_test_error_response)
+        }
+
+        fn post_json_with_accept(
+            &self,
+            url: &str,
+            body: &str,
+            accept: &str,
+        ) -> http_tests::TestResponse {
+            let mut headers = Headers::new();
+            headers.set(ContentType::json());
+            headers.set_raw("accept", vec![accept.as_bytes().to_vec()]);
+            request::post(&fixup_url(url), headers, body, &make_handler())
+                .map(make_test_response)
+                .unwrap_or_else(make_test_error_response)
+        }
+    }
+
+    impl http_tests::MultipartIntegration for TestIronIntegration {
+        fn post_multipart(
+            &self,
+            url: &str,
+            boundary: &str,
+            body: &str,
+        ) -> http_tests::TestResponse {
+            let mut headers = Headers::new();
+            headers.set_raw(
+                "content-type",
+                vec![format!("multipart/form-data; boundary={}", boundary).into_bytes()],
+            );
+            request::post(&fixup_url(url), headers, body, &make_handler())
+                .map(make_test_response)
+                .unwrap_or_else(make_test_error_response)
+        }
     }
 
     #[test]
@@ -472,17 +456,21 @@ mod tests {
         let integration = TestIronIntegration;
 
         http_tests::run_http_test_suite(&integration);
+        http_tests::run_multipart_test_suite(&integration);
     }
 
     fn context_factory(_: &mut Request) -> IronResult<Database> {
         Ok(Database::new())
     }
 
-    fn make_test_error_response(_: IronError) -> http_tests::TestResponse {
-        // For now all errors return the same status code.
-        // `juniper_iron` users can choose to do something different if desired.
+    fn make_test_error_response(err: IronError) -> http_tests::TestResponse {
+        let status_code = err
+            .response
+            .status
+            .expect("No status code returned from handler")
+            .to_u16() as i32;
         http_tests::TestResponse {
-            status_code: 400,
+            status_code,
             body: None,
             content_type: "application/json".to_string(),
         }
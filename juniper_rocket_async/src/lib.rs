@@ -50,9 +50,15 @@ use rocket::{
 };
 
 use juniper::{
-    http::{self, GraphQLBatchRequest},
+    http::{
+        self,
+        graphiql::GraphiQLSettings,
+        parse::{parse_get_request, parse_post_request},
+        playground::PlaygroundSettings,
+        GraphQLBatchRequest, HttpConfig, ResponseContentType,
+    },
     DefaultScalarValue, FieldError, GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync,
-    InputValue, RootNode, ScalarValue,
+    RootNode, ScalarValue,
 };
 
 /// Simple wrapper around an incoming GraphQL request
@@ -76,6 +82,12 @@ pub fn graphiql_source(graphql_endpoint_url: &str) -> content::Html<String> {
     ))
 }
 
+/// Generate an HTML page containing GraphiQL, configured with the given
+/// settings
+pub fn graphiql_source_with_settings(settings: &GraphiQLSettings) -> content::Html<String> {
+    content::Html(settings.source())
+}
+
 /// Generate an HTML page containing GraphQL Playground
 pub fn playground_source(graphql_endpoint_url: &str) -> content::Html<String> {
     content::Html(juniper::http::playground::playground_source(
@@ -84,6 +96,12 @@ pub fn playground_source(graphql_endpoint_url: &str) -> content::Html<String> {
     ))
 }
 
+/// Generate an HTML page containing GraphQL Playground, configured with the
+/// given settings
+pub fn playground_source_with_settings(settings: &PlaygroundSettings) -> content::Html<String> {
+    content::Html(settings.source())
+}
+
 impl<S> GraphQLRequest<S>
 where
     S: ScalarValue,
@@ -100,11 +118,27 @@ where
         SubscriptionT: GraphQLType<S, Context = CtxT>,
     {
         let response = self.0.execute_sync(root_node, context);
-        let status = if response.is_ok() {
-            Status::Ok
-        } else {
-            Status::BadRequest
-        };
+        let status = response_status(&response);
+        let json = serde_json::to_string(&response).unwrap();
+
+        GraphQLResponse(status, json)
+    }
+
+    /// Synchronously execute an incoming GraphQL query along with the given
+    /// [`HttpConfig`], e.g. enabling persisted queries or safelisting.
+    pub fn execute_sync_with_config<CtxT, QueryT, MutationT, SubscriptionT>(
+        &self,
+        root_node: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+        context: &CtxT,
+        config: &HttpConfig<CtxT, S>,
+    ) -> GraphQLResponse
+    where
+        QueryT: GraphQLType<S, Context = CtxT>,
+        MutationT: GraphQLType<S, Context = CtxT>,
+        SubscriptionT: GraphQLType<S, Context = CtxT>,
+    {
+        let response = self.0.execute_sync_with_config(root_node, context, config);
+        let status = response_status(&response);
         let json = serde_json::to_string(&response).unwrap();
 
         GraphQLResponse(status, json)
@@ -127,11 +161,32 @@ where
         S: Send + Sync,
     {
         let response = self.0.execute(root_node, context).await;
-        let status = if response.is_ok() {
-            Status::Ok
-        } else {
-            Status::BadRequest
-        };
+        let status = response_status(&response);
+        let json = serde_json::to_string(&response).unwrap();
+
+        GraphQLResponse(status, json)
+    }
+
+    /// Asynchronously execute an incoming GraphQL query along with the given
+    /// [`HttpConfig`], e.g. enabling persisted queries or safelisting.
+    pub async fn execute_with_config<CtxT, QueryT, MutationT, SubscriptionT>(
+        &self,
+        root_node: &RootNode<'_, QueryT, MutationT, SubscriptionT, S>,
+        context: &CtxT,
+        config: &HttpConfig<CtxT, S>,
+    ) -> GraphQLResponse
+    where
+        QueryT: GraphQLTypeAsync<S, Context = CtxT>,
+        QueryT::TypeInfo: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = CtxT>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT>,
+        SubscriptionT::TypeInfo: Sync,
+        CtxT: Sync,
+        S: Send + Sync,
+    {
+        let response = self.0.execute_with_config(root_node, context, config).await;
+        let status = response_status(&response);
         let json = serde_json::to_string(&response).unwrap();
 
         GraphQLResponse(status, json)
@@ -193,6 +248,22 @@ impl GraphQLResponse {
     }
 }
 
+/// Status of an executed GraphQL request.
+///
+/// The response media type is only negotiated once responding, so rejected
+/// requests get the status codes of `application/graphql-response+json`, the
+/// same way as [`GraphQLResponse::error`].
+fn response_status<S>(response: &http::GraphQLBatchResponse<'_, S>) -> Status
+where
+    S: ScalarValue,
+{
+    let status_code = response.status_code(ResponseContentType::GraphQLResponseJson);
+    Status::from_code(status_code).unwrap_or(Status::BadRequest)
+}
+
+/// Parameters of a GraphQL request sent with GET
+const GET_PARAMETERS: &[&str] = &["query", "operationName", "variables", "extensions", "id"];
+
 impl<'f, S> FromForm<'f> for GraphQLRequest<S>
 where
     S: ScalarValue,
@@ -200,67 +271,26 @@ where
     type Error = String;
 
     fn from_form(form_items: &mut FormItems<'f>, strict: bool) -> Result<Self, String> {
-        let mut query = None;
-        let mut operation_name = None;
-        let mut variables = None;
+        // The items are decoded while parsing the whole query string, the same
+        // way as in the other integrations.
+        let mut query_string = String::new();
 
         for form_item in form_items {
             let (key, value) = form_item.key_value();
-            // Note: we explicitly decode in the match arms to save work rather
-            // than decoding every form item blindly.
-            match key.as_str() {
-                "query" => {
-                    if query.is_some() {
-                        return Err("Query parameter must not occur more than once".to_owned());
-                    } else {
-                        match value.url_decode() {
-                            Ok(v) => query = Some(v),
-                            Err(e) => return Err(e.to_string()),
-                        }
-                    }
-                }
-                "operation_name" => {
-                    if operation_name.is_some() {
-                        return Err(
-                            "Operation name parameter must not occur more than once".to_owned()
-                        );
-                    } else {
-                        match value.url_decode() {
-                            Ok(v) => operation_name = Some(v),
-                            Err(e) => return Err(e.to_string()),
-                        }
-                    }
-                }
-                "variables" => {
-                    if variables.is_some() {
-                        return Err("Variables parameter must not occur more than once".to_owned());
-                    } else {
-                        let decoded;
-                        match value.url_decode() {
-                            Ok(v) => decoded = v,
-                            Err(e) => return Err(e.to_string()),
-                        }
-                        variables = Some(
-                            serde_json::from_str::<InputValue<_>>(&decoded)
-                                .map_err(|err| err.to_string())?,
-                        );
-                    }
-                }
-                _ => {
-                    if strict {
-                        return Err(format!("Prohibited extra field '{}'", key));
-                    }
-                }
+            if strict && !GET_PARAMETERS.contains(&key.as_str()) {
+                return Err(format!("Prohibited extra field '{}'", key));
+            }
+            if !query_string.is_empty() {
+                query_string.push('&');
             }
+            query_string.push_str(key.as_str());
+            query_string.push('=');
+            query_string.push_str(value.as_str());
         }
 
-        if let Some(query) = query {
-            Ok(GraphQLRequest(GraphQLBatchRequest::Single(
-                http::GraphQLRequest::new(query, operation_name, variables),
-            )))
-        } else {
-            Err("Query parameter missing".to_owned())
-        }
+        parse_get_request(&query_string)
+            .map(|req| GraphQLRequest(GraphQLBatchRequest::Single(req)))
+            .map_err(|e| e.to_string())
     }
 }
 
@@ -292,38 +322,56 @@ where
         let content_type = req
             .content_type()
             .map(|ct| (ct.top().as_str(), ct.sub().as_str()));
-        let is_json = match content_type {
-            Some(("application", "json")) => true,
-            Some(("application", "graphql")) => false,
+        match content_type {
+            Some(("application", "json")) | Some(("application", "graphql")) => {}
             _ => return Box::pin(async move { Forward(data) }).await,
-        };
+        }
+
+        // Refuse before executing anything, mutations included.
+        if response_content_type(req).is_none() {
+            return Failure((Status::NotAcceptable, NOT_ACCEPTABLE.to_owned()));
+        }
+        let content_type = req.content_type().map(ToString::to_string);
 
         Box::pin(async move {
-            let mut body = String::new();
+            let mut body = Vec::new();
             let mut reader = data.open(BODY_LIMIT.bytes());
-            if let Err(e) = reader.read_to_string(&mut body).await {
+            if let Err(e) = reader.read_to_end(&mut body).await {
                 return Failure((Status::InternalServerError, format!("{:?}", e)));
             }
 
-            Success(GraphQLRequest(if is_json {
-                match serde_json::from_str(&body) {
-                    Ok(req) => req,
-                    Err(e) => return Failure((Status::BadRequest, format!("{}", e))),
+            match parse_post_request(content_type.as_deref(), &body) {
+                Ok(req) => Success(GraphQLRequest(req)),
+                Err(e) => {
+                    let status = Status::from_code(e.status_code()).unwrap_or(Status::BadRequest);
+                    Failure((status, e.to_string()))
                 }
-            } else {
-                GraphQLBatchRequest::Single(http::GraphQLRequest::new(body, None, None))
-            }))
+            }
         })
         .await
     }
 }
 
+const NOT_ACCEPTABLE: &str =
+    "GraphQL responses are only available as application/graphql-response+json or application/json";
+
+fn response_content_type(req: &Request<'_>) -> Option<ResponseContentType> {
+    let accept = req.headers().get("Accept").collect::<Vec<_>>().join(",");
+    ResponseContentType::negotiate(Some(&accept))
+}
+
 impl<'r, 'o: 'r> Responder<'r, 'o> for GraphQLResponse {
-    fn respond_to(self, _req: &'r Request<'_>) -> response::Result<'o> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
         let GraphQLResponse(status, body) = self;
+        let content_type = match response_content_type(req).ok_or(Status::NotAcceptable)? {
+            ResponseContentType::GraphQLResponseJson => {
+                ContentType::new("application", "graphql-response+json")
+            }
+            ResponseContentType::Json => ContentType::new("application", "json"),
+        };
 
         Response::build()
-            .header(ContentType::new("application", "json"))
+            .header(content_type)
             .status(status)
             .sized_body(body.len(), Cursor::new(body))
             .ok()
@@ -346,14 +394,14 @@ mod fromform_tests {
 
     #[test]
     fn test_empty_form() {
-        check_error("", "Query parameter missing", false);
+        check_error("", "'query' parameter is missing", false);
     }
 
     #[test]
     fn test_no_query() {
         check_error(
-            "operation_name=foo&variables={}",
-            "Query parameter missing",
+            "operationName=foo&variables={}",
+            "'query' parameter is missing",
             false,
         );
     }
@@ -367,7 +415,7 @@ mod fromform_tests {
     fn test_duplicate_query() {
         check_error(
             "query=foo&query=bar",
-            "Query parameter must not occur more than once",
+            "'query' parameter is specified multiple times",
             false,
         );
     }
@@ -375,8 +423,8 @@ mod fromform_tests {
     #[test]
     fn test_duplicate_operation_name() {
         check_error(
-            "query=test&operation_name=op1&operation_name=op2",
-            "Operation name parameter must not occur more than once",
+            "query=test&operationName=op1&operationName=op2",
+            "'operationName' parameter is specified multiple times",
             false,
         );
     }
@@ -385,7 +433,7 @@ mod fromform_tests {
     fn test_duplicate_variables() {
         check_error(
             "query=test&variables={}&variables={}",
-            "Variables parameter must not occur more than once",
+            "'variables' parameter is specified multiple times",
             false,
         );
     }
@@ -394,7 +442,7 @@ mod fromform_tests {
     fn test_variables_invalid_json() {
         check_error(
             "query=test&variables=NOT_JSON",
-            "expected value at line 1 column 1",
+            "Invalid 'variables' parameter: expected value at line 1 column 1",
             false,
         );
     }
@@ -406,11 +454,9 @@ mod fromform_tests {
         let result = GraphQLRequest::from_form(&mut items, false);
         assert!(result.is_ok());
         let variables = ::serde_json::from_str::<InputValue>(r#"{"foo":"bar"}"#).unwrap();
-        let expected = GraphQLRequest(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
-            "test".to_string(),
-            None,
-            Some(variables),
-        )));
+        let expected = GraphQLRequest(GraphQLBatchRequest::Single(
+            http::GraphQLRequest::new("test".to_string(), None, Some(variables)).forbid_mutations(),
+        ));
         assert_eq!(result.unwrap(), expected);
     }
 
@@ -421,25 +467,22 @@ mod fromform_tests {
         let result = GraphQLRequest::from_form(&mut items, false);
         assert!(result.is_ok());
         let variables = ::serde_json::from_str::<InputValue>(r#"{"foo":"x y&? z"}"#).unwrap();
-        let expected = GraphQLRequest(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
-            "test".to_string(),
-            None,
-            Some(variables),
-        )));
+        let expected = GraphQLRequest(GraphQLBatchRequest::Single(
+            http::GraphQLRequest::new("test".to_string(), None, Some(variables)).forbid_mutations(),
+        ));
         assert_eq!(result.unwrap(), expected);
     }
 
     #[test]
     fn test_url_decode() {
-        let form_string = "query=%25foo%20bar+baz%26%3F&operation_name=test";
+        let form_string = "query=%25foo%20bar+baz%26%3F&operationName=test";
         let mut items = FormItems::from(form_string);
         let result: Result<GraphQLRequest, _> = GraphQLRequest::from_form(&mut items, false);
         assert!(result.is_ok());
-        let expected = GraphQLRequest(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
-            "%foo bar baz&?".to_string(),
-            Some("test".to_string()),
-            None,
-        )));
+        let expected = GraphQLRequest(GraphQLBatchRequest::Single(
+            http::GraphQLRequest::new("%foo bar baz&?".to_string(), Some("test".to_string()), None)
+                .forbid_mutations(),
+        ));
         assert_eq!(result.unwrap(), expected);
     }
 }
@@ -456,7 +499,7 @@ mod tests {
     };
     use rocket::{
         self, get,
-        http::ContentType,
+        http::{ContentType, Header},
         local::asynchronous::{Client, LocalResponse},
         post,
         request::Form,
@@ -509,6 +552,31 @@ mod tests {
             let req = futures::executor::block_on(req.dispatch());
             futures::executor::block_on(make_test_response(req))
         }
+
+        fn get_with_accept(&self, url: &str, accept: &str) -> http_tests::TestResponse {
+            let req = self
+                .client
+                .get(url)
+                .header(Header::new("Accept", accept.to_owned()));
+            let req = futures::executor::block_on(req.dispatch());
+            futures::executor::block_on(make_test_response(req))
+        }
+
+        fn post_json_with_accept(
+            &self,
+            url: &str,
+            body: &str,
+            accept: &str,
+        ) -> http_tests::TestResponse {
+            let req = self
+                .client
+                .post(url)
+                .header(ContentType::JSON)
+                .header(Header::new("Accept", accept.to_owned()))
+                .body(body);
+            let req = futures::executor::block_on(req.dispatch());
+            futures::executor::block_on(make_test_response(req))
+        }
     }
 
     #[tokio::test]
@@ -19,13 +19,15 @@ use std::{
 
 use futures::{future, stream, FutureExt as _, Stream, StreamExt as _, TryFutureExt as _};
 use juniper::{
-    http::GraphQLRequest, BoxFuture, ExecutionError, ExecutionOutput, GraphQLError,
-    GraphQLSubscriptionType, GraphQLTypeAsync, Object, ScalarValue, SubscriptionConnection,
-    SubscriptionCoordinator, Value, ValuesStream,
+    http::{GraphQLRequest, HttpConfig},
+    BoxFuture, ExecutionError, ExecutionOutput, GraphQLError, GraphQLSubscriptionType,
+    GraphQLTypeAsync, Object, ScalarValue, SubscriptionConnection, SubscriptionCoordinator, Value,
+    ValuesStream,
 };
 
 /// Simple [`SubscriptionCoordinator`] implementation:
-/// - contains the schema
+/// - contains the schema, along with the [`HttpConfig`] whose safelist
+///   subscriptions are checked against
 /// - handles subscription start
 pub struct Coordinator<'a, QueryT, MutationT, SubscriptionT, CtxT, S>
 where
@@ -39,6 +41,7 @@ where
     S: ScalarValue + Send + Sync,
 {
     root_node: juniper::RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    config: HttpConfig<CtxT, S>,
 }
 
 impl<'a, QueryT, MutationT, SubscriptionT, CtxT, S>
@@ -55,7 +58,16 @@ where
 {
     /// Builds new [`Coordinator`] with specified `root_node`
     pub fn new(root_node: juniper::RootNode<'a, QueryT, MutationT, SubscriptionT, S>) -> Self {
-        Self { root_node }
+        Self::with_config(root_node, HttpConfig::default())
+    }
+
+    /// Builds new [`Coordinator`] with specified `root_node`, only starting the
+    /// subscriptions allowed by the safelist of the given `config`, if any
+    pub fn with_config(
+        root_node: juniper::RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        config: HttpConfig<CtxT, S>,
+    ) -> Self {
+        Self { root_node, config }
     }
 }
 
@@ -80,7 +92,7 @@ where
         req: &'a GraphQLRequest<S>,
         context: &'a CtxT,
     ) -> BoxFuture<'a, Result<Self::Connection, Self::Error>> {
-        juniper::http::resolve_into_stream(req, &self.root_node, context)
+        juniper::http::resolve_into_stream_with_config(req, &self.root_node, context, &self.config)
             .map_ok(|(stream, errors)| Connection::from_stream(stream, errors))
             .boxed()
     }
@@ -40,16 +40,20 @@ Check the LICENSE file for details.
 #![deny(warnings)]
 #![doc(html_root_url = "https://docs.rs/juniper_warp/0.2.0")]
 
-use anyhow::anyhow;
 use bytes::Bytes;
-use futures::{FutureExt as _, TryFutureExt};
+use futures::TryFutureExt;
 use juniper::{
-    http::{GraphQLBatchRequest, GraphQLRequest},
+    http::{
+        graphiql::GraphiQLSettings,
+        parse::{parse_get_request, parse_post_request_with_limits, ParseRequestError},
+        playground::PlaygroundSettings,
+        GraphQLBatchResponse, HttpConfig, ResponseContentType,
+    },
     ScalarValue,
 };
-use std::{collections::HashMap, str, sync::Arc};
+use std::sync::Arc;
 use tokio::task;
-use warp::{body, filters::BoxedFilter, http, query, Filter};
+use warp::{body, filters::BoxedFilter, header, http, query, Filter};
 
 /// Make a filter for graphql queries/mutations.
 ///
@@ -121,74 +125,98 @@ where
     CtxT: Send + Sync + 'static,
     S: ScalarValue + Send + Sync + 'static,
 {
-    let schema = Arc::new(schema);
-    let post_json_schema = schema.clone();
-    let post_graphql_schema = schema.clone();
-
-    let handle_post_json_request = move |context: CtxT, req: GraphQLBatchRequest<S>| {
-        let schema = post_json_schema.clone();
-        async move {
-            let resp = req.execute(&schema, &context).await;
-
-            Ok::<_, warp::Rejection>(build_response(
-                serde_json::to_vec(&resp)
-                    .map(|json| (json, resp.is_ok()))
-                    .map_err(Into::into),
-            ))
-        }
-    };
-    let post_json_filter = warp::post()
-        .and(context_extractor.clone())
-        .and(body::json())
-        .and_then(handle_post_json_request);
-
-    let handle_post_graphql_request = move |context: CtxT, body: Bytes| {
-        let schema = post_graphql_schema.clone();
-        async move {
-            let query = str::from_utf8(body.as_ref())
-                .map_err(|e| anyhow!("Request body query is not a valid UTF-8 string: {}", e))?;
-            let req = GraphQLRequest::new(query.into(), None, None);
-
-            let resp = req.execute(&schema, &context).await;
+    make_graphql_filter_with_config(schema, context_extractor, HttpConfig::default())
+}
 
-            Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
-        }
-        .then(|res| async { Ok::<_, warp::Rejection>(build_response(res)) })
-    };
-    let post_graphql_filter = warp::post()
+/// Make a filter for graphql queries/mutations, executed along with the given
+/// [`HttpConfig`].
+///
+/// The `config` argument enables persisted queries, safelisting, request and batch limits or
+/// response caching, see [`make_graphql_filter`] for the others.
+pub fn make_graphql_filter_with_config<Query, Mutation, Subscription, CtxT, S>(
+    schema: juniper::RootNode<'static, Query, Mutation, Subscription, S>,
+    context_extractor: BoxedFilter<(CtxT,)>,
+    config: HttpConfig<CtxT, S>,
+) -> BoxedFilter<(http::Response<Vec<u8>>,)>
+where
+    Query: juniper::GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: juniper::GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: juniper::GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    let (schema, config) = (Arc::new(schema), Arc::new(config));
+    let (post_schema, post_config) = (schema.clone(), config.clone());
+
+    let handle_post_request =
+        move |context: CtxT, accept: Option<String>, content_type: Option<String>, body: Bytes| {
+            let (schema, config) = (post_schema.clone(), post_config.clone());
+            async move {
+                let response_content_type = match response_content_type(accept) {
+                    Ok(content_type) => content_type,
+                    Err(resp) => return Ok(resp),
+                };
+                let req = parse_post_request_with_limits::<S>(
+                    content_type.as_deref(),
+                    &body,
+                    config.request_limits(),
+                );
+                let req = match req {
+                    Ok(req) => req,
+                    Err(e) => return Ok::<_, warp::Rejection>(error_response(e)),
+                };
+
+                let resp = req.execute_with_config(&schema, &context, &config).await;
+                let status_code = resp.status_code(response_content_type);
+
+                Ok(build_response(
+                    serde_json::to_vec(&resp)
+                        .map(|json| (json, status_code, cache_control(&resp)))
+                        .map_err(Into::into),
+                    response_content_type,
+                ))
+            }
+        };
+    let post_filter = warp::post()
         .and(context_extractor.clone())
+        .and(header::optional::<String>("accept"))
+        .and(header::optional::<String>("content-type"))
         .and(body::bytes())
-        .and_then(handle_post_graphql_request);
+        .and_then(handle_post_request);
 
-    let handle_get_request = move |context: CtxT, mut qry: HashMap<String, String>| {
-        let schema = schema.clone();
+    let handle_get_request = move |context: CtxT, accept: Option<String>, qry: String| {
+        let (schema, config) = (schema.clone(), config.clone());
         async move {
-            let req = GraphQLRequest::new(
-                qry.remove("query")
-                    .ok_or_else(|| anyhow!("Missing GraphQL query string in query parameters"))?,
-                qry.remove("operation_name"),
-                qry.remove("variables")
-                    .map(|vs| serde_json::from_str(&vs))
-                    .transpose()?,
-            );
-
-            let resp = req.execute(&schema, &context).await;
-
-            Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
+            let response_content_type = match response_content_type(accept) {
+                Ok(content_type) => content_type,
+                Err(resp) => return Ok(resp),
+            };
+            let req = match parse_get_request::<S>(&qry) {
+                Ok(req) => req,
+                Err(e) => return Ok::<_, warp::Rejection>(error_response(e)),
+            };
+
+            let resp = req.execute_with_config(&schema, &context, &config).await;
+            let status_code = resp.status_code(response_content_type);
+
+            Ok(build_response(
+                serde_json::to_vec(&resp)
+                    .map(|json| (json, status_code, cache_control(&resp)))
+                    .map_err(Into::into),
+                response_content_type,
+            ))
         }
-        .then(|res| async move { Ok::<_, warp::Rejection>(build_response(res)) })
     };
     let get_filter = warp::get()
         .and(context_extractor)
-        .and(query::query())
+        .and(header::optional::<String>("accept"))
+        .and(raw_query())
         .and_then(handle_get_request);
 
-    get_filter
-        .or(post_json_filter)
-        .unify()
-        .or(post_graphql_filter)
-        .unify()
-        .boxed()
+    get_filter.or(post_filter).unify().boxed()
 }
 
 /// Make a synchronous filter for graphql endpoint.
@@ -203,101 +231,129 @@ where
     CtxT: Send + Sync + 'static,
     S: ScalarValue + Send + Sync + 'static,
 {
-    let schema = Arc::new(schema);
-    let post_json_schema = schema.clone();
-    let post_graphql_schema = schema.clone();
-
-    let handle_post_json_request = move |context: CtxT, req: GraphQLBatchRequest<S>| {
-        let schema = post_json_schema.clone();
-        async move {
-            let res = task::spawn_blocking(move || {
-                let resp = req.execute_sync(&schema, &context);
-                Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
-            })
-            .await?;
-
-            Ok(build_response(res))
-        }
-        .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
-    };
-    let post_json_filter = warp::post()
-        .and(context_extractor.clone())
-        .and(body::json())
-        .and_then(handle_post_json_request);
-
-    let handle_post_graphql_request = move |context: CtxT, body: Bytes| {
-        let schema = post_graphql_schema.clone();
-        async move {
-            let res = task::spawn_blocking(move || {
-                let query = str::from_utf8(body.as_ref())
-                    .map_err(|e| anyhow!("Request body is not a valid UTF-8 string: {}", e))?;
-                let req = GraphQLRequest::new(query.into(), None, None);
+    make_graphql_filter_sync_with_config(schema, context_extractor, HttpConfig::default())
+}
 
-                let resp = req.execute_sync(&schema, &context);
-                Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
-            })
-            .await?;
+/// Make a synchronous filter for graphql endpoint, executing requests along with the given
+/// [`HttpConfig`].
+pub fn make_graphql_filter_sync_with_config<Query, Mutation, Subscription, CtxT, S>(
+    schema: juniper::RootNode<'static, Query, Mutation, Subscription, S>,
+    context_extractor: BoxedFilter<(CtxT,)>,
+    config: HttpConfig<CtxT, S>,
+) -> BoxedFilter<(http::Response<Vec<u8>>,)>
+where
+    Query: juniper::GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    Mutation: juniper::GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    Subscription: juniper::GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    let (schema, config) = (Arc::new(schema), Arc::new(config));
+    let (post_schema, post_config) = (schema.clone(), config.clone());
+
+    let handle_post_request =
+        move |context: CtxT, accept: Option<String>, content_type: Option<String>, body: Bytes| {
+            let (schema, config) = (post_schema.clone(), post_config.clone());
+            async move {
+                let response_content_type = match response_content_type(accept) {
+                    Ok(content_type) => content_type,
+                    Err(resp) => return Ok(resp),
+                };
+                let req = parse_post_request_with_limits::<S>(
+                    content_type.as_deref(),
+                    &body,
+                    config.request_limits(),
+                );
+                let req = match req {
+                    Ok(req) => req,
+                    Err(e) => return Ok(error_response(e)),
+                };
+
+                let res = task::spawn_blocking(move || {
+                    let resp = req.execute_sync_with_config(&schema, &context, &config);
+                    Ok((
+                        serde_json::to_vec(&resp)?,
+                        resp.status_code(response_content_type),
+                        cache_control(&resp),
+                    ))
+                })
+                .await?;
 
-            Ok(build_response(res))
-        }
-        .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
-    };
-    let post_graphql_filter = warp::post()
+                Ok(build_response(res, response_content_type))
+            }
+            .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
+        };
+    let post_filter = warp::post()
         .and(context_extractor.clone())
+        .and(header::optional::<String>("accept"))
+        .and(header::optional::<String>("content-type"))
         .and(body::bytes())
-        .and_then(handle_post_graphql_request);
+        .and_then(handle_post_request);
 
-    let handle_get_request = move |context: CtxT, mut qry: HashMap<String, String>| {
-        let schema = schema.clone();
+    let handle_get_request = move |context: CtxT, accept: Option<String>, qry: String| {
+        let (schema, config) = (schema.clone(), config.clone());
         async move {
-            let res = task::spawn_blocking(move || {
-                let req = GraphQLRequest::new(
-                    qry.remove("query").ok_or_else(|| {
-                        anyhow!("Missing GraphQL query string in query parameters")
-                    })?,
-                    qry.remove("operation_name"),
-                    qry.remove("variables")
-                        .map(|vs| serde_json::from_str(&vs))
-                        .transpose()?,
-                );
+            let response_content_type = match response_content_type(accept) {
+                Ok(content_type) => content_type,
+                Err(resp) => return Ok(resp),
+            };
+            let req = match parse_get_request::<S>(&qry) {
+                Ok(req) => req,
+                Err(e) => return Ok(error_response(e)),
+            };
 
-                let resp = req.execute_sync(&schema, &context);
-                Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
+            let res = task::spawn_blocking(move || {
+                let resp = req.execute_sync_with_config(&schema, &context, &config);
+                Ok((
+                    serde_json::to_vec(&resp)?,
+                    resp.status_code(response_content_type),
+                    cache_control(&resp),
+                ))
             })
             .await?;
 
-            Ok(build_response(res))
+            Ok(build_response(res, response_content_type))
         }
         .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
     };
     let get_filter = warp::get()
         .and(context_extractor)
-        .and(query::query())
+        .and(header::optional::<String>("accept"))
+        .and(raw_query())
         .and_then(handle_get_request);
 
-    get_filter
-        .or(post_json_filter)
-        .unify()
-        .or(post_graphql_filter)
-        .unify()
-        .boxed()
+    get_filter.or(post_filter).unify().boxed()
+}
+
+/// The raw query string of a request, empty if there is none
+fn raw_query() -> impl Filter<Extract = (String,), Error = std::convert::Infallible> + Clone {
+    query::raw().or(warp::any().map(String::new)).unify()
 }
 
-/// Error raised by `tokio_threadpool` if the thread pool has been shutdown.
-///
-/// Wrapper type is needed as inner type does not implement `warp::reject::Reject`.
 #[derive(Debug)]
 pub struct JoinError(task::JoinError);
 
 impl warp::reject::Reject for JoinError {}
 
-fn build_response(response: Result<(Vec<u8>, bool), anyhow::Error>) -> http::Response<Vec<u8>> {
+/// The `Cache-Control` header to send along with a response, if it's cacheable
+fn cache_control<S: ScalarValue>(response: &GraphQLBatchResponse<'_, S>) -> Option<String> {
+    response.cache_policy()?.header_value()
+}
+
+fn build_response(
+    response: Result<(Vec<u8>, u16, Option<String>), anyhow::Error>,
+    content_type: ResponseContentType,
+) -> http::Response<Vec<u8>> {
     match response {
-        Ok((body, is_ok)) => http::Response::builder()
-            .status(if is_ok { 200 } else { 400 })
-            .header("content-type", "application/json")
-            .body(body)
-            .expect("response is valid"),
+        Ok((body, status, cache_control)) => {
+            let mut builder = http::Response::builder()
+                .status(status)
+                .header("content-type", content_type.as_str());
+            if let Some(cache_control) = cache_control {
+                builder = builder.header("cache-control", cache_control);
+            }
+            builder.body(body).expect("response is valid")
+        }
         Err(_) => http::Response::builder()
             .status(http::StatusCode::INTERNAL_SERVER_ERROR)
             .body(Vec::new())
@@ -305,6 +361,32 @@ fn build_response(response: Result<(Vec<u8>, bool), anyhow::Error>) -> http::Res
     }
 }
 
+fn error_response(err: ParseRequestError) -> http::Response<Vec<u8>> {
+    http::Response::builder()
+        .status(err.status_code())
+        .header("content-type", "text/plain; charset=utf-8")
+        .body(err.to_string().into_bytes())
+        .expect("status code is valid")
+}
+
+fn response_content_type(
+    accept: Option<String>,
+) -> Result<ResponseContentType, http::Response<Vec<u8>>> {
+    ResponseContentType::negotiate(accept.as_deref()).ok_or_else(not_acceptable_response)
+}
+
+fn not_acceptable_response() -> http::Response<Vec<u8>> {
+    http::Response::builder()
+        .status(http::StatusCode::NOT_ACCEPTABLE)
+        .header("content-type", "text/plain; charset=utf-8")
+        .body(
+            b"GraphQL responses are only available as application/graphql-response+json \
+              or application/json"
+                .to_vec(),
+        )
+        .expect("status code is valid")
+}
+
 /// Create a filter that replies with an HTML page containing GraphiQL. This does not handle routing, so you can mount it on any endpoint.
 ///
 /// For example:
@@ -339,13 +421,31 @@ fn graphiql_response(
     graphql_endpoint_url: &'static str,
     subscriptions_endpoint: Option<&'static str>,
 ) -> http::Response<Vec<u8>> {
-    http::Response::builder()
-        .header("content-type", "text/html;charset=utf-8")
-        .body(
-            juniper::http::graphiql::graphiql_source(graphql_endpoint_url, subscriptions_endpoint)
-                .into_bytes(),
-        )
-        .expect("response is valid")
+    html_response(juniper::http::graphiql::graphiql_source(
+        graphql_endpoint_url,
+        subscriptions_endpoint,
+    ))
+}
+
+/// Create a filter that replies with an HTML page containing GraphiQL, configured with the given settings. This does not handle routing, so you can mount it on any endpoint.
+///
+/// For example, to load the assets of GraphiQL from another route instead of public CDNs:
+///
+/// ```
+/// # use warp::Filter;
+/// # use juniper::http::graphiql::GraphiQLSettings;
+/// # use juniper_warp::graphiql_filter_with_settings;
+/// #
+/// let settings = GraphiQLSettings::new("/graphql").assets_url("/static/graphiql");
+/// let graphiql_route = warp::path("graphiql").and(graphiql_filter_with_settings(settings));
+/// ```
+pub fn graphiql_filter_with_settings(
+    settings: GraphiQLSettings,
+) -> warp::filters::BoxedFilter<(http::Response<Vec<u8>>,)> {
+    let source = settings.source();
+    warp::any()
+        .map(move || html_response(source.clone()))
+        .boxed()
 }
 
 /// Create a filter that replies with an HTML page containing GraphQL Playground. This does not handle routing, so you can mount it on any endpoint.
@@ -362,15 +462,26 @@ fn playground_response(
     graphql_endpoint_url: &'static str,
     subscriptions_endpoint_url: Option<&'static str>,
 ) -> http::Response<Vec<u8>> {
+    html_response(juniper::http::playground::playground_source(
+        graphql_endpoint_url,
+        subscriptions_endpoint_url,
+    ))
+}
+
+/// Create a filter that replies with an HTML page containing GraphQL Playground, configured with the given settings. This does not handle routing, so you can mount it on any endpoint.
+pub fn playground_filter_with_settings(
+    settings: PlaygroundSettings,
+) -> warp::filters::BoxedFilter<(http::Response<Vec<u8>>,)> {
+    let source = settings.source();
+    warp::any()
+        .map(move || html_response(source.clone()))
+        .boxed()
+}
+
+fn html_response(source: String) -> http::Response<Vec<u8>> {
     http::Response::builder()
         .header("content-type", "text/html;charset=utf-8")
-        .body(
-            juniper::http::playground::playground_source(
-                graphql_endpoint_url,
-                subscriptions_endpoint_url,
-            )
-            .into_bytes(),
-        )
+        .body(source.into_bytes())
         .expect("response is valid")
 }
 
@@ -483,6 +594,191 @@ pub mod subscriptions {
     }
 }
 
+/// `juniper_warp` handler serving subscriptions over Server-Sent Events, following the
+/// "distinct connections" mode of the GraphQL over SSE protocol[1].
+///
+/// [1]: https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md
+#[cfg(feature = "sse")]
+pub mod sse {
+    use bytes::Bytes;
+    use hyper::Body;
+    use juniper::{
+        futures::stream::StreamExt,
+        http::{
+            parse::{parse_get_request, parse_post_request_with_limits},
+            GraphQLBatchRequest, GraphQLRequest, HttpConfig,
+        },
+        GraphQLSubscriptionType, GraphQLTypeAsync, RootNode, ScalarValue,
+    };
+    use juniper_graphql_sse::EventStreamConfig;
+    use std::{convert::Infallible, sync::Arc};
+    use warp::{body, filters::BoxedFilter, header, http, Filter};
+
+    use super::{error_response, raw_query};
+
+    /// Make a filter serving subscriptions over Server-Sent Events.
+    ///
+    /// Subscriptions are sent either as `GET` requests, or as `POST` requests with a single
+    /// operation, and each is served with its own event stream. The `config` argument sets how
+    /// often keep-alives are sent on these streams.
+    ///
+    /// ```
+    /// # use juniper::{EmptyMutation, EmptySubscription, RootNode};
+    /// # use juniper_graphql_sse::EventStreamConfig;
+    /// # use juniper_warp::sse::make_graphql_sse_filter;
+    /// # use warp::Filter;
+    /// #
+    /// # struct Query;
+    /// #
+    /// # #[juniper::graphql_object]
+    /// # impl Query {
+    /// #     fn answer() -> i32 {
+    /// #         42
+    /// #     }
+    /// # }
+    /// #
+    /// let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::new());
+    ///
+    /// let sse_endpoint = warp::path("graphql")
+    ///     .and(warp::path("stream"))
+    ///     .and(make_graphql_sse_filter(
+    ///         schema,
+    ///         warp::any().map(|| ()).boxed(),
+    ///         EventStreamConfig::new(),
+    ///     ));
+    /// ```
+    pub fn make_graphql_sse_filter<Query, Mutation, Subscription, CtxT, S>(
+        schema: RootNode<'static, Query, Mutation, Subscription, S>,
+        context_extractor: BoxedFilter<(CtxT,)>,
+        config: EventStreamConfig,
+    ) -> BoxedFilter<(http::Response<Body>,)>
+    where
+        Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Query::TypeInfo: Send + Sync,
+        Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Mutation::TypeInfo: Send + Sync,
+        Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+        Subscription::TypeInfo: Send + Sync,
+        CtxT: Send + Sync + 'static,
+        S: ScalarValue + Send + Sync + 'static,
+    {
+        make_graphql_sse_filter_with_config(
+            schema,
+            context_extractor,
+            config,
+            HttpConfig::default(),
+        )
+    }
+
+    /// Make a filter serving subscriptions over Server-Sent Events, checking them against the
+    /// safelist of the given [`HttpConfig`], if any.
+    ///
+    /// Its [`RequestLimits`](juniper::http::RequestLimits) also apply to the bodies of `POST`
+    /// requests.
+    pub fn make_graphql_sse_filter_with_config<Query, Mutation, Subscription, CtxT, S>(
+        schema: RootNode<'static, Query, Mutation, Subscription, S>,
+        context_extractor: BoxedFilter<(CtxT,)>,
+        config: EventStreamConfig,
+        http_config: HttpConfig<CtxT, S>,
+    ) -> BoxedFilter<(http::Response<Body>,)>
+    where
+        Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Query::TypeInfo: Send + Sync,
+        Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Mutation::TypeInfo: Send + Sync,
+        Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+        Subscription::TypeInfo: Send + Sync,
+        CtxT: Send + Sync + 'static,
+        S: ScalarValue + Send + Sync + 'static,
+    {
+        let (schema, http_config) = (Arc::new(schema), Arc::new(http_config));
+        let (post_schema, post_http_config) = (schema.clone(), http_config.clone());
+        let post_config = config.clone();
+
+        let handle_post_request = move |context: CtxT,
+                                        content_type: Option<String>,
+                                        body: Bytes| {
+            let limits = post_http_config.request_limits();
+            let req = parse_post_request_with_limits::<S>(content_type.as_deref(), &body, limits);
+            match req {
+                Ok(GraphQLBatchRequest::Single(req)) => event_stream_response(
+                    post_schema.clone(),
+                    req,
+                    context,
+                    &post_config,
+                    post_http_config.clone(),
+                ),
+                Ok(GraphQLBatchRequest::Batch(_)) => batch_response(),
+                Err(e) => error_response(e).map(Body::from),
+            }
+        };
+        let post_filter = warp::post()
+            .and(context_extractor.clone())
+            .and(header::optional::<String>("content-type"))
+            .and(body::bytes())
+            .map(handle_post_request);
+
+        let handle_get_request =
+            move |context: CtxT, qry: String| match parse_get_request::<S>(&qry) {
+                Ok(req) => event_stream_response(
+                    schema.clone(),
+                    req,
+                    context,
+                    &config,
+                    http_config.clone(),
+                ),
+                Err(e) => error_response(e).map(Body::from),
+            };
+        let get_filter = warp::get()
+            .and(context_extractor)
+            .and(raw_query())
+            .map(handle_get_request);
+
+        get_filter.or(post_filter).unify().boxed()
+    }
+
+    fn event_stream_response<Query, Mutation, Subscription, CtxT, S>(
+        schema: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+        request: GraphQLRequest<S>,
+        context: CtxT,
+        config: &EventStreamConfig,
+        http_config: Arc<HttpConfig<CtxT, S>>,
+    ) -> http::Response<Body>
+    where
+        Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Query::TypeInfo: Send + Sync,
+        Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Mutation::TypeInfo: Send + Sync,
+        Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+        Subscription::TypeInfo: Send + Sync,
+        CtxT: Send + Sync + 'static,
+        S: ScalarValue + Send + Sync + 'static,
+    {
+        let frames = juniper_graphql_sse::subscribe_with_config(
+            schema,
+            request,
+            Arc::new(context),
+            config,
+            http_config,
+        );
+        http::Response::builder()
+            .header("content-type", juniper_graphql_sse::CONTENT_TYPE)
+            .header("cache-control", "no-cache")
+            .body(Body::wrap_stream(frames.map(Ok::<_, Infallible>)))
+            .expect("response is valid")
+    }
+
+    fn batch_response() -> http::Response<Body> {
+        http::Response::builder()
+            .status(http::StatusCode::BAD_REQUEST)
+            .header("content-type", "text/plain; charset=utf-8")
+            .body(Body::from(
+                "Batch requests can't be served over Server-Sent Events",
+            ))
+            .expect("status code is valid")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -589,6 +885,33 @@ mod tests {
         assert!(body.contains("GraphQLPlayground.init(root, { endpoint: '/dogs-api/graphql', subscriptionEndpoint: '/dogs-api/subscriptions' })"));
     }
 
+    #[tokio::test]
+    async fn endpoints_with_settings_return_configured_sources() {
+        let graphiql = warp::get().and(graphiql_filter_with_settings(
+            GraphiQLSettings::new("/graphql")
+                .header("Authorization", "Bearer token")
+                .assets_url("/static/graphiql"),
+        ));
+        let playground = warp::get().and(playground_filter_with_settings(
+            PlaygroundSettings::new("/graphql").assets_url("/static/playground"),
+        ));
+
+        let response = request().method("GET").reply(&graphiql).await;
+        assert_eq!(response.status(), http::StatusCode::OK);
+        let body = String::from_utf8(response.body().to_vec()).unwrap();
+        assert!(body.contains(r#"<script src="/static/graphiql/graphiql.min.js"></script>"#));
+        assert!(body.contains("{'Authorization': 'Bearer token'}"));
+
+        let response = request().method("GET").reply(&playground).await;
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            "text/html;charset=utf-8"
+        );
+        let body = String::from_utf8(response.body().to_vec()).unwrap();
+        assert!(body.contains(r#"<script src="/static/playground/middleware.js"></script>"#));
+    }
+
     #[tokio::test]
     async fn graphql_handler_works_json_post() {
         use juniper::{
@@ -672,6 +995,56 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "sse")]
+    #[tokio::test]
+    async fn sse_filter_serves_event_streams() {
+        use juniper::{
+            tests::fixtures::starwars::schema::{Database, Query},
+            EmptyMutation, EmptySubscription, RootNode,
+        };
+        use juniper_graphql_sse::EventStreamConfig;
+
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+
+        let state = warp::any().map(Database::new);
+        let filter = warp::path("stream").and(sse::make_graphql_sse_filter(
+            schema,
+            state.boxed(),
+            EventStreamConfig::new(),
+        ));
+
+        let response = request()
+            .method("GET")
+            .path("/stream?query=%7Bhero%7Bname%7D%7D")
+            .reply(&filter)
+            .await;
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            "text/event-stream",
+        );
+        assert_eq!(
+            String::from_utf8(response.body().to_vec()).unwrap(),
+            "event: next\ndata: {\"errors\":[{\"message\":\"Expected subscription, got query\"}]}\n\n\
+             event: complete\ndata:\n\n",
+        );
+
+        let response = request()
+            .method("POST")
+            .path("/stream")
+            .header("content-type", "application/json")
+            .body(r#"[{ "query": "{ hero { name } }" }, { "query": "{ hero { id } }" }]"#)
+            .reply(&filter)
+            .await;
+
+        assert_eq!(response.status(), http::StatusCode::BAD_REQUEST);
+    }
+
     #[test]
     fn batch_request_deserialization_can_fail() {
         let json = r#"blah"#;
@@ -685,7 +1058,10 @@ mod tests {
 mod tests_http_harness {
     use super::*;
     use juniper::{
-        http::tests::{run_http_test_suite, HttpIntegration, TestResponse},
+        http::tests::{
+            run_http_test_suite, run_multipart_test_suite, HttpIntegration, MultipartIntegration,
+            TestResponse,
+        },
         tests::fixtures::starwars::schema::{Database, Query},
         EmptyMutation, EmptySubscription, RootNode,
     };
@@ -739,27 +1115,29 @@ mod tests_http_harness {
         }
     }
 
-    impl HttpIntegration for TestWarpIntegration {
-        fn get(&self, url: &str) -> TestResponse {
-            use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
-            use url::Url;
+    fn get_request(url: &str) -> warp::test::RequestBuilder {
+        use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+        use url::Url;
 
-            /// https://url.spec.whatwg.org/#query-state
-            const QUERY_ENCODE_SET: &AsciiSet =
-                &CONTROLS.add(b' ').add(b'"').add(b'#').add(b'<').add(b'>');
+        /// https://url.spec.whatwg.org/#query-state
+        const QUERY_ENCODE_SET: &AsciiSet =
+            &CONTROLS.add(b' ').add(b'"').add(b'#').add(b'<').add(b'>');
 
-            let url = Url::parse(&format!("http://localhost:3000{}", url)).expect("url to parse");
+        let url = Url::parse(&format!("http://localhost:3000{}", url)).expect("url to parse");
 
-            let url: String = utf8_percent_encode(url.query().unwrap_or(""), QUERY_ENCODE_SET)
-                .into_iter()
-                .collect::<Vec<_>>()
-                .join("");
+        let url: String = utf8_percent_encode(url.query().unwrap_or(""), QUERY_ENCODE_SET)
+            .into_iter()
+            .collect::<Vec<_>>()
+            .join("");
 
-            self.make_request(
-                warp::test::request()
-                    .method("GET")
-                    .path(&format!("/?{}", url)),
-            )
+        warp::test::request()
+            .method("GET")
+            .path(&format!("/?{}", url))
+    }
+
+    impl HttpIntegration for TestWarpIntegration {
+        fn get(&self, url: &str) -> TestResponse {
+            self.make_request(get_request(url))
         }
 
         fn post_json(&self, url: &str, body: &str) -> TestResponse {
@@ -781,6 +1159,36 @@ mod tests_http_harness {
                     .body(body),
             )
         }
+
+        fn get_with_accept(&self, url: &str, accept: &str) -> TestResponse {
+            self.make_request(get_request(url).header("accept", accept))
+        }
+
+        fn post_json_with_accept(&self, url: &str, body: &str, accept: &str) -> TestResponse {
+            self.make_request(
+                warp::test::request()
+                    .method("POST")
+                    .header("content-type", "application/json; charset=utf-8")
+                    .header("accept", accept)
+                    .path(url)
+                    .body(body),
+            )
+        }
+    }
+
+    impl MultipartIntegration for TestWarpIntegration {
+        fn post_multipart(&self, url: &str, boundary: &str, body: &str) -> TestResponse {
+            self.make_request(
+                warp::test::request()
+                    .method("POST")
+                    .header(
+                        "content-type",
+                        format!("multipart/form-data; boundary={}", boundary),
+                    )
+                    .path(url)
+                    .body(body),
+            )
+        }
     }
 
     fn make_test_response(resp: http::Response<Vec<u8>>) -> TestResponse {
@@ -800,10 +1208,12 @@ mod tests_http_harness {
     #[test]
     fn test_warp_integration() {
         run_http_test_suite(&TestWarpIntegration::new(false));
+        run_multipart_test_suite(&TestWarpIntegration::new(false));
     }
 
     #[test]
     fn test_sync_warp_integration() {
         run_http_test_suite(&TestWarpIntegration::new(true));
+        run_multipart_test_suite(&TestWarpIntegration::new(true));
     }
 }